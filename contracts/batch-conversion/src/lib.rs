@@ -10,23 +10,45 @@
 //! - Validates all amounts and currency types
 //!
 //! ## Note on Conversion Mechanism
-//! This implementation uses a simplified conversion model where users specify
-//! the expected output amount (min_amount_out). In production, this would integrate
-//! with a price oracle, DEX, or liquidity pool.
+//! Each request is settled by swapping through the liquidity pools registered
+//! for its route: `from_asset`, then every asset in `path`, then `to_asset`.
+//! The output of each hop is the input of the next. The whole route is quoted
+//! before any tokens move, and only the final output is checked against
+//! `min_amount_out`; a request that would fall short moves no tokens. Once
+//! swapping starts the route is all-or-nothing: a hop that fails after a
+//! successful quote aborts the whole transaction, so no request is ever left
+//! holding an intermediate asset.
+//! `find_best_path` searches registered pools for the best route of up to
+//! `MAX_HOPS` swaps. Pools may cover at most `MAX_POOL_ASSETS` assets, and
+//! only routes whose every hop has a registered pool are quoted.
+//!
+//! The configured price oracle (see `shared::oracle`) guards every swap:
+//! requests fail while the oracle has no fresh price, while its circuit
+//! breaker is open for the pair, or while the spot price is further than the
+//! configured bps from the oracle's TWAP. The same bps bound the pool: a hop
+//! whose pool output falls further than that below the oracle-priced output
+//! is rejected.
 
 #![no_std]
 
+pub mod pool;
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use shared::oracle::{
+    convert_amount, deviation_bps, read_guarded_rate, OracleError, DEFAULT_MAX_DEVIATION_BPS,
+};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, vec, Address, Env, Vec};
 
+use crate::pool::LiquidityPoolClient;
 pub use crate::types::{
     BatchConversionResult, ConversionEvents, ConversionRequest, ConversionResult, DataKey,
-    MAX_BATCH_SIZE,
+    HopResult, PathQuote, ERROR_CIRCUIT_OPEN, ERROR_INVALID_PATH, ERROR_NO_POOL,
+    ERROR_POOL_PRICE_DEVIATION, ERROR_PRICE_DEVIATION, ERROR_RATE_UNAVAILABLE,
+    ERROR_SLIPPAGE_EXCEEDED, ERROR_SWAP_FAILED, MAX_BATCH_SIZE, MAX_HOPS, MAX_POOL_ASSETS,
 };
 use crate::validation::{
-    validate_address, validate_amount, validate_asset_pair, validate_min_output,
+    validate_address, validate_amount, validate_asset_pair, validate_min_output, validate_path,
 };
 
 /// Error codes for the batch conversion contract.
@@ -49,6 +71,12 @@ pub enum BatchConversionError {
     InsufficientBalance = 7,
     /// Slippage tolerance exceeded
     SlippageExceeded = 8,
+    /// No price oracle has been configured
+    OracleNotConfigured = 9,
+    /// A quoted swap failed mid-route
+    SwapFailed = 10,
+    /// Registering the pool would exceed `MAX_POOL_ASSETS`
+    TooManyPoolAssets = 11,
 }
 
 impl From<BatchConversionError> for soroban_sdk::Error {
@@ -78,6 +106,159 @@ impl BatchConversionContract {
             .set(&DataKey::TotalVolumeConverted, &0i128);
     }
 
+    /// Sets the price oracle and the maximum accepted price age. Admin only.
+    pub fn set_oracle(env: Env, admin: Address, oracle: Address, max_age: u64) {
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPriceAge, &max_age);
+        ConversionEvents::oracle_updated(&env, &admin, &oracle, max_age);
+    }
+
+    /// Sets the maximum spot-to-TWAP deviation accepted for a conversion. Admin only.
+    pub fn set_max_deviation(env: Env, admin: Address, max_deviation_bps: u32) {
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDeviationBps, &max_deviation_bps);
+        ConversionEvents::max_deviation_updated(&env, &admin, max_deviation_bps);
+    }
+
+    /// Returns the maximum accepted spot-to-TWAP deviation in bps.
+    pub fn get_max_deviation(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxDeviationBps)
+            .unwrap_or(DEFAULT_MAX_DEVIATION_BPS)
+    }
+
+    /// Registers the liquidity pool that settles conversions between
+    /// `asset_a` and `asset_b`, in either direction. Admin only. Rejects a
+    /// pool that would bring more than `MAX_POOL_ASSETS` assets into routing.
+    pub fn set_pool(env: Env, admin: Address, asset_a: Address, asset_b: Address, pool: Address) {
+        Self::require_admin(&env, &admin);
+        if asset_a == asset_b {
+            panic_with_error!(&env, BatchConversionError::InvalidAsset);
+        }
+
+        let mut assets = Self::get_pool_assets(env.clone());
+        for asset in [&asset_a, &asset_b] {
+            if !assets.contains(asset) {
+                assets.push_back(asset.clone());
+            }
+        }
+        if assets.len() > MAX_POOL_ASSETS {
+            panic_with_error!(&env, BatchConversionError::TooManyPoolAssets);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Pool(asset_a.clone(), asset_b.clone()), &pool);
+        env.storage()
+            .instance()
+            .set(&DataKey::Pool(asset_b.clone(), asset_a.clone()), &pool);
+        env.storage().instance().set(&DataKey::PoolAssets, &assets);
+
+        ConversionEvents::pool_registered(&env, &asset_a, &asset_b, &pool);
+    }
+
+    /// Returns the liquidity pool registered for the pair, if any.
+    pub fn get_pool(env: Env, from_asset: Address, to_asset: Address) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pool(from_asset, to_asset))
+    }
+
+    /// Returns every asset that appears in a registered pool.
+    pub fn get_pool_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PoolAssets)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Finds the route from `from_asset` to `to_asset` with the highest
+    /// output for `amount_in`, using registered pools whose pairs the oracle
+    /// currently prices. Considers routes of up to `MAX_HOPS` swaps, and
+    /// only quotes routes whose every hop has a registered pool.
+    ///
+    /// Returns `None` when no usable route exists.
+    pub fn find_best_path(
+        env: Env,
+        from_asset: Address,
+        to_asset: Address,
+        amount_in: i128,
+    ) -> Option<PathQuote> {
+        if validate_amount(amount_in).is_err()
+            || validate_asset_pair(&from_asset, &to_asset).is_err()
+        {
+            return None;
+        }
+        let oracle = Self::require_oracle(&env);
+        let max_age = Self::get_max_price_age(env.clone());
+        let max_deviation = Self::get_max_deviation(env.clone());
+
+        let mut best: Option<PathQuote> = None;
+        let mut consider = |path: Vec<Address>| {
+            let route = Self::route(&env, &from_asset, &to_asset, &path);
+            if let Ok(hops) =
+                Self::quote_route(&env, &oracle, max_age, max_deviation, &route, amount_in)
+            {
+                let amount_out = hops.last_unchecked().amount_out;
+                if best.as_ref().is_none_or(|b| amount_out > b.amount_out) {
+                    best = Some(PathQuote {
+                        path,
+                        amount_out,
+                        hops,
+                    });
+                }
+            }
+        };
+
+        consider(Vec::new(&env));
+        let assets = Self::get_pool_assets(env.clone());
+        for first in assets.iter() {
+            if first == from_asset
+                || first == to_asset
+                || !Self::has_pool(&env, &from_asset, &first)
+            {
+                continue;
+            }
+            if Self::has_pool(&env, &first, &to_asset) {
+                consider(vec![&env, first.clone()]);
+            }
+            for second in assets.iter() {
+                if second == from_asset
+                    || second == to_asset
+                    || second == first
+                    || !Self::has_pool(&env, &first, &second)
+                    || !Self::has_pool(&env, &second, &to_asset)
+                {
+                    continue;
+                }
+                consider(vec![&env, first.clone(), second]);
+            }
+        }
+
+        best
+    }
+
+    /// Returns the configured price oracle, if any.
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Oracle)
+    }
+
+    /// Returns the maximum accepted oracle price age in seconds.
+    pub fn get_max_price_age(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxPriceAge)
+            .unwrap_or(0)
+    }
+
     /// Executes batch currency conversions for multiple users.
     ///
     /// # Arguments
@@ -90,6 +271,9 @@ impl BatchConversionContract {
     /// # Implementation Notes
     /// - Uses two-pass validation (validate all, then execute)
     /// - Handles partial failures (continues if one fails)
+    /// - A swap that fails after its route was quoted panics with
+    ///   `SwapFailed`, reverting the whole batch rather than stranding a
+    ///   user mid-route
     /// - Emits events for each conversion
     /// - Optimized with batched storage updates
     pub fn batch_convert_currency(
@@ -104,6 +288,9 @@ impl BatchConversionContract {
         if request_count > MAX_BATCH_SIZE {
             panic_with_error!(&env, BatchConversionError::BatchTooLarge);
         }
+        let oracle = Self::require_oracle(&env);
+        let max_age = Self::get_max_price_age(env.clone());
+        let max_deviation = Self::get_max_deviation(env.clone());
 
         // Get batch ID and increment
         let batch_id: u64 = env
@@ -159,6 +346,12 @@ impl BatchConversionContract {
                 is_valid = false;
                 error_code = 5; // Same asset conversion
             }
+            // Validate intermediate path
+            else if validate_path(&request.from_asset, &request.to_asset, &request.path).is_err()
+            {
+                is_valid = false;
+                error_code = ERROR_INVALID_PATH;
+            }
 
             validated_requests.push_back((request.clone(), is_valid, error_code));
         }
@@ -172,7 +365,7 @@ impl BatchConversionContract {
                     request.from_asset.clone(),
                     request.to_asset.clone(),
                     request.amount_in,
-                    error_code.clone(),
+                    error_code,
                 ));
                 failed_count += 1;
                 ConversionEvents::conversion_failure(
@@ -182,14 +375,14 @@ impl BatchConversionContract {
                     &request.from_asset,
                     &request.to_asset,
                     request.amount_in,
-                    error_code.clone(),
+                    error_code,
                 );
                 continue;
             }
 
             // Execute conversion
-            match Self::execute_conversion(&env, &request) {
-                Ok(amount_out) => {
+            match Self::execute_conversion(&env, &oracle, max_age, max_deviation, &request) {
+                Ok((amount_out, hops)) => {
                     // Conversion succeeded
                     results.push_back(ConversionResult::Success(
                         request.user.clone(),
@@ -197,6 +390,7 @@ impl BatchConversionContract {
                         request.to_asset.clone(),
                         request.amount_in,
                         amount_out,
+                        hops,
                     ));
                     successful_count += 1;
                     total_converted = total_converted
@@ -309,22 +503,116 @@ impl BatchConversionContract {
             .unwrap_or(0)
     }
 
-    // Internal helper to execute a single conversion
-    fn execute_conversion(env: &Env, request: &ConversionRequest) -> Result<i128, u32> {
-        // TODO: Implement actual conversion mechanism
-        // Current implementation uses a simplified model where:
-        // 1. User specifies min_amount_out (expected output with slippage tolerance)
-        // 2. Contract validates and executes the swap
-        //
-        // In production, this would:
-        // - Query a price oracle for current exchange rate
-        // - OR integrate with Stellar DEX using path_payment
-        // - OR use a liquidity pool contract
-        //
-        // For Wave 1 demo purposes, we'll use the user-provided rate
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, BatchConversionError::NotInitialized));
+        if admin != &stored_admin {
+            panic_with_error!(env, BatchConversionError::Unauthorized);
+        }
+    }
 
+    fn require_oracle(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .unwrap_or_else(|| panic_with_error!(env, BatchConversionError::OracleNotConfigured))
+    }
+
+    /// Whether a pool is registered for the pair.
+    fn has_pool(env: &Env, from_asset: &Address, to_asset: &Address) -> bool {
+        env.storage()
+            .instance()
+            .has(&DataKey::Pool(from_asset.clone(), to_asset.clone()))
+    }
+
+    /// Full list of assets a conversion passes through, endpoints included.
+    fn route(
+        env: &Env,
+        from_asset: &Address,
+        to_asset: &Address,
+        path: &Vec<Address>,
+    ) -> Vec<Address> {
+        let mut route = vec![env, from_asset.clone()];
+        route.append(path);
+        route.push_back(to_asset.clone());
+        route
+    }
+
+    /// Quotes every hop of `route`, feeding each output into the next hop.
+    /// Each hop must pass the oracle guard and have a registered pool whose
+    /// output is no more than `max_deviation` bps below the oracle-priced output.
+    fn quote_route(
+        env: &Env,
+        oracle: &Address,
+        max_age: u64,
+        max_deviation: u32,
+        route: &Vec<Address>,
+        amount_in: i128,
+    ) -> Result<Vec<HopResult>, u32> {
+        let mut hops = Vec::new(env);
+        let mut amount = amount_in;
+
+        for index in 0..route.len() - 1 {
+            let from_asset = route.get_unchecked(index);
+            let to_asset = route.get_unchecked(index + 1);
+
+            // Oracle guard: fresh price, circuit closed, spot close to TWAP
+            let rate =
+                read_guarded_rate(env, oracle, &from_asset, &to_asset, max_age, max_deviation)
+                    .map_err(|error| match error {
+                        OracleError::PriceDeviation => ERROR_PRICE_DEVIATION,
+                        OracleError::CircuitOpen => ERROR_CIRCUIT_OPEN,
+                        _ => ERROR_RATE_UNAVAILABLE,
+                    })?;
+
+            let pool: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Pool(from_asset.clone(), to_asset.clone()))
+                .ok_or(ERROR_NO_POOL)?;
+            let amount_out = match LiquidityPoolClient::new(env, &pool)
+                .try_get_amount_out(&from_asset, &amount)
+            {
+                Ok(Ok(amount_out)) if amount_out > 0 => amount_out,
+                _ => return Err(ERROR_SWAP_FAILED),
+            };
+
+            // Pool guard: a drained or skewed pool must not pay out well
+            // below the oracle rate
+            let oracle_out = convert_amount(amount, rate).map_err(|_| ERROR_SWAP_FAILED)?;
+            if amount_out < oracle_out
+                && deviation_bps(amount_out, oracle_out).map_err(|_| ERROR_SWAP_FAILED)?
+                    > max_deviation as i128
+            {
+                return Err(ERROR_POOL_PRICE_DEVIATION);
+            }
+
+            hops.push_back(HopResult {
+                from_asset,
+                to_asset,
+                pool,
+                amount_in: amount,
+                amount_out,
+            });
+            amount = amount_out;
+        }
+
+        Ok(hops)
+    }
+
+    // Internal helper to execute a single conversion
+    fn execute_conversion(
+        env: &Env,
+        oracle: &Address,
+        max_age: u64,
+        max_deviation: u32,
+        request: &ConversionRequest,
+    ) -> Result<(i128, Vec<HopResult>), u32> {
         let from_token = token::Client::new(env, &request.from_asset);
-        let _to_token = token::Client::new(env, &request.to_asset);
 
         // Check user has sufficient balance
         let user_balance = from_token.balance(&request.user);
@@ -332,23 +620,43 @@ impl BatchConversionContract {
             return Err(6); // Insufficient balance
         }
 
-        // Calculate output amount (simplified: use min_amount_out as actual output)
-        // In production, calculate based on actual rates
-        let amount_out = request.min_amount_out;
+        // Quote the whole route first so failures are reported before any
+        // tokens move; slippage applies to the final output only
+        let route = Self::route(env, &request.from_asset, &request.to_asset, &request.path);
+        let hops = Self::quote_route(
+            env,
+            oracle,
+            max_age,
+            max_deviation,
+            &route,
+            request.amount_in,
+        )?;
+        let amount_out = hops.last_unchecked().amount_out;
+        if amount_out < request.min_amount_out {
+            return Err(ERROR_SLIPPAGE_EXCEEDED);
+        }
 
         // Authorize user
         request.user.require_auth();
 
-        // Execute the swap:
-        // 1. Transfer from_asset from user to contract (or burn)
-        // TODO: Determine where from_asset goes (contract? liquidity pool?)
-
-        // 2. Transfer to_asset from contract (or mint) to user
-        // TODO: Determine where to_asset comes from
+        // Each pool pulls the hop's input from the user and pays its output
+        // back, which the next hop then spends. Quotes are exact within the
+        // transaction, so each hop is bounded by its quoted output. A failure
+        // here would leave earlier hops settled, so it aborts the transaction
+        // instead of being reported as a per-request failure.
+        for hop in hops.iter() {
+            match LiquidityPoolClient::new(env, &hop.pool).try_swap(
+                &request.user,
+                &hop.from_asset,
+                &hop.amount_in,
+                &hop.amount_out,
+            ) {
+                Ok(Ok(_)) => {}
+                _ => panic_with_error!(env, BatchConversionError::SwapFailed),
+            }
+        }
 
-        // For now, we'll return the expected amount
-        // This is a placeholder that demonstrates the batch processing logic
-        Ok(amount_out)
+        Ok((amount_out, hops))
     }
 }
 
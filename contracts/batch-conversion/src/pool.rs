@@ -0,0 +1,15 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Subset of the liquidity pool contract used to settle conversions.
+#[contractclient(name = "LiquidityPoolClient")]
+pub trait LiquidityPoolInterface {
+    fn get_amount_out(env: Env, token_in: Address, amount_in: i128) -> i128;
+
+    fn swap(
+        env: Env,
+        user: Address,
+        token_in: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+    ) -> i128;
+}
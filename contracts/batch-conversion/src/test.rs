@@ -3,13 +3,22 @@
 #![cfg(test)]
 
 use crate::{
-    BatchConversionContract, BatchConversionContractClient, ConversionRequest, ConversionResult,
+    BatchConversionContract, BatchConversionContractClient, BatchConversionError,
+    ConversionRequest, ConversionResult, ERROR_CIRCUIT_OPEN, ERROR_INVALID_PATH, ERROR_NO_POOL,
+    ERROR_POOL_PRICE_DEVIATION, ERROR_PRICE_DEVIATION, ERROR_RATE_UNAVAILABLE,
+    ERROR_SLIPPAGE_EXCEEDED, MAX_POOL_ASSETS,
 };
+use liquidity_pool::{LiquidityPoolContract, LiquidityPoolContractClient};
+use price_oracle::{PriceOracleContract, PriceOracleContractClient};
 use soroban_sdk::{
+    contract, contractimpl,
     testutils::{Address as _, Events as _, Ledger},
-    token, Address, Env, Vec,
+    token, vec, Address, Env, Vec,
 };
 
+const PRICE_TIMESTAMP: u64 = 1_000;
+const MAX_PRICE_AGE: u64 = 300;
+
 /// Creates a test environment with the contract deployed and initialized.
 fn setup_test_env() -> (
     Env,
@@ -24,6 +33,7 @@ fn setup_test_env() -> (
     env.mock_all_auths();
     env.ledger().with_mut(|li| {
         li.sequence_number = 12345;
+        li.timestamp = PRICE_TIMESTAMP;
     });
 
     // Deploy from_asset token contract
@@ -47,6 +57,25 @@ fn setup_test_env() -> (
     let admin = Address::generate(&env);
     client.initialize(&admin);
 
+    // Local oracle with a stand-in feeder quoting 1 from_asset = 0.95 to_asset
+    let oracle_id = env.register(PriceOracleContract, ());
+    let oracle = PriceOracleContractClient::new(&env, &oracle_id);
+    let feeder = Address::generate(&env);
+    oracle.initialize(&admin);
+    oracle.add_feeder(&admin, &feeder);
+    oracle.set_price(&feeder, &from_asset, &to_asset, &95, &2, &PRICE_TIMESTAMP);
+    client.set_oracle(&admin, &oracle_id, &MAX_PRICE_AGE);
+
+    // Pool seeded at the same 0.95 ratio with a 0.3% swap fee
+    let pool_id = env.register(LiquidityPoolContract, ());
+    let pool = LiquidityPoolContractClient::new(&env, &pool_id);
+    pool.initialize(&admin, &from_asset, &to_asset, &30);
+    let provider = Address::generate(&env);
+    from_token_admin_client.mint(&provider, &100_000);
+    token::StellarAssetClient::new(&env, &to_asset).mint(&provider, &95_000);
+    pool.add_liquidity(&provider, &100_000, &95_000, &0);
+    client.set_pool(&admin, &from_asset, &to_asset, &pool_id);
+
     (
         env,
         from_asset,
@@ -65,12 +94,14 @@ fn create_conversion_request(
     amount_in: i128,
     min_amount_out: i128,
 ) -> ConversionRequest {
+    let path = Vec::new(user.env());
     ConversionRequest {
         user,
         from_asset,
         to_asset,
         amount_in,
         min_amount_out,
+        path,
     }
 }
 
@@ -79,10 +110,10 @@ fn test_batch_convert_single_success() {
     let (
         env,
         from_asset,
-        _from_token_client,
+        from_token_client,
         from_token_admin_client,
         to_asset,
-        _to_token_client,
+        to_token_client,
         client,
     ) = setup_test_env();
 
@@ -100,6 +131,9 @@ fn test_batch_convert_single_success() {
 
     let result = client.batch_convert_currency(&conversions);
 
+    // 100 * 0.997 * 95_000 / (100_000 + 99.7) = 94.6 through the pool
+    assert_eq!(from_token_client.balance(&user), 900);
+    assert_eq!(to_token_client.balance(&user), 94);
     assert_eq!(result.total_requests, 1);
     assert_eq!(result.successful, 1);
     assert_eq!(result.failed, 0);
@@ -107,12 +141,14 @@ fn test_batch_convert_single_success() {
     assert_eq!(result.results.len(), 1);
 
     match result.results.get(0).unwrap() {
-        ConversionResult::Success(u, f, t, amount_in, amount_out) => {
+        ConversionResult::Success(u, f, t, amount_in, amount_out, hops) => {
             assert_eq!(u.clone(), user);
             assert_eq!(f.clone(), from_asset);
             assert_eq!(t.clone(), to_asset);
             assert_eq!(amount_in.clone(), 100);
-            assert_eq!(amount_out.clone(), 90);
+            assert_eq!(amount_out.clone(), 94);
+            assert_eq!(hops.len(), 1);
+            assert_eq!(hops.get(0).unwrap().amount_out, 94);
         }
         _ => panic!("Expected success"),
     }
@@ -309,3 +345,588 @@ fn test_batch_convert_empty_batch() {
     let conversions: Vec<ConversionRequest> = Vec::new(&env);
     client.batch_convert_currency(&conversions);
 }
+
+fn single_failure_code(result: &crate::BatchConversionResult) -> u32 {
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        ConversionResult::Failure(_user, _from, _to, _amount_in, error_code) => error_code,
+        _ => panic!("Expected failure"),
+    }
+}
+
+#[test]
+fn test_batch_convert_slippage_exceeded() {
+    let (
+        env,
+        from_asset,
+        from_token_client,
+        from_token_admin_client,
+        to_asset,
+        to_token_client,
+        client,
+    ) = setup_test_env();
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        user.clone(),
+        from_asset,
+        to_asset,
+        100,
+        96,
+    ));
+
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_SLIPPAGE_EXCEEDED);
+    assert_eq!(from_token_client.balance(&user), 1000);
+    assert_eq!(to_token_client.balance(&user), 0);
+}
+
+#[test]
+fn test_batch_convert_stale_price_fails() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        from_token_admin_client,
+        to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + MAX_PRICE_AGE + 1);
+
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        user, from_asset, to_asset, 100, 90,
+    ));
+
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_RATE_UNAVAILABLE);
+}
+
+#[test]
+fn test_batch_convert_unpriced_pair_fails() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        _from_token_admin_client,
+        to_asset,
+        to_token_client,
+        client,
+    ) = setup_test_env();
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &to_token_client.address).mint(&user, &1000);
+
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        user, to_asset, from_asset, 100, 90,
+    ));
+
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_RATE_UNAVAILABLE);
+}
+
+#[test]
+#[should_panic]
+fn test_batch_convert_requires_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(BatchConversionContract, ());
+    let client = BatchConversionContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let asset = Address::generate(&env);
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        Address::generate(&env),
+        asset.clone(),
+        Address::generate(&env),
+        100,
+        90,
+    ));
+    client.batch_convert_currency(&conversions);
+}
+
+fn oracle_client(
+    env: &Env,
+    client: &BatchConversionContractClient<'static>,
+) -> (PriceOracleContractClient<'static>, Address, Address) {
+    let oracle = PriceOracleContractClient::new(env, &client.get_oracle().unwrap());
+    let admin = oracle.get_admin();
+    let feeder = oracle.get_feeders().get(0).unwrap();
+    (oracle, admin, feeder)
+}
+
+#[test]
+fn test_batch_convert_rejects_price_far_from_twap() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        from_token_admin_client,
+        to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (oracle, admin, feeder) = oracle_client(&env, &client);
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 1_000);
+    oracle.set_price(
+        &feeder,
+        &from_asset,
+        &to_asset,
+        &150,
+        &2,
+        &(PRICE_TIMESTAMP + 1_000),
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 1_010);
+
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        user.clone(),
+        from_asset.clone(),
+        to_asset.clone(),
+        100,
+        90,
+    ));
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_PRICE_DEVIATION);
+
+    // A looser tolerance lets the same request through at the spot price.
+    client.set_max_deviation(&admin, &10_000);
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(result.successful, 1);
+}
+
+#[test]
+fn test_batch_convert_halts_when_circuit_open() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        from_token_admin_client,
+        to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (oracle, admin, feeder) = oracle_client(&env, &client);
+    oracle.set_config(&admin, &3_600, &Some(500));
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 100);
+    oracle.set_price(
+        &feeder,
+        &from_asset,
+        &to_asset,
+        &150,
+        &2,
+        &(PRICE_TIMESTAMP + 100),
+    );
+    assert!(oracle.circuit_open(&from_asset, &to_asset));
+
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        user, from_asset, to_asset, 100, 90,
+    ));
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_CIRCUIT_OPEN);
+}
+
+#[test]
+fn test_batch_convert_requires_pool_for_pair() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (oracle, _admin, feeder) = oracle_client(&env, &client);
+
+    let other_asset = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    oracle.set_price(&feeder, &from_asset, &other_asset, &1, &0, &PRICE_TIMESTAMP);
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+
+    let mut conversions: Vec<ConversionRequest> = Vec::new(&env);
+    conversions.push_back(create_conversion_request(
+        user,
+        from_asset,
+        other_asset,
+        100,
+        90,
+    ));
+
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_NO_POOL);
+}
+
+/// Registers a pool for `asset_a`/`asset_b` seeded with the given reserves
+/// and an oracle price for both directions.
+fn add_market(
+    env: &Env,
+    client: &BatchConversionContractClient<'static>,
+    asset_a: &Address,
+    asset_b: &Address,
+    reserve_a: i128,
+    reserve_b: i128,
+) -> Address {
+    let (oracle, admin, feeder) = oracle_client(env, client);
+    let pool_id = env.register(LiquidityPoolContract, ());
+    let pool = LiquidityPoolContractClient::new(env, &pool_id);
+    pool.initialize(&admin, asset_a, asset_b, &30);
+
+    let provider = Address::generate(env);
+    token::StellarAssetClient::new(env, asset_a).mint(&provider, &reserve_a);
+    token::StellarAssetClient::new(env, asset_b).mint(&provider, &reserve_b);
+    pool.add_liquidity(&provider, &reserve_a, &reserve_b, &0);
+    client.set_pool(&admin, asset_a, asset_b, &pool_id);
+
+    let timestamp = env.ledger().timestamp();
+    oracle.set_price(
+        &feeder,
+        asset_a,
+        asset_b,
+        &(reserve_b * 10_000 / reserve_a),
+        &4,
+        &timestamp,
+    );
+    oracle.set_price(
+        &feeder,
+        asset_b,
+        asset_a,
+        &(reserve_a * 10_000 / reserve_b),
+        &4,
+        &timestamp,
+    );
+    pool_id
+}
+
+fn new_asset(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
+
+#[test]
+fn test_batch_convert_multi_hop_path() {
+    let (
+        env,
+        from_asset,
+        from_token_client,
+        from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let mid_asset = new_asset(&env);
+    let end_asset = new_asset(&env);
+    let first_pool = add_market(&env, &client, &from_asset, &mid_asset, 100_000, 100_000);
+    let second_pool = add_market(&env, &client, &mid_asset, &end_asset, 100_000, 200_000);
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+
+    let mut request = create_conversion_request(
+        user.clone(),
+        from_asset.clone(),
+        end_asset.clone(),
+        1000,
+        1900,
+    );
+    request.path = vec![&env, mid_asset.clone()];
+    let result = client.batch_convert_currency(&vec![&env, request]);
+
+    assert_eq!(result.successful, 1);
+    match result.results.get(0).unwrap() {
+        ConversionResult::Success(_u, _f, _t, amount_in, amount_out, hops) => {
+            assert_eq!(amount_in, 1000);
+            assert_eq!(hops.len(), 2);
+
+            let first = hops.get(0).unwrap();
+            assert_eq!(first.pool, first_pool);
+            assert_eq!(first.to_asset, mid_asset);
+            assert_eq!(first.amount_out, 987);
+
+            // The second hop spends exactly what the first produced.
+            let second = hops.get(1).unwrap();
+            assert_eq!(second.pool, second_pool);
+            assert_eq!(second.amount_in, 987);
+            assert_eq!(second.amount_out, amount_out);
+        }
+        _ => panic!("Expected success"),
+    }
+
+    assert_eq!(from_token_client.balance(&user), 0);
+    assert_eq!(token::Client::new(&env, &mid_asset).balance(&user), 0);
+    assert!(token::Client::new(&env, &end_asset).balance(&user) >= 1900);
+}
+
+#[test]
+fn test_batch_convert_multi_hop_checks_final_output_only() {
+    let (
+        env,
+        from_asset,
+        from_token_client,
+        from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let mid_asset = new_asset(&env);
+    let end_asset = new_asset(&env);
+    add_market(&env, &client, &from_asset, &mid_asset, 100_000, 100_000);
+    add_market(&env, &client, &mid_asset, &end_asset, 100_000, 200_000);
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+
+    // The final output (1948) misses 1960 even though every hop succeeds.
+    let mut request = create_conversion_request(user.clone(), from_asset, end_asset, 1000, 1960);
+    request.path = vec![&env, mid_asset.clone()];
+    let result = client.batch_convert_currency(&vec![&env, request]);
+
+    assert_eq!(single_failure_code(&result), ERROR_SLIPPAGE_EXCEEDED);
+    assert_eq!(from_token_client.balance(&user), 1000);
+    assert_eq!(token::Client::new(&env, &mid_asset).balance(&user), 0);
+}
+
+#[test]
+fn test_batch_convert_rejects_invalid_path() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        from_token_admin_client,
+        to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+
+    let mut request = create_conversion_request(user, from_asset.clone(), to_asset, 100, 90);
+    request.path = vec![&env, from_asset];
+    let result = client.batch_convert_currency(&vec![&env, request]);
+
+    assert_eq!(single_failure_code(&result), ERROR_INVALID_PATH);
+}
+
+#[test]
+fn test_find_best_path_prefers_deeper_route() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        _from_token_admin_client,
+        to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    // A deep two-hop route beats the shallow direct pool from setup.
+    let mid_asset = new_asset(&env);
+    add_market(
+        &env,
+        &client,
+        &from_asset,
+        &mid_asset,
+        10_000_000,
+        10_000_000,
+    );
+    add_market(&env, &client, &mid_asset, &to_asset, 10_000_000, 9_500_000);
+
+    let quote = client
+        .find_best_path(&from_asset, &to_asset, &10_000)
+        .unwrap();
+    assert_eq!(quote.path, vec![&env, mid_asset]);
+    assert_eq!(quote.hops.len(), 2);
+    assert!(quote.amount_out > 8_700);
+
+    // Small amounts barely move either market, so the single fee wins.
+    let quote = client.find_best_path(&from_asset, &to_asset, &10).unwrap();
+    assert_eq!(quote.path.len(), 0);
+}
+
+#[test]
+fn test_find_best_path_none_without_route() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        _from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+
+    let isolated = new_asset(&env);
+    assert_eq!(client.find_best_path(&from_asset, &isolated, &100), None);
+}
+
+#[test]
+fn test_find_best_path_skips_unconnected_pools() {
+    let (
+        env,
+        from_asset,
+        _from_token_client,
+        _from_token_admin_client,
+        to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (_oracle, admin, _feeder) = oracle_client(&env, &client);
+
+    // Fill routing up to the cap with pools unreachable from either endpoint.
+    while client.get_pool_assets().len() < MAX_POOL_ASSETS {
+        let pool = Address::generate(&env);
+        client.set_pool(&admin, &new_asset(&env), &new_asset(&env), &pool);
+    }
+
+    let quote = client.find_best_path(&from_asset, &to_asset, &100).unwrap();
+    assert_eq!(quote.path.len(), 0);
+}
+
+#[test]
+fn test_set_pool_rejects_assets_beyond_cap() {
+    let (
+        env,
+        _from_asset,
+        _from_token_client,
+        _from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (_oracle, admin, _feeder) = oracle_client(&env, &client);
+    while client.get_pool_assets().len() < MAX_POOL_ASSETS {
+        let pool = Address::generate(&env);
+        client.set_pool(&admin, &new_asset(&env), &new_asset(&env), &pool);
+    }
+
+    let pool = Address::generate(&env);
+    let result = client.try_set_pool(&admin, &new_asset(&env), &new_asset(&env), &pool);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchConversionError::TooManyPoolAssets.into()))
+    );
+    assert_eq!(client.get_pool_assets().len(), MAX_POOL_ASSETS);
+}
+
+#[test]
+fn test_batch_convert_rejects_pool_far_below_oracle() {
+    let (
+        env,
+        _from_asset,
+        _from_token_client,
+        _from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (oracle, admin, feeder) = oracle_client(&env, &client);
+    let asset_a = new_asset(&env);
+    let asset_b = new_asset(&env);
+
+    // The oracle prices the pair 1:1, but the pool is skewed to 0.8.
+    let pool_id = env.register(LiquidityPoolContract, ());
+    let pool = LiquidityPoolContractClient::new(&env, &pool_id);
+    pool.initialize(&admin, &asset_a, &asset_b, &30);
+    let provider = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &asset_a).mint(&provider, &100_000);
+    token::StellarAssetClient::new(&env, &asset_b).mint(&provider, &80_000);
+    pool.add_liquidity(&provider, &100_000, &80_000, &0);
+    client.set_pool(&admin, &asset_a, &asset_b, &pool_id);
+    oracle.set_price(&feeder, &asset_a, &asset_b, &1, &0, &PRICE_TIMESTAMP);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &asset_a).mint(&user, &1000);
+    let request = create_conversion_request(user.clone(), asset_a.clone(), asset_b.clone(), 100, 1);
+    let conversions = vec![&env, request];
+
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(single_failure_code(&result), ERROR_POOL_PRICE_DEVIATION);
+    assert_eq!(token::Client::new(&env, &asset_a).balance(&user), 1000);
+    assert!(client.find_best_path(&asset_a, &asset_b, &100).is_none());
+
+    // A looser tolerance accepts the pool's price.
+    client.set_max_deviation(&admin, &2_500);
+    let result = client.batch_convert_currency(&conversions);
+    assert_eq!(result.successful, 1);
+    assert_eq!(token::Client::new(&env, &asset_b).balance(&user), 79);
+}
+
+/// Pool stand-in that quotes 1:1 but fails every swap.
+#[contract]
+struct FailingPool;
+
+#[contractimpl]
+impl FailingPool {
+    pub fn get_amount_out(_env: Env, _token_in: Address, amount_in: i128) -> i128 {
+        amount_in
+    }
+
+    pub fn swap(
+        _env: Env,
+        _user: Address,
+        _token_in: Address,
+        _amount_in: i128,
+        _min_amount_out: i128,
+    ) -> i128 {
+        panic!("swap failed")
+    }
+}
+
+#[test]
+fn test_batch_convert_route_is_atomic() {
+    let (
+        env,
+        from_asset,
+        from_token_client,
+        from_token_admin_client,
+        _to_asset,
+        _to_token_client,
+        client,
+    ) = setup_test_env();
+    let (oracle, admin, feeder) = oracle_client(&env, &client);
+    let mid_asset = new_asset(&env);
+    let end_asset = new_asset(&env);
+    let first_pool = add_market(&env, &client, &from_asset, &mid_asset, 100_000, 100_000);
+    let failing_pool = env.register(FailingPool, ());
+    client.set_pool(&admin, &mid_asset, &end_asset, &failing_pool);
+    oracle.set_price(&feeder, &mid_asset, &end_asset, &1, &0, &PRICE_TIMESTAMP);
+
+    let user = Address::generate(&env);
+    from_token_admin_client.mint(&user, &1000);
+
+    let mut request =
+        create_conversion_request(user.clone(), from_asset.clone(), end_asset.clone(), 1000, 1);
+    request.path = vec![&env, mid_asset.clone()];
+
+    // The first hop settles before the second fails; the whole call reverts.
+    let result = client.try_batch_convert_currency(&vec![&env, request]);
+    assert!(result.is_err());
+    assert_eq!(from_token_client.balance(&user), 1000);
+    assert_eq!(token::Client::new(&env, &mid_asset).balance(&user), 0);
+    let info = LiquidityPoolContractClient::new(&env, &first_pool).get_info();
+    assert_eq!((info.reserve_a, info.reserve_b), (100_000, 100_000));
+    assert_eq!(client.get_total_batches(), 0);
+}
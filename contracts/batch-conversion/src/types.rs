@@ -2,6 +2,30 @@ use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
 
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Maximum number of swaps in one conversion path.
+pub const MAX_HOPS: u32 = 3;
+
+/// Maximum number of distinct assets across registered pools, which bounds
+/// the routes `find_best_path` has to consider.
+pub const MAX_POOL_ASSETS: u32 = 50;
+
+/// Result error code: the oracle has no usable rate for the pair.
+pub const ERROR_RATE_UNAVAILABLE: u32 = 7;
+/// Result error code: the oracle-priced output is below `min_amount_out`.
+pub const ERROR_SLIPPAGE_EXCEEDED: u32 = 8;
+/// Result error code: the spot price is too far from the oracle TWAP.
+pub const ERROR_PRICE_DEVIATION: u32 = 9;
+/// Result error code: the oracle's circuit breaker has halted the pair.
+pub const ERROR_CIRCUIT_OPEN: u32 = 10;
+/// Result error code: no liquidity pool is registered for the pair.
+pub const ERROR_NO_POOL: u32 = 11;
+/// Result error code: the pool could not quote or execute the swap.
+pub const ERROR_SWAP_FAILED: u32 = 12;
+/// Result error code: the path is too long or repeats an asset.
+pub const ERROR_INVALID_PATH: u32 = 13;
+/// Result error code: the pool output is too far below the oracle rate.
+pub const ERROR_POOL_PRICE_DEVIATION: u32 = 14;
+
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct ConversionRequest {
@@ -10,12 +34,35 @@ pub struct ConversionRequest {
     pub to_asset: Address,
     pub amount_in: i128,      // How much user is converting
     pub min_amount_out: i128, // Minimum they expect to receive (slippage protection)
+    pub path: Vec<Address>, // Intermediate assets between from_asset and to_asset (empty = direct)
+}
+
+/// What happened on one swap of a conversion path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct HopResult {
+    pub from_asset: Address,
+    pub to_asset: Address,
+    pub pool: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
+
+/// Best route found by `find_best_path`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PathQuote {
+    /// Intermediate assets, ready to use as `ConversionRequest::path`.
+    pub path: Vec<Address>,
+    pub amount_out: i128,
+    pub hops: Vec<HopResult>,
 }
 
 #[derive(Clone, Debug)]
 #[contracttype]
 pub enum ConversionResult {
-    Success(Address, Address, Address, i128, i128),
+    /// (user, from_asset, to_asset, amount_in, amount_out, hops)
+    Success(Address, Address, Address, i128, i128, Vec<HopResult>),
     Failure(Address, Address, Address, i128, u32),
 }
 
@@ -36,6 +83,16 @@ pub enum DataKey {
     TotalBatches,
     TotalConversionsProcessed,
     TotalVolumeConverted,
+    /// Price oracle contract used to price conversions.
+    Oracle,
+    /// Maximum age in seconds of an oracle price before it is rejected.
+    MaxPriceAge,
+    /// Maximum spot-to-TWAP deviation in bps accepted for a conversion.
+    MaxDeviationBps,
+    /// Liquidity pool settling conversions between two assets (stored for both orderings).
+    Pool(Address, Address),
+    /// Every asset that appears in a registered pool, for path finding.
+    PoolAssets,
 }
 
 pub struct ConversionEvents;
@@ -90,6 +147,24 @@ impl ConversionEvents {
         );
     }
 
+    pub fn oracle_updated(env: &Env, admin: &Address, oracle: &Address, max_age: u64) {
+        let topics = (symbol_short!("oracle"), symbol_short!("updated"));
+        env.events()
+            .publish(topics, (admin.clone(), oracle.clone(), max_age));
+    }
+
+    pub fn max_deviation_updated(env: &Env, admin: &Address, max_deviation_bps: u32) {
+        let topics = (symbol_short!("oracle"), symbol_short!("deviation"));
+        env.events()
+            .publish(topics, (admin.clone(), max_deviation_bps));
+    }
+
+    pub fn pool_registered(env: &Env, asset_a: &Address, asset_b: &Address, pool: &Address) {
+        let topics = (symbol_short!("pool"), symbol_short!("set"));
+        env.events()
+            .publish(topics, (asset_a.clone(), asset_b.clone(), pool.clone()));
+    }
+
     pub fn batch_completed(
         env: &Env,
         batch_id: u64,
@@ -1,6 +1,8 @@
 //! Validation utilities for batch currency conversions.
 
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::MAX_HOPS;
 
 /// Validation error types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,6 +15,8 @@ pub enum ValidationError {
     InvalidMinOutput,
     /// Same asset conversion (from_asset == to_asset)
     SameAsset,
+    /// Path has too many hops or revisits an asset
+    InvalidPath,
 }
 
 /// Validates an address.
@@ -50,10 +54,31 @@ pub fn validate_asset_pair(
     Ok(())
 }
 
+/// Validates the intermediate assets of a conversion path.
+/// Allows at most `MAX_HOPS` swaps and no asset may appear twice in the route.
+pub fn validate_path(
+    from_asset: &Address,
+    to_asset: &Address,
+    path: &Vec<Address>,
+) -> Result<(), ValidationError> {
+    if path.len() >= MAX_HOPS {
+        return Err(ValidationError::InvalidPath);
+    }
+    for (index, asset) in path.iter().enumerate() {
+        if &asset == from_asset || &asset == to_asset {
+            return Err(ValidationError::InvalidPath);
+        }
+        if path.first_index_of(&asset) != Some(index as u32) {
+            return Err(ValidationError::InvalidPath);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use soroban_sdk::{testutils::Address as _, vec, Env};
 
     #[test]
     fn test_validate_amount_positive() {
@@ -100,4 +125,29 @@ mod tests {
         let address = Address::generate(&env);
         assert!(validate_address(&env, &address).is_ok());
     }
+
+    #[test]
+    fn test_validate_path() {
+        let env = Env::default();
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        let mid1 = Address::generate(&env);
+        let mid2 = Address::generate(&env);
+        let mid3 = Address::generate(&env);
+
+        assert!(validate_path(&from, &to, &vec![&env]).is_ok());
+        assert!(validate_path(&from, &to, &vec![&env, mid1.clone(), mid2.clone()]).is_ok());
+        assert_eq!(
+            validate_path(&from, &to, &vec![&env, mid1.clone(), mid2.clone(), mid3]),
+            Err(ValidationError::InvalidPath)
+        );
+        assert_eq!(
+            validate_path(&from, &to, &vec![&env, mid1.clone(), mid1.clone()]),
+            Err(ValidationError::InvalidPath)
+        );
+        assert_eq!(
+            validate_path(&from, &to, &vec![&env, to.clone()]),
+            Err(ValidationError::InvalidPath)
+        );
+    }
 }
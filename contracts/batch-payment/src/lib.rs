@@ -3,8 +3,33 @@
 mod test;
 mod types;
 
-use crate::types::Payment;
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Vec};
+pub use crate::types::{
+    BatchMode, BatchPaymentResult, DataKey, ErrorCode, Payment, PaymentResult, MAX_BATCH_SIZE,
+};
+use shared::idempotency::{self, DEFAULT_RETENTION_SECS};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, symbol_short, token, Address, BytesN, Env, Vec,
+};
+
+/// Error codes for the batch payment contract.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BatchPaymentError {
+    /// Batch is empty
+    EmptyBatch = 1,
+    /// Batch exceeds maximum size
+    BatchTooLarge = 2,
+    /// A payment amount is zero or negative (atomic mode)
+    InvalidAmount = 3,
+    /// Sender balance does not cover the batch (atomic mode)
+    InsufficientBalance = 4,
+}
+
+impl From<BatchPaymentError> for soroban_sdk::Error {
+    fn from(e: BatchPaymentError) -> Self {
+        soroban_sdk::Error::from_contract_error(e as u32)
+    }
+}
 
 #[contract]
 pub struct BatchPaymentContract;
@@ -13,51 +38,180 @@ pub struct BatchPaymentContract;
 impl BatchPaymentContract {
     /// Transfers tokens from the caller to multiple recipients.
     ///
+    /// In `Atomic` mode every payment is validated and the sender's balance is
+    /// checked against the batch total before anything moves; any problem
+    /// aborts the batch. In `BestEffort` mode invalid, unaffordable, or
+    /// rejected payments are skipped and reported as failures.
+    ///
+    /// The result is stored and can be fetched later with `get_batch`. A retry
+    /// carrying the same `idempotency_key` within the retention window returns
+    /// the original result without paying out again.
+    ///
     /// # Arguments
     /// * `env` - The contract environment.
     /// * `from` - The address sending the tokens (must authorize the call).
     /// * `token` - The address of the token contract (e.g., USDC).
     /// * `payments` - A vector of `Payment` structs containing recipients and amounts.
-    pub fn batch_transfer(env: Env, from: Address, token: Address, payments: Vec<Payment>) {
+    /// * `mode` - Whether the batch is all-or-nothing or best-effort.
+    /// * `idempotency_key` - Optional client key; a retry within the retention window returns the original result
+    pub fn batch_transfer(
+        env: Env,
+        from: Address,
+        token: Address,
+        payments: Vec<Payment>,
+        mode: BatchMode,
+        idempotency_key: Option<BytesN<32>>,
+    ) -> BatchPaymentResult {
         // Require authorization from the sender
         from.require_auth();
 
+        // Return the stored result if this batch was already executed
+        let idempotency_args = (from.clone(), token.clone(), payments.clone(), mode);
+        if let Some(result) = idempotency::replay(
+            &env,
+            &from,
+            &idempotency_key,
+            &idempotency_args,
+            DEFAULT_RETENTION_SECS,
+        ) {
+            return result;
+        }
+
+        let request_count = payments.len();
+        if request_count == 0 {
+            panic_with_error!(&env, BatchPaymentError::EmptyBatch);
+        }
+        if request_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, BatchPaymentError::BatchTooLarge);
+        }
+
         let token_client = token::Client::new(&env, &token);
+        let mut available_balance = token_client.balance(&from);
 
-        let mut total_amount: i128 = 0;
-        let mut count: u32 = 0;
+        if mode == BatchMode::Atomic {
+            let mut total_needed: i128 = 0;
+            for payment in payments.iter() {
+                if payment.amount <= 0 {
+                    panic_with_error!(&env, BatchPaymentError::InvalidAmount);
+                }
+                total_needed = total_needed.saturating_add(payment.amount);
+            }
+            if total_needed > available_balance {
+                panic_with_error!(&env, BatchPaymentError::InsufficientBalance);
+            }
+        }
+
+        let batch_id: u64 = Self::get_last_batch_id(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBatchId, &batch_id);
 
-        // Generate a pseudo-unique batch ID based on ledger and timestamp (just for event tracking)
-        let batch_id = env.ledger().sequence() as u64; // Simple ID for now
+        let mut results: Vec<PaymentResult> = Vec::new(&env);
+        let mut total_amount: i128 = 0;
+        let mut successful: u32 = 0;
+        let mut failed: u32 = 0;
 
         for payment in payments.iter() {
-            // Validation
-            if payment.amount <= 0 {
-                panic!("Payment amount must be positive");
-            }
+            let error_code = if payment.amount <= 0 {
+                Some(ErrorCode::INVALID_AMOUNT)
+            } else if payment.amount > available_balance {
+                Some(ErrorCode::INSUFFICIENT_BALANCE)
+            } else if mode == BatchMode::Atomic {
+                token_client.transfer(&from, &payment.recipient, &payment.amount);
+                None
+            } else {
+                match token_client.try_transfer(&from, &payment.recipient, &payment.amount) {
+                    Ok(Ok(())) => None,
+                    _ => Some(ErrorCode::TRANSFER_FAILED),
+                }
+            };
+
+            match error_code {
+                None => {
+                    available_balance -= payment.amount;
+                    total_amount += payment.amount;
+                    successful += 1;
+
+                    // Emit per-payment event
+                    // Topics: (payment, batch_id, recipient)
+                    // Data: (token, amount)
+                    let topics = (
+                        symbol_short!("payment"),
+                        batch_id,
+                        payment.recipient.clone(),
+                    );
+                    env.events()
+                        .publish(topics, (token.clone(), payment.amount));
+
+                    results.push_back(PaymentResult::Success(
+                        payment.recipient.clone(),
+                        payment.amount,
+                    ));
+                }
+                Some(error_code) => {
+                    failed += 1;
 
-            // Execute transfer
-            token_client.transfer(&from, &payment.recipient, &payment.amount);
-
-            total_amount += payment.amount;
-            count += 1;
-
-            // Emit per-payment event
-            // Topics: (payment, batch_id, recipient)
-            // Data: (token, amount)
-            let topics = (
-                symbol_short!("payment"),
-                batch_id,
-                payment.recipient.clone(),
-            );
-            env.events()
-                .publish(topics, (token.clone(), payment.amount));
+                    // Emit per-payment failure event
+                    // Topics: (pay_fail, batch_id, recipient)
+                    // Data: (token, amount, error_code)
+                    let topics = (
+                        symbol_short!("pay_fail"),
+                        batch_id,
+                        payment.recipient.clone(),
+                    );
+                    env.events()
+                        .publish(topics, (token.clone(), payment.amount, error_code));
+
+                    results.push_back(PaymentResult::Failure(
+                        payment.recipient.clone(),
+                        payment.amount,
+                        error_code,
+                    ));
+                }
+            }
         }
 
         // Emit batch completion event
         // Topics: (batch, complete, batch_id)
         // Data: (total_payments, total_amount)
         let topics = (symbol_short!("batch"), symbol_short!("complete"), batch_id);
-        env.events().publish(topics, (count, total_amount));
+        env.events().publish(topics, (successful, total_amount));
+
+        let result = BatchPaymentResult {
+            batch_id,
+            mode,
+            sender: from.clone(),
+            token,
+            total_requests: request_count,
+            successful,
+            failed,
+            total_paid: total_amount,
+            results,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Batch(batch_id), &result);
+        idempotency::record(
+            &env,
+            &from,
+            &idempotency_key,
+            &idempotency_args,
+            &result,
+            DEFAULT_RETENTION_SECS,
+        );
+        result
+    }
+
+    /// Returns the stored result of a batch, if it exists.
+    pub fn get_batch(env: Env, batch_id: u64) -> Option<BatchPaymentResult> {
+        env.storage().persistent().get(&DataKey::Batch(batch_id))
+    }
+
+    /// Returns the ID of the most recent batch (0 if none).
+    pub fn get_last_batch_id(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LastBatchId)
+            .unwrap_or(0)
     }
 }
@@ -4,7 +4,7 @@ extern crate std;
 use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Events},
-    Address, Env, Vec,
+    Address, BytesN, Env, Vec,
 };
 
 #[test]
@@ -42,7 +42,16 @@ fn test_batch_transfer() {
     });
 
     // Execute batch transfer
-    client.batch_transfer(&sender, &token_contract.address(), &payments);
+    let result = client.batch_transfer(
+        &sender,
+        &token_contract.address(),
+        &payments,
+        &BatchMode::Atomic,
+        &None,
+    );
+    assert_eq!(result.batch_id, 1);
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.total_paid, 300);
 
     // Verify balances
     assert_eq!(token_client.balance(&sender), 700);
@@ -64,7 +73,7 @@ fn test_batch_transfer() {
 }
 
 #[test]
-#[should_panic(expected = "Payment amount must be positive")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_batch_transfer_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
@@ -85,5 +94,220 @@ fn test_batch_transfer_zero_amount() {
         amount: 0,
     });
 
-    client.batch_transfer(&sender, &token_contract.address(), &payments);
+    client.batch_transfer(
+        &sender,
+        &token_contract.address(),
+        &payments,
+        &BatchMode::Atomic,
+        &None,
+    );
+}
+
+fn setup_funded(
+    env: &Env,
+    balance: i128,
+) -> (
+    BatchPaymentContractClient<'static>,
+    token::Client<'static>,
+    Address,
+) {
+    env.mock_all_auths();
+    let contract_id = env.register(BatchPaymentContract, ());
+    let client = BatchPaymentContractClient::new(env, &contract_id);
+
+    let token_contract = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let token_client = token::Client::new(env, &token_contract.address());
+    let sender = Address::generate(env);
+    token::StellarAssetClient::new(env, &token_contract.address()).mint(&sender, &balance);
+
+    (client, token_client, sender)
+}
+
+fn payment(recipient: &Address, amount: i128) -> Payment {
+    Payment {
+        recipient: recipient.clone(),
+        amount,
+    }
+}
+
+#[test]
+fn test_best_effort_skips_and_reports_failures() {
+    let env = Env::default();
+    let (client, token_client, sender) = setup_funded(&env, 250);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+
+    let mut payments = Vec::new(&env);
+    payments.push_back(payment(&user1, 100));
+    payments.push_back(payment(&user2, 0));
+    payments.push_back(payment(&user3, 200));
+    payments.push_back(payment(&user3, 150));
+
+    let result = client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::BestEffort,
+        &None,
+    );
+
+    assert_eq!(result.total_requests, 4);
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 2);
+    assert_eq!(result.total_paid, 250);
+    assert_eq!(
+        result.results.get(1).unwrap(),
+        PaymentResult::Failure(user2.clone(), 0, ErrorCode::INVALID_AMOUNT)
+    );
+    assert_eq!(
+        result.results.get(2).unwrap(),
+        PaymentResult::Failure(user3.clone(), 200, ErrorCode::INSUFFICIENT_BALANCE)
+    );
+    assert_eq!(token_client.balance(&user3), 150);
+    assert_eq!(token_client.balance(&sender), 0);
+
+    assert_eq!(client.get_batch(&result.batch_id), Some(result));
+    assert_eq!(client.get_batch(&99), None);
+}
+
+#[test]
+fn test_atomic_insufficient_balance_moves_nothing() {
+    let env = Env::default();
+    let (client, token_client, sender) = setup_funded(&env, 250);
+    let user1 = Address::generate(&env);
+
+    let mut payments = Vec::new(&env);
+    payments.push_back(payment(&user1, 100));
+    payments.push_back(payment(&user1, 200));
+
+    assert!(client
+        .try_batch_transfer(
+            &sender,
+            &token_client.address,
+            &payments,
+            &BatchMode::Atomic,
+            &None,
+        )
+        .is_err());
+    assert_eq!(token_client.balance(&sender), 250);
+    assert_eq!(client.get_last_batch_id(), 0);
+}
+
+#[test]
+fn test_batch_ids_are_unique_within_a_ledger() {
+    let env = Env::default();
+    let (client, token_client, sender) = setup_funded(&env, 1_000);
+    let user1 = Address::generate(&env);
+
+    let mut payments = Vec::new(&env);
+    payments.push_back(payment(&user1, 10));
+
+    let first = client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::Atomic,
+        &None,
+    );
+    let second = client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::BestEffort,
+        &None,
+    );
+
+    assert_eq!(first.batch_id, 1);
+    assert_eq!(second.batch_id, 2);
+    assert_eq!(client.get_last_batch_id(), 2);
+    assert_eq!(client.get_batch(&1).unwrap().mode, BatchMode::Atomic);
+}
+
+#[test]
+fn test_batch_transfer_retry_with_same_key_is_not_reexecuted() {
+    let env = Env::default();
+    let (client, token_client, sender) = setup_funded(&env, 1_000);
+    let user1 = Address::generate(&env);
+
+    let mut payments = Vec::new(&env);
+    payments.push_back(payment(&user1, 100));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    let first = client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::Atomic,
+        &key,
+    );
+    let retry = client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::Atomic,
+        &key,
+    );
+
+    assert_eq!(retry, first);
+    assert_eq!(token_client.balance(&user1), 100);
+    assert_eq!(client.get_last_batch_id(), 1);
+
+    // Reusing the key for a different batch is rejected
+    assert!(client
+        .try_batch_transfer(
+            &sender,
+            &token_client.address,
+            &payments,
+            &BatchMode::BestEffort,
+            &key,
+        )
+        .is_err());
+
+    // A different key executes again
+    let other_key = Some(BytesN::from_array(&env, &[8; 32]));
+    client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::Atomic,
+        &other_key,
+    );
+    assert_eq!(token_client.balance(&user1), 200);
+    assert_eq!(client.get_last_batch_id(), 2);
+}
+
+#[test]
+fn test_batch_transfer_key_is_scoped_to_the_sender() {
+    let env = Env::default();
+    let (client, token_client, sender) = setup_funded(&env, 1_000);
+    let attacker = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_client.address).mint(&attacker, &1_000);
+    let user1 = Address::generate(&env);
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    // Another sender claims the key first with a different batch
+    let mut decoy = Vec::new(&env);
+    decoy.push_back(payment(&attacker, 1));
+    client.batch_transfer(
+        &attacker,
+        &token_client.address,
+        &decoy,
+        &BatchMode::Atomic,
+        &key,
+    );
+
+    let mut payments = Vec::new(&env);
+    payments.push_back(payment(&user1, 100));
+    let result = client.batch_transfer(
+        &sender,
+        &token_client.address,
+        &payments,
+        &BatchMode::Atomic,
+        &key,
+    );
+
+    assert_eq!(result.batch_id, 2);
+    assert_eq!(token_client.balance(&user1), 100);
+    assert_eq!(token_client.balance(&sender), 900);
 }
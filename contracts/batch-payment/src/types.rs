@@ -1,4 +1,18 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Maximum number of payments in a single batch.
+pub const MAX_BATCH_SIZE: u32 = 100;
+
+/// Per-payment error codes reported in `PaymentResult::Failure`.
+#[allow(non_snake_case)]
+pub mod ErrorCode {
+    /// Amount is zero or negative
+    pub const INVALID_AMOUNT: u32 = 1;
+    /// Sender balance does not cover the payment
+    pub const INSUFFICIENT_BALANCE: u32 = 2;
+    /// Token contract rejected the transfer
+    pub const TRANSFER_FAILED: u32 = 3;
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -6,3 +20,43 @@ pub struct Payment {
     pub recipient: Address,
     pub amount: i128,
 }
+
+/// How a batch treats invalid or failing payments.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchMode {
+    /// Any invalid payment aborts the whole batch.
+    Atomic,
+    /// Invalid or failing payments are skipped and reported.
+    BestEffort,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PaymentResult {
+    Success(Address, i128),
+    Failure(Address, i128, u32), // recipient, requested amount, error code
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPaymentResult {
+    pub batch_id: u64,
+    pub mode: BatchMode,
+    pub sender: Address,
+    pub token: Address,
+    pub total_requests: u32,
+    pub successful: u32,
+    pub failed: u32,
+    pub total_paid: i128,
+    pub results: Vec<PaymentResult>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Last assigned batch ID
+    LastBatchId,
+    /// Stored result of a batch by ID
+    Batch(u64),
+}
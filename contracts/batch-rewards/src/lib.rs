@@ -7,7 +7,10 @@ mod validation;
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use shared::idempotency::{self, DEFAULT_RETENTION_SECS};
+use shared::staged::{self, StagedError};
+pub use shared::staged::{StagedBatch, StagedStatus};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, BytesN, Env, Vec};
 
 pub use crate::types::{
     BatchRewardResult, DataKey, RewardEvents, RewardRequest, RewardResult, MAX_BATCH_SIZE,
@@ -34,6 +37,19 @@ pub enum BatchRewardsError {
     InsufficientBalance = 7,
     /// Invalid reward amount
     InvalidAmount = 8,
+    /// Staged batch header has no items, a non-positive total or no chunk hashes
+    InvalidStagedHeader = 9,
+    /// Staged batch does not exist
+    StagedBatchNotFound = 10,
+    /// Staged batch is already finalized or cancelled
+    StagedBatchClosed = 11,
+    /// Chunk cursor does not match the items received so far
+    CursorMismatch = 12,
+    /// Chunk would exceed the committed item count, amount or number of chunks
+    StagedTotalExceeded = 13,
+    /// Chunk does not match its committed hash, or the last chunk leaves the
+    /// staged batch short of the committed count or amount
+    StagedHashMismatch = 14,
 }
 
 impl From<BatchRewardsError> for soroban_sdk::Error {
@@ -112,6 +128,7 @@ impl BatchRewardsContract {
     /// * `caller` - The address initiating the batch rewards
     /// * `token` - The token contract address (e.g., XLM)
     /// * `rewards` - Vector of reward requests containing recipient and amount
+    /// * `idempotency_key` - Optional client key; a retry within the retention window returns the original result
     ///
     /// # Returns
     /// A `BatchRewardResult` containing the results of the distribution
@@ -120,11 +137,24 @@ impl BatchRewardsContract {
         caller: Address,
         token: Address,
         rewards: Vec<RewardRequest>,
+        idempotency_key: Option<BytesN<32>>,
     ) -> BatchRewardResult {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
+        // Return the stored result if this batch was already executed
+        let idempotency_args = (caller.clone(), token.clone(), rewards.clone());
+        if let Some(result) = idempotency::replay(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            DEFAULT_RETENTION_SECS,
+        ) {
+            return result;
+        }
+
         // Validate batch size
         let request_count = rewards.len();
         if request_count == 0 {
@@ -134,6 +164,100 @@ impl BatchRewardsContract {
             panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
         }
 
+        let result = Self::process_rewards(&env, &caller, &token, &rewards);
+        idempotency::record(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            &result,
+            DEFAULT_RETENTION_SECS,
+        );
+        result
+    }
+
+    /// Commits the header of a staged reward distribution and returns its ID.
+    ///
+    /// Use this for distributions larger than `MAX_BATCH_SIZE`: send the
+    /// rewards afterwards with `submit_rewards_chunk`. `chunk_hashes` holds
+    /// one `shared::staged::chunk_hash` per chunk, in the order they will be
+    /// sent.
+    pub fn begin_staged_rewards(
+        env: Env,
+        caller: Address,
+        token: Address,
+        total_count: u32,
+        total_amount: i128,
+        chunk_hashes: Vec<BytesN<32>>,
+    ) -> u64 {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        staged::begin(
+            &env,
+            &caller,
+            &token,
+            total_count,
+            total_amount,
+            chunk_hashes,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, staged_error(e)))
+        .id
+    }
+
+    /// Checks the next chunk of a staged distribution against its header and
+    /// pays out its rewards.
+    ///
+    /// `cursor` must equal the number of rewards already received, every
+    /// amount must be positive and the chunk must match the next committed
+    /// chunk hash before anything is paid. The last committed chunk must
+    /// bring the distribution to the committed totals exactly, and finalizes
+    /// it. A rejected chunk changes nothing.
+    pub fn submit_rewards_chunk(
+        env: Env,
+        caller: Address,
+        staged_id: u64,
+        cursor: u32,
+        rewards: Vec<RewardRequest>,
+    ) -> BatchRewardResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let batch = staged::accept_chunk(
+            &env,
+            staged_id,
+            cursor,
+            &rewards,
+            MAX_BATCH_SIZE,
+            |reward| reward.amount,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, staged_error(e)));
+
+        Self::process_rewards(&env, &caller, &batch.token, &rewards)
+    }
+
+    /// Cancels an open staged distribution. Chunks already paid stay paid.
+    pub fn cancel_staged_rewards(env: Env, caller: Address, staged_id: u64) -> StagedBatch {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        staged::cancel(&env, staged_id).unwrap_or_else(|e| panic_with_error!(&env, staged_error(e)))
+    }
+
+    /// Returns a staged distribution header and its progress.
+    pub fn get_staged_batch(env: Env, staged_id: u64) -> Option<StagedBatch> {
+        staged::get(&env, staged_id)
+    }
+
+    // Distributes one batch (or staged chunk) of rewards and updates the stats
+    fn process_rewards(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+        rewards: &Vec<RewardRequest>,
+    ) -> BatchRewardResult {
+        let request_count = rewards.len();
+
         // Get batch ID and increment
         let batch_id: u64 = env
             .storage()
@@ -143,31 +267,31 @@ impl BatchRewardsContract {
             + 1;
 
         // Emit batch started event
-        RewardEvents::batch_started(&env, batch_id, request_count);
+        RewardEvents::batch_started(env, batch_id, request_count);
 
         // Initialize result vectors
-        let mut results: Vec<RewardResult> = Vec::new(&env);
+        let mut results: Vec<RewardResult> = Vec::new(env);
         let mut successful_count: u32 = 0;
         let mut failed_count: u32 = 0;
         let mut total_distributed: i128 = 0;
 
         // Create token client
-        let token_client = token::Client::new(&env, &token);
+        let token_client = token::Client::new(env, token);
 
         // Get initial balance to ensure sufficient funds
-        let available_balance = token_client.balance(&caller);
+        let available_balance = token_client.balance(caller);
         let total_required: i128 = rewards
             .iter()
             .fold(0i128, |sum, reward| sum + reward.amount);
 
         if available_balance < total_required {
-            panic_with_error!(&env, BatchRewardsError::InsufficientBalance);
+            panic_with_error!(env, BatchRewardsError::InsufficientBalance);
         }
 
         // Process each reward request
         for reward in rewards.iter() {
             // Validate reward amount
-            if let Err(_) = validate_amount(reward.amount) {
+            if validate_amount(reward.amount).is_err() {
                 failed_count += 1;
                 let error_code = BatchRewardsError::InvalidAmount as u32;
                 results.push_back(RewardResult::Failure(
@@ -176,7 +300,7 @@ impl BatchRewardsContract {
                     error_code,
                 ));
                 RewardEvents::reward_failure(
-                    &env,
+                    env,
                     batch_id,
                     &reward.recipient,
                     reward.amount,
@@ -186,7 +310,7 @@ impl BatchRewardsContract {
             }
 
             // Validate recipient address
-            if let Err(_) = validate_address(&env, &reward.recipient) {
+            if validate_address(env, &reward.recipient).is_err() {
                 failed_count += 1;
                 let error_code = BatchRewardsError::InvalidBatch as u32;
                 results.push_back(RewardResult::Failure(
@@ -195,7 +319,7 @@ impl BatchRewardsContract {
                     error_code,
                 ));
                 RewardEvents::reward_failure(
-                    &env,
+                    env,
                     batch_id,
                     &reward.recipient,
                     reward.amount,
@@ -205,7 +329,7 @@ impl BatchRewardsContract {
             }
 
             // Attempt to transfer the reward
-            match token_client.try_transfer(&caller, &reward.recipient, &reward.amount) {
+            match token_client.try_transfer(caller, &reward.recipient, &reward.amount) {
                 Ok(_) => {
                     successful_count += 1;
                     total_distributed += reward.amount;
@@ -213,7 +337,7 @@ impl BatchRewardsContract {
                         reward.recipient.clone(),
                         reward.amount,
                     ));
-                    RewardEvents::reward_success(&env, batch_id, &reward.recipient, reward.amount);
+                    RewardEvents::reward_success(env, batch_id, &reward.recipient, reward.amount);
                 }
                 Err(_) => {
                     failed_count += 1;
@@ -224,7 +348,7 @@ impl BatchRewardsContract {
                         error_code,
                     ));
                     RewardEvents::reward_failure(
-                        &env,
+                        env,
                         batch_id,
                         &reward.recipient,
                         reward.amount,
@@ -261,7 +385,7 @@ impl BatchRewardsContract {
 
         // Emit batch completed event
         RewardEvents::batch_completed(
-            &env,
+            env,
             batch_id,
             successful_count,
             failed_count,
@@ -269,7 +393,7 @@ impl BatchRewardsContract {
         );
 
         BatchRewardResult {
-            total_requests: request_count as u32,
+            total_requests: request_count,
             successful: successful_count,
             failed: failed_count,
             total_distributed,
@@ -290,3 +414,18 @@ impl BatchRewardsContract {
         }
     }
 }
+
+// Maps a staged batch rejection to the contract error
+fn staged_error(error: StagedError) -> BatchRewardsError {
+    match error {
+        StagedError::InvalidHeader => BatchRewardsError::InvalidStagedHeader,
+        StagedError::NotFound => BatchRewardsError::StagedBatchNotFound,
+        StagedError::NotOpen => BatchRewardsError::StagedBatchClosed,
+        StagedError::CursorMismatch => BatchRewardsError::CursorMismatch,
+        StagedError::EmptyChunk => BatchRewardsError::EmptyBatch,
+        StagedError::ChunkTooLarge => BatchRewardsError::BatchTooLarge,
+        StagedError::InvalidAmount => BatchRewardsError::InvalidAmount,
+        StagedError::TotalExceeded => BatchRewardsError::StagedTotalExceeded,
+        StagedError::HashMismatch => BatchRewardsError::StagedHashMismatch,
+    }
+}
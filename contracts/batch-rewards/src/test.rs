@@ -2,10 +2,13 @@
 
 #![cfg(test)]
 
-use crate::{BatchRewardsContract, BatchRewardsContractClient, RewardRequest, RewardResult};
+use crate::{
+    BatchRewardsContract, BatchRewardsContractClient, BatchRewardsError, RewardRequest,
+    RewardResult, StagedStatus,
+};
 use soroban_sdk::{
     testutils::{Address as _, Events as _, Ledger},
-    token, Address, Env, Vec,
+    token, Address, BytesN, Env, Symbol, TryFromVal, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -38,11 +41,29 @@ fn setup_test_env() -> (
     (env, admin, token_id, token_client, client)
 }
 
+/// Returns true if the last invocation emitted an event with `topic` among its topics.
+fn has_event_topic(env: &Env, topic: Symbol) -> bool {
+    env.events().all().iter().any(|(_, topics, _)| {
+        topics
+            .iter()
+            .any(|t| Symbol::try_from_val(env, &t).is_ok_and(|s| s == topic))
+    })
+}
+
 /// Helper to create a reward request.
 fn create_reward_request(_env: &Env, recipient: Address, amount: i128) -> RewardRequest {
     RewardRequest { recipient, amount }
 }
 
+/// Hashes each chunk the way a staged batch header commits to them.
+fn staged_chunk_hashes(env: &Env, chunks: &[Vec<RewardRequest>]) -> Vec<BytesN<32>> {
+    let mut hashes = Vec::new(env);
+    for chunk in chunks {
+        hashes.push_back(shared::staged::chunk_hash(env, chunk));
+    }
+    hashes
+}
+
 // Initialization Tests
 
 #[test]
@@ -58,7 +79,7 @@ fn test_initialize_contract() {
 #[test]
 #[should_panic(expected = "Contract already initialized")]
 fn test_cannot_initialize_twice() {
-    let (env, admin, _token, _token_client, client) = setup_test_env();
+    let (env, _admin, _token, _token_client, client) = setup_test_env();
 
     let new_admin = Address::generate(&env);
     client.initialize(&new_admin);
@@ -84,7 +105,7 @@ fn test_distribute_rewards_single_recipient() {
     let reward_amount: i128 = 10_000_000; // 1 XLM equivalent
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(reward_amount * 2));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(reward_amount * 2));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(
@@ -93,7 +114,7 @@ fn test_distribute_rewards_single_recipient() {
         reward_amount,
     ));
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     assert_eq!(result.total_requests, 1);
     assert_eq!(result.successful, 1);
@@ -115,14 +136,14 @@ fn test_distribute_rewards_multiple_recipients() {
     let amount: i128 = 5_000_000;
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * 3 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount * 3 + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient1.clone(), amount));
     rewards.push_back(create_reward_request(&env, recipient2.clone(), amount));
     rewards.push_back(create_reward_request(&env, recipient3.clone(), amount));
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 3);
@@ -145,7 +166,7 @@ fn test_distribute_rewards_partial_failures() {
     let invalid_amount: i128 = -1_000_000; // Invalid amount
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(valid_amount * 2));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(valid_amount * 2));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(
@@ -159,7 +180,7 @@ fn test_distribute_rewards_partial_failures() {
         invalid_amount,
     ));
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -173,21 +194,21 @@ fn test_distribute_rewards_partial_failures() {
 
 #[test]
 fn test_distribute_rewards_accumulates_stats() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient1 = Address::generate(&env);
     let recipient2 = Address::generate(&env);
     let amount: i128 = 5_000_000;
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * 4 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount * 4 + 10_000_000));
 
     // First batch
     let mut rewards = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient1.clone(), amount));
     rewards.push_back(create_reward_request(&env, recipient2.clone(), amount));
 
-    let result1 = client.distribute_rewards(&admin, &token, &rewards);
+    let result1 = client.distribute_rewards(&admin, &token, &rewards, &None);
     assert_eq!(result1.total_distributed, amount * 2);
 
     // Check stats after first batch
@@ -200,7 +221,7 @@ fn test_distribute_rewards_accumulates_stats() {
     rewards.push_back(create_reward_request(&env, recipient1.clone(), amount));
     rewards.push_back(create_reward_request(&env, recipient2.clone(), amount));
 
-    let result2 = client.distribute_rewards(&admin, &token, &rewards);
+    let result2 = client.distribute_rewards(&admin, &token, &rewards, &None);
     assert_eq!(result2.total_distributed, amount * 2);
 
     // Check accumulated stats
@@ -211,22 +232,23 @@ fn test_distribute_rewards_accumulates_stats() {
 
 #[test]
 fn test_distribute_rewards_large_batch() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let amount: i128 = 1_000_000;
     let batch_size = 50u32;
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * batch_size as i128 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token)
+        .mint(&admin, &(amount * batch_size as i128 + 10_000_000));
 
     // Create batch of rewards
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
-    for i in 0..batch_size {
+    for _ in 0..batch_size {
         let recipient = Address::generate(&env);
         rewards.push_back(create_reward_request(&env, recipient, amount));
     }
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     assert_eq!(result.total_requests, batch_size);
     assert_eq!(result.successful, batch_size);
@@ -235,24 +257,25 @@ fn test_distribute_rewards_large_batch() {
 }
 
 #[test]
-#[should_panic(expected = "EmptyBatch")]
+#[should_panic(expected = "Error(Contract, #4)")] // EmptyBatch
 fn test_distribute_rewards_empty_batch() {
     let (env, admin, token, _token_client, client) = setup_test_env();
 
     let rewards: Vec<RewardRequest> = Vec::new(&env);
-    client.distribute_rewards(&admin, &token, &rewards);
+    client.distribute_rewards(&admin, &token, &rewards, &None);
 }
 
 #[test]
-#[should_panic(expected = "BatchTooLarge")]
+#[should_panic(expected = "Error(Contract, #5)")] // BatchTooLarge
 fn test_distribute_rewards_batch_too_large() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let amount: i128 = 1_000_000;
     let batch_size = 101u32; // Exceeds MAX_BATCH_SIZE of 100
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * batch_size as i128 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token)
+        .mint(&admin, &(amount * batch_size as i128 + 10_000_000));
 
     // Create oversized batch
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
@@ -261,104 +284,88 @@ fn test_distribute_rewards_batch_too_large() {
         rewards.push_back(create_reward_request(&env, recipient, amount));
     }
 
-    client.distribute_rewards(&admin, &token, &rewards);
+    client.distribute_rewards(&admin, &token, &rewards, &None);
 }
 
 #[test]
-#[should_panic(expected = "InsufficientBalance")]
+#[should_panic(expected = "Error(Contract, #7)")] // InsufficientBalance
 fn test_distribute_rewards_insufficient_balance() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let amount: i128 = 10_000_000;
 
     // Mint only half of what's needed
-    token_client.mint(&admin, &(amount / 2));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount / 2));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient, amount));
 
-    client.distribute_rewards(&admin, &token, &rewards);
+    client.distribute_rewards(&admin, &token, &rewards, &None);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #2)")] // Unauthorized
 fn test_distribute_rewards_unauthorized() {
-    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (env, _admin, token, _token_client, client) = setup_test_env();
 
     let unauthorized_caller = Address::generate(&env);
     let recipient = Address::generate(&env);
     let amount: i128 = 10_000_000;
 
-    token_client.mint(&unauthorized_caller, &amount);
+    token::StellarAssetClient::new(&env, &token).mint(&unauthorized_caller, &amount);
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient, amount));
 
-    client.distribute_rewards(&unauthorized_caller, &token, &rewards);
+    client.distribute_rewards(&unauthorized_caller, &token, &rewards, &None);
 }
 
 #[test]
 fn test_distribute_rewards_events_emitted() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let amount: i128 = 10_000_000;
 
-    token_client.mint(&admin, &(amount + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient.clone(), amount));
 
-    client.distribute_rewards(&admin, &token, &rewards);
+    client.distribute_rewards(&admin, &token, &rewards, &None);
 
     // Verify events were emitted
-    let events = env.events().all();
-    assert!(events.len() > 0);
+    assert!(!env.events().all().is_empty());
 
     // Check for batch_started event
-    let has_batch_started = events.iter().any(|event| {
-        event
-            .1
-            .iter()
-            .any(|topic: &soroban_sdk::Val| topic.to_string().contains("batch"))
-    });
+    let has_batch_started = has_event_topic(&env, Symbol::new(&env, "started"));
     assert!(has_batch_started, "batch_started event not found");
 
     // Check for reward_success event
-    let has_reward_success = events.iter().any(|event| {
-        event
-            .1
-            .iter()
-            .any(|topic: &soroban_sdk::Val| topic.to_string().contains("success"))
-    });
+    let has_reward_success = has_event_topic(&env, Symbol::new(&env, "success"));
     assert!(has_reward_success, "reward_success event not found");
 
     // Check for batch_completed event
-    let has_batch_completed = events.iter().any(|event| {
-        event
-            .1
-            .iter()
-            .any(|topic: &soroban_sdk::Val| topic.to_string().contains("completed"))
-    });
+    let has_batch_completed = has_event_topic(&env, Symbol::new(&env, "completed"));
     assert!(has_batch_completed, "batch_completed event not found");
 }
 
 #[test]
 fn test_distribute_rewards_with_zero_amount() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let valid_amount: i128 = 5_000_000;
     let zero_amount: i128 = 0;
 
-    token_client.mint(&admin, &(valid_amount + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(valid_amount + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient.clone(), valid_amount));
     rewards.push_back(create_reward_request(&env, recipient.clone(), zero_amount));
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -367,12 +374,12 @@ fn test_distribute_rewards_with_zero_amount() {
 
 #[test]
 fn test_distribute_rewards_events_on_failure() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let invalid_amount: i128 = -5_000_000;
 
-    token_client.mint(&admin, &(10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(
@@ -381,38 +388,31 @@ fn test_distribute_rewards_events_on_failure() {
         invalid_amount,
     ));
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     assert_eq!(result.failed, 1);
 
-    let events = env.events().all();
-
     // Check for failure event
-    let has_failure_event = events.iter().any(|event| {
-        event
-            .1
-            .iter()
-            .any(|topic: &soroban_sdk::Val| topic.to_string().contains("failure"))
-    });
+    let has_failure_event = has_event_topic(&env, Symbol::new(&env, "failure"));
     assert!(has_failure_event, "reward_failure event not found");
 }
 
 #[test]
 fn test_distribute_rewards_result_structure() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient1 = Address::generate(&env);
     let recipient2 = Address::generate(&env);
     let amount1: i128 = 5_000_000;
     let amount2: i128 = 3_000_000;
 
-    token_client.mint(&admin, &(amount1 + amount2 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount1 + amount2 + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient1.clone(), amount1));
     rewards.push_back(create_reward_request(&env, recipient2.clone(), amount2));
 
-    let result = client.distribute_rewards(&admin, &token, &rewards);
+    let result = client.distribute_rewards(&admin, &token, &rewards, &None);
 
     // Verify result structure
     assert_eq!(result.total_requests, 2);
@@ -423,16 +423,16 @@ fn test_distribute_rewards_result_structure() {
     // Verify individual results
     match result.results.get(0).unwrap() {
         RewardResult::Success(addr, amt) => {
-            assert_eq!(*addr, recipient1);
-            assert_eq!(*amt, amount1);
+            assert_eq!(addr, recipient1);
+            assert_eq!(amt, amount1);
         }
         _ => panic!("Expected success result"),
     }
 
     match result.results.get(1).unwrap() {
         RewardResult::Success(addr, amt) => {
-            assert_eq!(*addr, recipient2);
-            assert_eq!(*amt, amount2);
+            assert_eq!(addr, recipient2);
+            assert_eq!(amt, amount2);
         }
         _ => panic!("Expected success result"),
     }
@@ -442,12 +442,15 @@ fn test_distribute_rewards_result_structure() {
 fn test_multiple_simultaneous_batch_distributions() {
     let (env, admin, token, token_client, client) = setup_test_env();
 
-    let recipients: Vec<Address> = (0..10).map(|_| Address::generate(&env)).collect::<Vec<_>>();
+    let mut recipients: Vec<Address> = Vec::new(&env);
+    for _ in 0..10 {
+        recipients.push_back(Address::generate(&env));
+    }
 
     let amount: i128 = 2_000_000;
 
     // Mint sufficient tokens
-    token_client.mint(&admin, &(amount * 30 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount * 30 + 10_000_000));
 
     // Execute 3 batches
     for _batch in 0..3 {
@@ -456,7 +459,7 @@ fn test_multiple_simultaneous_batch_distributions() {
             rewards.push_back(create_reward_request(&env, recipient.clone(), amount));
         }
 
-        let result = client.distribute_rewards(&admin, &token, &rewards);
+        let result = client.distribute_rewards(&admin, &token, &rewards, &None);
         assert_eq!(result.successful, 10);
         assert_eq!(result.total_distributed, amount * 10);
     }
@@ -471,3 +474,226 @@ fn test_multiple_simultaneous_batch_distributions() {
         assert_eq!(token_client.balance(&recipient), amount * 3);
     }
 }
+
+// Idempotency Tests
+
+#[test]
+fn test_distribute_rewards_retry_with_same_key_is_not_reexecuted() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    let first = client.distribute_rewards(&admin, &token, &rewards, &key);
+    let retry = client.distribute_rewards(&admin, &token, &rewards, &key);
+
+    assert_eq!(retry.successful, first.successful);
+    assert_eq!(retry.total_distributed, first.total_distributed);
+    assert_eq!(token_client.balance(&recipient), 10_000_000);
+    assert_eq!(client.get_total_batches(), 1);
+
+    // A different key executes again
+    let other_key = Some(BytesN::from_array(&env, &[8; 32]));
+    client.distribute_rewards(&admin, &token, &rewards, &other_key);
+    assert_eq!(token_client.balance(&recipient), 20_000_000);
+}
+
+#[test]
+fn test_distribute_rewards_key_reused_with_different_rewards_is_rejected() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+    client.distribute_rewards(&admin, &token, &rewards, &key);
+
+    let mut changed: Vec<RewardRequest> = Vec::new(&env);
+    changed.push_back(create_reward_request(&env, recipient.clone(), 20_000_000));
+    assert!(client
+        .try_distribute_rewards(&admin, &token, &changed, &key)
+        .is_err());
+
+    assert_eq!(token_client.balance(&recipient), 10_000_000);
+    assert_eq!(client.get_total_batches(), 1);
+}
+
+#[test]
+fn test_distribute_rewards_key_expires_after_retention_window() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    client.distribute_rewards(&admin, &token, &rewards, &key);
+    env.ledger().with_mut(|li| {
+        li.timestamp += shared::idempotency::DEFAULT_RETENTION_SECS + 1;
+    });
+    client.distribute_rewards(&admin, &token, &rewards, &key);
+
+    assert_eq!(token_client.balance(&recipient), 20_000_000);
+    assert_eq!(client.get_total_batches(), 2);
+}
+
+// Staged Batch Tests
+
+#[test]
+fn test_staged_rewards_finalize_across_chunks() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    for amount in [1_000_000, 2_000_000, 3_000_000] {
+        rewards.push_back(create_reward_request(&env, Address::generate(&env), amount));
+    }
+    let chunks = [rewards.slice(0..2), rewards.slice(2..3)];
+    let staged_id = client.begin_staged_rewards(
+        &admin,
+        &token,
+        &3,
+        &6_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    let first = client.submit_rewards_chunk(&admin, &staged_id, &0, &chunks[0]);
+    assert_eq!(first.successful, 2);
+    assert_eq!(
+        client.get_staged_batch(&staged_id).unwrap().status,
+        StagedStatus::Open
+    );
+
+    let last = client.submit_rewards_chunk(&admin, &staged_id, &2, &chunks[1]);
+    assert_eq!(last.successful, 1);
+
+    let staged = client.get_staged_batch(&staged_id).unwrap();
+    assert_eq!(staged.status, StagedStatus::Finalized);
+    assert_eq!(staged.received_amount, 6_000_000);
+    assert_eq!(token_client.balance(&admin), 94_000_000);
+}
+
+#[test]
+fn test_staged_rewards_reject_cursor_mismatch() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        1_000_000,
+    ));
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        2_000_000,
+    ));
+    let chunks = [rewards.slice(0..1), rewards.slice(1..2)];
+    let staged_id = client.begin_staged_rewards(
+        &admin,
+        &token,
+        &2,
+        &3_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    let result = client.try_submit_rewards_chunk(&admin, &staged_id, &1, &chunks[0]);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchRewardsError::CursorMismatch.into()))
+    );
+    assert_eq!(
+        client.get_staged_batch(&staged_id).unwrap().received_count,
+        0
+    );
+}
+
+#[test]
+fn test_staged_rewards_reject_tampered_chunk_before_paying() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        1_000_000,
+    ));
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        2_000_000,
+    ));
+    let chunks = [rewards.slice(0..1), rewards.slice(1..2)];
+    let staged_id = client.begin_staged_rewards(
+        &admin,
+        &token,
+        &2,
+        &3_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    // Same amount, different recipient, in the first of two chunks
+    let mut tampered: Vec<RewardRequest> = Vec::new(&env);
+    tampered.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        1_000_000,
+    ));
+    let result = client.try_submit_rewards_chunk(&admin, &staged_id, &0, &tampered);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchRewardsError::StagedHashMismatch.into()))
+    );
+
+    let staged = client.get_staged_batch(&staged_id).unwrap();
+    assert_eq!(staged.status, StagedStatus::Open);
+    assert_eq!(staged.received_count, 0);
+    assert_eq!(token_client.balance(&admin), 100_000_000);
+}
+
+#[test]
+fn test_staged_rewards_reject_non_positive_amount() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        4_000_000,
+    ));
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        -1_000_000,
+    ));
+    let chunks = [rewards.clone()];
+    let staged_id = client.begin_staged_rewards(
+        &admin,
+        &token,
+        &2,
+        &3_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    let result = client.try_submit_rewards_chunk(&admin, &staged_id, &0, &rewards);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchRewardsError::InvalidAmount.into()))
+    );
+    assert_eq!(
+        client.get_staged_batch(&staged_id).unwrap().received_amount,
+        0
+    );
+    assert_eq!(token_client.balance(&admin), 100_000_000);
+}
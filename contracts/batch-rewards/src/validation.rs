@@ -7,8 +7,6 @@ use soroban_sdk::{Address, Env};
 pub enum ValidationError {
     /// Invalid reward amount
     InvalidAmount,
-    /// Invalid recipient address
-    InvalidRecipient,
 }
 
 /// Validates a recipient address.
@@ -24,13 +24,14 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use shared::idempotency::{self, DEFAULT_RETENTION_SECS};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, Vec};
 
 pub use crate::types::{
     BatchMintMetrics, BatchMintResult, DataKey, ErrorCode, MintEvents, MintResult,
     TokenMintRequest, TokenMinted, MAX_BATCH_SIZE,
 };
-use crate::validation::validate_mint_request;
+use crate::validation::{validate_batch, validate_mint_request};
 
 /// Error codes for the batch token mint contract.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -87,6 +88,7 @@ impl BatchTokenMintContract {
     /// * `caller` - The address calling this function (must be admin)
     /// * `token` - The token contract address to mint from
     /// * `requests` - Vector of mint requests
+    /// * `idempotency_key` - Optional client key; a retry within the retention window returns the original result
     ///
     /// # Returns
     /// * `BatchMintResult` - Result containing minted tokens and metrics
@@ -107,14 +109,27 @@ impl BatchTokenMintContract {
         caller: Address,
         token: Address,
         requests: Vec<TokenMintRequest>,
+        idempotency_key: Option<BytesN<32>>,
     ) -> BatchMintResult {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
+        // Return the stored result if this batch was already executed
+        let idempotency_args = (caller.clone(), token.clone(), requests.clone());
+        if let Some(result) = idempotency::replay(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            DEFAULT_RETENTION_SECS,
+        ) {
+            return result;
+        }
+
         // Validate batch size
         let request_count = requests.len();
-        if request_count == 0 {
+        if validate_batch(&requests).is_err() {
             panic_with_error!(&env, BatchTokenMintError::EmptyBatch);
         }
         if request_count > MAX_BATCH_SIZE {
@@ -135,9 +150,6 @@ impl BatchTokenMintContract {
         // Get current ledger timestamp
         let current_ledger = env.ledger().sequence() as u64;
 
-        // Initialize token client
-        let token_client = token::Client::new(&env, &token);
-
         // Initialize result tracking
         let mut results: Vec<MintResult> = Vec::new(&env);
         let mut successful_count: u32 = 0;
@@ -244,7 +256,7 @@ impl BatchTokenMintContract {
             total_amount_minted,
         );
 
-        BatchMintResult {
+        let result = BatchMintResult {
             batch_id,
             token_address: token,
             total_requests: request_count,
@@ -252,7 +264,16 @@ impl BatchTokenMintContract {
             failed: failed_count,
             results,
             metrics,
-        }
+        };
+        idempotency::record(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            &result,
+            DEFAULT_RETENTION_SECS,
+        );
+        result
     }
 
     /// Returns the admin address.
@@ -1,9 +1,13 @@
 //! Comprehensive unit and integration tests for batch token minting.
 
 #![cfg(test)]
+extern crate std;
 
 use crate::{BatchTokenMintContract, BatchTokenMintContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Vec,
+};
 
 use crate::types::{ErrorCode, MintResult, TokenMintRequest};
 
@@ -55,7 +59,7 @@ fn test_batch_mint_single_recipient() {
     let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
     requests.push_back(create_valid_request(&env, 100_000_000));
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 1);
     assert_eq!(result.failed, 0);
@@ -74,7 +78,7 @@ fn test_batch_mint_multiple_recipients() {
     requests.push_back(create_valid_request(&env, 200_000_000));
     requests.push_back(create_valid_request(&env, 150_000_000));
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 3);
     assert_eq!(result.failed, 0);
@@ -93,7 +97,7 @@ fn test_batch_mint_metrics() {
         requests.push_back(create_valid_request(&env, 50_000_000));
     }
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.metrics.total_requests, 5);
     assert_eq!(result.metrics.successful_mints, 5);
@@ -112,7 +116,7 @@ fn test_batch_mint_invalid_amount_zero() {
     invalid_req.amount = 0;
     requests.push_back(invalid_req);
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 0);
     assert_eq!(result.failed, 1);
@@ -135,7 +139,7 @@ fn test_batch_mint_invalid_amount_negative() {
     invalid_req.amount = -1000;
     requests.push_back(invalid_req);
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 0);
     assert_eq!(result.failed, 1);
@@ -171,7 +175,7 @@ fn test_batch_mint_partial_failures() {
     invalid2.amount = -100;
     requests.push_back(invalid2);
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.total_requests, 4);
     assert_eq!(result.successful, 2);
@@ -188,7 +192,7 @@ fn test_batch_mint_storage_updates() {
     let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
     requests.push_back(create_valid_request(&env, 100_000_000));
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(client.get_total_minted(), 100_000_000);
     assert_eq!(client.get_total_batches_processed(), 1);
@@ -203,12 +207,12 @@ fn test_batch_mint_multiple_batches() {
     // First batch
     let mut requests1: Vec<TokenMintRequest> = Vec::new(&env);
     requests1.push_back(create_valid_request(&env, 100_000_000));
-    let result1 = client.batch_mint_tokens(&admin, &token, &requests1);
+    let result1 = client.batch_mint_tokens(&admin, &token, &requests1, &None);
 
     // Second batch
     let mut requests2: Vec<TokenMintRequest> = Vec::new(&env);
     requests2.push_back(create_valid_request(&env, 200_000_000));
-    let result2 = client.batch_mint_tokens(&admin, &token, &requests2);
+    let result2 = client.batch_mint_tokens(&admin, &token, &requests2, &None);
 
     assert_eq!(client.get_total_minted(), 300_000_000);
     assert_eq!(client.get_total_batches_processed(), 2);
@@ -224,7 +228,7 @@ fn test_batch_mint_large_amount_event() {
     // This should trigger the large_mint event (>= 1 billion stroops)
     requests.push_back(create_valid_request(&env, 1_000_000_000));
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 1);
     assert_eq!(result.metrics.total_amount_minted, 1_000_000_000);
@@ -242,7 +246,7 @@ fn test_batch_mint_admin_update() {
 
 #[test]
 fn test_batch_mint_set_admin_unauthorized() {
-    let (env, admin, client) = setup_test_contract();
+    let (env, _admin, client) = setup_test_contract();
     let unauthorized = Address::generate(&env);
     let new_admin = Address::generate(&env);
 
@@ -262,7 +266,7 @@ fn test_batch_mint_empty_batch() {
     let requests: Vec<TokenMintRequest> = Vec::new(&env);
 
     let panic_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        client.batch_mint_tokens(&admin, &token, &requests);
+        client.batch_mint_tokens(&admin, &token, &requests, &None);
     }));
 
     assert!(panic_result.is_err());
@@ -280,7 +284,7 @@ fn test_batch_mint_too_large() {
     }
 
     let panic_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        client.batch_mint_tokens(&admin, &token, &requests);
+        client.batch_mint_tokens(&admin, &token, &requests, &None);
     }));
 
     assert!(panic_result.is_err());
@@ -294,13 +298,13 @@ fn test_batch_mint_batch_id_increment() {
     let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
     requests.push_back(create_valid_request(&env, 100_000_000));
 
-    let result1 = client.batch_mint_tokens(&admin, &token, &requests);
+    let result1 = client.batch_mint_tokens(&admin, &token, &requests, &None);
     assert_eq!(result1.batch_id, 1);
 
-    let result2 = client.batch_mint_tokens(&admin, &token, &requests);
+    let result2 = client.batch_mint_tokens(&admin, &token, &requests, &None);
     assert_eq!(result2.batch_id, 2);
 
-    let result3 = client.batch_mint_tokens(&admin, &token, &requests);
+    let result3 = client.batch_mint_tokens(&admin, &token, &requests, &None);
     assert_eq!(result3.batch_id, 3);
 }
 
@@ -314,7 +318,7 @@ fn test_batch_mint_all_valid_requests() {
         requests.push_back(create_valid_request(&env, i as i128 * 10_000_000));
     }
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 10);
     assert_eq!(result.failed, 0);
@@ -332,7 +336,7 @@ fn test_batch_mint_max_amount() {
     // Use a valid large amount (not the absolute max to avoid overflow)
     requests.push_back(create_valid_request(&env, 100_000_000_000_000_000));
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     assert_eq!(result.successful, 1);
     assert_eq!(result.failed, 0);
@@ -348,7 +352,7 @@ fn test_batch_mint_unauthorized_caller() {
     requests.push_back(create_valid_request(&env, 100_000_000));
 
     let panic_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        client.batch_mint_tokens(&unauthorized, &token, &requests);
+        client.batch_mint_tokens(&unauthorized, &token, &requests, &None);
     }));
 
     assert!(panic_result.is_err());
@@ -362,7 +366,7 @@ fn test_batch_mint_result_structure() {
     let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
     requests.push_back(create_valid_request(&env, 100_000_000));
 
-    let result = client.batch_mint_tokens(&admin, &token, &requests);
+    let result = client.batch_mint_tokens(&admin, &token, &requests, &None);
 
     // Verify result structure
     assert_eq!(result.batch_id, 1);
@@ -375,3 +379,65 @@ fn test_batch_mint_result_structure() {
     assert_eq!(result.metrics.successful_mints, 1);
     assert_eq!(result.metrics.failed_mints, 0);
 }
+
+// Idempotency Tests
+
+#[test]
+fn test_batch_mint_retry_with_same_key_is_not_reexecuted() {
+    let (env, admin, client) = setup_test_contract();
+    let token = Address::generate(&env);
+
+    let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, 100_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    let first = client.batch_mint_tokens(&admin, &token, &requests, &key);
+    let retry = client.batch_mint_tokens(&admin, &token, &requests, &key);
+
+    assert_eq!(retry.batch_id, first.batch_id);
+    assert_eq!(client.get_total_minted(), 100_000_000);
+    assert_eq!(client.get_total_batches_processed(), 1);
+
+    // A different key executes again
+    let other_key = Some(BytesN::from_array(&env, &[8; 32]));
+    let other = client.batch_mint_tokens(&admin, &token, &requests, &other_key);
+    assert_ne!(other.batch_id, first.batch_id);
+    assert_eq!(client.get_total_minted(), 200_000_000);
+}
+
+#[test]
+fn test_batch_mint_key_reused_with_different_requests_is_rejected() {
+    let (env, admin, client) = setup_test_contract();
+    let token = Address::generate(&env);
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, 100_000_000));
+    client.batch_mint_tokens(&admin, &token, &requests, &key);
+
+    let mut changed: Vec<TokenMintRequest> = Vec::new(&env);
+    changed.push_back(create_valid_request(&env, 100_000_000));
+    assert!(client
+        .try_batch_mint_tokens(&admin, &token, &changed, &key)
+        .is_err());
+    assert_eq!(client.get_total_batches_processed(), 1);
+}
+
+#[test]
+fn test_batch_mint_key_expires_after_retention_window() {
+    let (env, admin, client) = setup_test_contract();
+    let token = Address::generate(&env);
+
+    let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, 100_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    client.batch_mint_tokens(&admin, &token, &requests, &key);
+    env.ledger().with_mut(|li| {
+        li.timestamp += shared::idempotency::DEFAULT_RETENTION_SECS + 1;
+    });
+    client.batch_mint_tokens(&admin, &token, &requests, &key);
+
+    assert_eq!(client.get_total_minted(), 200_000_000);
+    assert_eq!(client.get_total_batches_processed(), 2);
+}
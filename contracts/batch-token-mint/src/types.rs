@@ -96,6 +96,7 @@ pub enum DataKey {
 }
 
 /// Error codes for token minting validation and execution.
+#[allow(non_snake_case)]
 pub mod ErrorCode {
     /// Invalid mint amount (too low or negative)
     pub const INVALID_AMOUNT: u32 = 0;
@@ -4,7 +4,10 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use shared::idempotency::{self, DEFAULT_RETENTION_SECS};
+use shared::staged::{self, StagedError};
+pub use shared::staged::{StagedBatch, StagedStatus};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, BytesN, Env, Vec};
 
 pub use crate::types::{
     BatchBurnResult, BatchTransferResult, BurnRequest, BurnResult, DataKey, TransferEvents,
@@ -28,6 +31,19 @@ pub enum BatchTransferError {
     BatchTooLarge = 5,
     /// Invalid token contract
     InvalidToken = 6,
+    /// Staged batch header has no items, a non-positive total or no chunk hashes
+    InvalidStagedHeader = 7,
+    /// Staged batch does not exist
+    StagedBatchNotFound = 8,
+    /// Staged batch is already finalized or cancelled
+    StagedBatchClosed = 9,
+    /// Chunk cursor does not match the items received so far
+    CursorMismatch = 10,
+    /// Chunk would exceed the committed item count, amount or number of chunks
+    StagedTotalExceeded = 11,
+    /// Chunk does not match its committed hash, or the last chunk leaves the
+    /// staged batch short of the committed count or amount
+    StagedHashMismatch = 12,
 }
 
 impl From<BatchTransferError> for soroban_sdk::Error {
@@ -58,16 +74,32 @@ impl BatchTransferContract {
     }
 
     /// Executes batch transfers of XLM to multiple recipients.
+    ///
+    /// A retry carrying the same `idempotency_key` within the retention window
+    /// returns the original result without executing the batch again.
     pub fn batch_transfer(
         env: Env,
         caller: Address,
         token: Address,
         transfers: Vec<TransferRequest>,
+        idempotency_key: Option<BytesN<32>>,
     ) -> BatchTransferResult {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
+        // Return the stored result if this batch was already executed
+        let idempotency_args = (caller.clone(), token.clone(), transfers.clone());
+        if let Some(result) = idempotency::replay(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            DEFAULT_RETENTION_SECS,
+        ) {
+            return result;
+        }
+
         // Validate batch size
         let request_count = transfers.len();
         if request_count == 0 {
@@ -77,165 +109,89 @@ impl BatchTransferContract {
             panic_with_error!(&env, BatchTransferError::BatchTooLarge);
         }
 
-        // Get batch ID and increment
-        let batch_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalBatches)
-            .unwrap_or(0)
-            + 1;
-
-        // Emit batch started event
-        TransferEvents::batch_started(&env, batch_id, request_count);
-
-        // Initialize result vectors
-        let mut results: Vec<TransferResult> = Vec::new(&env);
-        let mut successful_count: u32 = 0;
-        let mut failed_count: u32 = 0;
-        let mut total_transferred: i128 = 0;
-
-        // Create token client
-        let token_client = token::Client::new(&env, &token);
-
-        // Get initial balance
-        let mut available_balance = token_client.balance(&caller);
-
-        // Calculate total needed for all valid transfers and validate upfront
-        let mut total_needed: i128 = 0;
-        let mut validated_requests: Vec<(TransferRequest, bool, u32)> = Vec::new(&env);
-
-        // First pass: Validate all requests and calculate total needed
-        for request in transfers.iter() {
-            let mut is_valid = true;
-            let mut error_code = 0u32;
-
-            // Validate recipient address
-            if validate_address(&env, &request.recipient).is_err() {
-                is_valid = false;
-                error_code = 0; // Invalid address
-            }
-            // Validate amount
-            else if validate_amount(request.amount).is_err() {
-                is_valid = false;
-                error_code = 1; // Invalid amount
-            }
-
-            if is_valid {
-                total_needed = total_needed
-                    .checked_add(request.amount)
-                    .unwrap_or(i128::MAX);
-            }
-
-            validated_requests.push_back((request.clone(), is_valid, error_code));
-        }
-
-        // Second pass: Process each request
-        for (request, is_valid, error_code) in validated_requests.iter() {
-            if !is_valid {
-                // Validation failed - record and continue
-                results.push_back(TransferResult::Failure(
-                    request.recipient.clone(),
-                    request.amount,
-                    error_code.clone(),
-                ));
-                failed_count += 1;
-                TransferEvents::transfer_failure(
-                    &env,
-                    batch_id,
-                    &request.recipient,
-                    request.amount,
-                    error_code.clone(),
-                );
-                continue;
-            }
-
-            // Check balance for this transfer
-            if available_balance < request.amount {
-                // Insufficient balance
-                results.push_back(TransferResult::Failure(
-                    request.recipient.clone(),
-                    request.amount,
-                    2, // Insufficient balance
-                ));
-                failed_count += 1;
-                TransferEvents::transfer_failure(
-                    &env,
-                    batch_id,
-                    &request.recipient,
-                    request.amount,
-                    2,
-                );
-                continue;
-            }
+        let result = Self::process_transfers(&env, &caller, &token, &transfers);
+        idempotency::record(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            &result,
+            DEFAULT_RETENTION_SECS,
+        );
+        result
+    }
 
-            // Execute transfer
-            // Note: After thorough validation, transfers should succeed.
-            // If a transfer fails due to contract-level issues (authorization, etc.),
-            // it will panic and revert the entire batch. This is acceptable as
-            // we've validated all inputs and balances.
-            token_client.transfer(&caller, &request.recipient, &request.amount);
+    /// Commits the header of a staged transfer batch and returns its ID.
+    ///
+    /// Use this for batches larger than `MAX_BATCH_SIZE`: send the items
+    /// afterwards with `submit_transfer_chunk`. `chunk_hashes` holds one
+    /// `shared::staged::chunk_hash` per chunk, in the order they will be sent.
+    pub fn begin_staged_transfer(
+        env: Env,
+        caller: Address,
+        token: Address,
+        total_count: u32,
+        total_amount: i128,
+        chunk_hashes: Vec<BytesN<32>>,
+    ) -> u64 {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-            // Transfer succeeded
-            available_balance -= request.amount;
-            results.push_back(TransferResult::Success(
-                request.recipient.clone(),
-                request.amount,
-            ));
-            successful_count += 1;
-            total_transferred = total_transferred
-                .checked_add(request.amount)
-                .unwrap_or(total_transferred);
+        staged::begin(
+            &env,
+            &caller,
+            &token,
+            total_count,
+            total_amount,
+            chunk_hashes,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, staged_error(e)))
+        .id
+    }
 
-            TransferEvents::transfer_success(&env, batch_id, &request.recipient, request.amount);
-        }
+    /// Checks the next chunk of a staged batch against its header and
+    /// executes its transfers.
+    ///
+    /// `cursor` must equal the number of items already received, every
+    /// amount must be positive and the chunk must match the next committed
+    /// chunk hash before any transfer runs. The running count and amount may
+    /// never exceed the header; the last committed chunk must bring them to
+    /// the committed totals exactly, and finalizes the batch. A rejected
+    /// chunk changes nothing.
+    pub fn submit_transfer_chunk(
+        env: Env,
+        caller: Address,
+        staged_id: u64,
+        cursor: u32,
+        transfers: Vec<TransferRequest>,
+    ) -> BatchTransferResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-        // Update storage (batched at the end for efficiency)
-        let total_batches: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalBatches)
-            .unwrap_or(0);
-        let total_processed: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalTransfersProcessed)
-            .unwrap_or(0);
-        let total_volume: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalVolumeTransferred)
-            .unwrap_or(0);
+        let batch = staged::accept_chunk(
+            &env,
+            staged_id,
+            cursor,
+            &transfers,
+            MAX_BATCH_SIZE,
+            |request| request.amount,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, staged_error(e)));
+
+        Self::process_transfers(&env, &caller, &batch.token, &transfers)
+    }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalBatches, &(total_batches + 1));
-        env.storage().instance().set(
-            &DataKey::TotalTransfersProcessed,
-            &(total_processed + request_count as u64),
-        );
-        env.storage().instance().set(
-            &DataKey::TotalVolumeTransferred,
-            &total_transferred
-                .checked_add(total_volume)
-                .unwrap_or(i128::MAX),
-        );
+    /// Cancels an open staged batch. Chunks already executed stay executed.
+    pub fn cancel_staged_transfer(env: Env, caller: Address, staged_id: u64) -> StagedBatch {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-        // Emit batch completed event
-        TransferEvents::batch_completed(
-            &env,
-            batch_id,
-            successful_count,
-            failed_count,
-            total_transferred,
-        );
+        staged::cancel(&env, staged_id).unwrap_or_else(|e| panic_with_error!(&env, staged_error(e)))
+    }
 
-        BatchTransferResult {
-            total_requests: request_count,
-            successful: successful_count,
-            failed: failed_count,
-            total_transferred,
-            results,
-        }
+    /// Returns a staged batch header and its progress.
+    pub fn get_staged_batch(env: Env, staged_id: u64) -> Option<StagedBatch> {
+        staged::get(&env, staged_id)
     }
 
     pub fn batch_burn(
@@ -381,6 +337,176 @@ impl BatchTransferContract {
             .unwrap_or(0)
     }
 
+    // Executes one batch (or staged chunk) of transfers and updates the stats
+    fn process_transfers(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+        transfers: &Vec<TransferRequest>,
+    ) -> BatchTransferResult {
+        let request_count = transfers.len();
+
+        // Get batch ID and increment
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBatches)
+            .unwrap_or(0)
+            + 1;
+
+        // Emit batch started event
+        TransferEvents::batch_started(env, batch_id, request_count);
+
+        // Initialize result vectors
+        let mut results: Vec<TransferResult> = Vec::new(env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+        let mut total_transferred: i128 = 0;
+
+        // Create token client
+        let token_client = token::Client::new(env, token);
+
+        // Get initial balance
+        let mut available_balance = token_client.balance(caller);
+
+        // Calculate total needed for all valid transfers and validate upfront
+        let mut total_needed: i128 = 0;
+        let mut validated_requests: Vec<(TransferRequest, bool, u32)> = Vec::new(env);
+
+        // First pass: Validate all requests and calculate total needed
+        for request in transfers.iter() {
+            let mut is_valid = true;
+            let mut error_code = 0u32;
+
+            // Validate recipient address
+            if validate_address(env, &request.recipient).is_err() {
+                is_valid = false;
+                error_code = 0; // Invalid address
+            }
+            // Validate amount
+            else if validate_amount(request.amount).is_err() {
+                is_valid = false;
+                error_code = 1; // Invalid amount
+            }
+
+            if is_valid {
+                total_needed = total_needed
+                    .checked_add(request.amount)
+                    .unwrap_or(i128::MAX);
+            }
+
+            validated_requests.push_back((request.clone(), is_valid, error_code));
+        }
+
+        // Second pass: Process each request
+        for (request, is_valid, error_code) in validated_requests.iter() {
+            if !is_valid {
+                // Validation failed - record and continue
+                results.push_back(TransferResult::Failure(
+                    request.recipient.clone(),
+                    request.amount,
+                    error_code,
+                ));
+                failed_count += 1;
+                TransferEvents::transfer_failure(
+                    env,
+                    batch_id,
+                    &request.recipient,
+                    request.amount,
+                    error_code,
+                );
+                continue;
+            }
+
+            // Check balance for this transfer
+            if available_balance < request.amount {
+                // Insufficient balance
+                results.push_back(TransferResult::Failure(
+                    request.recipient.clone(),
+                    request.amount,
+                    2, // Insufficient balance
+                ));
+                failed_count += 1;
+                TransferEvents::transfer_failure(
+                    env,
+                    batch_id,
+                    &request.recipient,
+                    request.amount,
+                    2,
+                );
+                continue;
+            }
+
+            // Execute transfer
+            // Note: After thorough validation, transfers should succeed.
+            // If a transfer fails due to contract-level issues (authorization, etc.),
+            // it will panic and revert the entire batch. This is acceptable as
+            // we've validated all inputs and balances.
+            token_client.transfer(caller, &request.recipient, &request.amount);
+
+            // Transfer succeeded
+            available_balance -= request.amount;
+            results.push_back(TransferResult::Success(
+                request.recipient.clone(),
+                request.amount,
+            ));
+            successful_count += 1;
+            total_transferred = total_transferred
+                .checked_add(request.amount)
+                .unwrap_or(total_transferred);
+
+            TransferEvents::transfer_success(env, batch_id, &request.recipient, request.amount);
+        }
+
+        // Update storage (batched at the end for efficiency)
+        let total_batches: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBatches)
+            .unwrap_or(0);
+        let total_processed: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalTransfersProcessed)
+            .unwrap_or(0);
+        let total_volume: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalVolumeTransferred)
+            .unwrap_or(0);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBatches, &(total_batches + 1));
+        env.storage().instance().set(
+            &DataKey::TotalTransfersProcessed,
+            &(total_processed + request_count as u64),
+        );
+        env.storage().instance().set(
+            &DataKey::TotalVolumeTransferred,
+            &total_transferred
+                .checked_add(total_volume)
+                .unwrap_or(i128::MAX),
+        );
+
+        // Emit batch completed event
+        TransferEvents::batch_completed(
+            env,
+            batch_id,
+            successful_count,
+            failed_count,
+            total_transferred,
+        );
+
+        BatchTransferResult {
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            total_transferred,
+            results,
+        }
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -395,5 +521,20 @@ impl BatchTransferContract {
     }
 }
 
+// Maps a staged batch rejection to the contract error
+fn staged_error(error: StagedError) -> BatchTransferError {
+    match error {
+        StagedError::InvalidHeader => BatchTransferError::InvalidStagedHeader,
+        StagedError::NotFound => BatchTransferError::StagedBatchNotFound,
+        StagedError::NotOpen => BatchTransferError::StagedBatchClosed,
+        StagedError::CursorMismatch => BatchTransferError::CursorMismatch,
+        StagedError::EmptyChunk => BatchTransferError::EmptyBatch,
+        StagedError::ChunkTooLarge => BatchTransferError::BatchTooLarge,
+        StagedError::InvalidAmount => BatchTransferError::InvalidBatch,
+        StagedError::TotalExceeded => BatchTransferError::StagedTotalExceeded,
+        StagedError::HashMismatch => BatchTransferError::StagedHashMismatch,
+    }
+}
+
 #[cfg(test)]
 mod test;
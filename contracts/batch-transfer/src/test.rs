@@ -3,12 +3,12 @@
 #![cfg(test)]
 
 use crate::{
-    BatchBurnResult, BatchTransferContract, BatchTransferContractClient, BurnRequest,
-    TransferRequest, TransferResult,
+    BatchBurnResult, BatchTransferContract, BatchTransferContractClient, BatchTransferError,
+    BurnRequest, StagedStatus, TransferRequest, TransferResult,
 };
 use soroban_sdk::{
     testutils::{Address as _, Events as _, Ledger},
-    token, Address, Env, Vec,
+    token, Address, BytesN, Env, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -48,6 +48,15 @@ fn create_transfer_request(_env: &Env, recipient: Address, amount: i128) -> Tran
     TransferRequest { recipient, amount }
 }
 
+/// Hashes each chunk the way a staged batch header commits to them.
+fn staged_chunk_hashes(env: &Env, chunks: &[Vec<TransferRequest>]) -> Vec<BytesN<32>> {
+    let mut hashes = Vec::new(env);
+    for chunk in chunks {
+        hashes.push_back(shared::staged::chunk_hash(env, chunk));
+    }
+    hashes
+}
+
 fn create_burn_request(_env: &Env, owner: Address, amount: i128) -> BurnRequest {
     BurnRequest { owner, amount }
 }
@@ -67,7 +76,7 @@ fn test_initialize_contract() {
 #[test]
 #[should_panic(expected = "Contract already initialized")]
 fn test_cannot_initialize_twice() {
-    let (env, admin, _token, _token_client, client) = setup_test_env();
+    let (env, _admin, _token, _token_client, client) = setup_test_env();
 
     let new_admin = Address::generate(&env);
     client.initialize(&new_admin);
@@ -88,7 +97,7 @@ fn test_batch_transfer_single_recipient() {
     let mut transfers: Vec<TransferRequest> = Vec::new(&env);
     transfers.push_back(create_transfer_request(&env, recipient.clone(), amount));
 
-    let result = client.batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers, &None);
 
     assert_eq!(result.total_requests, 1);
     assert_eq!(result.successful, 1);
@@ -122,7 +131,7 @@ fn test_batch_transfer_multiple_recipients() {
     transfers.push_back(create_transfer_request(&env, recipient2.clone(), amount2));
     transfers.push_back(create_transfer_request(&env, recipient3.clone(), amount3));
 
-    let result = client.batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers, &None);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 3);
@@ -152,7 +161,7 @@ fn test_batch_transfer_with_invalid_amount() {
         valid_amount,
     )); // Valid
 
-    let result = client.batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers, &None);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -196,7 +205,7 @@ fn test_batch_transfer_with_insufficient_balance() {
     transfers.push_back(create_transfer_request(&env, recipient1.clone(), amount1));
     transfers.push_back(create_transfer_request(&env, recipient2.clone(), amount2));
 
-    let result = client.batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers, &None);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -234,7 +243,7 @@ fn test_batch_transfer_partial_failures() {
     )); // Valid
     transfers.push_back(create_transfer_request(&env, recipient4.clone(), -100)); // Invalid: negative
 
-    let result = client.batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers, &None);
 
     assert_eq!(result.total_requests, 4);
     assert_eq!(result.successful, 2);
@@ -264,7 +273,7 @@ fn test_batch_transfer_events_emitted() {
     ));
     transfers.push_back(create_transfer_request(&env, recipient2.clone(), -100)); // Invalid
 
-    client.batch_transfer(&admin, &token, &transfers);
+    client.batch_transfer(&admin, &token, &transfers, &None);
 
     let events = env.events().all();
     // Should have: batch_started, transfer_success (1), transfer_failure (1), batch_completed
@@ -300,12 +309,12 @@ fn test_batch_transfer_accumulates_stats() {
     assert_eq!(client.get_total_transfers_processed(), 0);
     assert_eq!(client.get_total_volume_transferred(), 0);
 
-    client.batch_transfer(&admin, &token, &transfers1);
+    client.batch_transfer(&admin, &token, &transfers1, &None);
     assert_eq!(client.get_total_batches(), 1);
     assert_eq!(client.get_total_transfers_processed(), 1);
     assert_eq!(client.get_total_volume_transferred(), 10_000_000);
 
-    client.batch_transfer(&admin, &token, &transfers2);
+    client.batch_transfer(&admin, &token, &transfers2, &None);
     assert_eq!(client.get_total_batches(), 2);
     assert_eq!(client.get_total_transfers_processed(), 2);
     assert_eq!(client.get_total_volume_transferred(), 30_000_000);
@@ -317,13 +326,13 @@ fn test_batch_transfer_empty_batch() {
     let (env, admin, token, _token_client, client) = setup_test_env();
 
     let transfers: Vec<TransferRequest> = Vec::new(&env);
-    client.batch_transfer(&admin, &token, &transfers);
+    client.batch_transfer(&admin, &token, &transfers, &None);
 }
 
 #[test]
 #[should_panic]
 fn test_batch_transfer_unauthorized() {
-    let (env, admin, token, _token_client, client) = setup_test_env();
+    let (env, _admin, token, _token_client, client) = setup_test_env();
 
     let unauthorized = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -332,7 +341,7 @@ fn test_batch_transfer_unauthorized() {
     transfers.push_back(create_transfer_request(&env, recipient, 10_000_000));
 
     // This should panic due to unauthorized access
-    client.batch_transfer(&unauthorized, &token, &transfers);
+    client.batch_transfer(&unauthorized, &token, &transfers, &None);
 }
 
 #[test]
@@ -360,7 +369,7 @@ fn test_batch_transfer_large_batch() {
         // 0.1 XLM each
     }
 
-    let result = client.batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers, &None);
 
     assert_eq!(result.total_requests, 50);
     assert_eq!(result.successful, 50);
@@ -370,6 +379,233 @@ fn test_batch_transfer_large_batch() {
     // Note: Balance verification for all recipients would be done in integration tests
 }
 
+// Idempotency Tests
+
+#[test]
+fn test_batch_transfer_retry_with_same_key_is_not_reexecuted() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(&env, recipient.clone(), 10_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    let first = client.batch_transfer(&admin, &token, &transfers, &key);
+    let retry = client.batch_transfer(&admin, &token, &transfers, &key);
+
+    assert_eq!(retry.successful, first.successful);
+    assert_eq!(retry.total_transferred, first.total_transferred);
+    assert_eq!(token_client.balance(&recipient), 10_000_000);
+    assert_eq!(client.get_total_batches(), 1);
+
+    // A different key executes again
+    let other_key = Some(BytesN::from_array(&env, &[8; 32]));
+    client.batch_transfer(&admin, &token, &transfers, &other_key);
+    assert_eq!(token_client.balance(&recipient), 20_000_000);
+}
+
+#[test]
+fn test_batch_transfer_key_reused_with_different_transfers_is_rejected() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(&env, recipient.clone(), 10_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+    client.batch_transfer(&admin, &token, &transfers, &key);
+
+    let mut changed: Vec<TransferRequest> = Vec::new(&env);
+    changed.push_back(create_transfer_request(&env, recipient.clone(), 20_000_000));
+    assert!(client
+        .try_batch_transfer(&admin, &token, &changed, &key)
+        .is_err());
+
+    assert_eq!(token_client.balance(&recipient), 10_000_000);
+    assert_eq!(client.get_total_batches(), 1);
+}
+
+#[test]
+fn test_batch_transfer_key_expires_after_retention_window() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(&env, recipient.clone(), 10_000_000));
+    let key = Some(BytesN::from_array(&env, &[7; 32]));
+
+    client.batch_transfer(&admin, &token, &transfers, &key);
+    env.ledger().with_mut(|li| {
+        li.timestamp += shared::idempotency::DEFAULT_RETENTION_SECS + 1;
+    });
+    client.batch_transfer(&admin, &token, &transfers, &key);
+
+    assert_eq!(token_client.balance(&recipient), 20_000_000);
+    assert_eq!(client.get_total_batches(), 2);
+}
+
+// Staged Batch Tests
+
+#[test]
+fn test_staged_transfer_finalizes_across_chunks() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    for amount in [1_000_000, 2_000_000, 3_000_000] {
+        transfers.push_back(create_transfer_request(
+            &env,
+            Address::generate(&env),
+            amount,
+        ));
+    }
+    let chunks = [transfers.slice(0..2), transfers.slice(2..3)];
+    let staged_id = client.begin_staged_transfer(
+        &admin,
+        &token,
+        &3,
+        &6_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    let first = client.submit_transfer_chunk(&admin, &staged_id, &0, &chunks[0]);
+    assert_eq!(first.successful, 2);
+    assert_eq!(
+        client.get_staged_batch(&staged_id).unwrap().status,
+        StagedStatus::Open
+    );
+
+    let last = client.submit_transfer_chunk(&admin, &staged_id, &2, &chunks[1]);
+    assert_eq!(last.successful, 1);
+
+    let staged = client.get_staged_batch(&staged_id).unwrap();
+    assert_eq!(staged.status, StagedStatus::Finalized);
+    assert_eq!(staged.received_amount, 6_000_000);
+    assert_eq!(token_client.balance(&admin), 94_000_000);
+}
+
+#[test]
+fn test_staged_transfer_rejects_cursor_mismatch() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        1_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        2_000_000,
+    ));
+    let chunks = [transfers.slice(0..1), transfers.slice(1..2)];
+    let staged_id = client.begin_staged_transfer(
+        &admin,
+        &token,
+        &2,
+        &3_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    let result = client.try_submit_transfer_chunk(&admin, &staged_id, &1, &chunks[0]);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchTransferError::CursorMismatch.into()))
+    );
+    assert_eq!(
+        client.get_staged_batch(&staged_id).unwrap().received_count,
+        0
+    );
+}
+
+#[test]
+fn test_staged_transfer_rejects_tampered_chunk_before_paying() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        1_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        2_000_000,
+    ));
+    let chunks = [transfers.slice(0..1), transfers.slice(1..2)];
+    let staged_id = client.begin_staged_transfer(
+        &admin,
+        &token,
+        &2,
+        &3_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    // Same amount, different recipient, in the first of two chunks
+    let mut tampered: Vec<TransferRequest> = Vec::new(&env);
+    tampered.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        1_000_000,
+    ));
+    let result = client.try_submit_transfer_chunk(&admin, &staged_id, &0, &tampered);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchTransferError::StagedHashMismatch.into()))
+    );
+
+    let staged = client.get_staged_batch(&staged_id).unwrap();
+    assert_eq!(staged.status, StagedStatus::Open);
+    assert_eq!(staged.received_count, 0);
+    assert_eq!(token_client.balance(&admin), 100_000_000);
+}
+
+#[test]
+fn test_staged_transfer_rejects_non_positive_amount() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        4_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        -1_000_000,
+    ));
+    let chunks = [transfers.clone()];
+    let staged_id = client.begin_staged_transfer(
+        &admin,
+        &token,
+        &2,
+        &3_000_000,
+        &staged_chunk_hashes(&env, &chunks),
+    );
+
+    let result = client.try_submit_transfer_chunk(&admin, &staged_id, &0, &transfers);
+    assert_eq!(
+        result.err(),
+        Some(Ok(BatchTransferError::InvalidBatch.into()))
+    );
+    assert_eq!(
+        client.get_staged_batch(&staged_id).unwrap().received_amount,
+        0
+    );
+    assert_eq!(token_client.balance(&admin), 100_000_000);
+}
+
 // Admin Tests
 
 #[test]
@@ -416,7 +652,7 @@ fn test_multiple_simultaneous_batch_transfers() {
         30_000_000,
     ));
 
-    let result1 = client.batch_transfer(&admin, &token, &batch1);
+    let result1 = client.batch_transfer(&admin, &token, &batch1, &None);
     assert_eq!(result1.successful, 3);
     assert_eq!(result1.total_transferred, 60_000_000);
 
@@ -431,7 +667,7 @@ fn test_multiple_simultaneous_batch_transfers() {
         15_000_000,
     ));
 
-    let result2 = client.batch_transfer(&admin, &token, &batch2);
+    let result2 = client.batch_transfer(&admin, &token, &batch2, &None);
     assert_eq!(result2.successful, 2);
     assert_eq!(result2.total_transferred, 20_000_000);
 
@@ -545,4 +781,4 @@ fn test_batch_burn_unauthorized() {
 
     let unauthorized = Address::generate(&env);
     client.batch_burn(&unauthorized, &token, &burns);
-}
\ No newline at end of file
+}
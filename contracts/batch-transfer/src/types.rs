@@ -116,4 +116,4 @@ impl TransferEvents {
         env.events()
             .publish(topics, (successful, failed, total_burned));
     }
-}
\ No newline at end of file
+}
@@ -4,7 +4,8 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use shared::idempotency::{self, DEFAULT_RETENTION_SECS};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, Vec};
 
 pub use crate::types::{
     BatchCreateResult, BatchRecoveryResult, DataKey, Wallet, WalletCreateRequest,
@@ -53,15 +54,31 @@ impl BatchWalletContract {
     }
 
     /// Executes batch creation of wallets for multiple owners.
+    ///
+    /// A retry carrying the same `idempotency_key` within the retention window
+    /// returns the original result without executing the batch again.
     pub fn batch_create_wallets(
         env: Env,
         caller: Address,
         requests: Vec<WalletCreateRequest>,
+        idempotency_key: Option<BytesN<32>>,
     ) -> BatchCreateResult {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
+        // Return the stored result if this batch was already executed
+        let idempotency_args = (caller.clone(), requests.clone());
+        if let Some(result) = idempotency::replay(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            DEFAULT_RETENTION_SECS,
+        ) {
+            return result;
+        }
+
         // Validate batch size
         let request_count = requests.len();
         if request_count == 0 {
@@ -167,12 +184,21 @@ impl BatchWalletContract {
         // Emit batch completed event
         WalletEvents::batch_completed(&env, batch_id, successful_count, failed_count);
 
-        BatchCreateResult {
+        let result = BatchCreateResult {
             total_requests: request_count,
             successful: successful_count,
             failed: failed_count,
             results,
-        }
+        };
+        idempotency::record(
+            &env,
+            &caller,
+            &idempotency_key,
+            &idempotency_args,
+            &result,
+            DEFAULT_RETENTION_SECS,
+        );
+        result
     }
 
     pub fn batch_recover_wallets(
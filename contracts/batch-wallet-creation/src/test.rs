@@ -8,7 +8,7 @@ use crate::{
 };
 use soroban_sdk::{
     testutils::{Address as _, Events as _, Ledger},
-    Address, Env, Vec,
+    Address, BytesN, Env, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -59,7 +59,7 @@ fn test_initialize_contract() {
 #[test]
 #[should_panic(expected = "Contract already initialized")]
 fn test_cannot_initialize_twice() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let new_admin = Address::generate(&env);
     client.initialize(&new_admin);
@@ -76,7 +76,7 @@ fn test_batch_create_wallets_single() {
     let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
     requests.push_back(create_wallet_request(&env, owner.clone()));
 
-    let result = client.batch_create_wallets(&admin, &requests);
+    let result = client.batch_create_wallets(&admin, &requests, &None);
 
     assert_eq!(result.total_requests, 1);
     assert_eq!(result.successful, 1);
@@ -102,7 +102,7 @@ fn test_batch_create_wallets_multiple() {
     requests.push_back(create_wallet_request(&env, owner2.clone()));
     requests.push_back(create_wallet_request(&env, owner3.clone()));
 
-    let result = client.batch_create_wallets(&admin, &requests);
+    let result = client.batch_create_wallets(&admin, &requests, &None);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 3);
@@ -129,7 +129,7 @@ fn test_batch_create_wallets_partial_failures() {
     let mut requests1: Vec<WalletCreateRequest> = Vec::new(&env);
     requests1.push_back(create_wallet_request(&env, owner1.clone()));
     requests1.push_back(create_wallet_request(&env, owner2.clone()));
-    client.batch_create_wallets(&admin, &requests1);
+    client.batch_create_wallets(&admin, &requests1, &None);
 
     // Second batch: try to create for owner1 (duplicate), owner2 (duplicate), owner3 (new)
     let mut requests2: Vec<WalletCreateRequest> = Vec::new(&env);
@@ -137,7 +137,7 @@ fn test_batch_create_wallets_partial_failures() {
     requests2.push_back(create_wallet_request(&env, owner2.clone())); // Duplicate
     requests2.push_back(create_wallet_request(&env, owner3.clone())); // New
 
-    let result = client.batch_create_wallets(&admin, &requests2);
+    let result = client.batch_create_wallets(&admin, &requests2, &None);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 1);
@@ -181,7 +181,7 @@ fn test_batch_create_wallets_events_emitted() {
     requests.push_back(create_wallet_request(&env, owner1.clone()));
     requests.push_back(create_wallet_request(&env, owner2.clone()));
 
-    client.batch_create_wallets(&admin, &requests);
+    client.batch_create_wallets(&admin, &requests, &None);
 
     let events = env.events().all();
     // Should have: batch_started, wallet_created (2), batch_completed
@@ -204,11 +204,11 @@ fn test_batch_create_wallets_accumulates_stats() {
     assert_eq!(client.get_total_batches(), 0);
     assert_eq!(client.get_total_wallets_created(), 0);
 
-    client.batch_create_wallets(&admin, &requests1);
+    client.batch_create_wallets(&admin, &requests1, &None);
     assert_eq!(client.get_total_batches(), 1);
     assert_eq!(client.get_total_wallets_created(), 1);
 
-    client.batch_create_wallets(&admin, &requests2);
+    client.batch_create_wallets(&admin, &requests2, &None);
     assert_eq!(client.get_total_batches(), 2);
     assert_eq!(client.get_total_wallets_created(), 2);
 }
@@ -219,13 +219,13 @@ fn test_batch_create_wallets_empty_batch() {
     let (env, admin, client) = setup_test_env();
 
     let requests: Vec<WalletCreateRequest> = Vec::new(&env);
-    client.batch_create_wallets(&admin, &requests);
+    client.batch_create_wallets(&admin, &requests, &None);
 }
 
 #[test]
 #[should_panic]
 fn test_batch_create_wallets_unauthorized() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let unauthorized = Address::generate(&env);
     let owner = Address::generate(&env);
@@ -234,7 +234,7 @@ fn test_batch_create_wallets_unauthorized() {
     requests.push_back(create_wallet_request(&env, owner));
 
     // This should panic due to unauthorized access
-    client.batch_create_wallets(&unauthorized, &requests);
+    client.batch_create_wallets(&unauthorized, &requests, &None);
 }
 
 #[test]
@@ -251,7 +251,7 @@ fn test_batch_create_wallets_large_batch() {
         requests.push_back(create_wallet_request(&env, owner));
     }
 
-    let result = client.batch_create_wallets(&admin, &requests);
+    let result = client.batch_create_wallets(&admin, &requests, &None);
 
     assert_eq!(result.total_requests, 50);
     assert_eq!(result.successful, 50);
@@ -264,6 +264,26 @@ fn test_batch_create_wallets_large_batch() {
     assert_eq!(wallet50.id, 50);
 }
 
+// Idempotency Tests
+
+#[test]
+fn test_batch_create_wallets_retry_with_same_key_is_not_reexecuted() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, Address::generate(&env)));
+    requests.push_back(create_wallet_request(&env, Address::generate(&env)));
+    let key = Some(BytesN::from_array(&env, &[1; 32]));
+
+    let first = client.batch_create_wallets(&admin, &requests, &key);
+    let retry = client.batch_create_wallets(&admin, &requests, &key);
+
+    assert_eq!(retry.successful, first.successful);
+    assert_eq!(retry.results.len(), first.results.len());
+    assert_eq!(client.get_total_batches(), 1);
+    assert_eq!(client.get_total_wallets_created(), 2);
+}
+
 // Admin Tests
 
 #[test]
@@ -292,7 +312,7 @@ fn test_multiple_simultaneous_batch_creations() {
     batch1.push_back(create_wallet_request(&env, owner2.clone()));
     batch1.push_back(create_wallet_request(&env, owner3.clone()));
 
-    let result1 = client.batch_create_wallets(&admin, &batch1);
+    let result1 = client.batch_create_wallets(&admin, &batch1, &None);
     assert_eq!(result1.successful, 3);
 
     // Second batch: 2 owners (one new, one duplicate)
@@ -302,7 +322,7 @@ fn test_multiple_simultaneous_batch_creations() {
     batch2.push_back(create_wallet_request(&env, owner1.clone())); // Duplicate
     batch2.push_back(create_wallet_request(&env, owner4.clone())); // New
 
-    let result2 = client.batch_create_wallets(&admin, &batch2);
+    let result2 = client.batch_create_wallets(&admin, &batch2, &None);
     assert_eq!(result2.successful, 1);
     assert_eq!(result2.failed, 1);
 
@@ -320,7 +340,8 @@ fn test_batch_recover_wallets_single_success() {
 
     let mut create_requests: Vec<WalletCreateRequest> = Vec::new(&env);
     create_requests.push_back(create_wallet_request(&env, original_owner.clone()));
-    let create_result: BatchCreateResult = client.batch_create_wallets(&admin, &create_requests);
+    let create_result: BatchCreateResult =
+        client.batch_create_wallets(&admin, &create_requests, &None);
     assert_eq!(create_result.successful, 1);
 
     let mut recovery_requests: Vec<WalletRecoveryRequest> = Vec::new(&env);
@@ -367,7 +388,7 @@ fn test_batch_recover_wallets_partial_failures() {
     let mut create_requests: Vec<WalletCreateRequest> = Vec::new(&env);
     create_requests.push_back(create_wallet_request(&env, existing_owner.clone()));
     create_requests.push_back(create_wallet_request(&env, other_existing_owner.clone()));
-    client.batch_create_wallets(&admin, &create_requests);
+    client.batch_create_wallets(&admin, &create_requests, &None);
 
     let mut recovery_requests: Vec<WalletRecoveryRequest> = Vec::new(&env);
     recovery_requests.push_back(create_recovery_request(
@@ -434,7 +455,7 @@ fn test_batch_recover_wallets_events_emitted() {
 
     let mut create_requests: Vec<WalletCreateRequest> = Vec::new(&env);
     create_requests.push_back(create_wallet_request(&env, original_owner.clone()));
-    client.batch_create_wallets(&admin, &create_requests);
+    client.batch_create_wallets(&admin, &create_requests, &None);
 
     let mut recovery_requests: Vec<WalletRecoveryRequest> = Vec::new(&env);
     recovery_requests.push_back(create_recovery_request(
@@ -0,0 +1,187 @@
+//! Asset conversion contract for Stellar assets.
+
+#![no_std]
+
+use shared::oracle::{convert_amount, read_guarded_rate, OracleError, DEFAULT_MAX_DEVIATION_BPS};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Env,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ConversionError {
+    SameToken = 1,
+    InvalidAmount = 2,
+    RateNotFound = 3,
+    // [SEC-CONV-01] Explicit overflow error instead of a generic &'static str.
+    Overflow = 4,
+    // 5 was `DivisionByZero`; oracle rates are validated as positive before use.
+    // [SEC-CONV-03] Zero conversion result guard.
+    ZeroResult = 6,
+    NotInitialized = 7,
+    AlreadyInitialized = 8,
+    Unauthorized = 9,
+    /// Oracle price is older than the configured maximum age.
+    StalePrice = 10,
+    /// Oracle spot price is too far from its time-weighted average.
+    PriceDeviation = 11,
+    /// Oracle circuit breaker has halted the pair.
+    CircuitOpen = 12,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    /// Price oracle contract implementing `shared::oracle::PriceOracleInterface`.
+    Oracle,
+    /// Maximum age in seconds of an oracle price before it is rejected.
+    MaxPriceAge,
+    /// Maximum spot-to-TWAP deviation in bps accepted for a conversion.
+    MaxDeviationBps,
+}
+
+#[contract]
+pub struct ConversionContract;
+
+#[contractimpl]
+impl ConversionContract {
+    /// Initializes the contract with an admin and the price oracle to use.
+    pub fn initialize(env: Env, admin: Address, oracle: Address, max_price_age: u64) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, ConversionError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPriceAge, &max_price_age);
+    }
+
+    /// Replaces the price oracle and maximum price age. Admin only.
+    pub fn set_oracle(env: Env, admin: Address, oracle: Address, max_price_age: u64) {
+        Self::require_admin(&env, &admin);
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPriceAge, &max_price_age);
+    }
+
+    /// Sets the maximum spot-to-TWAP deviation accepted for a conversion. Admin only.
+    pub fn set_max_deviation(env: Env, admin: Address, max_deviation_bps: u32) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDeviationBps, &max_deviation_bps);
+    }
+
+    /// Converts `amount` of `from_token` to `to_token` using the oracle rate.
+    ///
+    /// Returns the converted amount (truncated, not rounded).
+    /// Missing or invalid oracle prices surface as `RateNotFound`.
+    ///
+    /// # Security
+    /// - [SEC-CONV-04] `user.require_auth()` ensures only the token owner can
+    ///   initiate a conversion; previously the caller was unchecked.
+    /// - [SEC-CONV-01] Overflow on `amount * rate` surfaces as a typed
+    ///   `Overflow` error rather than a panic string.
+    /// - [SEC-CONV-05] Rates come from the configured oracle through
+    ///   `shared::oracle::read_guarded_rate`, which enforces the staleness window and
+    ///   normalizes feeder decimals.
+    /// - [SEC-CONV-06] Conversions halt with `CircuitOpen` while the oracle's
+    ///   circuit breaker is tripped, and with `PriceDeviation` when the spot
+    ///   price is further than the configured bps from the oracle's TWAP.
+    /// - [SEC-CONV-03] A zero conversion result is rejected; it indicates an
+    ///   amount too small for the current rate, preventing dust-drain attacks.
+    /// - Same-token conversion and non-positive amounts are rejected up front.
+    pub fn convert_assets(
+        env: Env,
+        user: Address,
+        from_token: Address,
+        to_token: Address,
+        amount: i128,
+    ) -> Result<i128, ConversionError> {
+        // [SEC-CONV-04] Authenticate the initiating user.
+        user.require_auth();
+
+        if from_token == to_token {
+            return Err(ConversionError::SameToken);
+        }
+        if amount <= 0 {
+            return Err(ConversionError::InvalidAmount);
+        }
+
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .ok_or(ConversionError::NotInitialized)?;
+        let max_price_age: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxPriceAge)
+            .unwrap_or(0);
+        let max_deviation_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxDeviationBps)
+            .unwrap_or(DEFAULT_MAX_DEVIATION_BPS);
+
+        let rate = read_guarded_rate(
+            &env,
+            &oracle,
+            &from_token,
+            &to_token,
+            max_price_age,
+            max_deviation_bps,
+        )
+        .map_err(Self::map_oracle_error)?;
+
+        // [SEC-CONV-01] Checked multiplication before scaling.
+        let converted = convert_amount(amount, rate).map_err(Self::map_oracle_error)?;
+
+        // [SEC-CONV-03] Reject dust conversions that round to zero.
+        if converted == 0 {
+            return Err(ConversionError::ZeroResult);
+        }
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("convert"), user.clone()),
+            (
+                from_token.clone(),
+                to_token.clone(),
+                amount,
+                converted,
+                env.ledger().timestamp(),
+            ),
+        );
+
+        Ok(converted)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, ConversionError::NotInitialized));
+        if admin != &stored_admin {
+            panic_with_error!(env, ConversionError::Unauthorized);
+        }
+    }
+
+    fn map_oracle_error(error: OracleError) -> ConversionError {
+        match error {
+            OracleError::Overflow => ConversionError::Overflow,
+            OracleError::StalePrice => ConversionError::StalePrice,
+            OracleError::PriceDeviation => ConversionError::PriceDeviation,
+            OracleError::CircuitOpen => ConversionError::CircuitOpen,
+            OracleError::PriceNotFound | OracleError::InvalidPrice => ConversionError::RateNotFound,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,278 @@
+//! Tests for the oracle-priced Conversion Contract.
+
+#![cfg(test)]
+
+use crate::{ConversionContract, ConversionContractClient, ConversionError};
+use price_oracle::{PriceOracleContract, PriceOracleContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const PRICE_TIMESTAMP: u64 = 1_000;
+const MAX_PRICE_AGE: u64 = 300;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    feeder: Address,
+    from_token: Address,
+    to_token: Address,
+    oracle: PriceOracleContractClient<'static>,
+    client: ConversionContractClient<'static>,
+}
+
+/// Deploys the conversion contract against a local oracle whose stand-in
+/// feeder quotes 1 `from_token` = 0.95 `to_token`.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = PRICE_TIMESTAMP);
+
+    let admin = Address::generate(&env);
+    let from_token = Address::generate(&env);
+    let to_token = Address::generate(&env);
+
+    let oracle_id = env.register(PriceOracleContract, ());
+    let oracle = PriceOracleContractClient::new(&env, &oracle_id);
+    let feeder = Address::generate(&env);
+    oracle.initialize(&admin);
+    oracle.add_feeder(&admin, &feeder);
+    oracle.set_price(&feeder, &from_token, &to_token, &95, &2, &PRICE_TIMESTAMP);
+
+    let contract_id = env.register(ConversionContract, ());
+    let client = ConversionContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &oracle_id, &MAX_PRICE_AGE);
+
+    Setup {
+        env,
+        admin,
+        feeder,
+        from_token,
+        to_token,
+        oracle,
+        client,
+    }
+}
+
+#[test]
+fn test_convert_at_oracle_rate() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    let converted = s
+        .client
+        .convert_assets(&user, &s.from_token, &s.to_token, &1_000);
+    assert_eq!(converted, 950);
+}
+
+#[test]
+fn test_convert_normalizes_feeder_decimals() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    // 1.5 quoted with 8 decimals.
+    s.oracle.set_price(
+        &s.feeder,
+        &s.from_token,
+        &s.to_token,
+        &150_000_000,
+        &8,
+        &PRICE_TIMESTAMP,
+    );
+    s.client.set_max_deviation(&s.admin, &10_000);
+
+    let converted = s
+        .client
+        .convert_assets(&user, &s.from_token, &s.to_token, &200);
+    assert_eq!(converted, 300);
+}
+
+#[test]
+#[should_panic]
+fn test_convert_requires_user_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    let user = Address::generate(&s.env);
+
+    s.client
+        .convert_assets(&user, &s.from_token, &s.to_token, &1_000);
+}
+
+#[test]
+fn test_same_token_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.from_token, &s.from_token, &1_000);
+    assert_eq!(result, Err(Ok(ConversionError::SameToken)));
+}
+
+#[test]
+fn test_non_positive_amount_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    for amount in [0i128, -5] {
+        let result = s
+            .client
+            .try_convert_assets(&user, &s.from_token, &s.to_token, &amount);
+        assert_eq!(result, Err(Ok(ConversionError::InvalidAmount)));
+    }
+}
+
+#[test]
+fn test_dust_amount_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    // 1 * 0.95 truncates to zero.
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.from_token, &s.to_token, &1);
+    assert_eq!(result, Err(Ok(ConversionError::ZeroResult)));
+}
+
+#[test]
+fn test_overflow_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.from_token, &s.to_token, &i128::MAX);
+    assert_eq!(result, Err(Ok(ConversionError::Overflow)));
+}
+
+#[test]
+fn test_stale_price_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + MAX_PRICE_AGE + 1);
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.from_token, &s.to_token, &1_000);
+    assert_eq!(result, Err(Ok(ConversionError::StalePrice)));
+
+    // Still accepted at the edge of the window.
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + MAX_PRICE_AGE);
+    assert_eq!(
+        s.client
+            .convert_assets(&user, &s.from_token, &s.to_token, &1_000),
+        950
+    );
+}
+
+#[test]
+fn test_unpriced_pair_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.to_token, &s.from_token, &1_000);
+    assert_eq!(result, Err(Ok(ConversionError::RateNotFound)));
+}
+
+#[test]
+fn test_price_far_from_twap_rejected() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 1_000);
+    s.oracle.set_price(
+        &s.feeder,
+        &s.from_token,
+        &s.to_token,
+        &150,
+        &2,
+        &(PRICE_TIMESTAMP + 1_000),
+    );
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 1_010);
+
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.from_token, &s.to_token, &1_000);
+    assert_eq!(result, Err(Ok(ConversionError::PriceDeviation)));
+
+    // A looser tolerance lets the same conversion through at the spot price.
+    s.client.set_max_deviation(&s.admin, &10_000);
+    assert_eq!(
+        s.client
+            .convert_assets(&user, &s.from_token, &s.to_token, &1_000),
+        1_500
+    );
+}
+
+#[test]
+fn test_open_circuit_halts_conversion() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    s.oracle.set_config(&s.admin, &3_600, &Some(500));
+
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 100);
+    s.oracle.set_price(
+        &s.feeder,
+        &s.from_token,
+        &s.to_token,
+        &150,
+        &2,
+        &(PRICE_TIMESTAMP + 100),
+    );
+    assert!(s.oracle.circuit_open(&s.from_token, &s.to_token));
+
+    let result = s
+        .client
+        .try_convert_assets(&user, &s.from_token, &s.to_token, &1_000);
+    assert_eq!(result, Err(Ok(ConversionError::CircuitOpen)));
+}
+
+#[test]
+fn test_convert_requires_initialization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(ConversionContract, ());
+    let client = ConversionContractClient::new(&env, &contract_id);
+
+    let result = client.try_convert_assets(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &1_000,
+    );
+    assert_eq!(result, Err(Ok(ConversionError::NotInitialized)));
+}
+
+#[test]
+fn test_initialize_twice_rejected() {
+    let s = setup();
+
+    let result = s
+        .client
+        .try_initialize(&s.admin, &s.oracle.address, &MAX_PRICE_AGE);
+    assert_eq!(result, Err(Ok(ConversionError::AlreadyInitialized.into())));
+}
+
+#[test]
+fn test_set_oracle_requires_admin() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_set_oracle(&other, &s.oracle.address, &MAX_PRICE_AGE);
+    assert_eq!(result, Err(Ok(ConversionError::Unauthorized.into())));
+}
@@ -1,9 +1,9 @@
-use soroban_sdk::{Env, Address, panic_with_error};
 use crate::storage::{has_admin, read_admin};
 use crate::FeeContractError;
+use soroban_sdk::{panic_with_error, Address, Env};
 
 /// Validates that the given address is the contract admin and has authorized the call.
-/// 
+///
 /// Panics with `NotInitialized` if the admin is not set.
 /// Panics with `Unauthorized` if the address is not the admin.
 /// Panics if the address has not authorized the invocation.
@@ -0,0 +1,55 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::storage::{
+    append_cycle_release, read_current_cycle, read_cycle_schedule, CycleBounds, CycleRelease,
+};
+use crate::FeeContractError;
+
+/// Returns the cycle fees are currently collected into. With a schedule
+/// configured the cycle is derived from the ledger timestamp; otherwise it is
+/// whatever the admin last rolled over to.
+pub fn current_cycle(env: &Env) -> u64 {
+    match read_cycle_schedule(env) {
+        Some(schedule) => {
+            let elapsed = env.ledger().timestamp().saturating_sub(schedule.start_time);
+            schedule.start_cycle + elapsed / schedule.duration
+        }
+        None => read_current_cycle(env),
+    }
+}
+
+/// Returns the timestamp range of a scheduled cycle. Cycles that predate the
+/// schedule have no bounds and panic with InvalidCycle.
+pub fn cycle_bounds(env: &Env, cycle: u64) -> CycleBounds {
+    let schedule = read_cycle_schedule(env)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::CycleScheduleNotSet));
+    if cycle < schedule.start_cycle {
+        panic_with_error!(env, FeeContractError::InvalidCycle);
+    }
+
+    let start_time = (cycle - schedule.start_cycle)
+        .checked_mul(schedule.duration)
+        .and_then(|offset| schedule.start_time.checked_add(offset))
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+    let end_time = start_time
+        .checked_add(schedule.duration)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+
+    CycleBounds {
+        cycle,
+        start_time,
+        end_time,
+    }
+}
+
+pub fn record_release(env: &Env, cycle: u64, asset: &Address, amount: i128) {
+    append_cycle_release(
+        env,
+        cycle,
+        CycleRelease {
+            asset: asset.clone(),
+            amount,
+            released_at: env.ledger().timestamp(),
+        },
+    );
+}
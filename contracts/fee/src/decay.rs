@@ -1,30 +1,123 @@
-use soroban_sdk::Env;
+use soroban_sdk::{contracttype, panic_with_error, Env};
+
+use crate::storage::read_decay_config;
+use crate::FeeContractError;
 
 pub const DECAY_RATE: i128 = 1; // per second
 pub const MIN_FEE: i128 = 10;
-pub const MAX_FEE: i128 = 100;
-
-pub fn calculate_fee_decay(
-    _env: &Env,
-    base_fee: i128,
-    last_active: u64,
-    current_time: u64,
-) -> i128 {
-    if current_time <= last_active {
-        return base_fee;
+
+/// Fixed-point scale used by the exponential curve (9 decimal places).
+const SCALE: i128 = 1_000_000_000;
+/// ln(2) scaled by `SCALE`.
+const LN_2: i128 = 693_147_181;
+
+/// Shape of the fee decay applied as time passes since a user's last activity.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DecayCurve {
+    /// No decay; the base fee is always charged.
+    Disabled,
+    /// Fee drops by `rate` per elapsed second.
+    Linear(i128),
+    /// Fee drops by `amount` for every full `interval` seconds elapsed.
+    Step(u64, i128),
+    /// Fee halves every `half_life` seconds.
+    Exponential(u64),
+}
+
+/// Decay parameters. Decayed fees are clamped into `[min_fee, max_fee]`.
+/// The default config leaves fees uncapped, as before decay was configurable.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DecayConfig {
+    pub curve: DecayCurve,
+    pub min_fee: i128,
+    pub max_fee: i128,
+}
+
+impl DecayConfig {
+    pub fn default_config() -> Self {
+        DecayConfig {
+            curve: DecayCurve::Linear(DECAY_RATE),
+            min_fee: MIN_FEE,
+            max_fee: i128::MAX,
+        }
+    }
+}
+
+/// Validate decay parameters. Panics with InvalidConfig on failure.
+pub fn validate_decay_config_or_panic(env: &Env, config: &DecayConfig) {
+    let curve_valid = match config.curve {
+        DecayCurve::Disabled => true,
+        DecayCurve::Linear(rate) => rate >= 0,
+        DecayCurve::Step(interval, amount) => interval > 0 && amount >= 0,
+        DecayCurve::Exponential(half_life) => half_life > 0,
+    };
+    if !curve_valid || config.min_fee < 0 || config.max_fee < config.min_fee {
+        panic_with_error!(env, FeeContractError::InvalidConfig);
+    }
+}
+
+/// e^(-x) for `x` scaled by `SCALE`, using a truncated Taylor series.
+/// Only called with 0 <= x < ln(2), where ten terms are well within precision.
+fn exp_neg(x: i128) -> i128 {
+    let mut sum = SCALE;
+    let mut term = SCALE;
+    for k in 1..=10 {
+        term = term * x / (SCALE * k);
+        if k % 2 == 1 {
+            sum -= term;
+        } else {
+            sum += term;
+        }
+    }
+    sum
+}
+
+/// base_fee * 2^(-elapsed / half_life) in fixed-point math.
+fn exponential_decay(base_fee: i128, elapsed: u64, half_life: u64) -> i128 {
+    let halvings = elapsed / half_life;
+    if halvings >= 127 {
+        return 0;
     }
+    let halved = base_fee >> halvings;
+
+    // 2^(-r/h) = e^(-(r/h) * ln 2)
+    let remainder = (elapsed % half_life) as i128;
+    let exponent = remainder * LN_2 / half_life as i128;
+    halved.checked_mul(exp_neg(exponent)).unwrap_or(i128::MAX) / SCALE
+}
+
+/// Apply `config` to `base_fee` after `elapsed` seconds of decay.
+pub fn apply_decay(config: &DecayConfig, base_fee: i128, elapsed: u64) -> i128 {
+    let decayed_fee = match config.curve {
+        DecayCurve::Disabled => return base_fee,
+        DecayCurve::Linear(rate) => {
+            let decay = (elapsed as i128).checked_mul(rate).unwrap_or(i128::MAX);
+            base_fee.saturating_sub(decay)
+        }
+        DecayCurve::Step(interval, amount) => {
+            let steps = (elapsed / interval) as i128;
+            let decay = steps.checked_mul(amount).unwrap_or(i128::MAX);
+            base_fee.saturating_sub(decay)
+        }
+        DecayCurve::Exponential(half_life) => exponential_decay(base_fee, elapsed, half_life),
+    };
+
+    decayed_fee.clamp(config.min_fee, config.max_fee)
+}
 
-    let time_elapsed = (current_time - last_active) as i128;
-    
-    // decay = time_elapsed * decay_rate
-    let decay = time_elapsed.checked_mul(DECAY_RATE).unwrap_or(base_fee);
-    
-    // Result: max(min_fee, base_fee - decay)
-    let decayed_fee = base_fee.checked_sub(decay).unwrap_or(MIN_FEE);
-
-    if decayed_fee < MIN_FEE {
-        MIN_FEE
-    } else {
-        decayed_fee
+/// Decay `base_fee` by the time elapsed since the user's last activity, using
+/// the stored decay configuration. Users with no recorded activity pay the
+/// base fee.
+pub fn calculate_fee_decay(env: &Env, base_fee: i128, last_active: u64, current_time: u64) -> i128 {
+    if last_active == 0 || current_time <= last_active {
+        return base_fee;
     }
+
+    apply_decay(
+        &read_decay_config(env),
+        base_fee,
+        current_time - last_active,
+    )
 }
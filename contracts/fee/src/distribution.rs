@@ -0,0 +1,96 @@
+use soroban_sdk::{panic_with_error, token, Address, Env, Map, Vec};
+
+use crate::events::FeeEvents;
+use crate::storage::{
+    add_recipient_total, read_dust_recipient, read_fee_recipients, read_treasury,
+    FeeRecipientShare, MAX_FEE_BPS,
+};
+use crate::FeeContractError;
+
+/// Validate a recipient split. Shares must be positive, recipients unique,
+/// and the total must be exactly 10,000 bps. An empty split is valid and
+/// sends releases to the treasury. Panics with InvalidConfig on failure.
+pub fn validate_recipient_shares_or_panic(env: &Env, recipients: &Vec<FeeRecipientShare>) {
+    if recipients.is_empty() {
+        return;
+    }
+
+    let mut seen: Map<Address, bool> = Map::new(env);
+    let mut total: u32 = 0;
+    for share in recipients.iter() {
+        if share.share_bps == 0 || seen.contains_key(share.recipient.clone()) {
+            panic_with_error!(env, FeeContractError::InvalidConfig);
+        }
+        seen.set(share.recipient.clone(), true);
+        total = total
+            .checked_add(share.share_bps)
+            .unwrap_or_else(|| panic_with_error!(env, FeeContractError::InvalidConfig));
+    }
+    if total != MAX_FEE_BPS {
+        panic_with_error!(env, FeeContractError::InvalidConfig);
+    }
+}
+
+/// Addresses a release is split between: the configured recipients, or the
+/// treasury when no split is set.
+pub fn release_recipients(env: &Env) -> Vec<Address> {
+    let recipients = read_fee_recipients(env);
+    let mut result = Vec::new(env);
+    if recipients.is_empty() {
+        result.push_back(read_treasury(env));
+    }
+    for share in recipients.iter() {
+        result.push_back(share.recipient);
+    }
+    result
+}
+
+fn pay(
+    env: &Env,
+    asset: &Address,
+    token_client: &token::Client,
+    recipient: &Address,
+    amount: i128,
+) {
+    if amount <= 0 {
+        return;
+    }
+    token_client.transfer(&env.current_contract_address(), recipient, &amount);
+    add_recipient_total(env, recipient, asset, amount)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+}
+
+/// Send `amount` of `asset` out of escrow. With a configured split each recipient gets
+/// its rounded-down share and the rounding dust goes to the dust recipient;
+/// otherwise everything goes to the treasury.
+pub fn distribute_released_fees(
+    env: &Env,
+    asset: &Address,
+    token_client: &token::Client,
+    amount: i128,
+) {
+    let recipients = read_fee_recipients(env);
+    if recipients.is_empty() {
+        pay(env, asset, token_client, &read_treasury(env), amount);
+        return;
+    }
+
+    let mut distributed: i128 = 0;
+    for share in recipients.iter() {
+        let portion = amount
+            .checked_mul(share.share_bps as i128)
+            .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow))
+            / MAX_FEE_BPS as i128;
+        pay(env, asset, token_client, &share.recipient, portion);
+        FeeEvents::fee_distributed(env, &share.recipient, portion, share.share_bps);
+        distributed += portion;
+    }
+
+    let dust = amount - distributed;
+    if dust > 0 {
+        let dust_recipient = read_dust_recipient(env)
+            .unwrap_or_else(|| panic_with_error!(env, FeeContractError::InvalidConfig));
+        pay(env, asset, token_client, &dust_recipient, dust);
+        FeeEvents::fee_dust(env, &dust_recipient, dust);
+    }
+}
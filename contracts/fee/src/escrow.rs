@@ -1,10 +1,11 @@
 use shared::utils::validate_amount as validate_non_negative_amount;
 use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
 
+use crate::cycles::current_cycle;
+use crate::distribution::distribute_released_fees;
 use crate::storage::{
     add_batch_call, add_escrow_balance, add_pending_fees, add_total_collected, add_total_released,
-    clear_pending_fees, read_current_cycle, read_min_fee, read_pending_fees, read_token,
-    read_treasury, sub_escrow_balance, BatchFeeResult,
+    clear_pending_fees, is_supported_asset, read_pending_fees, sub_escrow_balance, BatchFeeResult,
 };
 use crate::FeeContractError;
 
@@ -14,54 +15,57 @@ fn require_positive_amount(env: &Env, amount: i128) {
     }
 }
 
-pub fn collect_to_escrow(env: &Env, payer: &Address, amount: i128) -> i128 {
-    require_positive_amount(env, amount);
-    let min_fee = read_min_fee(env);
-    if amount < min_fee {
-        panic_with_error!(env, FeeContractError::InvalidAmount);
+pub fn require_supported_asset(env: &Env, asset: &Address) {
+    if !is_supported_asset(env, asset) {
+        panic_with_error!(env, FeeContractError::UnsupportedAsset);
     }
+}
 
-    let token_id = read_token(env);
-    let token_client = token::Client::new(env, &token_id);
+pub fn collect_to_escrow(env: &Env, payer: &Address, asset: &Address, amount: i128) -> i128 {
+    require_supported_asset(env, asset);
+    require_positive_amount(env, amount);
+
+    let token_client = token::Client::new(env, asset);
     let contract_address = env.current_contract_address();
     token_client.transfer(payer, &contract_address, &amount);
 
-    let cycle = read_current_cycle(env);
-    let pending = add_pending_fees(env, cycle, amount)
+    let cycle = current_cycle(env);
+    let pending = add_pending_fees(env, asset, cycle, amount)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
-    add_escrow_balance(env, amount)
+    add_escrow_balance(env, asset, amount)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
-    add_total_collected(env, amount)
+    add_total_collected(env, asset, amount)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
 
     pending
 }
 
-pub fn collect_batch_to_escrow(env: &Env, payer: &Address, amounts: &Vec<i128>) -> BatchFeeResult {
+pub fn collect_batch_to_escrow(
+    env: &Env,
+    payer: &Address,
+    asset: &Address,
+    amounts: &Vec<i128>,
+) -> BatchFeeResult {
+    require_supported_asset(env, asset);
     let mut total_amount: i128 = 0;
-    let min_fee = read_min_fee(env);
 
     for amount in amounts.iter() {
         require_positive_amount(env, amount);
-        if amount < min_fee {
-            panic_with_error!(env, FeeContractError::InvalidAmount);
-        }
         total_amount = total_amount
             .checked_add(amount)
             .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
     }
 
-    let token_id = read_token(env);
-    let token_client = token::Client::new(env, &token_id);
+    let token_client = token::Client::new(env, asset);
     let contract_address = env.current_contract_address();
     token_client.transfer(payer, &contract_address, &total_amount);
 
-    let cycle = read_current_cycle(env);
-    let pending_fees = add_pending_fees(env, cycle, total_amount)
+    let cycle = current_cycle(env);
+    let pending_fees = add_pending_fees(env, asset, cycle, total_amount)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
-    add_escrow_balance(env, total_amount)
+    add_escrow_balance(env, asset, total_amount)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
-    add_total_collected(env, total_amount)
+    add_total_collected(env, asset, total_amount)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
     add_batch_call(env).unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
 
@@ -73,33 +77,30 @@ pub fn collect_batch_to_escrow(env: &Env, payer: &Address, amounts: &Vec<i128>)
     }
 }
 
-pub fn release_cycle_fees(env: &Env, cycle: u64) -> i128 {
-    let pending = read_pending_fees(env, cycle);
+pub fn release_cycle_fees(env: &Env, asset: &Address, cycle: u64) -> i128 {
+    let pending = read_pending_fees(env, asset, cycle);
     if pending <= 0 {
         panic_with_error!(env, FeeContractError::NoPendingFees);
     }
 
-    let token_id = read_token(env);
-    let treasury = read_treasury(env);
-    let token_client = token::Client::new(env, &token_id);
-    let contract_address = env.current_contract_address();
-    token_client.transfer(&contract_address, &treasury, &pending);
+    let token_client = token::Client::new(env, asset);
+    distribute_released_fees(env, asset, &token_client, pending);
 
-    clear_pending_fees(env, cycle);
-    sub_escrow_balance(env, pending)
+    clear_pending_fees(env, asset, cycle);
+    sub_escrow_balance(env, asset, pending)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::InsufficientEscrow));
-    add_total_released(env, pending)
+    add_total_released(env, asset, pending)
         .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
 
     pending
 }
 
-pub fn rollover_cycle_fees(env: &Env, from_cycle: u64, to_cycle: u64) -> i128 {
-    let pending = read_pending_fees(env, from_cycle);
+pub fn rollover_cycle_fees(env: &Env, asset: &Address, from_cycle: u64, to_cycle: u64) -> i128 {
+    let pending = read_pending_fees(env, asset, from_cycle);
     if pending > 0 {
-        add_pending_fees(env, to_cycle, pending)
+        add_pending_fees(env, asset, to_cycle, pending)
             .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
     }
-    clear_pending_fees(env, from_cycle);
+    clear_pending_fees(env, asset, from_cycle);
     pending
 }
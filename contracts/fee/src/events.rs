@@ -1,5 +1,6 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
 
+use crate::decay::DecayConfig;
 use crate::storage::{DEFAULT_FEE_BPS, DEFAULT_MIN_FEE};
 use crate::utils::format_amount;
 
@@ -11,9 +12,16 @@ impl FeeEvents {
         env.events().publish(topics, (user.clone(), amount));
     }
 
-    pub fn fee_escrowed(env: &Env, payer: &Address, amount: i128, cycle: u64) {
+    pub fn fee_quoted(env: &Env, payer: &Address, quote_id: u64, total_fee: i128, expires_at: u32) {
+        let topics = (symbol_short!("fee"), symbol_short!("quoted"));
+        env.events()
+            .publish(topics, (payer.clone(), quote_id, total_fee, expires_at));
+    }
+
+    pub fn fee_escrowed(env: &Env, payer: &Address, asset: &Address, amount: i128, cycle: u64) {
         let topics = (symbol_short!("fee"), symbol_short!("escrowed"));
-        env.events().publish(topics, (payer.clone(), amount, cycle));
+        env.events()
+            .publish(topics, (payer.clone(), asset.clone(), amount, cycle));
     }
 
     pub fn fee_batched(
@@ -28,15 +36,33 @@ impl FeeEvents {
             .publish(topics, (payer.clone(), total_amount, batch_size, cycle));
     }
 
-    pub fn fee_released(env: &Env, cycle: u64, amount: i128, treasury: &Address) {
+    pub fn fee_released(
+        env: &Env,
+        asset: &Address,
+        cycle: u64,
+        amount: i128,
+        recipients: &Vec<Address>,
+    ) {
         let topics = (symbol_short!("fee"), symbol_short!("released"));
         env.events()
-            .publish(topics, (cycle, amount, treasury.clone()));
+            .publish(topics, (asset.clone(), cycle, amount, recipients.clone()));
     }
 
-    pub fn fee_rolled(env: &Env, from_cycle: u64, to_cycle: u64, amount: i128) {
+    pub fn fee_distributed(env: &Env, recipient: &Address, amount: i128, share_bps: u32) {
+        let topics = (symbol_short!("fee"), symbol_short!("split"));
+        env.events()
+            .publish(topics, (recipient.clone(), amount, share_bps));
+    }
+
+    pub fn fee_dust(env: &Env, recipient: &Address, amount: i128) {
+        let topics = (symbol_short!("fee"), symbol_short!("dust"));
+        env.events().publish(topics, (recipient.clone(), amount));
+    }
+
+    pub fn fee_rolled(env: &Env, asset: &Address, from_cycle: u64, to_cycle: u64, amount: i128) {
         let topics = (symbol_short!("fee"), symbol_short!("rollover"));
-        env.events().publish(topics, (from_cycle, to_cycle, amount));
+        env.events()
+            .publish(topics, (asset.clone(), from_cycle, to_cycle, amount));
     }
 
     pub fn locked(env: &Env) {
@@ -67,6 +93,71 @@ impl FeeEvents {
             .publish(topics, (symbol_short!("min_fee"), min_fee));
     }
 
+    pub fn asset_added(env: &Env, asset: &Address) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("asset"), asset.clone()));
+    }
+
+    pub fn asset_min_fee_updated(env: &Env, asset: &Address, min_fee: i128) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("min_fee"), asset.clone(), min_fee));
+    }
+
+    pub fn asset_fee_bps_updated(env: &Env, asset: &Address, fee_bps: u32) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("bps"), asset.clone(), fee_bps));
+    }
+
+    pub fn decay_config_updated(env: &Env, config: &DecayConfig) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("decay"), config.clone()));
+    }
+
+    pub fn recipients_updated(env: &Env, recipient_count: u32, dust_recipient: &Address) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events().publish(
+            topics,
+            (
+                symbol_short!("split"),
+                recipient_count,
+                dust_recipient.clone(),
+            ),
+        );
+    }
+
+    pub fn cycle_schedule_updated(env: &Env, duration: u64, start_cycle: u64) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("cycle"), duration, start_cycle));
+    }
+
+    pub fn cycle_settled(env: &Env, cycle: u64, assets_released: u32) {
+        let topics = (symbol_short!("fee"), symbol_short!("settled"));
+        env.events().publish(topics, (cycle, assets_released));
+    }
+
+    pub fn volume_tiers_updated(env: &Env, tier_count: u32) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("tiers"), tier_count));
+    }
+
+    pub fn adjustment_posted(env: &Env, id: u64, asset: &Address, amount: i128, reason: u32) {
+        let topics = (symbol_short!("fee"), symbol_short!("adjust"));
+        env.events()
+            .publish(topics, (id, asset.clone(), amount, reason));
+    }
+
+    pub fn tolerance_updated(env: &Env, tolerance: i128) {
+        let topics = (symbol_short!("fee"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (symbol_short!("tolerance"), tolerance));
+    }
+
     pub fn fee_reconciled(env: &Env, stored: i128, calculated: i128) {
         let topics = (symbol_short!("fee"), symbol_short!("recon"));
         env.events().publish(topics, (stored, calculated));
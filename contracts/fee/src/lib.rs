@@ -2,41 +2,63 @@
 
 extern crate alloc;
 
+mod auth;
+mod cycles;
 mod decay;
+mod distribution;
 mod escrow;
 mod events;
+mod quote;
 mod reconciliation;
 mod storage;
 mod utils;
 mod validation;
-mod auth;
+mod volume;
 
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Map, Symbol, Vec};
 
-use crate::decay::calculate_fee_decay;
+use crate::auth::require_admin;
+use crate::cycles::{current_cycle, cycle_bounds, record_release};
+use crate::decay::{calculate_fee_decay, validate_decay_config_or_panic};
+pub use crate::decay::{DecayConfig, DecayCurve};
+use crate::distribution::{release_recipients, validate_recipient_shares_or_panic};
 use crate::escrow::{
-    collect_batch_to_escrow, collect_to_escrow, release_cycle_fees, rollover_cycle_fees,
+    collect_batch_to_escrow, collect_to_escrow, release_cycle_fees, require_supported_asset,
+    rollover_cycle_fees,
 };
 use crate::events::{ConfigEvents, FeeEvents, TierEvents};
-use crate::reconciliation::reconcile;
-pub use crate::reconciliation::ReconciliationResult;
+use crate::quote::{consume_quote, create_quote};
+pub use crate::quote::{FeeQuote, QUOTE_TTL_LEDGERS};
+use crate::reconciliation::{apply_adjustment, reconcile, require_reconciled_or_panic};
+pub use crate::reconciliation::{AdjustmentEntry, ReconciliationResult};
 use crate::storage::{
-    has_admin, is_valid_tier, read_admin, read_current_cycle, read_escrow_balance, read_fee_bps,
-    read_last_active, read_locked, read_min_fee, read_pending_fees, read_token,
+    add_asset, has_admin, is_valid_tier, read_adjustment, read_adjustment_count, read_admin,
+    read_asset_fee_bps, read_asset_min_fee, read_assets, read_current_cycle, read_cycle_releases,
+    read_cycle_schedule, read_decay_config, read_dust_recipient, read_escrow_balance, read_fee_bps,
+    read_fee_recipients, read_last_active, read_locked, read_min_fee, read_next_settle_cycle,
+    read_pending_fees, read_quote, read_recipient_total, read_recon_tolerance, read_token,
     read_total_batch_calls, read_total_collected, read_total_released, read_treasury,
-    read_user_tier, remove_user_tier, write_admin, write_current_cycle, write_fee_bps,
-    write_last_active, write_locked, write_min_fee, write_token, write_treasury, write_user_tier,
-    DEFAULT_FEE_BPS, DEFAULT_MIN_FEE,
+    read_user_tier, read_volume_tiers, remove_cycle_schedule, remove_user_tier, write_admin,
+    write_asset_fee_bps, write_asset_min_fee, write_current_cycle, write_cycle_schedule,
+    write_decay_config, write_dust_recipient, write_fee_bps, write_fee_recipients,
+    write_last_active, write_locked, write_min_fee, write_next_settle_cycle, write_recon_tolerance,
+    write_token, write_treasury, write_user_tier, write_volume_tiers, DEFAULT_FEE_BPS,
+    DEFAULT_MIN_FEE,
 };
-pub use crate::storage::{BatchFeeResult, DataKey, MAX_BATCH_SIZE, MAX_FEE_BPS};
-use crate::utils::format_amount;
+pub use crate::storage::{
+    BatchFeePreview, BatchFeeResult, CycleBounds, CycleRelease, CycleSchedule, DataKey,
+    FeeRecipientShare, VolumeTier, MAX_BATCH_SIZE, MAX_FEE_BPS, VOLUME_WINDOW_DAYS,
+};
+use crate::utils::compute_fee;
 use crate::validation::{validate_fee_bps_or_panic, validate_min_fee_or_panic};
+use crate::volume::{
+    current_tier, fee_rate, record_volume, resolve_tier, trailing_volume,
+    validate_volume_tiers_or_panic, volume_fee,
+};
 use shared::utils::validate_amount as validate_non_negative_amount;
-use crate::auth::require_admin;
-use crate::utils::compute_fee;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -53,6 +75,14 @@ pub enum FeeContractError {
     InvalidConfig = 10,
     NoPendingFees = 11,
     InvalidTier = 12,
+    UnsupportedAsset = 13,
+    CycleScheduleActive = 14,
+    CycleScheduleNotSet = 15,
+    UnresolvedDiscrepancy = 16,
+    QuoteNotFound = 17,
+    QuoteMismatch = 18,
+    QuoteAlreadyUsed = 19,
+    QuoteExpired = 20,
 }
 
 impl From<FeeContractError> for soroban_sdk::Error {
@@ -86,6 +116,7 @@ impl FeeContract {
 
         write_admin(&env, &admin);
         write_token(&env, &token);
+        add_asset(&env, &token);
         write_treasury(&env, &treasury);
         write_fee_bps(&env, fee_bps);
         write_locked(&env, false);
@@ -99,60 +130,134 @@ impl FeeContract {
         Self::initialize(env, admin, token, treasury, 300, 1);
     }
 
+    /// Collects `amount` in the default asset from `payer` into escrow for
+    /// the current cycle as a fee, decayed by the payer's inactivity.
+    ///
+    /// Panics with `InvalidAmount` if the decayed fee is below the asset's
+    /// minimum fee. Returns the cycle's pending fees.
     pub fn collect_fee(env: Env, payer: Address, amount: i128) -> i128 {
         payer.require_auth();
+        Self::collect(&env, &payer, &read_token(&env), amount)
+    }
 
-        let last_active = read_last_active(&env, &payer);
-        let current_time = env.ledger().timestamp();
-        let decayed_amount = calculate_fee_decay(&env, amount, last_active, current_time);
-
-        let pending = collect_to_escrow(&env, &payer, decayed_amount);
-
-        write_last_active(&env, &payer, current_time);
-
-        FeeEvents::fee_collected(&env, &payer, amount);
-        FeeEvents::fee_escrowed(&env, &payer, decayed_amount, read_current_cycle(&env));
-        pending
+    /// Same as `collect_fee`, for any registered asset.
+    pub fn collect_asset_fee(env: Env, payer: Address, asset: Address, amount: i128) -> i128 {
+        payer.require_auth();
+        Self::collect(&env, &payer, &asset, amount)
     }
 
+    /// Collects a batch of fees in the default asset.
     pub fn collect_fee_batch(env: Env, payer: Address, amounts: Vec<i128>) -> BatchFeeResult {
         payer.require_auth();
+        Self::collect_batch(&env, &payer, &read_token(&env), &amounts)
+    }
 
-        let batch_size = amounts.len();
-        if batch_size == 0 {
-            panic_with_error!(&env, FeeContractError::EmptyBatch);
-        }
-        if batch_size > MAX_BATCH_SIZE {
-            panic_with_error!(&env, FeeContractError::BatchTooLarge);
-        }
+    /// Collects the fee on a transaction of `volume` in the default asset
+    /// from `payer` into escrow for the current cycle.
+    ///
+    /// The fee is charged at the payer's volume tier rate when a tier
+    /// schedule is configured and at the asset's fee bps otherwise, then
+    /// decayed by the payer's inactivity and floored at the asset's minimum
+    /// fee. The volume counts towards the payer's tier. Returns the cycle's
+    /// pending fees.
+    pub fn collect_volume_fee(env: Env, payer: Address, volume: i128) -> i128 {
+        payer.require_auth();
+        Self::collect_volume(&env, &payer, &read_token(&env), volume)
+    }
 
-        let last_active = read_last_active(&env, &payer);
-        let current_time = env.ledger().timestamp();
+    /// Same as `collect_volume_fee`, for any registered asset.
+    pub fn collect_asset_volume_fee(
+        env: Env,
+        payer: Address,
+        asset: Address,
+        volume: i128,
+    ) -> i128 {
+        payer.require_auth();
+        Self::collect_volume(&env, &payer, &asset, volume)
+    }
 
-        let mut decayed_amounts = Vec::new(&env);
-        let mut total_original_amount: i128 = 0;
-        for amount in amounts.iter() {
-            total_original_amount = total_original_amount
-                .checked_add(amount)
-                .unwrap_or_else(|| panic_with_error!(&env, FeeContractError::Overflow));
-            decayed_amounts.push_back(calculate_fee_decay(&env, amount, last_active, current_time));
-        }
+    /// Collects the fees on a batch of transaction volumes in the default asset.
+    pub fn collect_volume_fee_batch(
+        env: Env,
+        payer: Address,
+        volumes: Vec<i128>,
+    ) -> BatchFeeResult {
+        payer.require_auth();
+        Self::collect_volume_batch(&env, &payer, &read_token(&env), &volumes)
+    }
 
-        let result = collect_batch_to_escrow(&env, &payer, &decayed_amounts);
+    /// Same as `collect_volume_fee_batch`, for any registered asset.
+    pub fn collect_asset_volume_fee_batch(
+        env: Env,
+        payer: Address,
+        asset: Address,
+        volumes: Vec<i128>,
+    ) -> BatchFeeResult {
+        payer.require_auth();
+        Self::collect_volume_batch(&env, &payer, &asset, &volumes)
+    }
 
-        write_last_active(&env, &payer, current_time);
+    /// Collects the fees locked in by quote `quote_id` instead of the current
+    /// schedule. The quote must belong to `payer`, cover exactly `amounts`,
+    /// be unused and not have expired.
+    pub fn collect_quoted_fee_batch(
+        env: Env,
+        payer: Address,
+        amounts: Vec<i128>,
+        quote_id: u64,
+    ) -> BatchFeeResult {
+        payer.require_auth();
 
-        FeeEvents::fee_collected(&env, &payer, total_original_amount);
-        FeeEvents::fee_batched(
+        let quote = consume_quote(&env, quote_id, &payer, &amounts);
+        // Quoted fees were checked against the minimum when the quote was
+        // issued, so a later minimum change must not reject them.
+        let result = Self::collect_batch_fees(&env, &payer, &quote.asset, &amounts, &quote.fees);
+        record_volume(
             &env,
             &payer,
-            result.total_amount,
-            result.batch_size,
-            result.cycle,
+            &quote.asset,
+            Self::total_amount(&env, &amounts),
         );
         result
     }
 
+    /// Locks in the fees `collect_volume_fee_batch` would charge for the
+    /// transaction volumes in `amounts` right now, so later rate, tier or
+    /// minimum fee changes do not affect them.
+    /// The quote expires after `QUOTE_TTL_LEDGERS` ledgers and can be used
+    /// once with `collect_quoted_fee_batch`.
+    pub fn quote_fee(env: Env, payer: Address, amounts: Vec<i128>) -> FeeQuote {
+        payer.require_auth();
+        Self::quote(&env, &payer, &read_token(&env), &amounts)
+    }
+
+    /// Same as `quote_fee`, for any registered asset.
+    pub fn quote_asset_fee(
+        env: Env,
+        payer: Address,
+        asset: Address,
+        amounts: Vec<i128>,
+    ) -> FeeQuote {
+        payer.require_auth();
+        require_supported_asset(&env, &asset);
+        Self::quote(&env, &payer, &asset, &amounts)
+    }
+
+    pub fn get_quote(env: Env, quote_id: u64) -> Option<FeeQuote> {
+        read_quote(&env, quote_id)
+    }
+
+    /// Same as `collect_fee_batch`, for any registered asset.
+    pub fn collect_asset_fee_batch(
+        env: Env,
+        payer: Address,
+        asset: Address,
+        amounts: Vec<i128>,
+    ) -> BatchFeeResult {
+        payer.require_auth();
+        Self::collect_batch(&env, &payer, &asset, &amounts)
+    }
+
     pub fn update_activity(env: Env, user: Address) {
         user.require_auth();
         write_last_active(&env, &user, env.ledger().timestamp());
@@ -162,44 +267,187 @@ impl FeeContract {
         read_last_active(&env, &user)
     }
 
-    pub fn release_fees(env: Env, _admin: Address, cycle: u64) -> i128 {
-        require_admin(&env, &_admin);
+    /// Replaces the fee decay curve and its bounds. Admin-only.
+    pub fn set_decay_config(env: Env, admin: Address, config: DecayConfig) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+
+        validate_decay_config_or_panic(&env, &config);
+
+        write_decay_config(&env, &config);
+        FeeEvents::decay_config_updated(&env, &config);
+    }
+
+    pub fn get_decay_config(env: Env) -> DecayConfig {
+        read_decay_config(&env)
+    }
+
+    /// Releases the default asset's pending fees for `cycle`.
+    pub fn release_fees(env: Env, admin: Address, cycle: u64) -> i128 {
+        require_admin(&env, &admin);
+
+        Self::release(&env, &read_token(&env), cycle)
+    }
+
+    /// Releases one asset's pending fees for `cycle`.
+    pub fn release_asset_fees(env: Env, admin: Address, asset: Address, cycle: u64) -> i128 {
+        require_admin(&env, &admin);
+        require_supported_asset(&env, &asset);
+
+        Self::release(&env, &asset, cycle)
+    }
 
-        let released = release_cycle_fees(&env, cycle);
-        FeeEvents::fee_released(&env, cycle, released, &read_treasury(&env));
+    /// Releases pending fees for `cycle` in every asset that has any.
+    /// Returns the released amount per asset.
+    pub fn release_all_fees(env: Env, admin: Address, cycle: u64) -> Map<Address, i128> {
+        require_admin(&env, &admin);
+
+        let mut released = Map::new(&env);
+        for asset in read_assets(&env).iter() {
+            if read_pending_fees(&env, &asset, cycle) > 0 {
+                released.set(asset.clone(), Self::release(&env, &asset, cycle));
+            }
+        }
+        if released.is_empty() {
+            panic_with_error!(&env, FeeContractError::NoPendingFees);
+        }
         released
     }
 
-    pub fn rollover_fees(env: Env, _admin: Address, next_cycle: u64) -> i128 {
-        require_admin(&env, &_admin);
+    /// Advances to `next_cycle`, carrying every asset's pending fees forward.
+    /// Returns the total amount rolled across all assets. Only available
+    /// while cycles are advanced manually.
+    pub fn rollover_fees(env: Env, admin: Address, next_cycle: u64) -> i128 {
+        require_admin(&env, &admin);
+        if read_cycle_schedule(&env).is_some() {
+            panic_with_error!(&env, FeeContractError::CycleScheduleActive);
+        }
 
         let current_cycle = read_current_cycle(&env);
         if next_cycle <= current_cycle {
             panic_with_error!(&env, FeeContractError::InvalidCycle);
         }
 
-        let rolled = rollover_cycle_fees(&env, current_cycle, next_cycle);
+        let mut rolled: i128 = 0;
+        for asset in read_assets(&env).iter() {
+            let asset_rolled = rollover_cycle_fees(&env, &asset, current_cycle, next_cycle);
+            if asset_rolled > 0 {
+                FeeEvents::fee_rolled(&env, &asset, current_cycle, next_cycle, asset_rolled);
+            }
+            rolled = rolled
+                .checked_add(asset_rolled)
+                .unwrap_or_else(|| panic_with_error!(&env, FeeContractError::Overflow));
+        }
         write_current_cycle(&env, next_cycle);
-        FeeEvents::fee_rolled(&env, current_cycle, next_cycle, rolled);
         rolled
     }
 
-    pub fn lock(env: Env, _admin: Address) {
-        require_admin(&env, &_admin);
+    /// Sets the cycle length in seconds. From now on the current cycle is
+    /// derived from the ledger timestamp, starting with the cycle in progress.
+    /// A duration of zero returns to manual rollovers. Admin-only.
+    pub fn set_cycle_duration(env: Env, admin: Address, duration: u64) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+
+        let start_cycle = current_cycle(&env);
+        write_current_cycle(&env, start_cycle);
+        if duration == 0 {
+            remove_cycle_schedule(&env);
+        } else {
+            write_cycle_schedule(
+                &env,
+                &CycleSchedule {
+                    duration,
+                    start_time: env.ledger().timestamp(),
+                    start_cycle,
+                },
+            );
+            if read_next_settle_cycle(&env).is_none() {
+                write_next_settle_cycle(&env, start_cycle);
+            }
+        }
+        FeeEvents::cycle_schedule_updated(&env, duration, start_cycle);
+    }
+
+    /// Releases pending fees of every asset for closed cycles, oldest first,
+    /// settling at most `limit` cycles. Anyone may call this. Returns the
+    /// number of cycles settled.
+    pub fn settle_due_cycles(env: Env, limit: u32) -> u32 {
+        if read_cycle_schedule(&env).is_none() {
+            panic_with_error!(&env, FeeContractError::CycleScheduleNotSet);
+        }
+        if limit > MAX_BATCH_SIZE {
+            panic_with_error!(&env, FeeContractError::BatchTooLarge);
+        }
+
+        let open_cycle = current_cycle(&env);
+        let mut cycle = read_next_settle_cycle(&env).unwrap_or(open_cycle);
+        let mut settled: u32 = 0;
+        while cycle < open_cycle && settled < limit {
+            let mut assets_released: u32 = 0;
+            for asset in read_assets(&env).iter() {
+                if read_pending_fees(&env, &asset, cycle) > 0 {
+                    Self::release(&env, &asset, cycle);
+                    assets_released += 1;
+                }
+            }
+            FeeEvents::cycle_settled(&env, cycle, assets_released);
+            cycle += 1;
+            settled += 1;
+        }
+        write_next_settle_cycle(&env, cycle);
+        settled
+    }
+
+    pub fn get_cycle_schedule(env: Env) -> Option<CycleSchedule> {
+        read_cycle_schedule(&env)
+    }
+
+    pub fn get_cycle_bounds(env: Env, cycle: u64) -> CycleBounds {
+        cycle_bounds(&env, cycle)
+    }
+
+    /// Returns the oldest cycle `settle_due_cycles` has not settled yet.
+    pub fn get_next_settle_cycle(env: Env) -> u64 {
+        read_next_settle_cycle(&env).unwrap_or_else(|| current_cycle(&env))
+    }
+
+    /// Returns every release made out of `cycle`, in order.
+    pub fn get_cycle_releases(env: Env, cycle: u64) -> Vec<CycleRelease> {
+        read_cycle_releases(&env, cycle)
+    }
+
+    /// Registers an additional asset for fee collection. Admin-only.
+    pub fn add_asset(env: Env, admin: Address, asset: Address) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+
+        if !add_asset(&env, &asset) {
+            panic_with_error!(&env, FeeContractError::InvalidConfig);
+        }
+        FeeEvents::asset_added(&env, &asset);
+    }
+
+    pub fn get_assets(env: Env) -> Vec<Address> {
+        read_assets(&env)
+    }
+
+    pub fn lock(env: Env, admin: Address) {
+        require_admin(&env, &admin);
 
         write_locked(&env, true);
         FeeEvents::locked(&env);
     }
 
-    pub fn unlock(env: Env, _admin: Address) {
-        require_admin(&env, &_admin);
+    pub fn unlock(env: Env, admin: Address) {
+        require_admin(&env, &admin);
 
         write_locked(&env, false);
         FeeEvents::unlocked(&env);
     }
 
-    pub fn set_fee_bps(env: Env, _admin: Address, fee_bps: u32) {
-        require_admin(&env, &_admin);
+    pub fn set_fee_bps(env: Env, admin: Address, fee_bps: u32) {
+        require_admin(&env, &admin);
         Self::require_unlocked(&env);
 
         validate_fee_bps_or_panic(&env, fee_bps);
@@ -208,16 +456,35 @@ impl FeeContract {
         FeeEvents::fee_bps_updated(&env, fee_bps);
     }
 
-    pub fn set_treasury(env: Env, _admin: Address, treasury: Address) {
-        require_admin(&env, &_admin);
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) {
+        require_admin(&env, &admin);
         Self::require_unlocked(&env);
 
         write_treasury(&env, &treasury);
         FeeEvents::treasury_updated(&env, &treasury);
     }
 
-    pub fn set_min_fee(env: Env, _admin: Address, min_fee: i128) {
-        require_admin(&env, &_admin);
+    /// Splits future releases between `recipients` by basis-point share.
+    /// Shares must add up to 10,000; rounding dust goes to `dust_recipient`.
+    /// An empty list clears the split so releases go to the treasury again.
+    pub fn set_fee_recipients(
+        env: Env,
+        admin: Address,
+        recipients: Vec<FeeRecipientShare>,
+        dust_recipient: Address,
+    ) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+
+        validate_recipient_shares_or_panic(&env, &recipients);
+
+        write_fee_recipients(&env, &recipients);
+        write_dust_recipient(&env, &dust_recipient);
+        FeeEvents::recipients_updated(&env, recipients.len(), &dust_recipient);
+    }
+
+    pub fn set_min_fee(env: Env, admin: Address, min_fee: i128) {
+        require_admin(&env, &admin);
         Self::require_unlocked(&env);
 
         validate_min_fee_or_panic(&env, min_fee);
@@ -226,14 +493,38 @@ impl FeeContract {
         FeeEvents::min_fee_updated(&env, min_fee);
     }
 
+    /// Overrides the minimum fee for one asset. Admin-only.
+    pub fn set_asset_min_fee(env: Env, admin: Address, asset: Address, min_fee: i128) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+        require_supported_asset(&env, &asset);
+
+        validate_min_fee_or_panic(&env, min_fee);
+
+        write_asset_min_fee(&env, &asset, min_fee);
+        FeeEvents::asset_min_fee_updated(&env, &asset, min_fee);
+    }
+
+    /// Overrides the fee bps charged on one asset's volume when no tier
+    /// schedule is configured. Admin-only.
+    pub fn set_asset_fee_bps(env: Env, admin: Address, asset: Address, fee_bps: u32) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+        require_supported_asset(&env, &asset);
+
+        validate_fee_bps_or_panic(&env, fee_bps);
+
+        write_asset_fee_bps(&env, &asset, fee_bps);
+        FeeEvents::asset_fee_bps_updated(&env, &asset, fee_bps);
+    }
+
     /// Resets fee configuration to default values. Admin-only.
     /// Restores:
     /// - fee_bps to DEFAULT_FEE_BPS (500 = 5%)
     /// - min_fee to DEFAULT_MIN_FEE (0)
     /// Emits a reset event with the restored default values.
     pub fn reset_fee_config(env: Env, admin: Address) {
-        admin.require_auth();
-        Self::require_admin(&env, &admin);
+        require_admin(&env, &admin);
         Self::require_unlocked(&env);
 
         write_fee_bps(&env, DEFAULT_FEE_BPS);
@@ -254,6 +545,24 @@ impl FeeContract {
         read_treasury(&env)
     }
 
+    pub fn get_fee_recipients(env: Env) -> Vec<FeeRecipientShare> {
+        read_fee_recipients(&env)
+    }
+
+    pub fn get_dust_recipient(env: Env) -> Option<Address> {
+        read_dust_recipient(&env)
+    }
+
+    /// Returns the lifetime amount of released fees paid to `recipient`.
+    pub fn get_recipient_total(env: Env, recipient: Address) -> i128 {
+        read_recipient_total(&env, &recipient, &read_token(&env))
+    }
+
+    /// Returns the lifetime amount of released `asset` fees paid to `recipient`.
+    pub fn get_asset_recipient_total(env: Env, recipient: Address, asset: Address) -> i128 {
+        read_recipient_total(&env, &recipient, &asset)
+    }
+
     pub fn get_fee_bps(env: Env) -> u32 {
         read_fee_bps(&env)
     }
@@ -262,38 +571,416 @@ impl FeeContract {
         read_min_fee(&env)
     }
 
+    pub fn get_asset_min_fee(env: Env, asset: Address) -> i128 {
+        read_asset_min_fee(&env, &asset)
+    }
+
+    pub fn get_asset_fee_bps(env: Env, asset: Address) -> u32 {
+        read_asset_fee_bps(&env, &asset)
+    }
+
     pub fn is_locked(env: Env) -> bool {
         read_locked(&env)
     }
 
     pub fn get_current_cycle(env: Env) -> u64 {
-        read_current_cycle(&env)
+        current_cycle(&env)
     }
 
     pub fn get_escrow_balance(env: Env) -> i128 {
-        read_escrow_balance(&env)
+        read_escrow_balance(&env, &read_token(&env))
+    }
+
+    pub fn get_asset_escrow_balance(env: Env, asset: Address) -> i128 {
+        read_escrow_balance(&env, &asset)
     }
 
     /// Returns the current total fee balance stored in the contract.
     /// This is an alias for get_escrow_balance() for clarity.
     pub fn get_fee_balance(env: Env) -> i128 {
-        read_escrow_balance(&env)
+        read_escrow_balance(&env, &read_token(&env))
     }
 
     pub fn get_pending_fees(env: Env, cycle: u64) -> i128 {
-        read_pending_fees(&env, cycle)
+        read_pending_fees(&env, &read_token(&env), cycle)
+    }
+
+    pub fn get_asset_pending_fees(env: Env, asset: Address, cycle: u64) -> i128 {
+        read_pending_fees(&env, &asset, cycle)
     }
 
     pub fn get_total_collected(env: Env) -> i128 {
-        read_total_collected(&env)
+        read_total_collected(&env, &read_token(&env))
+    }
+
+    pub fn get_asset_total_collected(env: Env, asset: Address) -> i128 {
+        read_total_collected(&env, &asset)
     }
 
     pub fn get_total_released(env: Env) -> i128 {
-        read_total_released(&env)
+        read_total_released(&env, &read_token(&env))
+    }
+
+    pub fn get_asset_total_released(env: Env, asset: Address) -> i128 {
+        read_total_released(&env, &asset)
     }
 
     pub fn get_total_batch_calls(env: Env) -> u64 {
         read_total_batch_calls(&env)
     }
 
-    pub fn preview_batch_fee(env: Env
\ No newline at end of file
+    /// Computes the total fee for a batch without transferring tokens or
+    /// mutating any state. Applies the same validation as `collect_fee_batch`
+    /// and reports the payer's volume tier and the volume needed to reach the
+    /// next one.
+    pub fn preview_batch_fee(env: Env, payer: Address, amounts: Vec<i128>) -> BatchFeePreview {
+        Self::require_batch_size(&env, &amounts);
+        let asset = read_token(&env);
+        let fees = Self::charged_fees(&env, &payer, &asset, &amounts);
+        Self::preview(&env, &payer, &asset, &fees)
+    }
+
+    /// Same as `preview_batch_fee`, for the fees `collect_volume_fee_batch`
+    /// would charge on `volumes`.
+    pub fn preview_volume_fee_batch(
+        env: Env,
+        payer: Address,
+        volumes: Vec<i128>,
+    ) -> BatchFeePreview {
+        Self::require_batch_size(&env, &volumes);
+        let asset = read_token(&env);
+        let fees = Self::batch_fees(&env, &payer, &asset, &volumes);
+        Self::preview(&env, &payer, &asset, &fees)
+    }
+
+    /// Replaces the volume tier schedule. Tiers must start at zero volume and
+    /// be strictly ascending. An empty schedule disables tiered pricing.
+    pub fn set_volume_tiers(env: Env, admin: Address, tiers: Vec<VolumeTier>) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+
+        validate_volume_tiers_or_panic(&env, &tiers);
+
+        write_volume_tiers(&env, &tiers);
+        FeeEvents::volume_tiers_updated(&env, tiers.len());
+    }
+
+    pub fn get_volume_tiers(env: Env) -> Vec<VolumeTier> {
+        read_volume_tiers(&env)
+    }
+
+    /// Returns the payer's default-asset volume over the trailing
+    /// `VOLUME_WINDOW_DAYS` days.
+    pub fn get_trailing_volume(env: Env, payer: Address) -> i128 {
+        trailing_volume(&env, &payer, &read_token(&env))
+    }
+
+    pub fn get_asset_trailing_volume(env: Env, payer: Address, asset: Address) -> i128 {
+        trailing_volume(&env, &payer, &asset)
+    }
+
+    /// Computes `amount * fee_bps / 10_000`, rounding down.
+    pub fn calculate_fee_amount(env: Env, amount: i128, fee_bps: u32) -> i128 {
+        compute_fee(amount, fee_bps)
+            .unwrap_or_else(|| panic_with_error!(&env, FeeContractError::Overflow))
+    }
+
+    /// Returns true if the given fee configuration would be accepted by the setters.
+    pub fn validate_config(env: Env, fee_bps: u32, min_fee: i128) -> bool {
+        validate_fee_bps_or_panic(&env, fee_bps) && validate_min_fee_or_panic(&env, min_fee)
+    }
+
+    /// Assigns a tier to a user. Admin-only.
+    pub fn set_user_tier(env: Env, admin: Address, user: Address, tier: Symbol) {
+        require_admin(&env, &admin);
+        if !is_valid_tier(&env, &tier) {
+            panic_with_error!(&env, FeeContractError::InvalidTier);
+        }
+
+        write_user_tier(&env, &user, &tier);
+        TierEvents::tier_set(&env, &admin, &user, &tier);
+    }
+
+    /// Removes a user's tier. Admin-only. No-op if the user has no tier.
+    pub fn remove_user_tier(env: Env, admin: Address, user: Address) {
+        require_admin(&env, &admin);
+
+        remove_user_tier(&env, &user);
+        TierEvents::tier_removed(&env, &admin, &user);
+    }
+
+    pub fn get_user_tier(env: Env, user: Address) -> Option<Symbol> {
+        read_user_tier(&env, &user)
+    }
+
+    /// Posts a correction to `asset`'s escrow ledger with a reason code and
+    /// records it in the adjustment journal. Admin-only.
+    pub fn post_adjustment(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        amount: i128,
+        reason: u32,
+    ) -> AdjustmentEntry {
+        require_admin(&env, &admin);
+        require_supported_asset(&env, &asset);
+
+        let entry = apply_adjustment(&env, &admin, &asset, amount, reason);
+        FeeEvents::adjustment_posted(&env, entry.id, &asset, amount, reason);
+        entry
+    }
+
+    pub fn get_adjustment(env: Env, id: u64) -> Option<AdjustmentEntry> {
+        read_adjustment(&env, id)
+    }
+
+    pub fn get_adjustment_count(env: Env) -> u64 {
+        read_adjustment_count(&env)
+    }
+
+    /// Sets the largest discrepancy that still allows releases. Admin-only.
+    pub fn set_reconciliation_tolerance(env: Env, admin: Address, tolerance: i128) {
+        require_admin(&env, &admin);
+        Self::require_unlocked(&env);
+
+        if tolerance < 0 {
+            panic_with_error!(&env, FeeContractError::InvalidConfig);
+        }
+        write_recon_tolerance(&env, tolerance);
+        FeeEvents::tolerance_updated(&env, tolerance);
+    }
+
+    pub fn get_reconciliation_tolerance(env: Env) -> i128 {
+        read_recon_tolerance(&env)
+    }
+
+    /// Returns the current reconciliation status of the default asset
+    /// without emitting events.
+    pub fn get_reconciliation_status(env: Env) -> ReconciliationResult {
+        reconcile(&env, &read_token(&env))
+    }
+
+    pub fn get_asset_reconciliation_status(env: Env, asset: Address) -> ReconciliationResult {
+        reconcile(&env, &asset)
+    }
+
+    /// Reconciles the default asset's escrow ledger and emits the outcome.
+    /// Admin-only.
+    pub fn reconcile_fees(env: Env, admin: Address) -> ReconciliationResult {
+        require_admin(&env, &admin);
+
+        let result = reconcile(&env, &read_token(&env));
+        if result.is_reconciled {
+            FeeEvents::fee_reconciled(&env, result.stored_balance, result.calculated_balance);
+        } else {
+            FeeEvents::fee_discrepancy(
+                &env,
+                result.stored_balance,
+                result.calculated_balance,
+                result.discrepancy,
+            );
+        }
+        result
+    }
+
+    fn collect(env: &Env, payer: &Address, asset: &Address, amount: i128) -> i128 {
+        let fee = Self::charged_fee(env, payer, asset, amount);
+        let pending = collect_to_escrow(env, payer, asset, fee);
+
+        write_last_active(env, payer, env.ledger().timestamp());
+
+        FeeEvents::fee_collected(env, payer, amount);
+        FeeEvents::fee_escrowed(env, payer, asset, fee, current_cycle(env));
+        pending
+    }
+
+    fn collect_volume(env: &Env, payer: &Address, asset: &Address, volume: i128) -> i128 {
+        let fee_bps = fee_rate(env, asset, current_tier(env, payer, asset).as_ref());
+        let fee = Self::fee_for(env, payer, asset, fee_bps, volume);
+
+        let pending = collect_to_escrow(env, payer, asset, fee);
+
+        write_last_active(env, payer, env.ledger().timestamp());
+        record_volume(env, payer, asset, volume);
+
+        FeeEvents::fee_collected(env, payer, volume);
+        FeeEvents::fee_escrowed(env, payer, asset, fee, current_cycle(env));
+        pending
+    }
+
+    fn collect_batch(
+        env: &Env,
+        payer: &Address,
+        asset: &Address,
+        amounts: &Vec<i128>,
+    ) -> BatchFeeResult {
+        Self::require_batch_size(env, amounts);
+        let fees = Self::charged_fees(env, payer, asset, amounts);
+        Self::collect_batch_fees(env, payer, asset, amounts, &fees)
+    }
+
+    fn collect_volume_batch(
+        env: &Env,
+        payer: &Address,
+        asset: &Address,
+        volumes: &Vec<i128>,
+    ) -> BatchFeeResult {
+        Self::require_batch_size(env, volumes);
+        let fees = Self::batch_fees(env, payer, asset, volumes);
+        let result = Self::collect_batch_fees(env, payer, asset, volumes, &fees);
+        record_volume(env, payer, asset, Self::total_amount(env, volumes));
+        result
+    }
+
+    /// Escrows `fees` charged on `amounts`.
+    fn collect_batch_fees(
+        env: &Env,
+        payer: &Address,
+        asset: &Address,
+        amounts: &Vec<i128>,
+        fees: &Vec<i128>,
+    ) -> BatchFeeResult {
+        let total_original_amount = Self::total_amount(env, amounts);
+
+        let result = collect_batch_to_escrow(env, payer, asset, fees);
+
+        write_last_active(env, payer, env.ledger().timestamp());
+
+        FeeEvents::fee_collected(env, payer, total_original_amount);
+        FeeEvents::fee_batched(
+            env,
+            payer,
+            result.total_amount,
+            result.batch_size,
+            result.cycle,
+        );
+        result
+    }
+
+    fn total_amount(env: &Env, amounts: &Vec<i128>) -> i128 {
+        let mut total: i128 = 0;
+        for amount in amounts.iter() {
+            total = total
+                .checked_add(amount)
+                .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+        }
+        total
+    }
+
+    fn preview(env: &Env, payer: &Address, asset: &Address, fees: &Vec<i128>) -> BatchFeePreview {
+        let tiers = read_volume_tiers(env);
+        let volume = trailing_volume(env, payer, asset);
+        let (tier, next_tier) = resolve_tier(&tiers, volume);
+
+        let volume_to_next_tier = next_tier
+            .as_ref()
+            .map(|next| next.min_volume - volume)
+            .unwrap_or(0);
+
+        BatchFeePreview {
+            total_fee: Self::total_amount(env, fees),
+            trailing_volume: volume,
+            tier_bps: tier.map(|tier| tier.fee_bps),
+            next_tier_bps: next_tier.map(|next| next.fee_bps),
+            volume_to_next_tier,
+        }
+    }
+
+    fn quote(env: &Env, payer: &Address, asset: &Address, amounts: &Vec<i128>) -> FeeQuote {
+        Self::require_batch_size(env, amounts);
+        let fees = Self::batch_fees(env, payer, asset, amounts);
+        for fee in fees.iter() {
+            if fee <= 0 {
+                panic_with_error!(env, FeeContractError::InvalidAmount);
+            }
+        }
+
+        let quote = create_quote(env, payer, asset, amounts, &fees);
+        FeeEvents::fee_quoted(
+            env,
+            payer,
+            quote.id,
+            quote.total_fee,
+            quote.expires_at_ledger,
+        );
+        quote
+    }
+
+    fn require_batch_size(env: &Env, amounts: &Vec<i128>) {
+        let batch_size = amounts.len();
+        if batch_size == 0 {
+            panic_with_error!(env, FeeContractError::EmptyBatch);
+        }
+        if batch_size > MAX_BATCH_SIZE {
+            panic_with_error!(env, FeeContractError::BatchTooLarge);
+        }
+    }
+
+    /// `amount` decayed by the payer's inactivity. Panics with
+    /// `InvalidAmount` if it is not positive or falls below the asset's
+    /// minimum fee.
+    fn charged_fee(env: &Env, payer: &Address, asset: &Address, amount: i128) -> i128 {
+        if validate_non_negative_amount(amount).is_err() || amount == 0 {
+            panic_with_error!(env, FeeContractError::InvalidAmount);
+        }
+        let fee = calculate_fee_decay(
+            env,
+            amount,
+            read_last_active(env, payer),
+            env.ledger().timestamp(),
+        );
+        if fee < read_asset_min_fee(env, asset) {
+            panic_with_error!(env, FeeContractError::InvalidAmount);
+        }
+        fee
+    }
+
+    /// `charged_fee` of each amount.
+    fn charged_fees(env: &Env, payer: &Address, asset: &Address, amounts: &Vec<i128>) -> Vec<i128> {
+        let mut fees = Vec::new(env);
+        for amount in amounts.iter() {
+            fees.push_back(Self::charged_fee(env, payer, asset, amount));
+        }
+        fees
+    }
+
+    /// Fees for each amount at the payer's current rate.
+    fn batch_fees(env: &Env, payer: &Address, asset: &Address, amounts: &Vec<i128>) -> Vec<i128> {
+        let fee_bps = fee_rate(env, asset, current_tier(env, payer, asset).as_ref());
+
+        let mut fees = Vec::new(env);
+        for amount in amounts.iter() {
+            fees.push_back(Self::fee_for(env, payer, asset, fee_bps, amount));
+        }
+        fees
+    }
+
+    /// Fee on a transaction of `amount` at `fee_bps`, decayed by the payer's
+    /// inactivity and floored at the asset's minimum fee.
+    fn fee_for(env: &Env, payer: &Address, asset: &Address, fee_bps: u32, amount: i128) -> i128 {
+        if validate_non_negative_amount(amount).is_err() || amount == 0 {
+            panic_with_error!(env, FeeContractError::InvalidAmount);
+        }
+        let fee = calculate_fee_decay(
+            env,
+            volume_fee(env, amount, fee_bps),
+            read_last_active(env, payer),
+            env.ledger().timestamp(),
+        );
+        fee.max(read_asset_min_fee(env, asset))
+    }
+
+    fn release(env: &Env, asset: &Address, cycle: u64) -> i128 {
+        require_reconciled_or_panic(env, asset);
+        let released = release_cycle_fees(env, asset, cycle);
+        record_release(env, cycle, asset, released);
+        FeeEvents::fee_released(env, asset, cycle, released, &release_recipients(env));
+        released
+    }
+
+    fn require_unlocked(env: &Env) {
+        if read_locked(env) {
+            panic_with_error!(env, FeeContractError::Locked);
+        }
+    }
+}
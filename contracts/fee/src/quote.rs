@@ -0,0 +1,71 @@
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::storage::{read_quote, read_quote_count, write_quote, write_quote_count};
+use crate::FeeContractError;
+
+/// Number of ledgers a quote stays valid for (~10 minutes at 5s ledgers).
+pub const QUOTE_TTL_LEDGERS: u32 = 120;
+
+/// Fees locked in for a specific batch until `expires_at_ledger`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeQuote {
+    pub id: u64,
+    pub payer: Address,
+    pub asset: Address,
+    pub amounts: Vec<i128>,
+    pub fees: Vec<i128>,
+    pub total_fee: i128,
+    pub expires_at_ledger: u32,
+    pub used: bool,
+}
+
+pub fn create_quote(
+    env: &Env,
+    payer: &Address,
+    asset: &Address,
+    amounts: &Vec<i128>,
+    fees: &Vec<i128>,
+) -> FeeQuote {
+    let mut total_fee: i128 = 0;
+    for fee in fees.iter() {
+        total_fee = total_fee
+            .checked_add(fee)
+            .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+    }
+
+    let id = read_quote_count(env) + 1;
+    let quote = FeeQuote {
+        id,
+        payer: payer.clone(),
+        asset: asset.clone(),
+        amounts: amounts.clone(),
+        fees: fees.clone(),
+        total_fee,
+        expires_at_ledger: env.ledger().sequence() + QUOTE_TTL_LEDGERS,
+        used: false,
+    };
+    write_quote(env, &quote);
+    write_quote_count(env, id);
+    quote
+}
+
+/// Marks quote `id` as used and returns it. Panics unless the quote belongs
+/// to `payer`, covers exactly `amounts`, is unused and has not expired.
+pub fn consume_quote(env: &Env, id: u64, payer: &Address, amounts: &Vec<i128>) -> FeeQuote {
+    let mut quote = read_quote(env, id)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::QuoteNotFound));
+    if quote.payer != *payer || quote.amounts != *amounts {
+        panic_with_error!(env, FeeContractError::QuoteMismatch);
+    }
+    if quote.used {
+        panic_with_error!(env, FeeContractError::QuoteAlreadyUsed);
+    }
+    if env.ledger().sequence() > quote.expires_at_ledger {
+        panic_with_error!(env, FeeContractError::QuoteExpired);
+    }
+
+    quote.used = true;
+    write_quote(env, &quote);
+    quote
+}
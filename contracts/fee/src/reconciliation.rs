@@ -1,6 +1,10 @@
-use soroban_sdk::{contracttype, Env};
+use soroban_sdk::{contracttype, panic_with_error, token, Address, Env};
 
-use crate::storage::{read_escrow_balance, read_total_collected, read_total_released};
+use crate::storage::{
+    add_total_adjusted, append_adjustment, read_escrow_balance, read_recon_tolerance,
+    read_total_adjusted, read_total_collected, read_total_released, write_escrow_balance,
+};
+use crate::FeeContractError;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -8,24 +12,100 @@ pub struct ReconciliationResult {
     pub stored_balance: i128,
     pub calculated_balance: i128,
     pub discrepancy: i128,
+    pub token_balance: i128,
+    pub token_discrepancy: i128,
     pub is_reconciled: bool,
 }
 
-/// Compare the stored escrow balance against the calculated balance
-/// (total_collected - total_released). Returns a result describing any
-/// discrepancy between the two values.
-pub fn reconcile(env: &Env) -> ReconciliationResult {
-    let stored_balance = read_escrow_balance(env);
-    let total_collected = read_total_collected(env);
-    let total_released = read_total_released(env);
+/// A journal entry recording an admin correction to an asset's escrow ledger.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AdjustmentEntry {
+    pub id: u64,
+    pub asset: Address,
+    pub amount: i128,
+    pub reason: u32,
+    pub admin: Address,
+    pub balance_before: i128,
+    pub balance_after: i128,
+    pub timestamp: u64,
+}
+
+/// Compare the stored escrow balance of `asset` against the calculated
+/// balance (total_collected - total_released + adjustments) and against the
+/// tokens the contract actually holds. Returns a result describing any
+/// discrepancy between those values.
+pub fn reconcile(env: &Env, asset: &Address) -> ReconciliationResult {
+    let stored_balance = read_escrow_balance(env, asset);
+    let total_collected = read_total_collected(env, asset);
+    let total_released = read_total_released(env, asset);
+    let total_adjusted = read_total_adjusted(env, asset);
 
-    let calculated_balance = total_collected - total_released;
+    let calculated_balance = total_collected - total_released + total_adjusted;
     let discrepancy = stored_balance - calculated_balance;
 
+    let token_balance = token::Client::new(env, asset).balance(&env.current_contract_address());
+    let token_discrepancy = token_balance - stored_balance;
+
     ReconciliationResult {
         stored_balance,
         calculated_balance,
         discrepancy,
-        is_reconciled: discrepancy == 0,
+        token_balance,
+        token_discrepancy,
+        is_reconciled: discrepancy == 0 && token_discrepancy == 0,
+    }
+}
+
+/// Panics with UnresolvedDiscrepancy when the internal discrepancy of
+/// `asset` exceeds the configured tolerance, or the contract holds less of
+/// the token than its stored escrow by more than the tolerance. A token
+/// surplus (e.g. a direct transfer to the contract) never blocks.
+pub fn require_reconciled_or_panic(env: &Env, asset: &Address) {
+    let result = reconcile(env, asset);
+    let tolerance = read_recon_tolerance(env);
+    if result.discrepancy.abs() > tolerance || result.token_discrepancy < -tolerance {
+        panic_with_error!(env, FeeContractError::UnresolvedDiscrepancy);
     }
 }
+
+/// Moves the stored escrow balance of `asset` by `amount` and appends the
+/// correction to the journal. The adjustment is also counted in the
+/// calculated balance, so it never opens an internal discrepancy.
+pub fn apply_adjustment(
+    env: &Env,
+    admin: &Address,
+    asset: &Address,
+    amount: i128,
+    reason: u32,
+) -> AdjustmentEntry {
+    if amount == 0 {
+        panic_with_error!(env, FeeContractError::InvalidAmount);
+    }
+
+    let balance_before = read_escrow_balance(env, asset);
+    let balance_after = balance_before
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+    if balance_after < 0 {
+        panic_with_error!(env, FeeContractError::InsufficientEscrow);
+    }
+
+    write_escrow_balance(env, asset, balance_after);
+    add_total_adjusted(env, asset, amount)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+
+    append_adjustment(
+        env,
+        AdjustmentEntry {
+            id: 0,
+            asset: asset.clone(),
+            amount,
+            reason,
+            admin: admin.clone(),
+            balance_before,
+            balance_after,
+            timestamp: env.ledger().timestamp(),
+        },
+    )
+}
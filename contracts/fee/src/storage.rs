@@ -1,4 +1,8 @@
-use soroban_sdk::{contracttype, Address, Env, Symbol};
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::decay::DecayConfig;
+use crate::quote::FeeQuote;
+use crate::reconciliation::AdjustmentEntry;
 
 pub const MAX_BATCH_SIZE: u32 = 100;
 pub const MAX_FEE_BPS: u32 = 10_000;
@@ -9,6 +13,10 @@ pub const DEFAULT_FEE_BPS: u32 = 500;
 /// Default minimum fee (0)
 pub const DEFAULT_MIN_FEE: i128 = 0;
 
+/// Length of the trailing window used for volume-tiered fees.
+pub const VOLUME_WINDOW_DAYS: u64 = 30;
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct BatchFeeResult {
@@ -18,6 +26,68 @@ pub struct BatchFeeResult {
     pub pending_fees: i128,
 }
 
+/// A fee rate that applies once a payer's trailing volume reaches `min_volume`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VolumeTier {
+    pub min_volume: i128,
+    pub fee_bps: u32,
+}
+
+/// Volume a payer moved through the contract on a single day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VolumeBucket {
+    pub day: u64,
+    pub volume: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BatchFeePreview {
+    pub total_fee: i128,
+    pub trailing_volume: i128,
+    pub tier_bps: Option<u32>,
+    pub next_tier_bps: Option<u32>,
+    pub volume_to_next_tier: i128,
+}
+
+/// Anchors timestamp-driven cycles: cycle `start_cycle` began at
+/// `start_time` and each cycle lasts `duration` seconds.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CycleSchedule {
+    pub duration: u64,
+    pub start_time: u64,
+    pub start_cycle: u64,
+}
+
+/// Timestamp range covered by a scheduled cycle; `end_time` is exclusive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CycleBounds {
+    pub cycle: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+/// One asset's release out of a cycle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CycleRelease {
+    pub asset: Address,
+    pub amount: i128,
+    pub released_at: u64,
+}
+
+/// A recipient's share of released fees, in basis points.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeRecipientShare {
+    pub recipient: Address,
+    pub share_bps: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -28,13 +98,65 @@ pub enum DataKey {
     MinFee,
     IsLocked,
     CurrentCycle,
+    CycleSchedule,
+    NextSettleCycle,
+    CycleReleases(u64),
+    Assets,
+    AssetMinFee(Address),
+    AssetFeeBps(Address),
+    EscrowBalance(Address),
+    TotalCollected(Address),
+    TotalReleased(Address),
+    TotalAdjusted(Address),
+    AdjustmentCount,
+    Adjustment(u64),
+    ReconTolerance,
+    QuoteCount,
+    Quote(u64),
+    TotalBatchCalls,
+    PendingFees(Address, u64),
+    UserActivity(Address),
+    UserTier(Address),
+    VolumeTiers,
+    PayerVolume(Address, Address),
+    DecayConfig,
+    FeeRecipients,
+    DustRecipient,
+    RecipientTotal(Address, Address),
+}
+
+/// Keys the ledgers were stored under before they were keyed by asset.
+/// Entries under them belong to the default token and are read until the
+/// asset-keyed entry is first written.
+#[derive(Clone)]
+#[contracttype]
+enum LegacyDataKey {
     EscrowBalance,
     TotalCollected,
     TotalReleased,
-    TotalBatchCalls,
     PendingFees(u64),
-    UserActivity(Address),
-    UserTier(Address),
+    PayerVolume(Address),
+}
+
+fn is_default_token(env: &Env, asset: &Address) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Token)
+        .is_some_and(|token| token == *asset)
+}
+
+/// Reads an instance ledger for `asset`, falling back to its legacy un-keyed
+/// entry for the default token.
+fn read_instance_ledger(env: &Env, key: &DataKey, legacy: &LegacyDataKey, asset: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(key)
+        .or_else(|| {
+            is_default_token(env, asset)
+                .then(|| env.storage().instance().get(legacy))
+                .flatten()
+        })
+        .unwrap_or(0)
 }
 
 pub fn has_admin(env: &Env) -> bool {
@@ -93,6 +215,56 @@ pub fn read_min_fee(env: &Env) -> i128 {
     env.storage().instance().get(&DataKey::MinFee).unwrap_or(0)
 }
 
+pub fn write_asset_min_fee(env: &Env, asset: &Address, min_fee: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AssetMinFee(asset.clone()), &min_fee);
+}
+
+/// Minimum fee for `asset`, falling back to the contract-wide minimum.
+pub fn read_asset_min_fee(env: &Env, asset: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AssetMinFee(asset.clone()))
+        .unwrap_or_else(|| read_min_fee(env))
+}
+
+pub fn write_asset_fee_bps(env: &Env, asset: &Address, fee_bps: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AssetFeeBps(asset.clone()), &fee_bps);
+}
+
+/// Fee bps for `asset`, falling back to the contract-wide rate.
+pub fn read_asset_fee_bps(env: &Env, asset: &Address) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AssetFeeBps(asset.clone()))
+        .unwrap_or_else(|| read_fee_bps(env))
+}
+
+pub fn read_assets(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Assets)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn is_supported_asset(env: &Env, asset: &Address) -> bool {
+    read_assets(env).contains(asset)
+}
+
+/// Registers `asset` for fee collection. Returns false if already registered.
+pub fn add_asset(env: &Env, asset: &Address) -> bool {
+    let mut assets = read_assets(env);
+    if assets.contains(asset) {
+        return false;
+    }
+    assets.push_back(asset.clone());
+    env.storage().instance().set(&DataKey::Assets, &assets);
+    true
+}
+
 pub fn write_locked(env: &Env, is_locked: bool) {
     env.storage().instance().set(&DataKey::IsLocked, &is_locked);
 }
@@ -115,79 +287,100 @@ pub fn read_current_cycle(env: &Env) -> u64 {
         .expect("Contract not initialized")
 }
 
-pub fn read_pending_fees(env: &Env, cycle: u64) -> i128 {
+pub fn read_pending_fees(env: &Env, asset: &Address, cycle: u64) -> i128 {
     env.storage()
         .persistent()
-        .get(&DataKey::PendingFees(cycle))
+        .get(&DataKey::PendingFees(asset.clone(), cycle))
+        .or_else(|| {
+            is_default_token(env, asset)
+                .then(|| {
+                    env.storage()
+                        .persistent()
+                        .get(&LegacyDataKey::PendingFees(cycle))
+                })
+                .flatten()
+        })
         .unwrap_or(0)
 }
 
-pub fn write_pending_fees(env: &Env, cycle: u64, amount: i128) {
+pub fn write_pending_fees(env: &Env, asset: &Address, cycle: u64, amount: i128) {
     env.storage()
         .persistent()
-        .set(&DataKey::PendingFees(cycle), &amount);
+        .set(&DataKey::PendingFees(asset.clone(), cycle), &amount);
 }
 
-pub fn add_pending_fees(env: &Env, cycle: u64, amount: i128) -> Option<i128> {
-    let updated = read_pending_fees(env, cycle).checked_add(amount)?;
-    write_pending_fees(env, cycle, updated);
+pub fn add_pending_fees(env: &Env, asset: &Address, cycle: u64, amount: i128) -> Option<i128> {
+    let updated = read_pending_fees(env, asset, cycle).checked_add(amount)?;
+    write_pending_fees(env, asset, cycle, updated);
     Some(updated)
 }
 
-pub fn clear_pending_fees(env: &Env, cycle: u64) {
-    write_pending_fees(env, cycle, 0);
+pub fn clear_pending_fees(env: &Env, asset: &Address, cycle: u64) {
+    write_pending_fees(env, asset, cycle, 0);
+}
+
+pub fn read_escrow_balance(env: &Env, asset: &Address) -> i128 {
+    read_instance_ledger(
+        env,
+        &DataKey::EscrowBalance(asset.clone()),
+        &LegacyDataKey::EscrowBalance,
+        asset,
+    )
 }
 
-pub fn read_escrow_balance(env: &Env) -> i128 {
+pub fn write_escrow_balance(env: &Env, asset: &Address, amount: i128) {
     env.storage()
         .instance()
-        .get(&DataKey::EscrowBalance)
-        .unwrap_or(0)
+        .set(&DataKey::EscrowBalance(asset.clone()), &amount);
 }
 
-pub fn add_escrow_balance(env: &Env, amount: i128) -> Option<i128> {
-    let updated = read_escrow_balance(env).checked_add(amount)?;
+pub fn add_escrow_balance(env: &Env, asset: &Address, amount: i128) -> Option<i128> {
+    let updated = read_escrow_balance(env, asset).checked_add(amount)?;
     env.storage()
         .instance()
-        .set(&DataKey::EscrowBalance, &updated);
+        .set(&DataKey::EscrowBalance(asset.clone()), &updated);
     Some(updated)
 }
 
-pub fn sub_escrow_balance(env: &Env, amount: i128) -> Option<i128> {
-    let updated = read_escrow_balance(env).checked_sub(amount)?;
+pub fn sub_escrow_balance(env: &Env, asset: &Address, amount: i128) -> Option<i128> {
+    let updated = read_escrow_balance(env, asset).checked_sub(amount)?;
     env.storage()
         .instance()
-        .set(&DataKey::EscrowBalance, &updated);
+        .set(&DataKey::EscrowBalance(asset.clone()), &updated);
     Some(updated)
 }
 
-pub fn read_total_collected(env: &Env) -> i128 {
-    env.storage()
-        .instance()
-        .get(&DataKey::TotalCollected)
-        .unwrap_or(0)
+pub fn read_total_collected(env: &Env, asset: &Address) -> i128 {
+    read_instance_ledger(
+        env,
+        &DataKey::TotalCollected(asset.clone()),
+        &LegacyDataKey::TotalCollected,
+        asset,
+    )
 }
 
-pub fn add_total_collected(env: &Env, amount: i128) -> Option<i128> {
-    let updated = read_total_collected(env).checked_add(amount)?;
+pub fn add_total_collected(env: &Env, asset: &Address, amount: i128) -> Option<i128> {
+    let updated = read_total_collected(env, asset).checked_add(amount)?;
     env.storage()
         .instance()
-        .set(&DataKey::TotalCollected, &updated);
+        .set(&DataKey::TotalCollected(asset.clone()), &updated);
     Some(updated)
 }
 
-pub fn read_total_released(env: &Env) -> i128 {
-    env.storage()
-        .instance()
-        .get(&DataKey::TotalReleased)
-        .unwrap_or(0)
+pub fn read_total_released(env: &Env, asset: &Address) -> i128 {
+    read_instance_ledger(
+        env,
+        &DataKey::TotalReleased(asset.clone()),
+        &LegacyDataKey::TotalReleased,
+        asset,
+    )
 }
 
-pub fn add_total_released(env: &Env, amount: i128) -> Option<i128> {
-    let updated = read_total_released(env).checked_add(amount)?;
+pub fn add_total_released(env: &Env, asset: &Address, amount: i128) -> Option<i128> {
+    let updated = read_total_released(env, asset).checked_add(amount)?;
     env.storage()
         .instance()
-        .set(&DataKey::TotalReleased, &updated);
+        .set(&DataKey::TotalReleased(asset.clone()), &updated);
     Some(updated)
 }
 
@@ -219,6 +412,184 @@ pub fn write_last_active(env: &Env, user: &Address, timestamp: u64) {
         .set(&DataKey::UserActivity(user.clone()), &timestamp);
 }
 
+pub fn read_fee_recipients(env: &Env) -> Vec<FeeRecipientShare> {
+    env.storage()
+        .instance()
+        .get(&DataKey::FeeRecipients)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn write_fee_recipients(env: &Env, recipients: &Vec<FeeRecipientShare>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::FeeRecipients, recipients);
+}
+
+pub fn read_dust_recipient(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::DustRecipient)
+}
+
+pub fn write_dust_recipient(env: &Env, recipient: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::DustRecipient, recipient);
+}
+
+pub fn read_recipient_total(env: &Env, recipient: &Address, asset: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RecipientTotal(recipient.clone(), asset.clone()))
+        .unwrap_or(0)
+}
+
+pub fn add_recipient_total(
+    env: &Env,
+    recipient: &Address,
+    asset: &Address,
+    amount: i128,
+) -> Option<i128> {
+    let updated = read_recipient_total(env, recipient, asset).checked_add(amount)?;
+    env.storage().persistent().set(
+        &DataKey::RecipientTotal(recipient.clone(), asset.clone()),
+        &updated,
+    );
+    Some(updated)
+}
+
+pub fn read_decay_config(env: &Env) -> DecayConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::DecayConfig)
+        .unwrap_or_else(DecayConfig::default_config)
+}
+
+pub fn write_decay_config(env: &Env, config: &DecayConfig) {
+    env.storage().instance().set(&DataKey::DecayConfig, config);
+}
+
+// ---------------------------------------------------------------------------
+// Reconciliation journal storage helpers
+// ---------------------------------------------------------------------------
+
+pub fn read_total_adjusted(env: &Env, asset: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalAdjusted(asset.clone()))
+        .unwrap_or(0)
+}
+
+pub fn add_total_adjusted(env: &Env, asset: &Address, amount: i128) -> Option<i128> {
+    let updated = read_total_adjusted(env, asset).checked_add(amount)?;
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalAdjusted(asset.clone()), &updated);
+    Some(updated)
+}
+
+pub fn read_adjustment_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AdjustmentCount)
+        .unwrap_or(0)
+}
+
+pub fn read_adjustment(env: &Env, id: u64) -> Option<AdjustmentEntry> {
+    env.storage().persistent().get(&DataKey::Adjustment(id))
+}
+
+/// Appends `entry` to the journal under the next id. Entries are never
+/// rewritten once stored.
+pub fn append_adjustment(env: &Env, mut entry: AdjustmentEntry) -> AdjustmentEntry {
+    let id = read_adjustment_count(env) + 1;
+    entry.id = id;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Adjustment(id), &entry);
+    env.storage().instance().set(&DataKey::AdjustmentCount, &id);
+    entry
+}
+
+pub fn read_recon_tolerance(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReconTolerance)
+        .unwrap_or(0)
+}
+
+pub fn write_recon_tolerance(env: &Env, tolerance: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ReconTolerance, &tolerance);
+}
+
+// ---------------------------------------------------------------------------
+// Fee quote storage helpers
+// ---------------------------------------------------------------------------
+
+pub fn read_quote_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::QuoteCount)
+        .unwrap_or(0)
+}
+
+pub fn write_quote_count(env: &Env, count: u64) {
+    env.storage().instance().set(&DataKey::QuoteCount, &count);
+}
+
+pub fn read_quote(env: &Env, id: u64) -> Option<FeeQuote> {
+    env.storage().persistent().get(&DataKey::Quote(id))
+}
+
+pub fn write_quote(env: &Env, quote: &FeeQuote) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Quote(quote.id), quote);
+}
+
+// ---------------------------------------------------------------------------
+// Cycle schedule storage helpers
+// ---------------------------------------------------------------------------
+
+pub fn read_cycle_schedule(env: &Env) -> Option<CycleSchedule> {
+    env.storage().instance().get(&DataKey::CycleSchedule)
+}
+
+pub fn write_cycle_schedule(env: &Env, schedule: &CycleSchedule) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CycleSchedule, schedule);
+}
+
+pub fn remove_cycle_schedule(env: &Env) {
+    env.storage().instance().remove(&DataKey::CycleSchedule);
+}
+
+pub fn read_next_settle_cycle(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::NextSettleCycle)
+}
+
+pub fn write_next_settle_cycle(env: &Env, cycle: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::NextSettleCycle, &cycle);
+}
+
+pub fn read_cycle_releases(env: &Env, cycle: u64) -> Vec<CycleRelease> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CycleReleases(cycle))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn append_cycle_release(env: &Env, cycle: u64, release: CycleRelease) {
+    let mut releases = read_cycle_releases(env, cycle);
+    releases.push_back(release);
+    env.storage()
+        .persistent()
+        .set(&DataKey::CycleReleases(cycle), &releases);
+}
+
 // ---------------------------------------------------------------------------
 // User tier storage helpers
 // ---------------------------------------------------------------------------
@@ -255,3 +626,45 @@ pub fn remove_user_tier(env: &Env, user: &Address) {
         .persistent()
         .remove(&DataKey::UserTier(user.clone()));
 }
+
+// ---------------------------------------------------------------------------
+// Volume tier storage helpers
+// ---------------------------------------------------------------------------
+
+pub fn read_volume_tiers(env: &Env) -> Vec<VolumeTier> {
+    env.storage()
+        .instance()
+        .get(&DataKey::VolumeTiers)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn write_volume_tiers(env: &Env, tiers: &Vec<VolumeTier>) {
+    env.storage().instance().set(&DataKey::VolumeTiers, tiers);
+}
+
+pub fn read_volume_buckets(env: &Env, payer: &Address, asset: &Address) -> Vec<VolumeBucket> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PayerVolume(payer.clone(), asset.clone()))
+        .or_else(|| {
+            is_default_token(env, asset)
+                .then(|| {
+                    env.storage()
+                        .persistent()
+                        .get(&LegacyDataKey::PayerVolume(payer.clone()))
+                })
+                .flatten()
+        })
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn write_volume_buckets(
+    env: &Env,
+    payer: &Address,
+    asset: &Address,
+    buckets: &Vec<VolumeBucket>,
+) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PayerVolume(payer.clone(), asset.clone()), buckets);
+}
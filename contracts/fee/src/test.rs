@@ -134,7 +134,7 @@ fn test_init_default() {
 
 #[test]
 fn test_reset_fee_config_restores_defaults() {
-    let (env, admin, client) = setup();
+    let (_env, admin, client) = setup();
     client.set_fee_bps(&admin, &1000u32);
     client.set_min_fee(&admin, &100i128);
     assert_eq!(client.get_fee_bps(), 1000);
@@ -205,8 +205,14 @@ fn test_validate_fee_bps_valid() {
 fn test_validate_fee_bps_invalid() {
     use crate::validation::validate_fee_bps;
     use crate::FeeContractError;
-    assert_eq!(validate_fee_bps(10001), Err(FeeContractError::InvalidConfig));
-    assert_eq!(validate_fee_bps(99999), Err(FeeContractError::InvalidConfig));
+    assert_eq!(
+        validate_fee_bps(10001),
+        Err(FeeContractError::InvalidConfig)
+    );
+    assert_eq!(
+        validate_fee_bps(99999),
+        Err(FeeContractError::InvalidConfig)
+    );
 }
 
 #[test]
@@ -222,5 +228,8 @@ fn test_validate_min_fee_invalid() {
     use crate::validation::validate_min_fee;
     use crate::FeeContractError;
     assert_eq!(validate_min_fee(-1), Err(FeeContractError::InvalidConfig));
-    assert_eq!(validate_min_fee(-1000), Err(FeeContractError::InvalidConfig));
-}
\ No newline at end of file
+    assert_eq!(
+        validate_min_fee(-1000),
+        Err(FeeContractError::InvalidConfig)
+    );
+}
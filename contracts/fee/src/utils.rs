@@ -1,6 +1,6 @@
+use crate::storage::MAX_FEE_BPS;
 use alloc::format;
 use soroban_sdk::{Env, String};
-use crate::storage::MAX_FEE_BPS;
 
 /// Round up division for i128 values.
 pub fn round_up_div(numerator: i128, denominator: i128) -> Option<i128> {
@@ -53,4 +53,4 @@ pub fn compute_fee(amount: i128, bps: u32) -> Option<i128> {
 pub fn format_amount(env: &Env, amount: i128) -> String {
     let formatted = format!("{amount}");
     String::from_str(env, formatted.as_str())
-}
\ No newline at end of file
+}
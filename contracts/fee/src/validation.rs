@@ -8,8 +8,8 @@ use soroban_sdk::panic_with_error;
 /// Panics with InvalidConfig on failure. Returns true on success to enable
 /// chaining in callers when desired.
 pub fn validate_fee_bps_or_panic(env: &Env, fee_bps: u32) -> bool {
-    if fee_bps > MAX_FEE_BPS {
-        panic_with_error!(env, FeeContractError::InvalidConfig);
+    if let Err(err) = validate_fee_bps(fee_bps) {
+        panic_with_error!(env, err);
     }
     true
 }
@@ -17,8 +17,8 @@ pub fn validate_fee_bps_or_panic(env: &Env, fee_bps: u32) -> bool {
 /// Validate minimum fee is non-negative.
 /// Panics with InvalidConfig on failure. Returns true on success.
 pub fn validate_min_fee_or_panic(env: &Env, min_fee: i128) -> bool {
-    if validate_non_negative_amount(min_fee).is_err() {
-        panic_with_error!(env, FeeContractError::InvalidConfig);
+    if let Err(err) = validate_min_fee(min_fee) {
+        panic_with_error!(env, err);
     }
     true
 }
@@ -26,6 +26,7 @@ pub fn validate_min_fee_or_panic(env: &Env, min_fee: i128) -> bool {
 /// Validate that a discount (in bps) is not greater than the base fee bps,
 /// and both are within allowed ranges. Not currently invoked by the contract,
 /// but provided for reuse by future config methods.
+#[allow(dead_code)]
 pub fn validate_discount_vs_base_or_panic(env: &Env, base_bps: u32, discount_bps: u32) -> bool {
     validate_fee_bps_or_panic(env, base_bps);
     validate_fee_bps_or_panic(env, discount_bps);
@@ -0,0 +1,118 @@
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::storage::{
+    read_asset_fee_bps, read_volume_buckets, read_volume_tiers, write_volume_buckets, VolumeBucket,
+    VolumeTier, MAX_FEE_BPS, SECONDS_PER_DAY, VOLUME_WINDOW_DAYS,
+};
+use crate::utils::calculate_fee_round_up;
+use crate::FeeContractError;
+
+/// Validate a volume tier schedule. An empty schedule disables tiering.
+/// Otherwise the first tier must start at zero volume and thresholds must be
+/// strictly ascending. Panics with InvalidConfig on failure.
+pub fn validate_volume_tiers_or_panic(env: &Env, tiers: &Vec<VolumeTier>) {
+    let mut previous: Option<i128> = None;
+    for tier in tiers.iter() {
+        if tier.fee_bps > MAX_FEE_BPS {
+            panic_with_error!(env, FeeContractError::InvalidConfig);
+        }
+        let valid = match previous {
+            None => tier.min_volume == 0,
+            Some(prev) => tier.min_volume > prev,
+        };
+        if !valid {
+            panic_with_error!(env, FeeContractError::InvalidConfig);
+        }
+        previous = Some(tier.min_volume);
+    }
+}
+
+fn current_day(env: &Env) -> u64 {
+    env.ledger().timestamp() / SECONDS_PER_DAY
+}
+
+fn in_window(bucket_day: u64, today: u64) -> bool {
+    bucket_day + VOLUME_WINDOW_DAYS > today
+}
+
+/// Sum of the payer's volume in `asset` over the trailing `VOLUME_WINDOW_DAYS` days.
+pub fn trailing_volume(env: &Env, payer: &Address, asset: &Address) -> i128 {
+    let today = current_day(env);
+    let mut total: i128 = 0;
+    for bucket in read_volume_buckets(env, payer, asset).iter() {
+        if in_window(bucket.day, today) {
+            total = total.saturating_add(bucket.volume);
+        }
+    }
+    total
+}
+
+/// Add `amount` to today's bucket and drop buckets that left the window.
+pub fn record_volume(env: &Env, payer: &Address, asset: &Address, amount: i128) {
+    let today = current_day(env);
+    let mut buckets = Vec::new(env);
+    let mut recorded = false;
+
+    for bucket in read_volume_buckets(env, payer, asset).iter() {
+        if !in_window(bucket.day, today) {
+            continue;
+        }
+        if bucket.day == today {
+            let volume = bucket
+                .volume
+                .checked_add(amount)
+                .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow));
+            buckets.push_back(VolumeBucket { day: today, volume });
+            recorded = true;
+        } else {
+            buckets.push_back(bucket);
+        }
+    }
+    if !recorded {
+        buckets.push_back(VolumeBucket {
+            day: today,
+            volume: amount,
+        });
+    }
+
+    write_volume_buckets(env, payer, asset, &buckets);
+}
+
+/// Returns the tier that applies to `volume` and the tier after it, if any.
+pub fn resolve_tier(
+    tiers: &Vec<VolumeTier>,
+    volume: i128,
+) -> (Option<VolumeTier>, Option<VolumeTier>) {
+    let mut current = None;
+    for tier in tiers.iter() {
+        if tier.min_volume > volume {
+            return (current, Some(tier));
+        }
+        current = Some(tier);
+    }
+    (current, None)
+}
+
+/// Returns the payer's current tier, or `None` when no schedule is configured.
+pub fn current_tier(env: &Env, payer: &Address, asset: &Address) -> Option<VolumeTier> {
+    let tiers = read_volume_tiers(env);
+    if tiers.is_empty() {
+        return None;
+    }
+    resolve_tier(&tiers, trailing_volume(env, payer, asset)).0
+}
+
+/// Rate charged on `asset` volume: the tier's rate when one applies,
+/// otherwise the asset's fee bps.
+pub fn fee_rate(env: &Env, asset: &Address, tier: Option<&VolumeTier>) -> u32 {
+    match tier {
+        Some(tier) => tier.fee_bps,
+        None => read_asset_fee_bps(env, asset),
+    }
+}
+
+/// Fee charged on `volume` at `fee_bps`, rounded up.
+pub fn volume_fee(env: &Env, volume: i128, fee_bps: u32) -> i128 {
+    calculate_fee_round_up(volume, fee_bps)
+        .unwrap_or_else(|| panic_with_error!(env, FeeContractError::Overflow))
+}
@@ -8,8 +8,9 @@ fn preview_sums_valid_batch_without_state_changes() {
     ctx.client.set_min_fee(&ctx.admin, &10i128);
 
     let batch = amounts(&ctx.env, &[10, 20, 30]);
-    let total = ctx.client.preview_batch_fee(&ctx.payer, &batch);
-    assert_eq!(total, 60);
+    let preview = ctx.client.preview_batch_fee(&ctx.payer, &batch);
+    assert_eq!(preview.total_fee, 60);
+    assert_eq!(preview.tier_bps, None);
 
     // Ensure no state mutated
     assert_eq!(ctx.client.get_escrow_balance(), 0);
@@ -35,12 +36,20 @@ fn preview_rejects_item_below_min_fee() {
     ctx.client.preview_batch_fee(&ctx.payer, &batch);
 }
 
+#[test]
+#[should_panic]
+fn preview_rejects_zero_amount() {
+    let ctx = setup();
+    let batch = amounts(&ctx.env, &[1_000, 0]);
+    ctx.client.preview_batch_fee(&ctx.payer, &batch);
+}
+
 #[test]
 fn preview_accepts_edge_at_min_and_large_values() {
     let ctx = setup();
     ctx.client.set_min_fee(&ctx.admin, &1i128);
     // Includes very large values within i128 range; also includes value exactly at min
     let batch = amounts(&ctx.env, &[1, 2, 1_000_000_000_000i128]);
-    let total = ctx.client.preview_batch_fee(&ctx.payer, &batch);
-    assert_eq!(total, 1 + 2 + 1_000_000_000_000i128);
+    let preview = ctx.client.preview_batch_fee(&ctx.payer, &batch);
+    assert_eq!(preview.total_fee, 1 + 2 + 1_000_000_000_000i128);
 }
@@ -0,0 +1,134 @@
+mod support;
+
+use soroban_sdk::testutils::Ledger;
+use support::setup;
+
+const DAY: u64 = 86_400;
+
+fn set_time(ctx: &support::TestContext, timestamp: u64) {
+    ctx.env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+#[test]
+fn current_cycle_follows_ledger_time() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+
+    assert_eq!(ctx.client.get_current_cycle(), 1);
+    set_time(&ctx, 1_000 + DAY - 1);
+    assert_eq!(ctx.client.get_current_cycle(), 1);
+    set_time(&ctx, 1_000 + DAY);
+    assert_eq!(ctx.client.get_current_cycle(), 2);
+    set_time(&ctx, 1_000 + 5 * DAY);
+    assert_eq!(ctx.client.get_current_cycle(), 6);
+}
+
+#[test]
+fn cycle_bounds_are_queryable() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+
+    let bounds = ctx.client.get_cycle_bounds(&3u64);
+    assert_eq!(bounds.start_time, 1_000 + 2 * DAY);
+    assert_eq!(bounds.end_time, 1_000 + 3 * DAY);
+    assert!(ctx.client.try_get_cycle_bounds(&0u64).is_err());
+}
+
+#[test]
+fn collections_land_in_the_time_derived_cycle() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    set_time(&ctx, 1_000 + DAY);
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 150);
+    assert!(ctx.client.get_pending_fees(&2u64) > 0);
+}
+
+#[test]
+fn settle_releases_closed_cycles_only() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    set_time(&ctx, 1_000 + DAY);
+    let open_pending = ctx.client.collect_fee(&ctx.payer, &150i128);
+
+    let settled = ctx.client.settle_due_cycles(&10u32);
+
+    assert_eq!(settled, 1);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 150);
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 0);
+    assert_eq!(ctx.client.get_pending_fees(&2u64), open_pending);
+    assert_eq!(ctx.client.get_next_settle_cycle(), 2);
+}
+
+#[test]
+fn settle_respects_limit_and_resumes() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    set_time(&ctx, 1_000 + 3 * DAY);
+
+    assert_eq!(ctx.client.settle_due_cycles(&2u32), 2);
+    assert_eq!(ctx.client.get_next_settle_cycle(), 3);
+    assert_eq!(ctx.client.settle_due_cycles(&2u32), 1);
+    assert_eq!(ctx.client.get_next_settle_cycle(), 4);
+    assert_eq!(ctx.client.settle_due_cycles(&2u32), 0);
+}
+
+#[test]
+fn release_history_is_recorded() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    set_time(&ctx, 1_000 + DAY + 10);
+    ctx.client.settle_due_cycles(&1u32);
+
+    let releases = ctx.client.get_cycle_releases(&1u64);
+    assert_eq!(releases.len(), 1);
+    let release = releases.get(0).unwrap();
+    assert_eq!(release.asset, ctx.token_id);
+    assert_eq!(release.amount, 150);
+    assert_eq!(release.released_at, 1_000 + DAY + 10);
+}
+
+#[test]
+#[should_panic]
+fn settle_requires_a_schedule() {
+    let ctx = setup();
+    ctx.client.settle_due_cycles(&1u32);
+}
+
+#[test]
+#[should_panic]
+fn manual_rollover_is_disabled_while_scheduled() {
+    let ctx = setup();
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+    ctx.client.rollover_fees(&ctx.admin, &2u64);
+}
+
+#[test]
+fn zero_duration_returns_to_manual_cycles() {
+    let ctx = setup();
+    set_time(&ctx, 1_000);
+    ctx.client.set_cycle_duration(&ctx.admin, &DAY);
+    set_time(&ctx, 1_000 + 2 * DAY);
+    ctx.client.set_cycle_duration(&ctx.admin, &0u64);
+
+    assert_eq!(ctx.client.get_cycle_schedule(), None);
+    set_time(&ctx, 1_000 + 9 * DAY);
+    assert_eq!(ctx.client.get_current_cycle(), 3);
+    ctx.client.rollover_fees(&ctx.admin, &4u64);
+    assert_eq!(ctx.client.get_current_cycle(), 4);
+}
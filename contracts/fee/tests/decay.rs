@@ -1,5 +1,6 @@
 mod support;
 
+use fee::{DecayConfig, DecayCurve};
 use soroban_sdk::testutils::Ledger;
 use support::setup;
 
@@ -13,7 +14,7 @@ fn test_update_activity_and_get_last_active() {
 
     // Update activity
     ctx.client.update_activity(&user);
-    
+
     // Ledger timestamp in setup is 0 by default usually, but let's check
     let current_time = ctx.env.ledger().timestamp();
     assert_eq!(ctx.client.get_last_active(&user), current_time);
@@ -23,7 +24,7 @@ fn test_update_activity_and_get_last_active() {
 fn test_fee_decay_logic() {
     let ctx = setup();
     let user = ctx.payer.clone();
-    
+
     // Set initial activity
     ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
     ctx.client.update_activity(&user);
@@ -31,12 +32,12 @@ fn test_fee_decay_logic() {
 
     // Move time forward by 10 seconds
     ctx.env.ledger().with_mut(|li| li.timestamp = 1010);
-    
+
     // base_fee = 100, elapsed = 10, decay_rate = 1 => decay = 10
     // expected decayed_fee = 100 - 10 = 90
     let pending = ctx.client.collect_fee(&user, &100i128);
     assert_eq!(pending, 90);
-    
+
     // Last active should be updated to 1010
     assert_eq!(ctx.client.get_last_active(&user), 1010);
 }
@@ -45,14 +46,14 @@ fn test_fee_decay_logic() {
 fn test_fee_decay_min_fee() {
     let ctx = setup();
     let user = ctx.payer.clone();
-    
+
     // Set initial activity
     ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
     ctx.client.update_activity(&user);
 
     // Move time forward by 1000 seconds
     ctx.env.ledger().with_mut(|li| li.timestamp = 2000);
-    
+
     // base_fee = 100, elapsed = 1000, decay = 1000
     // 100 - 1000 = -900, but min_fee is 10
     let pending = ctx.client.collect_fee(&user, &100i128);
@@ -63,21 +64,153 @@ fn test_fee_decay_min_fee() {
 fn test_fee_decay_batch() {
     let ctx = setup();
     let user = ctx.payer.clone();
-    
+
     // Set initial activity
     ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
     ctx.client.update_activity(&user);
 
     // Move time forward by 20 seconds
     ctx.env.ledger().with_mut(|li| li.timestamp = 1020);
-    
+
     // 100 -> 100 - 20 = 80
     // 50 -> 50 - 20 = 30
     // 25 -> 25 - 20 = 5 (wait, min_fee is 10, so 10)
     let amounts = support::amounts(&ctx.env, &[100, 50, 25]);
     let res = ctx.client.collect_fee_batch(&user, &amounts);
-    
+
     // 80 + 30 + 10 = 120
     assert_eq!(res.total_amount, 120);
     assert_eq!(res.batch_size, 3);
 }
+
+fn active_since(ctx: &support::TestContext, since: u64, now: u64) {
+    ctx.env.ledger().with_mut(|li| li.timestamp = since);
+    ctx.client.update_activity(&ctx.payer);
+    ctx.env.ledger().with_mut(|li| li.timestamp = now);
+}
+
+#[test]
+fn test_default_decay_config_matches_legacy_constants() {
+    let ctx = setup();
+    let config = ctx.client.get_decay_config();
+    assert_eq!(config.curve, DecayCurve::Linear(1));
+    assert_eq!(config.min_fee, 10);
+    assert_eq!(config.max_fee, i128::MAX);
+}
+
+#[test]
+fn test_default_decay_does_not_cap_large_fees() {
+    let ctx = setup();
+    active_since(&ctx, 1000, 1100);
+
+    // 1000 - 100 seconds of decay
+    let first = ctx.client.collect_fee(&ctx.payer, &1_000i128);
+    assert_eq!(first, 900);
+
+    // Collecting refreshed the activity, so only 50 seconds have elapsed
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1150);
+    let second = ctx.client.collect_fee(&ctx.payer, &1_000i128);
+    assert_eq!(second, 900 + 950);
+}
+
+#[test]
+fn test_user_without_activity_pays_base_fee() {
+    let ctx = setup();
+    ctx.env.ledger().with_mut(|li| li.timestamp = 5000);
+
+    let pending = ctx.client.collect_fee(&ctx.payer, &100i128);
+    assert_eq!(pending, 100);
+}
+
+#[test]
+fn test_step_decay_curve() {
+    let ctx = setup();
+    ctx.client.set_decay_config(
+        &ctx.admin,
+        &DecayConfig {
+            curve: DecayCurve::Step(60, 5),
+            min_fee: 0,
+            max_fee: 1_000,
+        },
+    );
+    active_since(&ctx, 1000, 1000 + 179);
+
+    // Two full 60s steps of 5
+    let pending = ctx.client.collect_fee(&ctx.payer, &100i128);
+    assert_eq!(pending, 90);
+}
+
+#[test]
+fn test_exponential_decay_halves_per_half_life() {
+    let ctx = setup();
+    ctx.client.set_decay_config(
+        &ctx.admin,
+        &DecayConfig {
+            curve: DecayCurve::Exponential(100),
+            min_fee: 0,
+            max_fee: 1_000_000,
+        },
+    );
+    active_since(&ctx, 1000, 1200);
+
+    // Two half-lives: 1000 -> 250
+    let pending = ctx.client.collect_fee(&ctx.payer, &1_000i128);
+    assert_eq!(pending, 250);
+}
+
+#[test]
+fn test_exponential_decay_between_half_lives() {
+    let ctx = setup();
+    ctx.client.set_decay_config(
+        &ctx.admin,
+        &DecayConfig {
+            curve: DecayCurve::Exponential(100),
+            min_fee: 0,
+            max_fee: 1_000_000,
+        },
+    );
+    active_since(&ctx, 1000, 1050);
+
+    // Half a half-life: 1_000_000 / sqrt(2) = 707_106.78
+    let pending = ctx.client.collect_fee(&ctx.payer, &1_000_000i128);
+    assert!((707_105..=707_107).contains(&pending));
+}
+
+#[test]
+fn test_disabled_decay_charges_base_fee() {
+    let ctx = setup();
+    ctx.client.set_decay_config(
+        &ctx.admin,
+        &DecayConfig {
+            curve: DecayCurve::Disabled,
+            min_fee: 0,
+            max_fee: 0,
+        },
+    );
+    active_since(&ctx, 1000, 5000);
+
+    let pending = ctx.client.collect_fee(&ctx.payer, &500i128);
+    assert_eq!(pending, 500);
+}
+
+#[test]
+#[should_panic]
+fn test_decay_config_rejects_zero_half_life() {
+    let ctx = setup();
+    ctx.client.set_decay_config(
+        &ctx.admin,
+        &DecayConfig {
+            curve: DecayCurve::Exponential(0),
+            min_fee: 0,
+            max_fee: 100,
+        },
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_decay_config_requires_admin() {
+    let ctx = setup();
+    ctx.client
+        .set_decay_config(&ctx.payer, &DecayConfig::default_config());
+}
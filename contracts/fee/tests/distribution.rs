@@ -0,0 +1,180 @@
+mod support;
+
+use fee::FeeRecipientShare;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _},
+    vec, Address, Env, IntoVal, Vec,
+};
+use support::setup;
+
+fn shares(env: &Env, items: &[(&Address, u32)]) -> Vec<FeeRecipientShare> {
+    let mut result = Vec::new(env);
+    for (recipient, share_bps) in items {
+        result.push_back(FeeRecipientShare {
+            recipient: (*recipient).clone(),
+            share_bps: *share_bps,
+        });
+    }
+    result
+}
+
+#[test]
+fn release_without_split_pays_treasury() {
+    let ctx = setup();
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    ctx.client.release_fees(&ctx.admin, &1u64);
+
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 150);
+    assert_eq!(ctx.client.get_recipient_total(&ctx.treasury), 150);
+}
+
+#[test]
+fn release_splits_between_recipients() {
+    let ctx = setup();
+    let ops = Address::generate(&ctx.env);
+    let rewards = Address::generate(&ctx.env);
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(
+            &ctx.env,
+            &[(&ctx.treasury, 6_000), (&ops, 3_000), (&rewards, 1_000)],
+        ),
+        &ctx.treasury,
+    );
+
+    ctx.client.collect_fee(&ctx.payer, &1_000i128);
+    let released = ctx.client.release_fees(&ctx.admin, &1u64);
+
+    assert_eq!(released, 1_000);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 600);
+    assert_eq!(ctx.token_client.balance(&ops), 300);
+    assert_eq!(ctx.token_client.balance(&rewards), 100);
+    assert_eq!(ctx.token_client.balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn release_event_lists_the_split_recipients() {
+    let ctx = setup();
+    let ops = Address::generate(&ctx.env);
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(&ctx.env, &[(&ctx.treasury, 5_000), (&ops, 5_000)]),
+        &ctx.treasury,
+    );
+
+    ctx.client.collect_fee(&ctx.payer, &200i128);
+    ctx.client.release_fees(&ctx.admin, &1u64);
+
+    let released = ctx
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            *topics
+                == vec![
+                    &ctx.env,
+                    symbol_short!("fee").into_val(&ctx.env),
+                    symbol_short!("released").into_val(&ctx.env),
+                ]
+        })
+        .unwrap();
+    let (_, _, _, recipients): (Address, u64, i128, Vec<Address>) = released.2.into_val(&ctx.env);
+    assert_eq!(recipients, vec![&ctx.env, ctx.treasury.clone(), ops]);
+}
+
+#[test]
+fn empty_split_clears_back_to_treasury() {
+    let ctx = setup();
+    let ops = Address::generate(&ctx.env);
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(&ctx.env, &[(&ctx.treasury, 5_000), (&ops, 5_000)]),
+        &ctx.treasury,
+    );
+    ctx.client
+        .set_fee_recipients(&ctx.admin, &Vec::new(&ctx.env), &ctx.treasury);
+    assert!(ctx.client.get_fee_recipients().is_empty());
+
+    ctx.client.collect_fee(&ctx.payer, &200i128);
+    ctx.client.release_fees(&ctx.admin, &1u64);
+
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 200);
+    assert_eq!(ctx.token_client.balance(&ops), 0);
+}
+
+#[test]
+fn rounding_dust_goes_to_dust_recipient() {
+    let ctx = setup();
+    let a = Address::generate(&ctx.env);
+    let b = Address::generate(&ctx.env);
+    let c = Address::generate(&ctx.env);
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(&ctx.env, &[(&a, 3_333), (&b, 3_333), (&c, 3_334)]),
+        &a,
+    );
+
+    ctx.client.collect_fee(&ctx.payer, &100i128);
+    ctx.client.release_fees(&ctx.admin, &1u64);
+
+    // 33 + 33 + 33 = 99, 1 unit of dust to `a`
+    assert_eq!(ctx.token_client.balance(&a), 34);
+    assert_eq!(ctx.token_client.balance(&b), 33);
+    assert_eq!(ctx.token_client.balance(&c), 33);
+    assert_eq!(ctx.client.get_escrow_balance(), 0);
+}
+
+#[test]
+fn recipient_totals_accumulate_across_releases() {
+    let ctx = setup();
+    let ops = Address::generate(&ctx.env);
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(&ctx.env, &[(&ctx.treasury, 5_000), (&ops, 5_000)]),
+        &ctx.treasury,
+    );
+
+    ctx.client.collect_fee(&ctx.payer, &200i128);
+    ctx.client.release_fees(&ctx.admin, &1u64);
+    ctx.client.collect_fee(&ctx.payer, &400i128);
+    ctx.client.release_fees(&ctx.admin, &1u64);
+
+    assert_eq!(ctx.client.get_recipient_total(&ops), 300);
+    assert_eq!(ctx.client.get_recipient_total(&ctx.treasury), 300);
+}
+
+#[test]
+#[should_panic]
+fn shares_must_total_ten_thousand_bps() {
+    let ctx = setup();
+    let ops = Address::generate(&ctx.env);
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(&ctx.env, &[(&ctx.treasury, 5_000), (&ops, 4_000)]),
+        &ctx.treasury,
+    );
+}
+
+#[test]
+#[should_panic]
+fn duplicate_recipients_are_rejected() {
+    let ctx = setup();
+    ctx.client.set_fee_recipients(
+        &ctx.admin,
+        &shares(&ctx.env, &[(&ctx.treasury, 5_000), (&ctx.treasury, 5_000)]),
+        &ctx.treasury,
+    );
+}
+
+#[test]
+#[should_panic]
+fn setting_recipients_requires_admin() {
+    let ctx = setup();
+    ctx.client.set_fee_recipients(
+        &ctx.payer,
+        &shares(&ctx.env, &[(&ctx.treasury, 10_000)]),
+        &ctx.treasury,
+    );
+}
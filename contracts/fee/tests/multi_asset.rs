@@ -0,0 +1,208 @@
+mod support;
+
+use soroban_sdk::{contracttype, testutils::Address as _, token, Address};
+use support::{amounts, setup, TestContext};
+
+/// Un-keyed ledger keys written before ledgers were keyed by asset.
+#[contracttype]
+enum LegacyDataKey {
+    EscrowBalance,
+    TotalCollected,
+    PendingFees(u64),
+}
+
+fn second_asset(ctx: &TestContext) -> (Address, token::Client<'static>) {
+    let issuer = Address::generate(&ctx.env);
+    let asset = ctx.env.register_stellar_asset_contract_v2(issuer).address();
+    token::StellarAssetClient::new(&ctx.env, &asset).mint(&ctx.payer, &1_000_000i128);
+    ctx.client.add_asset(&ctx.admin, &asset);
+    (asset.clone(), token::Client::new(&ctx.env, &asset))
+}
+
+#[test]
+fn initialize_registers_default_asset() {
+    let ctx = setup();
+    let assets = ctx.client.get_assets();
+
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets.get(0).unwrap(), ctx.token_id);
+}
+
+#[test]
+fn ledgers_are_kept_per_asset() {
+    let ctx = setup();
+    let (usdc, _) = second_asset(&ctx);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    ctx.client
+        .collect_asset_fee_batch(&ctx.payer, &usdc, &amounts(&ctx.env, &[200, 300]));
+
+    assert_eq!(ctx.client.get_escrow_balance(), 150);
+    assert_eq!(ctx.client.get_asset_escrow_balance(&usdc), 500);
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 150);
+    assert_eq!(ctx.client.get_asset_pending_fees(&usdc, &1u64), 500);
+    assert_eq!(ctx.client.get_asset_total_collected(&usdc), 500);
+    assert!(
+        ctx.client
+            .get_asset_reconciliation_status(&usdc)
+            .is_reconciled
+    );
+}
+
+#[test]
+fn release_asset_fees_only_moves_that_asset() {
+    let ctx = setup();
+    let (usdc, usdc_client) = second_asset(&ctx);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    ctx.client.collect_asset_fee(&ctx.payer, &usdc, &400i128);
+
+    let released = ctx.client.release_asset_fees(&ctx.admin, &usdc, &1u64);
+
+    assert_eq!(released, 400);
+    assert_eq!(usdc_client.balance(&ctx.treasury), 400);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 0);
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 150);
+    assert_eq!(ctx.client.get_asset_total_released(&usdc), 400);
+}
+
+#[test]
+fn release_all_fees_skips_empty_assets() {
+    let ctx = setup();
+    let (usdc, usdc_client) = second_asset(&ctx);
+    let (_eurc, _) = second_asset(&ctx);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    ctx.client.collect_asset_fee(&ctx.payer, &usdc, &400i128);
+
+    let released = ctx.client.release_all_fees(&ctx.admin, &1u64);
+
+    assert_eq!(released.len(), 2);
+    assert_eq!(released.get(ctx.token_id.clone()), Some(150));
+    assert_eq!(released.get(usdc.clone()), Some(400));
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 150);
+    assert_eq!(usdc_client.balance(&ctx.treasury), 400);
+}
+
+#[test]
+#[should_panic]
+fn release_all_fees_panics_when_nothing_pending() {
+    let ctx = setup();
+    second_asset(&ctx);
+
+    ctx.client.release_all_fees(&ctx.admin, &1u64);
+}
+
+#[test]
+fn rollover_carries_every_asset() {
+    let ctx = setup();
+    let (usdc, _) = second_asset(&ctx);
+
+    ctx.client.collect_fee(&ctx.payer, &150i128);
+    ctx.client.collect_asset_fee(&ctx.payer, &usdc, &400i128);
+
+    let rolled = ctx.client.rollover_fees(&ctx.admin, &2u64);
+
+    assert_eq!(rolled, 550);
+    assert_eq!(ctx.client.get_pending_fees(&2u64), 150);
+    assert_eq!(ctx.client.get_asset_pending_fees(&usdc, &2u64), 400);
+}
+
+#[test]
+#[should_panic]
+fn collect_rejects_unsupported_asset() {
+    let ctx = setup();
+    let issuer = Address::generate(&ctx.env);
+    let unknown = ctx.env.register_stellar_asset_contract_v2(issuer).address();
+
+    ctx.client.collect_asset_fee(&ctx.payer, &unknown, &150i128);
+}
+
+#[test]
+#[should_panic]
+fn add_asset_rejects_duplicates() {
+    let ctx = setup();
+    ctx.client.add_asset(&ctx.admin, &ctx.token_id);
+}
+
+#[test]
+fn asset_min_fee_overrides_global() {
+    let ctx = setup();
+    let (usdc, _) = second_asset(&ctx);
+
+    assert_eq!(
+        ctx.client.get_asset_min_fee(&usdc),
+        ctx.client.get_min_fee()
+    );
+
+    ctx.client.set_asset_min_fee(&ctx.admin, &usdc, &500i128);
+    assert_eq!(ctx.client.get_asset_min_fee(&usdc), 500);
+
+    // 400 is below the USDC minimum but fine for the default asset
+    let result = ctx
+        .client
+        .try_collect_asset_fee(&ctx.payer, &usdc, &400i128);
+    assert!(result.is_err());
+
+    ctx.client.collect_fee(&ctx.payer, &400i128);
+    assert_eq!(ctx.client.get_escrow_balance(), 400);
+}
+
+#[test]
+fn asset_fee_bps_falls_back_to_global() {
+    let ctx = setup();
+    let (usdc, _) = second_asset(&ctx);
+
+    assert_eq!(ctx.client.get_asset_fee_bps(&usdc), 250);
+    ctx.client.set_asset_fee_bps(&ctx.admin, &usdc, &100u32);
+    assert_eq!(ctx.client.get_asset_fee_bps(&usdc), 100);
+    assert_eq!(ctx.client.get_asset_fee_bps(&ctx.token_id), 250);
+}
+
+#[test]
+fn asset_fee_bps_prices_that_asset_only() {
+    let ctx = setup();
+    let (usdc, _) = second_asset(&ctx);
+    ctx.client.set_asset_fee_bps(&ctx.admin, &usdc, &100u32);
+
+    // 100 bps on 10_000 USDC, 250 bps on 10_000 of the default asset
+    assert_eq!(
+        ctx.client
+            .collect_asset_volume_fee(&ctx.payer, &usdc, &10_000i128),
+        100
+    );
+    assert_eq!(ctx.client.collect_volume_fee(&ctx.payer, &10_000i128), 250);
+}
+
+#[test]
+fn legacy_ledgers_are_read_for_the_default_asset() {
+    let ctx = setup();
+    let (usdc, _) = second_asset(&ctx);
+
+    ctx.env.as_contract(&ctx.contract_id, || {
+        let instance = ctx.env.storage().instance();
+        instance.set(&LegacyDataKey::EscrowBalance, &150i128);
+        instance.set(&LegacyDataKey::TotalCollected, &150i128);
+        ctx.env
+            .storage()
+            .persistent()
+            .set(&LegacyDataKey::PendingFees(1), &150i128);
+    });
+    ctx.asset_client.mint(&ctx.contract_id, &150i128);
+
+    assert_eq!(ctx.client.get_escrow_balance(), 150);
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 150);
+    assert_eq!(ctx.client.get_asset_escrow_balance(&usdc), 0);
+    assert_eq!(ctx.client.get_asset_pending_fees(&usdc, &1u64), 0);
+
+    ctx.client.collect_fee(&ctx.payer, &100i128);
+    assert_eq!(ctx.client.get_escrow_balance(), 250);
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 250);
+
+    let released = ctx.client.release_fees(&ctx.admin, &1u64);
+
+    assert_eq!(released, 250);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 250);
+    assert_eq!(ctx.client.get_escrow_balance(), 0);
+    assert_eq!(ctx.client.get_pending_fees(&1u64), 0);
+}
@@ -0,0 +1,156 @@
+mod support;
+
+use fee::{VolumeTier, QUOTE_TTL_LEDGERS};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger, token, Address, Env, Vec};
+use support::{amounts, setup};
+
+fn flat_rate(env: &Env, fee_bps: u32) -> Vec<VolumeTier> {
+    let mut tiers = Vec::new(env);
+    tiers.push_back(VolumeTier {
+        min_volume: 0,
+        fee_bps,
+    });
+    tiers
+}
+
+#[test]
+fn quote_records_fees_and_expiry() {
+    let ctx = setup();
+    ctx.client
+        .set_volume_tiers(&ctx.admin, &flat_rate(&ctx.env, 50));
+    let sequence = ctx.env.ledger().sequence();
+
+    let quote = ctx
+        .client
+        .quote_fee(&ctx.payer, &amounts(&ctx.env, &[10_000, 20_000]));
+
+    assert_eq!(quote.id, 1);
+    assert_eq!(quote.fees, amounts(&ctx.env, &[50, 100]));
+    assert_eq!(quote.total_fee, 150);
+    assert_eq!(quote.expires_at_ledger, sequence + QUOTE_TTL_LEDGERS);
+    assert!(!quote.used);
+    assert_eq!(ctx.client.get_quote(&1u64), Some(quote));
+}
+
+#[test]
+fn quoted_fees_survive_a_rate_change() {
+    let ctx = setup();
+    ctx.client
+        .set_volume_tiers(&ctx.admin, &flat_rate(&ctx.env, 50));
+    let batch = amounts(&ctx.env, &[10_000, 10_000]);
+    let quote = ctx.client.quote_fee(&ctx.payer, &batch);
+
+    ctx.client
+        .set_volume_tiers(&ctx.admin, &flat_rate(&ctx.env, 100));
+    let result = ctx
+        .client
+        .collect_quoted_fee_batch(&ctx.payer, &batch, &quote.id);
+
+    assert_eq!(result.total_amount, 100);
+    assert!(ctx.client.get_quote(&quote.id).unwrap().used);
+}
+
+#[test]
+fn quoted_fees_survive_a_fee_bps_change() {
+    let ctx = setup();
+    let batch = amounts(&ctx.env, &[10_000, 20_000]);
+    let quote = ctx.client.quote_fee(&ctx.payer, &batch);
+    assert_eq!(quote.total_fee, 750);
+
+    ctx.client.set_fee_bps(&ctx.admin, &500u32);
+    let result = ctx
+        .client
+        .collect_quoted_fee_batch(&ctx.payer, &batch, &quote.id);
+
+    assert_eq!(result.total_amount, 750);
+    assert_eq!(
+        ctx.client
+            .collect_volume_fee_batch(&ctx.payer, &batch)
+            .total_amount,
+        1_500
+    );
+}
+
+#[test]
+fn asset_quotes_lock_in_that_asset() {
+    let ctx = setup();
+    let issuer = Address::generate(&ctx.env);
+    let usdc = ctx.env.register_stellar_asset_contract_v2(issuer).address();
+    token::StellarAssetClient::new(&ctx.env, &usdc).mint(&ctx.payer, &1_000_000i128);
+    ctx.client.add_asset(&ctx.admin, &usdc);
+    ctx.client.set_asset_fee_bps(&ctx.admin, &usdc, &100u32);
+
+    let batch = amounts(&ctx.env, &[10_000]);
+    let quote = ctx.client.quote_asset_fee(&ctx.payer, &usdc, &batch);
+    assert_eq!(quote.asset, usdc);
+    assert_eq!(quote.total_fee, 100);
+
+    ctx.client.set_asset_fee_bps(&ctx.admin, &usdc, &300u32);
+    ctx.client
+        .collect_quoted_fee_batch(&ctx.payer, &batch, &quote.id);
+
+    assert_eq!(ctx.client.get_asset_escrow_balance(&usdc), 100);
+    assert_eq!(ctx.client.get_escrow_balance(), 0);
+}
+
+#[test]
+fn quotes_can_only_be_used_once() {
+    let ctx = setup();
+    let batch = amounts(&ctx.env, &[200, 300]);
+    let quote = ctx.client.quote_fee(&ctx.payer, &batch);
+
+    ctx.client
+        .collect_quoted_fee_batch(&ctx.payer, &batch, &quote.id);
+    let result = ctx
+        .client
+        .try_collect_quoted_fee_batch(&ctx.payer, &batch, &quote.id);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn expired_quote_is_rejected() {
+    let ctx = setup();
+    let batch = amounts(&ctx.env, &[200, 300]);
+    let quote = ctx.client.quote_fee(&ctx.payer, &batch);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.sequence_number = quote.expires_at_ledger + 1);
+    let result = ctx
+        .client
+        .try_collect_quoted_fee_batch(&ctx.payer, &batch, &quote.id);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn quote_must_match_payer_and_amounts() {
+    let ctx = setup();
+    let batch = amounts(&ctx.env, &[200, 300]);
+    let quote = ctx.client.quote_fee(&ctx.payer, &batch);
+
+    let other_batch = amounts(&ctx.env, &[200, 301]);
+    let result = ctx
+        .client
+        .try_collect_quoted_fee_batch(&ctx.payer, &other_batch, &quote.id);
+    assert!(result.is_err());
+
+    let stranger = Address::generate(&ctx.env);
+    let result = ctx
+        .client
+        .try_collect_quoted_fee_batch(&stranger, &batch, &quote.id);
+    assert!(result.is_err());
+}
+
+#[test]
+fn unknown_quote_is_rejected() {
+    let ctx = setup();
+    let batch = amounts(&ctx.env, &[200, 300]);
+
+    let result = ctx
+        .client
+        .try_collect_quoted_fee_batch(&ctx.payer, &batch, &42u64);
+
+    assert!(result.is_err());
+}
@@ -1,8 +1,7 @@
 mod support;
 
-use soroban_sdk::Address;
-use support::setup;
 use soroban_sdk::{testutils::Address as _, Address};
+use support::setup;
 
 #[test]
 fn reconciliation_balanced_after_init() {
@@ -19,7 +18,7 @@ fn reconciliation_balanced_after_init() {
 fn reconciliation_balanced_after_collect() {
     let ctx = setup();
 
-    ctx.client.collect_fee(&ctx.payer, &100);
+    ctx.client.collect_fee(&ctx.payer, &100i128);
 
     let result = ctx.client.reconcile_fees(&ctx.admin);
 
@@ -48,7 +47,7 @@ fn reconciliation_balanced_after_batch_collect() {
 fn reconciliation_balanced_after_release() {
     let ctx = setup();
 
-    ctx.client.collect_fee(&ctx.payer, &500);
+    ctx.client.collect_fee(&ctx.payer, &500i128);
     ctx.client.release_fees(&ctx.admin, &1);
 
     let result = ctx.client.reconcile_fees(&ctx.admin);
@@ -64,11 +63,11 @@ fn reconciliation_balanced_after_partial_release() {
     let ctx = setup();
 
     // Collect in cycle 1
-    ctx.client.collect_fee(&ctx.payer, &300);
+    ctx.client.collect_fee(&ctx.payer, &300i128);
     // Rollover to cycle 2 (moves pending from cycle 1 -> 2)
     ctx.client.rollover_fees(&ctx.admin, &2);
     // Collect more in cycle 2
-    ctx.client.collect_fee(&ctx.payer, &200);
+    ctx.client.collect_fee(&ctx.payer, &200i128);
     // Release only the original rollover amount by releasing cycle 2
     ctx.client.release_fees(&ctx.admin, &2);
 
@@ -85,7 +84,7 @@ fn reconciliation_balanced_after_partial_release() {
 fn reconciliation_balanced_after_rollover() {
     let ctx = setup();
 
-    ctx.client.collect_fee(&ctx.payer, &400);
+    ctx.client.collect_fee(&ctx.payer, &400i128);
     ctx.client.rollover_fees(&ctx.admin, &2);
 
     let result = ctx.client.get_reconciliation_status();
@@ -102,11 +101,11 @@ fn reconciliation_balanced_after_multiple_cycles() {
     let ctx = setup();
 
     // Cycle 1: collect 100
-    ctx.client.collect_fee(&ctx.payer, &100);
+    ctx.client.collect_fee(&ctx.payer, &100i128);
     ctx.client.rollover_fees(&ctx.admin, &2);
 
     // Cycle 2: collect 200
-    ctx.client.collect_fee(&ctx.payer, &200);
+    ctx.client.collect_fee(&ctx.payer, &200i128);
     ctx.client.release_fees(&ctx.admin, &2);
 
     let result = ctx.client.reconcile_fees(&ctx.admin);
@@ -127,26 +126,7 @@ fn reconcile_fees_requires_admin() {
     assert!(result.is_reconciled);
 
     // reconcile_fees requires admin - non-admin should fail
-    // Using mock_all_auths so auth passes, but require_admin check will fail
-    let env = soroban_sdk::Env::default();
-    env.mock_all_auths();
-
-    let issuer = Address::generate(&env);
-    let stellar_asset = env.register_stellar_asset_contract_v2(issuer);
-    let token_id = stellar_asset.address();
-
-    let contract_id = env.register(fee::FeeContract, ());
-    let client = fee::FeeContractClient::new(&env, &contract_id);
-    let admin = Address::generate(&env);
-    let treasury = Address::generate(&env);
-    let fake_admin = Address::generate(&env);
-
-    client.initialize(&admin, &token_id, &treasury, &250u32, &1u64);
-
-    // This should panic because fake_admin is not the admin
-    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        client.reconcile_fees(&fake_admin);
-    }));
+    let result = ctx.client.try_reconcile_fees(&non_admin);
     assert!(result.is_err());
 }
 
@@ -154,8 +134,8 @@ fn reconcile_fees_requires_admin() {
 fn reconciliation_result_fields_correct() {
     let ctx = setup();
 
-    ctx.client.collect_fee(&ctx.payer, &1000);
-    ctx.client.collect_fee(&ctx.payer, &500);
+    ctx.client.collect_fee(&ctx.payer, &1000i128);
+    ctx.client.collect_fee(&ctx.payer, &500i128);
     // Release cycle 1
     ctx.client.release_fees(&ctx.admin, &1);
 
@@ -167,7 +147,7 @@ fn reconciliation_result_fields_correct() {
     assert!(result.is_reconciled);
 
     // Collect more after release
-    ctx.client.collect_fee(&ctx.payer, &250);
+    ctx.client.collect_fee(&ctx.payer, &250i128);
     let result = ctx.client.get_reconciliation_status();
 
     assert_eq!(result.stored_balance, 250);
@@ -175,3 +155,104 @@ fn reconciliation_result_fields_correct() {
     assert_eq!(result.discrepancy, 0);
     assert!(result.is_reconciled);
 }
+
+#[test]
+fn reconciliation_reports_token_balance() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    let result = ctx.client.get_reconciliation_status();
+
+    assert_eq!(result.token_balance, 300);
+    assert_eq!(result.token_discrepancy, 0);
+    assert!(result.is_reconciled);
+}
+
+#[test]
+fn direct_transfer_does_not_block_release() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    ctx.token_client.transfer(&ctx.payer, &ctx.contract_id, &50);
+
+    let result = ctx.client.get_reconciliation_status();
+    assert_eq!(result.token_discrepancy, 50);
+    assert!(!result.is_reconciled);
+
+    assert_eq!(ctx.client.release_fees(&ctx.admin, &1), 300);
+    assert_eq!(ctx.token_client.balance(&ctx.contract_id), 50);
+}
+
+#[test]
+fn surplus_can_be_swept_into_escrow() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    ctx.token_client.transfer(&ctx.payer, &ctx.contract_id, &50);
+
+    let entry = ctx
+        .client
+        .post_adjustment(&ctx.admin, &ctx.token_id, &50, &1u32);
+    assert_eq!(entry.id, 1);
+    assert_eq!(entry.balance_before, 300);
+    assert_eq!(entry.balance_after, 350);
+
+    let result = ctx.client.get_reconciliation_status();
+    assert_eq!(result.stored_balance, 350);
+    assert_eq!(result.calculated_balance, 350);
+    assert!(result.is_reconciled);
+}
+
+#[test]
+fn token_shortfall_blocks_release() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    ctx.client
+        .post_adjustment(&ctx.admin, &ctx.token_id, &50, &1u32);
+
+    let result = ctx.client.get_reconciliation_status();
+    assert_eq!(result.token_discrepancy, -50);
+    assert!(ctx.client.try_release_fees(&ctx.admin, &1).is_err());
+}
+
+#[test]
+fn shortfall_within_tolerance_allows_release() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    ctx.client
+        .post_adjustment(&ctx.admin, &ctx.token_id, &5, &1u32);
+    ctx.client.set_reconciliation_tolerance(&ctx.admin, &10);
+
+    assert_eq!(ctx.client.release_fees(&ctx.admin, &1), 300);
+}
+
+#[test]
+fn adjustments_are_journaled_in_order() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    ctx.client
+        .post_adjustment(&ctx.admin, &ctx.token_id, &20, &1u32);
+    ctx.client
+        .post_adjustment(&ctx.admin, &ctx.token_id, &-20, &2u32);
+
+    assert_eq!(ctx.client.get_adjustment_count(), 2);
+    let second = ctx.client.get_adjustment(&2).unwrap();
+    assert_eq!(second.amount, -20);
+    assert_eq!(second.reason, 2);
+    assert_eq!(second.admin, ctx.admin);
+    assert_eq!(ctx.client.get_adjustment(&3), None);
+}
+
+#[test]
+fn adjustment_cannot_overdraw_escrow() {
+    let ctx = setup();
+
+    ctx.client.collect_fee(&ctx.payer, &300i128);
+    let result = ctx
+        .client
+        .try_post_adjustment(&ctx.admin, &ctx.token_id, &-301, &3u32);
+    assert!(result.is_err());
+}
@@ -0,0 +1,152 @@
+mod support;
+
+use fee::VolumeTier;
+use soroban_sdk::{testutils::Ledger, Env, Vec};
+use support::{amounts, setup};
+
+const DAY: u64 = 86_400;
+
+fn schedule(env: &Env) -> Vec<VolumeTier> {
+    let mut tiers = Vec::new(env);
+    tiers.push_back(VolumeTier {
+        min_volume: 0,
+        fee_bps: 50,
+    });
+    tiers.push_back(VolumeTier {
+        min_volume: 1_000,
+        fee_bps: 30,
+    });
+    tiers.push_back(VolumeTier {
+        min_volume: 10_000,
+        fee_bps: 10,
+    });
+    tiers
+}
+
+#[test]
+fn without_tiers_fee_bps_applies() {
+    let ctx = setup();
+
+    // 250 bps on 4_000
+    let pending = ctx.client.collect_volume_fee(&ctx.payer, &4_000i128);
+
+    assert_eq!(pending, 100);
+    assert_eq!(ctx.client.get_trailing_volume(&ctx.payer), 4_000);
+}
+
+#[test]
+fn collect_fee_charges_the_amount_and_records_no_volume() {
+    let ctx = setup();
+    ctx.client.set_volume_tiers(&ctx.admin, &schedule(&ctx.env));
+
+    assert_eq!(ctx.client.collect_fee(&ctx.payer, &40i128), 40);
+    assert_eq!(ctx.client.get_trailing_volume(&ctx.payer), 0);
+}
+
+#[test]
+fn fee_rate_drops_as_trailing_volume_grows() {
+    let ctx = setup();
+    ctx.client.set_volume_tiers(&ctx.admin, &schedule(&ctx.env));
+
+    // 50 bps on 1_000
+    assert_eq!(ctx.client.collect_volume_fee(&ctx.payer, &1_000i128), 5);
+    assert_eq!(ctx.client.get_trailing_volume(&ctx.payer), 1_000);
+
+    // 30 bps on 10_000
+    assert_eq!(ctx.client.collect_volume_fee(&ctx.payer, &10_000i128), 35);
+
+    // 10 bps on 10_000
+    assert_eq!(ctx.client.collect_volume_fee(&ctx.payer, &10_000i128), 45);
+    assert_eq!(ctx.client.get_trailing_volume(&ctx.payer), 21_000);
+}
+
+#[test]
+fn batch_collection_tracks_volume_and_applies_tier() {
+    let ctx = setup();
+    ctx.client.set_volume_tiers(&ctx.admin, &schedule(&ctx.env));
+
+    let batch = amounts(&ctx.env, &[400, 400, 400]);
+    let result = ctx.client.collect_volume_fee_batch(&ctx.payer, &batch);
+
+    // 50 bps on 400 = 2 per item
+    assert_eq!(result.total_amount, 6);
+    assert_eq!(ctx.client.get_trailing_volume(&ctx.payer), 1_200);
+}
+
+#[test]
+fn volume_older_than_window_is_dropped() {
+    let ctx = setup();
+    ctx.env.ledger().with_mut(|li| li.timestamp = DAY);
+    ctx.client.collect_volume_fee(&ctx.payer, &5_000i128);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp = DAY * (1 + fee::VOLUME_WINDOW_DAYS));
+    ctx.client.update_activity(&ctx.payer);
+
+    assert_eq!(ctx.client.get_trailing_volume(&ctx.payer), 0);
+}
+
+#[test]
+fn preview_reports_tier_and_distance_to_next() {
+    let ctx = setup();
+    ctx.client.set_volume_tiers(&ctx.admin, &schedule(&ctx.env));
+    ctx.client.collect_volume_fee(&ctx.payer, &600i128);
+
+    let batch = amounts(&ctx.env, &[200, 200]);
+    let preview = ctx.client.preview_volume_fee_batch(&ctx.payer, &batch);
+
+    assert_eq!(preview.total_fee, 2);
+    assert_eq!(preview.trailing_volume, 600);
+    assert_eq!(preview.tier_bps, Some(50));
+    assert_eq!(preview.next_tier_bps, Some(30));
+    assert_eq!(preview.volume_to_next_tier, 400);
+}
+
+#[test]
+fn preview_at_top_tier_has_no_next_tier() {
+    let ctx = setup();
+    ctx.client.set_volume_tiers(&ctx.admin, &schedule(&ctx.env));
+    ctx.client.collect_volume_fee(&ctx.payer, &20_000i128);
+
+    let preview = ctx
+        .client
+        .preview_volume_fee_batch(&ctx.payer, &amounts(&ctx.env, &[1_000]));
+
+    assert_eq!(preview.tier_bps, Some(10));
+    assert_eq!(preview.next_tier_bps, None);
+    assert_eq!(preview.volume_to_next_tier, 0);
+}
+
+#[test]
+fn tiered_fee_is_floored_at_min_fee() {
+    let ctx = setup();
+    ctx.client.set_volume_tiers(&ctx.admin, &schedule(&ctx.env));
+    ctx.client.set_min_fee(&ctx.admin, &3i128);
+
+    assert_eq!(ctx.client.collect_volume_fee(&ctx.payer, &100i128), 3);
+}
+
+#[test]
+#[should_panic]
+fn tiers_must_start_at_zero_volume() {
+    let ctx = setup();
+    let mut tiers = Vec::new(&ctx.env);
+    tiers.push_back(VolumeTier {
+        min_volume: 100,
+        fee_bps: 50,
+    });
+    ctx.client.set_volume_tiers(&ctx.admin, &tiers);
+}
+
+#[test]
+#[should_panic]
+fn tiers_must_be_ascending() {
+    let ctx = setup();
+    let mut tiers = schedule(&ctx.env);
+    tiers.push_back(VolumeTier {
+        min_volume: 5_000,
+        fee_bps: 5,
+    });
+    ctx.client.set_volume_tiers(&ctx.admin, &tiers);
+}
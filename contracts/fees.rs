@@ -21,6 +21,12 @@ pub enum RoundingMode {
     /// Round up to the nearest whole number (ceiling).
     /// Ensures minimum expected revenue, may overcharge users slightly.
     Ceiling = 2,
+    /// Round half to even (banker's rounding).
+    /// Exact halves go to the nearest even number, so ties do not bias totals upward.
+    Bankers = 3,
+    /// Floor each fee and carry the fractional part per payer.
+    /// The carried remainder is charged once it adds up to a whole unit.
+    Accumulate = 4,
 }
 
 impl Default for RoundingMode {
@@ -36,6 +42,8 @@ impl RoundingMode {
             0 => Some(RoundingMode::Floor),
             1 => Some(RoundingMode::Round),
             2 => Some(RoundingMode::Ceiling),
+            3 => Some(RoundingMode::Bankers),
+            4 => Some(RoundingMode::Accumulate),
             _ => None,
         }
     }
@@ -129,6 +137,33 @@ pub struct SnapshotMetadata {
     pub latest_timestamp: u64,
 }
 
+/// Fee configuration in effect from `effective_from` until the next snapshot.
+/// Written automatically whenever any fee-affecting setting changes.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct FeeConfigSnapshot {
+    /// Sequential snapshot index, starting at 0
+    pub index: u64,
+    /// Ledger timestamp from which this configuration applies
+    pub effective_from: u64,
+    /// Fee percentage in basis points
+    pub percentage_bps: u32,
+    /// Minimum fee threshold
+    pub min_fee: i128,
+    /// Maximum fee threshold
+    pub max_fee: i128,
+    /// Rounding mode for fee calculations
+    pub rounding_mode: RoundingMode,
+    /// Priority multipliers
+    pub priority_config: PriorityFeeConfig,
+    /// Fee distribution recipients
+    pub recipients: Vec<FeeRecipient>,
+    /// Treasury address, if configured
+    pub treasury: Option<Address>,
+    /// Portion of fees routed to the treasury, in basis points
+    pub treasury_percentage_bps: u32,
+}
+
 // =============================================================================
 // Priority Levels for Fee Calculation
 // =============================================================================
@@ -274,6 +309,14 @@ pub enum DataKey {
     SnapshotMetadata,
     /// Fee snapshot at a specific period
     FeeSnapshot(u64),
+    /// Fractional fee carried for a payer under `RoundingMode::Accumulate`,
+    /// stored in 1/10_000 units of the fee token. Kept in persistent storage so
+    /// the per-payer entries do not grow the instance footprint.
+    RoundingRemainder(Address),
+    /// Number of fee configuration snapshots written.
+    ConfigSnapshotCount,
+    /// Fee configuration snapshot by sequential index.
+    ConfigSnapshot(u64),
 }
 
 #[contracterror]
@@ -316,6 +359,8 @@ pub enum FeeError {
     InvalidFeeCategory = 20,
     /// Snapshot period must be greater than 0.
     InvalidSnapshotPeriod = 21,
+    /// No fee configuration snapshot exists at or before the requested timestamp.
+    NoConfigAtTimestamp = 22,
 }
 
 /// Events emitted by the fees contract.
@@ -479,6 +524,11 @@ impl FeeEvents {
         );
     }
 
+    pub fn config_snapshot_recorded(env: &Env, index: u64, effective_from: u64) {
+        let topics = (symbol_short!("fee"), symbol_short!("cfg_snap"));
+        env.events().publish(topics, (index, effective_from));
+    }
+
     pub fn snapshot_created(
         env: &Env,
         period_start: u64,
@@ -514,21 +564,194 @@ impl FeesContract {
         }
     }
 
-    /// Apply rounding mode to a fee calculation
-    fn apply_rounding(fee: i128, mode: RoundingMode) -> i128 {
+    /// Apply rounding mode to a raw fee expressed in 1/10_000 units.
+    ///
+    /// `carried` is the payer's accumulated remainder and is only used by
+    /// `RoundingMode::Accumulate`. Returns `(fee, remainder_to_carry)`.
+    fn apply_rounding(raw_fee: i128, carried: i128, mode: RoundingMode) -> (i128, i128) {
+        let quotient = raw_fee / 10_000;
+        let remainder = raw_fee % 10_000;
         match mode {
-            RoundingMode::Floor => fee,
-            RoundingMode::Round => {
-                // For positive values, standard rounding: add 0.5 and floor
-                // Since we work with integers, we check if there's a remainder >= 5000
-                // (which represents 0.5 in basis points context)
-                fee
-            }
+            RoundingMode::Floor => (quotient, 0),
+            // Add 5000 (0.5 in basis points) before dividing for round half up
+            RoundingMode::Round => ((raw_fee + 5_000) / 10_000, 0),
             RoundingMode::Ceiling => {
                 // If there's any remainder, round up
-                fee
+                if remainder == 0 {
+                    (quotient, 0)
+                } else {
+                    (quotient + 1, 0)
+                }
+            }
+            RoundingMode::Bankers => {
+                // Exact halves round to the even neighbour
+                if remainder > 5_000 || (remainder == 5_000 && quotient % 2 != 0) {
+                    (quotient + 1, 0)
+                } else {
+                    (quotient, 0)
+                }
+            }
+            RoundingMode::Accumulate => {
+                let total = remainder + carried;
+                (quotient + total / 10_000, total % 10_000)
+            }
+        }
+    }
+
+    /// Clamp a rounded fee to the configured min/max bounds.
+    fn apply_fee_bounds(env: &Env, fee: i128) -> i128 {
+        // [SEC-FEES-18] Apply min/max fee bounds.
+        let min_fee = Self::get_min_fee(env.clone());
+        let max_fee = Self::get_max_fee(env.clone());
+        Self::clamp_fee(fee, min_fee, max_fee)
+    }
+
+    fn clamp_fee(fee: i128, min_fee: i128, max_fee: i128) -> i128 {
+        if fee < min_fee {
+            return min_fee;
+        }
+        if fee > max_fee {
+            return max_fee;
+        }
+
+        fee
+    }
+
+    /// Record the current fee configuration as a new snapshot.
+    ///
+    /// Snapshots are appended in ledger-time order, so their `effective_from`
+    /// timestamps are non-decreasing and can be binary searched.
+    fn record_config_snapshot(env: &Env) {
+        let index: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConfigSnapshotCount)
+            .unwrap_or(0);
+        let effective_from = env.ledger().timestamp();
+
+        let snapshot = FeeConfigSnapshot {
+            index,
+            effective_from,
+            percentage_bps: Self::get_percentage(env.clone()),
+            min_fee: Self::get_min_fee(env.clone()),
+            max_fee: Self::get_max_fee(env.clone()),
+            rounding_mode: Self::load_rounding_mode(env),
+            priority_config: Self::get_priority_config(env.clone()),
+            recipients: Self::get_distribution(env.clone()),
+            treasury: Self::get_treasury(env.clone()),
+            treasury_percentage_bps: Self::get_treasury_percentage(env.clone()),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConfigSnapshot(index), &snapshot);
+        env.storage()
+            .instance()
+            .set(&DataKey::ConfigSnapshotCount, &(index + 1));
+        FeeEvents::config_snapshot_recorded(env, index, effective_from);
+    }
+
+    fn load_config_snapshot(env: &Env, index: u64) -> FeeConfigSnapshot {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConfigSnapshot(index))
+            .unwrap_or_else(|| panic_with_error!(env, FeeError::NoConfigAtTimestamp))
+    }
+
+    /// Unrounded fee for `amount` at the base percentage, in 1/10_000 units.
+    fn raw_fee(env: &Env, amount: i128) -> i128 {
+        // [SEC-FEES-04] Reject non-positive amounts.
+        if amount <= 0 {
+            panic_with_error!(env, FeeError::InvalidAmount);
+        }
+        let pct: u32 = Self::get_percentage(env.clone());
+
+        // [SEC-FEES-05] Checked arithmetic throughout.
+        amount
+            .checked_mul(pct as i128)
+            .unwrap_or_else(|| panic_with_error!(env, FeeError::Overflow))
+    }
+
+    /// Unrounded fee for `amount` at the priority-adjusted percentage, in 1/10_000 units.
+    fn raw_fee_with_priority(env: &Env, amount: i128, priority: PriorityLevel) -> i128 {
+        // [SEC-FEES-04] Reject non-positive amounts.
+        if amount <= 0 {
+            panic_with_error!(env, FeeError::InvalidAmount);
+        }
+
+        let base_pct: u32 = Self::get_percentage(env.clone());
+        let priority_config: PriorityFeeConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::PriorityFeeConfig)
+            .unwrap_or_else(PriorityFeeConfig::default);
+
+        let adjusted_pct = Self::priority_adjusted_bps(base_pct, &priority_config, priority);
+
+        // [SEC-FEES-05] Checked arithmetic throughout.
+        amount
+            .checked_mul(adjusted_pct as i128)
+            .unwrap_or_else(|| panic_with_error!(env, FeeError::Overflow))
+    }
+
+    /// Effective fee rate for a priority level: `base_pct * multiplier / 10000`.
+    fn priority_adjusted_bps(
+        base_pct: u32,
+        priority_config: &PriorityFeeConfig,
+        priority: PriorityLevel,
+    ) -> u32 {
+        let multiplier_bps = priority_config.get_multiplier_bps(priority);
+        (base_pct as u64 * multiplier_bps as u64 / 10_000) as u32
+    }
+
+    /// Fee for `amount` under the snapshot in force at `timestamp`, with
+    /// the snapshot's multiplier for `priority` when one is given.
+    fn recalculate_at(
+        env: &Env,
+        amount: i128,
+        priority: Option<PriorityLevel>,
+        timestamp: u64,
+    ) -> i128 {
+        if amount <= 0 {
+            panic_with_error!(env, FeeError::InvalidAmount);
+        }
+        let config = Self::get_fee_config_at(env.clone(), timestamp)
+            .unwrap_or_else(|| panic_with_error!(env, FeeError::NoConfigAtTimestamp));
+
+        let pct = match priority {
+            Some(priority) => {
+                Self::priority_adjusted_bps(config.percentage_bps, &config.priority_config, priority)
             }
+            None => config.percentage_bps,
+        };
+        let raw_fee = amount
+            .checked_mul(pct as i128)
+            .unwrap_or_else(|| panic_with_error!(env, FeeError::Overflow));
+        let (fee, _) = Self::apply_rounding(raw_fee, 0, config.rounding_mode);
+        Self::clamp_fee(fee, config.min_fee, config.max_fee)
+    }
+
+    /// Round a raw fee for a charge against `payer`.
+    ///
+    /// Under `RoundingMode::Accumulate` the payer's carried remainder is
+    /// consumed and the new remainder persisted; other modes leave it untouched.
+    fn charge_raw_fee(env: &Env, payer: &Address, raw_fee: i128) -> i128 {
+        let mode = Self::load_rounding_mode(env);
+        if mode != RoundingMode::Accumulate {
+            let (fee, _) = Self::apply_rounding(raw_fee, 0, mode);
+            return Self::apply_fee_bounds(env, fee);
+        }
+
+        let key = DataKey::RoundingRemainder(payer.clone());
+        let carried: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let (fee, remainder) = Self::apply_rounding(raw_fee, carried, mode);
+        if remainder == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &remainder);
         }
+
+        Self::apply_fee_bounds(env, fee)
     }
 
     /// Get the current rounding mode (defaults to Round)
@@ -624,6 +847,7 @@ impl FeesContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalFeesCollected, &0i128);
+        Self::record_config_snapshot(&env);
     }
 
     /// Updates the fee percentage. Only the current admin may call.
@@ -644,6 +868,7 @@ impl FeesContract {
         env.storage()
             .instance()
             .set(&DataKey::FeePercentage, &percentage_bps);
+        Self::record_config_snapshot(&env);
         FeeEvents::config_updated(&env, &caller, percentage_bps);
     }
 
@@ -663,7 +888,9 @@ impl FeesContract {
     /// - At most max_fee (if configured)
     /// - Otherwise, fee_percentage * amount / 10000
     ///
-    /// Uses the configured rounding mode for fee calculations.
+    /// Uses the configured rounding mode for fee calculations. This is a
+    /// read-only quote, so `Accumulate` mode returns the floored fee without
+    /// the payer's carried remainder.
     ///
     /// # Security
     /// - [SEC-FEES-04] Rejects non-positive amounts to prevent zero-fee bypass.
@@ -672,53 +899,9 @@ impl FeesContract {
     /// - [SEC-FEES-18] Min/max fee bounds are applied to prevent unbounded fees
     ///   and ensure fees stay within configured ranges.
     pub fn calculate_fee(env: Env, amount: i128) -> i128 {
-        // [SEC-FEES-04] Reject non-positive amounts.
-        if amount <= 0 {
-            panic_with_error!(&env, FeeError::InvalidAmount);
-        }
-        let pct: u32 = Self::get_percentage(env.clone());
-
-        // Get rounding mode
-        let rounding_mode = Self::load_rounding_mode(&env);
-
-        // [SEC-FEES-05] Checked arithmetic throughout.
-        let raw_fee = amount
-            .checked_mul(pct as i128)
-            .unwrap_or_else(|| panic_with_error!(&env, FeeError::Overflow));
-
-        // Apply rounding mode
-        let fee = match rounding_mode {
-            RoundingMode::Floor => raw_fee / 10_000,
-            RoundingMode::Round => {
-                // Add 5000 (0.5 in basis points) before dividing for round half up
-                (raw_fee + 5_000) / 10_000
-            }
-            RoundingMode::Ceiling => {
-                // If there's any remainder, round up
-                if raw_fee % 10_000 == 0 {
-                    raw_fee / 10_000
-                } else {
-                    raw_fee / 10_000 + 1
-                }
-            }
-        };
-
-        // [SEC-FEES-18] Apply min/max fee bounds.
-        let min_fee: i128 = env.storage().instance().get(&DataKey::MinFee).unwrap_or(0);
-        let max_fee: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxFee)
-            .unwrap_or(i128::MAX);
-
-        if fee < min_fee {
-            return min_fee;
-        }
-        if fee > max_fee {
-            return max_fee;
-        }
-
-        fee
+        let raw_fee = Self::raw_fee(&env, amount);
+        let (fee, _) = Self::apply_rounding(raw_fee, 0, Self::load_rounding_mode(&env));
+        Self::apply_fee_bounds(&env, fee)
     }
 
     /// Calculates the fee for `amount` using the current percentage and priority level.
@@ -736,66 +919,9 @@ impl FeesContract {
     /// - [SEC-FEES-21] Priority configuration must be valid (ascending multipliers).
     /// - [SEC-FEES-22] All arithmetic uses `checked_*` to prevent overflow.
     pub fn calculate_fee_with_priority(env: Env, amount: i128, priority: PriorityLevel) -> i128 {
-        // [SEC-FEES-04] Reject non-positive amounts.
-        if amount <= 0 {
-            panic_with_error!(&env, FeeError::InvalidAmount);
-        }
-
-        let base_pct: u32 = Self::get_percentage(env.clone());
-        let priority_config: PriorityFeeConfig = env
-            .storage()
-            .instance()
-            .get(&DataKey::PriorityFeeConfig)
-            .unwrap_or_else(PriorityFeeConfig::default);
-
-        // Get the multiplier for the priority level
-        let multiplier_bps = priority_config.get_multiplier_bps(priority);
-
-        // Calculate adjusted fee rate: base_pct * multiplier / 10000
-        // This gives us the effective fee rate for the priority level
-        let adjusted_pct = (base_pct as u64 * multiplier_bps as u64 / 10_000) as u32;
-
-        // Get rounding mode
-        let rounding_mode = Self::load_rounding_mode(&env);
-
-        // [SEC-FEES-05] Checked arithmetic throughout.
-        let raw_fee = amount
-            .checked_mul(adjusted_pct as i128)
-            .unwrap_or_else(|| panic_with_error!(&env, FeeError::Overflow));
-
-        // Apply rounding mode
-        let fee = match rounding_mode {
-            RoundingMode::Floor => raw_fee / 10_000,
-            RoundingMode::Round => {
-                // Add 5000 (0.5 in basis points) before dividing for round half up
-                (raw_fee + 5_000) / 10_000
-            }
-            RoundingMode::Ceiling => {
-                // If there's any remainder, round up
-                if raw_fee % 10_000 == 0 {
-                    raw_fee / 10_000
-                } else {
-                    raw_fee / 10_000 + 1
-                }
-            }
-        };
-
-        // [SEC-FEES-18] Apply min/max fee bounds.
-        let min_fee: i128 = env.storage().instance().get(&DataKey::MinFee).unwrap_or(0);
-        let max_fee: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxFee)
-            .unwrap_or(i128::MAX);
-
-        if fee < min_fee {
-            return min_fee;
-        }
-        if fee > max_fee {
-            return max_fee;
-        }
-
-        fee
+        let raw_fee = Self::raw_fee_with_priority(&env, amount, priority);
+        let (fee, _) = Self::apply_rounding(raw_fee, 0, Self::load_rounding_mode(&env));
+        Self::apply_fee_bounds(&env, fee)
     }
 
     /// Deducts the configured fee from `amount` with a specified priority level.
@@ -808,7 +934,7 @@ impl FeesContract {
     ///   mutations can occur without authorization.
     /// - [SEC-FEES-07] `TotalFeesCollected` accumulation uses `checked_add` so
     ///   a saturated counter triggers `Overflow` rather than wrapping silently.
-    /// - Requires the contract to be initialized; `calculate_fee` propagates
+    /// - Requires the contract to be initialized; fee calculation propagates
     ///   `NotInitialized` via `get_percentage` if called before `initialize`.
     /// - [SEC-FEES-08] Per-user fee tracking is updated with `checked_add` to
     ///   prevent overflow on per-user accumulation.
@@ -819,7 +945,7 @@ impl FeesContract {
         // Ensure contract is initialized before proceeding.
         Self::require_initialized(&env);
 
-        let fee = Self::calculate_fee(env.clone(), amount);
+        let fee = Self::charge_raw_fee(&env, &payer, Self::raw_fee(&env, amount));
 
         // [SEC-FEES-07] Checked subtraction for net amount.
         let net = amount
@@ -932,7 +1058,8 @@ impl FeesContract {
         // Ensure contract is initialized before proceeding.
         Self::require_initialized(&env);
 
-        let fee = Self::calculate_fee_with_priority(env.clone(), amount, priority);
+        let raw_fee = Self::raw_fee_with_priority(&env, amount, priority);
+        let fee = Self::charge_raw_fee(&env, &payer, raw_fee);
 
         // [SEC-FEES-07] Checked subtraction for net amount.
         let net = amount
@@ -1006,7 +1133,7 @@ impl FeesContract {
         payer.require_auth();
         Self::require_initialized(&env);
 
-        let fee = Self::calculate_fee(env.clone(), amount);
+        let fee = Self::charge_raw_fee(&env, &payer, Self::raw_fee(&env, amount));
         let net = amount.checked_sub(fee).expect("Overflow");
 
         let mut escrowed: i128 = env
@@ -1095,6 +1222,7 @@ impl FeesContract {
         env.storage()
             .instance()
             .set(&DataKey::TreasuryFeePercentage, &treasury_percentage_bps);
+        Self::record_config_snapshot(&env);
         FeeEvents::treasury_configured(&env, &caller, &treasury, treasury_percentage_bps);
     }
 
@@ -1125,11 +1253,12 @@ impl FeesContract {
     ///
     /// # Arguments
     /// * `caller` - The admin address
-    /// * `mode` - The rounding mode (Floor, Round, or Ceiling)
+    /// * `mode` - The rounding mode (Floor, Round, Ceiling, Bankers, or Accumulate)
     pub fn set_rounding_mode(env: Env, caller: Address, mode: RoundingMode) {
         caller.require_auth();
         Self::require_admin(&env, &caller);
         env.storage().instance().set(&DataKey::RoundingMode, &mode);
+        Self::record_config_snapshot(&env);
         FeeEvents::rounding_mode_updated(&env, &caller, mode);
     }
 
@@ -1138,6 +1267,15 @@ impl FeesContract {
         Self::load_rounding_mode(&env)
     }
 
+    /// Get the fractional fee carried for `user` under `RoundingMode::Accumulate`,
+    /// in 1/10_000 units of the fee token.
+    pub fn get_rounding_remainder(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoundingRemainder(user))
+            .unwrap_or(0)
+    }
+
     /// Set fee category for a user for tracking purposes.
     ///
     /// Only callable by the admin.
@@ -1233,6 +1371,68 @@ impl FeesContract {
             })
     }
 
+    /// Returns the number of fee configuration snapshots recorded.
+    pub fn get_config_snapshot_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConfigSnapshotCount)
+            .unwrap_or(0)
+    }
+
+    /// Returns the fee configuration that applied at `timestamp`.
+    ///
+    /// Binary searches the configuration snapshots for the latest one with
+    /// `effective_from <= timestamp`. When several changes share a timestamp,
+    /// the last one wins. Returns `None` for times before initialization.
+    pub fn get_fee_config_at(env: Env, timestamp: u64) -> Option<FeeConfigSnapshot> {
+        let count = Self::get_config_snapshot_count(env.clone());
+
+        // Find the first snapshot that became effective after `timestamp`.
+        let mut low: u64 = 0;
+        let mut high: u64 = count;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::load_config_snapshot(&env, mid).effective_from <= timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        if low == 0 {
+            return None;
+        }
+        Some(Self::load_config_snapshot(&env, low - 1))
+    }
+
+    /// Recalculates the `deduct_fee` charge for `amount` using the
+    /// configuration that applied at `timestamp`, so historical charges can
+    /// be checked. `Accumulate` rounding is evaluated without any carried
+    /// remainder, as for `calculate_fee`.
+    ///
+    /// # Security
+    /// - [SEC-FEES-04] Rejects non-positive amounts.
+    /// - [SEC-FEES-05] Checked arithmetic traps overflow.
+    pub fn recalculate_fee_at(env: Env, amount: i128, timestamp: u64) -> i128 {
+        Self::recalculate_at(&env, amount, None, timestamp)
+    }
+
+    /// Recalculates the `deduct_fee_with_priority` charge for `amount` at
+    /// `priority` using the configuration, including the priority
+    /// multipliers, that applied at `timestamp`.
+    ///
+    /// # Security
+    /// - [SEC-FEES-04] Rejects non-positive amounts.
+    /// - [SEC-FEES-05] Checked arithmetic traps overflow.
+    pub fn recalculate_priority_fee_at(
+        env: Env,
+        amount: i128,
+        priority: PriorityLevel,
+        timestamp: u64,
+    ) -> i128 {
+        Self::recalculate_at(&env, amount, Some(priority), timestamp)
+    }
+
     /// Returns cumulative fees collected since deployment.
     pub fn get_total_collected(env: Env) -> i128 {
         env.storage()
@@ -1388,6 +1588,7 @@ impl FeesContract {
         env.storage()
             .instance()
             .set(&DataKey::FeeDistribution, &recipients);
+        Self::record_config_snapshot(&env);
         FeeEvents::distribution_configured(&env, &caller, recipients.len() as u32);
     }
 
@@ -1535,6 +1736,7 @@ impl FeesContract {
 
         env.storage().instance().set(&DataKey::MinFee, &min_fee);
         env.storage().instance().set(&DataKey::MaxFee, &max_fee);
+        Self::record_config_snapshot(&env);
         FeeEvents::fee_bounds_configured(&env, &caller, min_fee, max_fee);
     }
 
@@ -1604,6 +1806,7 @@ impl FeesContract {
         env.storage()
             .instance()
             .set(&DataKey::PriorityFeeConfig, &config);
+        Self::record_config_snapshot(&env);
 
         FeeEvents::priority_config_updated(
             &env,
@@ -0,0 +1,305 @@
+//! # Liquidity Pool Contract
+//!
+//! A constant-product (`x * y = k`) pool for a single token pair.
+//!
+//! ## Features
+//! - Add and remove liquidity against LP shares
+//! - Swaps in either direction with a fee retained for LPs
+//! - `get_amount_out` quotes so callers can enforce slippage limits
+//!
+//! Reserves are tracked in storage rather than read from token balances, so
+//! tokens sent to the pool outside `add_liquidity`/`swap` do not move prices.
+
+#![no_std]
+
+mod types;
+
+use soroban_sdk::{contract, contracterror, contractimpl, panic_with_error, token, Address, Env};
+
+pub use crate::types::{DataKey, PoolEvents, PoolInfo, MAX_FEE_BPS};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PoolError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    /// Pool tokens must differ.
+    InvalidPair = 3,
+    /// Fee exceeds `MAX_FEE_BPS`.
+    InvalidFee = 4,
+    InvalidAmount = 5,
+    /// Token is not one of the pool's pair.
+    UnsupportedToken = 6,
+    /// Pool has no liquidity to swap against.
+    InsufficientLiquidity = 7,
+    /// Provider holds fewer shares than requested.
+    InsufficientShares = 8,
+    /// Output or minted shares are below the caller's minimum.
+    SlippageExceeded = 9,
+    Overflow = 10,
+}
+
+#[contract]
+pub struct LiquidityPoolContract;
+
+#[contractimpl]
+impl LiquidityPoolContract {
+    pub fn initialize(env: Env, admin: Address, token_a: Address, token_b: Address, fee_bps: u32) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, PoolError::AlreadyInitialized);
+        }
+        if token_a == token_b {
+            panic_with_error!(&env, PoolError::InvalidPair);
+        }
+        if fee_bps > MAX_FEE_BPS {
+            panic_with_error!(&env, PoolError::InvalidFee);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::TokenA, &token_a);
+        env.storage().instance().set(&DataKey::TokenB, &token_b);
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        env.storage().instance().set(&DataKey::ReserveA, &0i128);
+        env.storage().instance().set(&DataKey::ReserveB, &0i128);
+        env.storage().instance().set(&DataKey::TotalShares, &0i128);
+    }
+
+    /// Deposits up to `desired_a`/`desired_b` at the current pool ratio and
+    /// mints LP shares. The first deposit sets the ratio.
+    ///
+    /// Returns `(amount_a, amount_b, shares)` actually deposited and minted.
+    pub fn add_liquidity(
+        env: Env,
+        provider: Address,
+        desired_a: i128,
+        desired_b: i128,
+        min_shares: i128,
+    ) -> (i128, i128, i128) {
+        provider.require_auth();
+        if desired_a <= 0 || desired_b <= 0 {
+            panic_with_error!(&env, PoolError::InvalidAmount);
+        }
+
+        let info = Self::get_info(env.clone());
+        let (amount_a, amount_b, shares) = if info.total_shares == 0 {
+            let product = Self::mul(&env, desired_a, desired_b);
+            (desired_a, desired_b, Self::sqrt(product))
+        } else {
+            // Use as much of the desired amounts as the current ratio allows.
+            let optimal_b = Self::mul(&env, desired_a, info.reserve_b) / info.reserve_a;
+            let (amount_a, amount_b) = if optimal_b <= desired_b {
+                (desired_a, optimal_b)
+            } else {
+                let optimal_a = Self::mul(&env, desired_b, info.reserve_a) / info.reserve_b;
+                (optimal_a, desired_b)
+            };
+            let shares_a = Self::mul(&env, amount_a, info.total_shares) / info.reserve_a;
+            let shares_b = Self::mul(&env, amount_b, info.total_shares) / info.reserve_b;
+            (amount_a, amount_b, shares_a.min(shares_b))
+        };
+
+        if shares <= 0 {
+            panic_with_error!(&env, PoolError::InvalidAmount);
+        }
+        if shares < min_shares {
+            panic_with_error!(&env, PoolError::SlippageExceeded);
+        }
+
+        let pool = env.current_contract_address();
+        token::Client::new(&env, &info.token_a).transfer(&provider, &pool, &amount_a);
+        token::Client::new(&env, &info.token_b).transfer(&provider, &pool, &amount_b);
+
+        Self::write_reserves(
+            &env,
+            Self::add(&env, info.reserve_a, amount_a),
+            Self::add(&env, info.reserve_b, amount_b),
+        );
+        let provider_shares = Self::get_shares(env.clone(), provider.clone());
+        env.storage().persistent().set(
+            &DataKey::Shares(provider.clone()),
+            &Self::add(&env, provider_shares, shares),
+        );
+        env.storage().instance().set(
+            &DataKey::TotalShares,
+            &Self::add(&env, info.total_shares, shares),
+        );
+
+        PoolEvents::liquidity_added(&env, &provider, amount_a, amount_b, shares);
+        (amount_a, amount_b, shares)
+    }
+
+    /// Burns `shares` and returns the provider's pro-rata reserves.
+    ///
+    /// Returns `(amount_a, amount_b)` withdrawn.
+    pub fn remove_liquidity(
+        env: Env,
+        provider: Address,
+        shares: i128,
+        min_a: i128,
+        min_b: i128,
+    ) -> (i128, i128) {
+        provider.require_auth();
+        if shares <= 0 {
+            panic_with_error!(&env, PoolError::InvalidAmount);
+        }
+        let provider_shares = Self::get_shares(env.clone(), provider.clone());
+        if provider_shares < shares {
+            panic_with_error!(&env, PoolError::InsufficientShares);
+        }
+
+        let info = Self::get_info(env.clone());
+        let amount_a = Self::mul(&env, info.reserve_a, shares) / info.total_shares;
+        let amount_b = Self::mul(&env, info.reserve_b, shares) / info.total_shares;
+        if amount_a < min_a || amount_b < min_b {
+            panic_with_error!(&env, PoolError::SlippageExceeded);
+        }
+
+        let remaining = provider_shares - shares;
+        if remaining == 0 {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Shares(provider.clone()));
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Shares(provider.clone()), &remaining);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(info.total_shares - shares));
+        Self::write_reserves(&env, info.reserve_a - amount_a, info.reserve_b - amount_b);
+
+        let pool = env.current_contract_address();
+        token::Client::new(&env, &info.token_a).transfer(&pool, &provider, &amount_a);
+        token::Client::new(&env, &info.token_b).transfer(&pool, &provider, &amount_b);
+
+        PoolEvents::liquidity_removed(&env, &provider, amount_a, amount_b, shares);
+        (amount_a, amount_b)
+    }
+
+    /// Swaps `amount_in` of `token_in` for the other pool token, failing if
+    /// the output is below `min_amount_out`. Returns the output amount.
+    pub fn swap(
+        env: Env,
+        user: Address,
+        token_in: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+    ) -> i128 {
+        user.require_auth();
+
+        let info = Self::get_info(env.clone());
+        let amount_out = Self::get_amount_out(env.clone(), token_in.clone(), amount_in);
+        if amount_out < min_amount_out {
+            panic_with_error!(&env, PoolError::SlippageExceeded);
+        }
+
+        let (token_out, reserve_a, reserve_b) = if token_in == info.token_a {
+            (
+                info.token_b,
+                Self::add(&env, info.reserve_a, amount_in),
+                info.reserve_b - amount_out,
+            )
+        } else {
+            (
+                info.token_a,
+                info.reserve_a - amount_out,
+                Self::add(&env, info.reserve_b, amount_in),
+            )
+        };
+
+        let pool = env.current_contract_address();
+        token::Client::new(&env, &token_in).transfer(&user, &pool, &amount_in);
+        token::Client::new(&env, &token_out).transfer(&pool, &user, &amount_out);
+        Self::write_reserves(&env, reserve_a, reserve_b);
+
+        PoolEvents::swapped(&env, &user, &token_in, amount_in, amount_out);
+        amount_out
+    }
+
+    /// Quotes the output of swapping `amount_in` of `token_in`, after the fee.
+    pub fn get_amount_out(env: Env, token_in: Address, amount_in: i128) -> i128 {
+        if amount_in <= 0 {
+            panic_with_error!(&env, PoolError::InvalidAmount);
+        }
+
+        let info = Self::get_info(env.clone());
+        let (reserve_in, reserve_out) = if token_in == info.token_a {
+            (info.reserve_a, info.reserve_b)
+        } else if token_in == info.token_b {
+            (info.reserve_b, info.reserve_a)
+        } else {
+            panic_with_error!(&env, PoolError::UnsupportedToken)
+        };
+        if reserve_in == 0 || reserve_out == 0 {
+            panic_with_error!(&env, PoolError::InsufficientLiquidity);
+        }
+
+        // out = in_after_fee * reserve_out / (reserve_in + in_after_fee)
+        let amount_in_with_fee = Self::mul(&env, amount_in, (10_000 - info.fee_bps) as i128);
+        let numerator = Self::mul(&env, amount_in_with_fee, reserve_out);
+        let denominator = Self::add(
+            &env,
+            Self::mul(&env, reserve_in, 10_000),
+            amount_in_with_fee,
+        );
+        numerator / denominator
+    }
+
+    pub fn get_info(env: Env) -> PoolInfo {
+        let token_a: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenA)
+            .unwrap_or_else(|| panic_with_error!(&env, PoolError::NotInitialized));
+        let storage = env.storage().instance();
+        PoolInfo {
+            token_a,
+            token_b: storage.get(&DataKey::TokenB).unwrap(),
+            reserve_a: storage.get(&DataKey::ReserveA).unwrap_or(0),
+            reserve_b: storage.get(&DataKey::ReserveB).unwrap_or(0),
+            total_shares: storage.get(&DataKey::TotalShares).unwrap_or(0),
+            fee_bps: storage.get(&DataKey::FeeBps).unwrap_or(0),
+        }
+    }
+
+    pub fn get_shares(env: Env, provider: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(provider))
+            .unwrap_or(0)
+    }
+
+    fn write_reserves(env: &Env, reserve_a: i128, reserve_b: i128) {
+        env.storage().instance().set(&DataKey::ReserveA, &reserve_a);
+        env.storage().instance().set(&DataKey::ReserveB, &reserve_b);
+    }
+
+    fn mul(env: &Env, a: i128, b: i128) -> i128 {
+        a.checked_mul(b)
+            .unwrap_or_else(|| panic_with_error!(env, PoolError::Overflow))
+    }
+
+    fn add(env: &Env, a: i128, b: i128) -> i128 {
+        a.checked_add(b)
+            .unwrap_or_else(|| panic_with_error!(env, PoolError::Overflow))
+    }
+
+    /// Integer square root (floor) by Newton's method.
+    fn sqrt(value: i128) -> i128 {
+        if value < 2 {
+            return value;
+        }
+        let mut x = value;
+        let mut y = (x + 1) / 2;
+        while y < x {
+            x = y;
+            y = (x + value / x) / 2;
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod test;
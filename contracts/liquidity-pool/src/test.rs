@@ -0,0 +1,141 @@
+//! Tests for the Liquidity Pool Contract.
+
+#![cfg(test)]
+
+use crate::{LiquidityPoolContract, LiquidityPoolContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+struct Setup {
+    env: Env,
+    provider: Address,
+    token_a: token::Client<'static>,
+    token_b: token::Client<'static>,
+    pool: Address,
+    client: LiquidityPoolContractClient<'static>,
+}
+
+fn create_token(env: &Env, holder: &Address) -> token::Client<'static> {
+    let asset = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    token::StellarAssetClient::new(env, &asset).mint(holder, &1_000_000);
+    token::Client::new(env, &asset)
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let provider = Address::generate(&env);
+    let token_a = create_token(&env, &provider);
+    let token_b = create_token(&env, &provider);
+    let pool = env.register(LiquidityPoolContract, ());
+    let client = LiquidityPoolContractClient::new(&env, &pool);
+    client.initialize(
+        &Address::generate(&env),
+        &token_a.address,
+        &token_b.address,
+        &30,
+    );
+
+    Setup {
+        env,
+        provider,
+        token_a,
+        token_b,
+        pool,
+        client,
+    }
+}
+
+#[test]
+fn test_first_deposit_mints_geometric_mean_shares() {
+    let s = setup();
+    let (a, b, shares) = s.client.add_liquidity(&s.provider, &10_000, &40_000, &0);
+
+    assert_eq!((a, b, shares), (10_000, 40_000, 20_000));
+    assert_eq!(s.client.get_shares(&s.provider), 20_000);
+    assert_eq!(s.token_a.balance(&s.pool), 10_000);
+    assert_eq!(s.token_b.balance(&s.pool), 40_000);
+}
+
+#[test]
+fn test_later_deposits_follow_pool_ratio() {
+    let s = setup();
+    s.client.add_liquidity(&s.provider, &10_000, &40_000, &0);
+
+    let other = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.token_a.address).mint(&other, &5_000);
+    token::StellarAssetClient::new(&s.env, &s.token_b.address).mint(&other, &5_000);
+    let (a, b, shares) = s.client.add_liquidity(&other, &5_000, &5_000, &0);
+
+    assert_eq!((a, b, shares), (1_250, 5_000, 2_500));
+    let info = s.client.get_info();
+    assert_eq!(info.reserve_a, 11_250);
+    assert_eq!(info.reserve_b, 45_000);
+    assert_eq!(info.total_shares, 22_500);
+}
+
+#[test]
+fn test_remove_liquidity_returns_pro_rata_reserves() {
+    let s = setup();
+    s.client.add_liquidity(&s.provider, &10_000, &40_000, &0);
+
+    let (a, b) = s.client.remove_liquidity(&s.provider, &5_000, &0, &0);
+
+    assert_eq!((a, b), (2_500, 10_000));
+    assert_eq!(s.client.get_shares(&s.provider), 15_000);
+    assert_eq!(s.token_a.balance(&s.provider), 992_500);
+    assert!(s
+        .client
+        .try_remove_liquidity(&s.provider, &20_000, &0, &0)
+        .is_err());
+}
+
+#[test]
+fn test_swap_charges_fee_and_grows_invariant() {
+    let s = setup();
+    s.client.add_liquidity(&s.provider, &100_000, &100_000, &0);
+    let user = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.token_a.address).mint(&user, &1_000);
+
+    let quoted = s.client.get_amount_out(&s.token_a.address, &1_000);
+    let out = s.client.swap(&user, &s.token_a.address, &1_000, &quoted);
+
+    // 1_000 * 0.997 * 100_000 / (100_000 + 997) = 987.16...
+    assert_eq!(out, 987);
+    assert_eq!(out, quoted);
+    assert_eq!(s.token_a.balance(&user), 0);
+    assert_eq!(s.token_b.balance(&user), 987);
+
+    let info = s.client.get_info();
+    assert_eq!(info.reserve_a, 101_000);
+    assert_eq!(info.reserve_b, 99_013);
+    assert!(info.reserve_a * info.reserve_b > 100_000 * 100_000);
+}
+
+#[test]
+fn test_swap_enforces_min_amount_out() {
+    let s = setup();
+    s.client.add_liquidity(&s.provider, &100_000, &100_000, &0);
+    let user = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.token_b.address).mint(&user, &1_000);
+
+    let result = s.client.try_swap(&user, &s.token_b.address, &1_000, &988);
+
+    assert!(result.is_err());
+    assert_eq!(s.token_b.balance(&user), 1_000);
+}
+
+#[test]
+fn test_swap_rejects_foreign_token_and_empty_pool() {
+    let s = setup();
+    let foreign = Address::generate(&s.env);
+
+    assert!(s
+        .client
+        .try_get_amount_out(&s.token_a.address, &100)
+        .is_err());
+    s.client.add_liquidity(&s.provider, &1_000, &1_000, &0);
+    assert!(s.client.try_get_amount_out(&foreign, &100).is_err());
+}
@@ -0,0 +1,74 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+/// Maximum swap fee, in basis points (10%).
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolInfo {
+    pub token_a: Address,
+    pub token_b: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+    pub total_shares: i128,
+    pub fee_bps: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    TokenA,
+    TokenB,
+    /// Swap fee in basis points, retained in the pool for LPs.
+    FeeBps,
+    /// Pool's accounted reserve of `TokenA`.
+    ReserveA,
+    /// Pool's accounted reserve of `TokenB`.
+    ReserveB,
+    TotalShares,
+    /// LP shares held by a provider.
+    Shares(Address),
+}
+
+pub struct PoolEvents;
+
+impl PoolEvents {
+    pub fn liquidity_added(
+        env: &Env,
+        provider: &Address,
+        amount_a: i128,
+        amount_b: i128,
+        shares: i128,
+    ) {
+        let topics = (symbol_short!("pool"), symbol_short!("deposit"));
+        env.events()
+            .publish(topics, (provider.clone(), amount_a, amount_b, shares));
+    }
+
+    pub fn liquidity_removed(
+        env: &Env,
+        provider: &Address,
+        amount_a: i128,
+        amount_b: i128,
+        shares: i128,
+    ) {
+        let topics = (symbol_short!("pool"), symbol_short!("withdraw"));
+        env.events()
+            .publish(topics, (provider.clone(), amount_a, amount_b, shares));
+    }
+
+    pub fn swapped(
+        env: &Env,
+        user: &Address,
+        token_in: &Address,
+        amount_in: i128,
+        amount_out: i128,
+    ) {
+        let topics = (symbol_short!("pool"), symbol_short!("swap"));
+        env.events().publish(
+            topics,
+            (user.clone(), token_in.clone(), amount_in, amount_out),
+        );
+    }
+}
@@ -1,12 +1,18 @@
 //! # Multi-Currency Wallet Contract
 //!
-//! A Soroban smart contract for managing batch balance updates
-//! for multiple users across multiple currencies simultaneously.
+//! A Soroban smart contract holding token-backed balances for multiple
+//! users across multiple currencies.
 //!
 //! ## Features
 //!
-//! - **Batch Processing**: Efficiently update balances for multiple users in a single call
-//! - **Multi-Currency Support**: Handle multiple currencies (XLM, USDC, EURC, etc.)
+//! - **Currency Registry**: Each currency symbol maps to a Stellar asset contract
+//! - **Deposits & Withdrawals**: Balances move only with real token transfers
+//! - **Audited Adjustments**: Admin balance writes carry a reason code and are journaled
+//! - **Reconciliation**: Compare internal balances against the contract's token holdings
+//! - **Exchange**: Swap between currency balances at an oracle rate plus a spread
+//! - **Portfolio Valuation**: Value all balances in a per-user base currency
+//! - **Dust Sweeping**: Convert tiny balances into the base currency in one call
+//! - **Batch Processing**: Adjust balances for multiple users in a single call
 //! - **Flexible Operations**: Set, add, or subtract balance amounts
 //! - **Comprehensive Validation**: Validates amounts, currencies, and operations
 //! - **Event Emission**: Emits events for balance updates and batch processing
@@ -25,11 +31,19 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use shared::oracle::{
+    check_staleness, convert_amount, read_guarded_rate, read_price, DEFAULT_MAX_DEVIATION_BPS,
+    RATE_SCALE,
+};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, symbol_short, token, Address, Env, Symbol, Vec,
+};
 
 pub use crate::types::{
-    BalanceUpdateRequest, BalanceUpdateResult, BatchBalanceMetrics, BatchBalanceResult,
-    CurrencyBalance, DataKey, ErrorCode, WalletEvents, MAX_BATCH_SIZE,
+    AdjustmentReason, AdjustmentRecord, BalanceUpdateRequest, BalanceUpdateResult,
+    BatchBalanceMetrics, BatchBalanceResult, CurrencyBalance, CurrencyValuation, DataKey,
+    ErrorCode, ExchangeRecord, PortfolioValue, PriceStatus, ReconciliationReport, WalletEvents,
+    MAX_BATCH_SIZE, MAX_DEVIATION_BPS, MAX_HISTORY_PAGE, MAX_SPREAD_BPS,
 };
 use crate::validation::{validate_and_compute_balance, validate_balance_request};
 
@@ -47,6 +61,30 @@ pub enum WalletError {
     EmptyBatch = 4,
     /// Batch exceeds maximum size
     BatchTooLarge = 5,
+    /// Currency has no registered asset contract
+    UnsupportedCurrency = 6,
+    /// Currency is already registered
+    CurrencyAlreadyRegistered = 7,
+    /// Amount must be positive
+    InvalidAmount = 8,
+    /// Balance too low for the withdrawal
+    InsufficientBalance = 9,
+    /// Arithmetic overflow
+    Overflow = 10,
+    /// No price oracle configured
+    OracleNotConfigured = 11,
+    /// Exchange source and target currency are the same
+    SameCurrency = 12,
+    /// Oracle rate missing, stale, or rejected by the circuit breaker
+    RateUnavailable = 13,
+    /// Exchange output below the caller's minimum
+    SlippageExceeded = 14,
+    /// Contract does not hold enough unallocated tokens to back the output
+    InsufficientLiquidity = 15,
+    /// Invalid configuration value
+    InvalidConfig = 16,
+    /// Neither the user nor the contract has a base currency
+    BaseCurrencyNotSet = 17,
 }
 
 impl From<WalletError> for soroban_sdk::Error {
@@ -80,10 +118,403 @@ impl MultiCurrencyWalletContract {
             .set(&DataKey::TotalBatchesProcessed, &0u64);
     }
 
-    /// Updates balances for multiple users across multiple currencies in a batch.
+    /// Maps a currency symbol to the Stellar asset contract that backs it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not admin
+    /// * `CurrencyAlreadyRegistered` - If the symbol is already mapped
+    pub fn register_currency(env: Env, caller: Address, currency: Symbol, asset: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let key = DataKey::Currency(currency.clone());
+        if env.storage().instance().has(&key) {
+            panic_with_error!(&env, WalletError::CurrencyAlreadyRegistered);
+        }
+        env.storage().instance().set(&key, &asset);
+
+        let mut currencies = Self::get_currencies(env.clone());
+        currencies.push_back(currency.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Currencies, &currencies);
+
+        WalletEvents::currency_registered(&env, &currency, &asset);
+    }
+
+    /// Returns the asset contract registered for a currency, if any.
+    pub fn get_currency_asset(env: Env, currency: Symbol) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Currency(currency))
+    }
+
+    /// Returns all registered currency symbols.
+    pub fn get_currencies(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Currencies)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Deposits tokens from the user into the wallet and credits their balance.
+    ///
+    /// # Returns
+    /// * `i128` - The user's new balance
+    ///
+    /// # Errors
+    /// * `UnsupportedCurrency` - If the currency is not registered
+    /// * `InvalidAmount` - If amount is not positive
+    pub fn deposit(env: Env, user: Address, currency: Symbol, amount: i128) -> i128 {
+        user.require_auth();
+        let asset = Self::require_asset(&env, &currency);
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+
+        token::Client::new(&env, &asset).transfer(&user, &env.current_contract_address(), &amount);
+
+        let new_balance = Self::get_balance(env.clone(), user.clone(), currency.clone())
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(&env, WalletError::Overflow));
+        Self::write_balance(&env, &user, &currency, new_balance);
+        add_to_total(&env, DataKey::TotalDeposited(currency.clone()), amount);
+
+        WalletEvents::deposited(&env, &user, &currency, amount, new_balance);
+        new_balance
+    }
+
+    /// Withdraws tokens from the user's balance back to the user.
+    ///
+    /// # Returns
+    /// * `i128` - The user's new balance
+    ///
+    /// # Errors
+    /// * `UnsupportedCurrency` - If the currency is not registered
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `InsufficientBalance` - If the user's balance is below `amount`
+    pub fn withdraw(env: Env, user: Address, currency: Symbol, amount: i128) -> i128 {
+        user.require_auth();
+        let asset = Self::require_asset(&env, &currency);
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+
+        let current = Self::get_balance(env.clone(), user.clone(), currency.clone());
+        if current < amount {
+            panic_with_error!(&env, WalletError::InsufficientBalance);
+        }
+        let new_balance = current - amount;
+        Self::write_balance(&env, &user, &currency, new_balance);
+        add_to_total(&env, DataKey::TotalWithdrawn(currency.clone()), amount);
+
+        token::Client::new(&env, &asset).transfer(&env.current_contract_address(), &user, &amount);
+
+        WalletEvents::withdrawn(&env, &user, &currency, amount, new_balance);
+        new_balance
+    }
+
+    /// Sets the price oracle, maximum accepted price age, maximum accepted
+    /// spot/TWAP deviation and exchange spread.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not admin
+    /// * `InvalidConfig` - If `max_deviation_bps` exceeds `MAX_DEVIATION_BPS`
+    ///   or `spread_bps` exceeds `MAX_SPREAD_BPS`
+    pub fn set_exchange_config(
+        env: Env,
+        caller: Address,
+        oracle: Address,
+        max_price_age: u64,
+        max_deviation_bps: u32,
+        spread_bps: u32,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        if max_deviation_bps > MAX_DEVIATION_BPS || spread_bps > MAX_SPREAD_BPS {
+            panic_with_error!(&env, WalletError::InvalidConfig);
+        }
+
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPriceAge, &max_price_age);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDeviationBps, &max_deviation_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::SpreadBps, &spread_bps);
+        WalletEvents::exchange_config_updated(
+            &env,
+            &oracle,
+            max_price_age,
+            max_deviation_bps,
+            spread_bps,
+        );
+    }
+
+    /// Returns the configured price oracle, if any.
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Oracle)
+    }
+
+    /// Returns the maximum accepted oracle price age in seconds.
+    pub fn get_max_price_age(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxPriceAge)
+            .unwrap_or(0)
+    }
+
+    /// Returns the maximum accepted spot/TWAP deviation in basis points,
+    /// `DEFAULT_MAX_DEVIATION_BPS` until one is configured.
+    pub fn get_max_deviation(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxDeviationBps)
+            .unwrap_or(DEFAULT_MAX_DEVIATION_BPS)
+    }
+
+    /// Returns the exchange spread in basis points.
+    pub fn get_spread(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SpreadBps)
+            .unwrap_or(0)
+    }
+
+    /// Exchanges `amount` of the user's `from_currency` balance into
+    /// `to_currency` at the oracle rate less the configured spread.
+    ///
+    /// Both legs are written in the same invocation, so either both balances
+    /// change or neither does. The credited amount must be covered by tokens
+    /// the contract holds beyond existing `to_currency` balances.
+    ///
+    /// # Returns
+    /// * `ExchangeRecord` - The recorded exchange
+    ///
+    /// # Errors
+    /// * `SameCurrency` - If both currencies are the same
+    /// * `UnsupportedCurrency` - If either currency is not registered
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `InsufficientBalance` - If the user's `from_currency` balance is below `amount`
+    /// * `OracleNotConfigured` - If no oracle has been set
+    /// * `RateUnavailable` - If the oracle cannot provide a fresh, in-band rate
+    /// * `SlippageExceeded` - If the output is below `min_out`
+    /// * `InsufficientLiquidity` - If the contract cannot back the output
+    pub fn exchange(
+        env: Env,
+        user: Address,
+        from_currency: Symbol,
+        to_currency: Symbol,
+        amount: i128,
+        min_out: i128,
+    ) -> ExchangeRecord {
+        user.require_auth();
+        if from_currency == to_currency {
+            panic_with_error!(&env, WalletError::SameCurrency);
+        }
+        let from_asset = Self::require_asset(&env, &from_currency);
+        let to_asset = Self::require_asset(&env, &to_currency);
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+        if Self::get_balance(env.clone(), user.clone(), from_currency.clone()) < amount {
+            panic_with_error!(&env, WalletError::InsufficientBalance);
+        }
+
+        Self::read_exchange_rate(&env, &from_asset, &to_asset)
+            .and_then(|rate| {
+                Self::exchange_balance(
+                    &env,
+                    &user,
+                    &from_currency,
+                    &to_currency,
+                    amount,
+                    min_out,
+                    rate,
+                )
+            })
+            .unwrap_or_else(|error| panic_with_error!(&env, error))
+    }
+
+    /// Returns the number of exchanges a user has made.
+    pub fn get_exchange_count(env: Env, user: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ExchangeCount(user))
+            .unwrap_or(0)
+    }
+
+    /// Returns up to `limit` of a user's exchanges starting at index `start`,
+    /// oldest first. `limit` is capped at `MAX_HISTORY_PAGE`.
+    pub fn get_exchange_history(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<ExchangeRecord> {
+        let count = Self::get_exchange_count(env.clone(), user.clone());
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(count);
+
+        let mut history = Vec::new(&env);
+        for index in start..end {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Exchange(user.clone(), index))
+            {
+                history.push_back(record);
+            }
+        }
+        history
+    }
+
+    /// Sets the currency the user's portfolio is valued in.
+    ///
+    /// # Errors
+    /// * `UnsupportedCurrency` - If the currency is not registered
+    pub fn set_base_currency(env: Env, user: Address, currency: Symbol) {
+        user.require_auth();
+        Self::require_asset(&env, &currency);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BaseCurrency(user.clone()), &currency);
+        WalletEvents::base_currency_set(&env, Some(user), &currency);
+    }
+
+    /// Sets the base currency used for users who have not chosen one.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not admin
+    /// * `UnsupportedCurrency` - If the currency is not registered
+    pub fn set_default_base_currency(env: Env, caller: Address, currency: Symbol) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        Self::require_asset(&env, &currency);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::DefaultBaseCurrency, &currency);
+        WalletEvents::base_currency_set(&env, None, &currency);
+    }
+
+    /// Returns the user's base currency, falling back to the contract default.
+    pub fn get_base_currency(env: Env, user: Address) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BaseCurrency(user))
+            .or_else(|| env.storage().instance().get(&DataKey::DefaultBaseCurrency))
+    }
+
+    /// Values every non-zero balance of the user in their base currency.
+    ///
+    /// Stale prices are still used but mark the result `incomplete`; currencies
+    /// without a price are listed with a zero value and also mark it
+    /// `incomplete`.
+    ///
+    /// # Errors
+    /// * `BaseCurrencyNotSet` - If neither the user nor the contract has a base currency
+    pub fn get_portfolio_value(env: Env, user: Address) -> PortfolioValue {
+        let base_currency = Self::get_base_currency(env.clone(), user.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, WalletError::BaseCurrencyNotSet));
+        let base_asset = Self::require_asset(&env, &base_currency);
+        let oracle = Self::get_oracle(env.clone());
+        let max_age = Self::get_max_price_age(env.clone());
+
+        let mut holdings = Vec::new(&env);
+        let mut total_value: i128 = 0;
+        let mut oldest_price_timestamp: Option<u64> = None;
+        let mut incomplete = false;
+
+        for currency in Self::get_currencies(env.clone()).iter() {
+            let balance = Self::get_balance(env.clone(), user.clone(), currency.clone());
+            if balance == 0 {
+                continue;
+            }
+
+            let (rate, price_timestamp, status) = if currency == base_currency {
+                (RATE_SCALE, 0, PriceStatus::Base)
+            } else {
+                let asset = Self::require_asset(&env, &currency);
+                match oracle
+                    .as_ref()
+                    .and_then(|oracle| read_price(&env, oracle, &asset, &base_asset).ok())
+                {
+                    Some(price) => {
+                        let status = if check_staleness(&env, &price, max_age).is_ok() {
+                            PriceStatus::Fresh
+                        } else {
+                            PriceStatus::Stale
+                        };
+                        (price.price, price.timestamp, status)
+                    }
+                    None => (0, 0, PriceStatus::Missing),
+                }
+            };
+
+            let value = convert_amount(balance, rate)
+                .unwrap_or_else(|_| panic_with_error!(&env, WalletError::Overflow));
+            total_value = total_value
+                .checked_add(value)
+                .unwrap_or_else(|| panic_with_error!(&env, WalletError::Overflow));
+            if matches!(status, PriceStatus::Fresh | PriceStatus::Stale) {
+                oldest_price_timestamp = Some(
+                    oldest_price_timestamp.map_or(price_timestamp, |ts| ts.min(price_timestamp)),
+                );
+            }
+            if matches!(status, PriceStatus::Stale | PriceStatus::Missing) {
+                incomplete = true;
+            }
+
+            holdings.push_back(CurrencyValuation {
+                currency,
+                balance,
+                rate,
+                value,
+                price_timestamp,
+                status,
+            });
+        }
+
+        PortfolioValue {
+            user,
+            base_currency,
+            holdings,
+            total_value,
+            oldest_price_timestamp,
+            incomplete,
+        }
+    }
+
+    /// Values the portfolios of multiple users.
+    ///
+    /// # Errors
+    /// * `EmptyBatch` - If no users provided
+    /// * `BatchTooLarge` - If more than `MAX_BATCH_SIZE` users are provided
+    /// * `BaseCurrencyNotSet` - If any user has no base currency
+    pub fn get_portfolio_values(env: Env, users: Vec<Address>) -> Vec<PortfolioValue> {
+        if users.is_empty() {
+            panic_with_error!(&env, WalletError::EmptyBatch);
+        }
+        if users.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, WalletError::BatchTooLarge);
+        }
+
+        let mut values = Vec::new(&env);
+        for user in users.iter() {
+            values.push_back(Self::get_portfolio_value(env.clone(), user));
+        }
+        values
+    }
+
+    /// Applies audited admin adjustments to balances for multiple users in a batch.
     ///
-    /// This is the main entry point for batch balance updates. It validates all requests,
-    /// updates balances, emits events, and handles partial failures gracefully.
+    /// This is the only path that changes balances without a token transfer. Each
+    /// request must name a registered currency and carry an `AdjustmentReason`;
+    /// every successful adjustment is journaled and counted towards the currency's
+    /// net adjustment so it shows up in `get_reconciliation`. Partial failures are
+    /// reported per request.
     ///
     /// # Arguments
     /// * `env` - The contract environment
@@ -96,6 +527,7 @@ impl MultiCurrencyWalletContract {
     /// # Events Emitted
     /// * `batch_started` - When processing begins
     /// * `balance_updated` - For each successful balance update
+    /// * `balance_adjusted` - For each journaled adjustment
     /// * `balance_update_failed` - For each failed balance update
     /// * `large_balance_update` - For large balance values
     /// * `batch_completed` - When processing completes
@@ -104,7 +536,7 @@ impl MultiCurrencyWalletContract {
     /// * `EmptyBatch` - If no requests provided
     /// * `BatchTooLarge` - If batch exceeds maximum size
     /// * `Unauthorized` - If caller is not admin
-    pub fn batch_update_balances(
+    pub fn batch_adjust_balances(
         env: Env,
         caller: Address,
         requests: Vec<BalanceUpdateRequest>,
@@ -133,9 +565,6 @@ impl MultiCurrencyWalletContract {
         // Emit batch started event
         WalletEvents::batch_started(&env, batch_id, request_count);
 
-        // Get current ledger timestamp
-        let current_ledger = env.ledger().sequence() as u64;
-
         // Initialize result tracking
         let mut results: Vec<BalanceUpdateResult> = Vec::new(&env);
         let mut successful_count: u32 = 0;
@@ -147,8 +576,19 @@ impl MultiCurrencyWalletContract {
 
         // Process each request
         for request in requests.iter() {
-            // Validate the request
-            match validate_balance_request(&request) {
+            // Validate the request and that the currency is backed by an asset
+            let validation = validate_balance_request(&request).and_then(|_| {
+                if env
+                    .storage()
+                    .instance()
+                    .has(&DataKey::Currency(request.currency.clone()))
+                {
+                    Ok(())
+                } else {
+                    Err(ErrorCode::UNSUPPORTED_CURRENCY)
+                }
+            });
+            match validation {
                 Ok(()) => {
                     // Validate and compute new balance
                     match validate_and_compute_balance(
@@ -159,21 +599,32 @@ impl MultiCurrencyWalletContract {
                         request.amount,
                     ) {
                         Ok(new_balance) => {
-                            // Update succeeded - create the balance record
-                            let balance = CurrencyBalance {
-                                user: request.user.clone(),
-                                currency: request.currency.clone(),
-                                balance: new_balance,
-                                updated_at: current_ledger,
-                            };
+                            let previous_balance = Self::get_balance(
+                                env.clone(),
+                                request.user.clone(),
+                                request.currency.clone(),
+                            );
+
+                            // Store the balance (optimized - one write per balance)
+                            let balance = Self::write_balance(
+                                &env,
+                                &request.user,
+                                &request.currency,
+                                new_balance,
+                            );
 
                             successful_count += 1;
 
-                            // Store the balance (optimized - one write per balance)
-                            env.storage().persistent().set(
-                                &DataKey::Balance(request.user.clone(), request.currency.clone()),
-                                &balance,
+                            // Journal the adjustment
+                            let record = Self::record_adjustment(
+                                &env,
+                                batch_id,
+                                &caller,
+                                &request,
+                                previous_balance,
+                                new_balance,
                             );
+                            WalletEvents::balance_adjusted(&env, &record);
 
                             // Track unique users
                             if !contains_address(&unique_users, &request.user) {
@@ -242,50 +693,177 @@ impl MultiCurrencyWalletContract {
             }
         }
 
-        // Create metrics
-        let metrics = BatchBalanceMetrics {
-            total_requests: request_count,
-            successful_updates: successful_count,
-            failed_updates: failed_count,
-            unique_users: unique_users.len(),
-            unique_currencies: unique_currencies.len(),
-            processed_at: current_ledger,
-        };
+        Self::complete_batch(
+            &env,
+            batch_id,
+            results,
+            successful_count,
+            failed_count,
+            unique_users.len(),
+            unique_currencies.len(),
+        )
+    }
 
-        // Update storage (batched at the end for efficiency)
-        let total_balances: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalBalancesUpdated)
-            .unwrap_or(0);
-        let total_batches: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalBatchesProcessed)
-            .unwrap_or(0);
+    /// Opts the user in or out of admin-run dust sweeps.
+    ///
+    /// # Errors
+    /// * `BaseCurrencyNotSet` - If enabling without a base currency to sweep into
+    pub fn set_dust_sweep(env: Env, user: Address, enabled: bool) {
+        user.require_auth();
+        if enabled && Self::get_base_currency(env.clone(), user.clone()).is_none() {
+            panic_with_error!(&env, WalletError::BaseCurrencyNotSet);
+        }
 
         env.storage()
-            .instance()
-            .set(&DataKey::LastBatchId, &batch_id);
-        env.storage().instance().set(
-            &DataKey::TotalBalancesUpdated,
-            &(total_balances + successful_count as u64),
-        );
+            .persistent()
+            .set(&DataKey::DustSweepOptIn(user.clone()), &enabled);
+        WalletEvents::dust_sweep_set(&env, &user, enabled);
+    }
+
+    /// Returns whether the user has opted in to admin-run dust sweeps.
+    pub fn is_dust_sweep_enabled(env: Env, user: Address) -> bool {
         env.storage()
-            .instance()
-            .set(&DataKey::TotalBatchesProcessed, &(total_batches + 1));
+            .persistent()
+            .get(&DataKey::DustSweepOptIn(user))
+            .unwrap_or(false)
+    }
 
-        // Emit batch completed event
-        WalletEvents::batch_completed(&env, batch_id, successful_count, failed_count);
+    /// Converts every balance of the user worth less than `threshold_in_base`
+    /// into their base currency, at the exchange rate less the spread.
+    ///
+    /// Each swept currency is reported as a `Success` holding its (now zero)
+    /// balance; currencies that could not be priced or converted are reported
+    /// as a `Failure` and left untouched.
+    ///
+    /// # Events Emitted
+    /// * `batch_started`, `balance_updated`, `balance_update_failed`,
+    ///   `batch_completed` - As for `batch_adjust_balances`
+    /// * `exchanged` - For each swept currency
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If `threshold_in_base` is not positive
+    /// * `BaseCurrencyNotSet` - If the user has no base currency
+    pub fn sweep_dust(env: Env, user: Address, threshold_in_base: i128) -> BatchBalanceResult {
+        user.require_auth();
+        if threshold_in_base <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+        let base_currency = Self::get_base_currency(env.clone(), user.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, WalletError::BaseCurrencyNotSet));
 
-        BatchBalanceResult {
+        let batch_id = Self::get_last_batch_id(env.clone()) + 1;
+        WalletEvents::batch_started(&env, batch_id, 1);
+
+        let mut results: Vec<BalanceUpdateResult> = Vec::new(&env);
+        let mut unique_currencies: Vec<Symbol> = Vec::new(&env);
+        let (successful, failed) = Self::sweep_user(
+            &env,
+            batch_id,
+            &user,
+            &base_currency,
+            threshold_in_base,
+            &mut results,
+            &mut unique_currencies,
+        );
+
+        Self::complete_batch(
+            &env,
             batch_id,
-            total_requests: request_count,
-            successful: successful_count,
-            failed: failed_count,
             results,
-            metrics,
+            successful,
+            failed,
+            if successful + failed > 0 { 1 } else { 0 },
+            unique_currencies.len(),
+        )
+    }
+
+    /// Runs `sweep_dust` for multiple users. Admin only.
+    ///
+    /// Users who have not opted in via `set_dust_sweep` are reported as a
+    /// single `Failure` with currency `ALL` and `ErrorCode::NOT_OPTED_IN`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not admin
+    /// * `EmptyBatch` - If no users provided
+    /// * `BatchTooLarge` - If more than `MAX_BATCH_SIZE` users are provided
+    /// * `InvalidAmount` - If `threshold_in_base` is not positive
+    pub fn batch_sweep_dust(
+        env: Env,
+        caller: Address,
+        users: Vec<Address>,
+        threshold_in_base: i128,
+    ) -> BatchBalanceResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let user_count = users.len();
+        if user_count == 0 {
+            panic_with_error!(&env, WalletError::EmptyBatch);
+        }
+        if user_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, WalletError::BatchTooLarge);
+        }
+        if threshold_in_base <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+
+        let batch_id = Self::get_last_batch_id(env.clone()) + 1;
+        WalletEvents::batch_started(&env, batch_id, user_count);
+
+        let mut results: Vec<BalanceUpdateResult> = Vec::new(&env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+        let mut unique_users: Vec<Address> = Vec::new(&env);
+        let mut unique_currencies: Vec<Symbol> = Vec::new(&env);
+
+        for user in users.iter() {
+            let base_currency = Self::get_base_currency(env.clone(), user.clone());
+            let (successful, failed) = match base_currency {
+                Some(base_currency) if Self::is_dust_sweep_enabled(env.clone(), user.clone()) => {
+                    Self::sweep_user(
+                        &env,
+                        batch_id,
+                        &user,
+                        &base_currency,
+                        threshold_in_base,
+                        &mut results,
+                        &mut unique_currencies,
+                    )
+                }
+                _ => {
+                    let all = symbol_short!("ALL");
+                    WalletEvents::balance_update_failed(
+                        &env,
+                        batch_id,
+                        &user,
+                        &all,
+                        ErrorCode::NOT_OPTED_IN,
+                    );
+                    results.push_back(BalanceUpdateResult::Failure(
+                        user.clone(),
+                        all,
+                        ErrorCode::NOT_OPTED_IN,
+                    ));
+                    (0, 1)
+                }
+            };
+
+            successful_count += successful;
+            failed_count += failed;
+            if successful + failed > 0 && !contains_address(&unique_users, &user) {
+                unique_users.push_back(user.clone());
+            }
         }
+
+        Self::complete_batch(
+            &env,
+            batch_id,
+            results,
+            successful_count,
+            failed_count,
+            unique_users.len(),
+            unique_currencies.len(),
+        )
     }
 
     /// Retrieves a user's balance for a specific currency.
@@ -324,6 +902,47 @@ impl MultiCurrencyWalletContract {
             .get(&DataKey::Balance(user, currency))
     }
 
+    /// Returns the number of adjustment records written.
+    pub fn get_adjustment_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AdjustmentCount)
+            .unwrap_or(0)
+    }
+
+    /// Returns an adjustment record by ID.
+    pub fn get_adjustment(env: Env, id: u64) -> Option<AdjustmentRecord> {
+        env.storage().persistent().get(&DataKey::Adjustment(id))
+    }
+
+    /// Compares the sum of user balances in a currency against the tokens the
+    /// contract actually holds.
+    ///
+    /// Deposits and withdrawals keep both sides in step, so any non-zero
+    /// `surplus` is explained by `net_adjustments`, `net_exchanged`, or by
+    /// tokens sent to the contract outside of `deposit`.
+    ///
+    /// # Errors
+    /// * `UnsupportedCurrency` - If the currency is not registered
+    pub fn get_reconciliation(env: Env, currency: Symbol) -> ReconciliationReport {
+        let asset = Self::require_asset(&env, &currency);
+        let token_holdings =
+            token::Client::new(&env, &asset).balance(&env.current_contract_address());
+        let total_balances = read_total(&env, DataKey::TotalBalance(currency.clone()));
+
+        ReconciliationReport {
+            currency: currency.clone(),
+            asset,
+            token_holdings,
+            total_balances,
+            total_deposited: read_total(&env, DataKey::TotalDeposited(currency.clone())),
+            total_withdrawn: read_total(&env, DataKey::TotalWithdrawn(currency.clone())),
+            net_adjustments: read_total(&env, DataKey::NetAdjustment(currency.clone())),
+            net_exchanged: read_total(&env, DataKey::NetExchanged(currency)),
+            surplus: token_holdings - total_balances,
+        }
+    }
+
     /// Returns the admin address.
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -364,6 +983,269 @@ impl MultiCurrencyWalletContract {
             .unwrap_or(0)
     }
 
+    // Internal helper to look up the asset backing a currency
+    fn require_asset(env: &Env, currency: &Symbol) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Currency(currency.clone()))
+            .unwrap_or_else(|| panic_with_error!(env, WalletError::UnsupportedCurrency))
+    }
+
+    // Internal helper to read the guarded oracle rate between two assets
+    fn read_exchange_rate(
+        env: &Env,
+        from_asset: &Address,
+        to_asset: &Address,
+    ) -> Result<i128, WalletError> {
+        let oracle = Self::get_oracle(env.clone()).ok_or(WalletError::OracleNotConfigured)?;
+        let max_age = Self::get_max_price_age(env.clone());
+        let max_deviation = Self::get_max_deviation(env.clone());
+
+        read_guarded_rate(env, &oracle, from_asset, to_asset, max_age, max_deviation)
+            .map_err(|_| WalletError::RateUnavailable)
+    }
+
+    // Internal helper to move `amount` of a user's balance between currencies
+    // at `rate` less the spread. The caller has checked the user's balance.
+    fn exchange_balance(
+        env: &Env,
+        user: &Address,
+        from_currency: &Symbol,
+        to_currency: &Symbol,
+        amount: i128,
+        min_out: i128,
+        rate: i128,
+    ) -> Result<ExchangeRecord, WalletError> {
+        let spread_bps = Self::get_spread(env.clone());
+        let amount_out = convert_amount(amount, rate)
+            .ok()
+            .and_then(|gross| gross.checked_mul((MAX_SPREAD_BPS - spread_bps) as i128))
+            .map(|scaled| scaled / MAX_SPREAD_BPS as i128)
+            .ok_or(WalletError::Overflow)?;
+        if amount_out <= 0 || amount_out < min_out {
+            return Err(WalletError::SlippageExceeded);
+        }
+
+        let to_asset = Self::require_asset(env, to_currency);
+        let holdings = token::Client::new(env, &to_asset).balance(&env.current_contract_address());
+        let allocated = read_total(env, DataKey::TotalBalance(to_currency.clone()));
+        if holdings - allocated < amount_out {
+            return Err(WalletError::InsufficientLiquidity);
+        }
+
+        let from_balance = Self::get_balance(env.clone(), user.clone(), from_currency.clone());
+        let to_balance = Self::get_balance(env.clone(), user.clone(), to_currency.clone())
+            .checked_add(amount_out)
+            .ok_or(WalletError::Overflow)?;
+        Self::write_balance(env, user, from_currency, from_balance - amount);
+        Self::write_balance(env, user, to_currency, to_balance);
+        add_to_total(env, DataKey::NetExchanged(from_currency.clone()), -amount);
+        add_to_total(env, DataKey::NetExchanged(to_currency.clone()), amount_out);
+
+        let index = Self::get_exchange_count(env.clone(), user.clone());
+        let record = ExchangeRecord {
+            index,
+            user: user.clone(),
+            from_currency: from_currency.clone(),
+            to_currency: to_currency.clone(),
+            amount_in: amount,
+            amount_out,
+            rate,
+            spread_bps,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Exchange(user.clone(), index), &record);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ExchangeCount(user.clone()), &(index + 1));
+
+        WalletEvents::exchanged(env, &record);
+        Ok(record)
+    }
+
+    // Internal helper to sweep one user's dust into their base currency.
+    // Returns the number of swept and failed currencies.
+    fn sweep_user(
+        env: &Env,
+        batch_id: u64,
+        user: &Address,
+        base_currency: &Symbol,
+        threshold_in_base: i128,
+        results: &mut Vec<BalanceUpdateResult>,
+        unique_currencies: &mut Vec<Symbol>,
+    ) -> (u32, u32) {
+        let base_asset = Self::require_asset(env, base_currency);
+        let mut successful: u32 = 0;
+        let mut failed: u32 = 0;
+
+        for currency in Self::get_currencies(env.clone()).iter() {
+            if currency == *base_currency {
+                continue;
+            }
+            let balance = Self::get_balance(env.clone(), user.clone(), currency.clone());
+            if balance <= 0 {
+                continue;
+            }
+
+            let asset = Self::require_asset(env, &currency);
+            let outcome = Self::read_exchange_rate(env, &asset, &base_asset).and_then(|rate| {
+                let value = convert_amount(balance, rate).map_err(|_| WalletError::Overflow)?;
+                if value >= threshold_in_base {
+                    return Ok(None);
+                }
+                Self::exchange_balance(env, user, &currency, base_currency, balance, 1, rate)
+                    .map(Some)
+            });
+
+            match outcome {
+                // Not dust
+                Ok(None) => continue,
+                Ok(Some(_)) => {
+                    let swept =
+                        Self::get_balance_details(env.clone(), user.clone(), currency.clone())
+                            .unwrap_or_else(|| panic_with_error!(env, WalletError::InvalidBatch));
+                    successful += 1;
+                    WalletEvents::balance_updated(env, batch_id, &swept);
+                    results.push_back(BalanceUpdateResult::Success(swept));
+                }
+                Err(error) => {
+                    let error_code = sweep_error_code(error);
+                    failed += 1;
+                    WalletEvents::balance_update_failed(env, batch_id, user, &currency, error_code);
+                    results.push_back(BalanceUpdateResult::Failure(
+                        user.clone(),
+                        currency.clone(),
+                        error_code,
+                    ));
+                }
+            }
+
+            if !contains_symbol(unique_currencies, &currency) {
+                unique_currencies.push_back(currency.clone());
+            }
+        }
+
+        (successful, failed)
+    }
+
+    // Internal helper to record batch counters and build the batch result
+    fn complete_batch(
+        env: &Env,
+        batch_id: u64,
+        results: Vec<BalanceUpdateResult>,
+        successful_count: u32,
+        failed_count: u32,
+        unique_users: u32,
+        unique_currencies: u32,
+    ) -> BatchBalanceResult {
+        let request_count = results.len();
+
+        // Create metrics
+        let metrics = BatchBalanceMetrics {
+            total_requests: request_count,
+            successful_updates: successful_count,
+            failed_updates: failed_count,
+            unique_users,
+            unique_currencies,
+            processed_at: env.ledger().sequence() as u64,
+        };
+
+        // Update storage (batched at the end for efficiency)
+        let total_balances: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBalancesUpdated)
+            .unwrap_or(0);
+        let total_batches: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBatchesProcessed)
+            .unwrap_or(0);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBatchId, &batch_id);
+        env.storage().instance().set(
+            &DataKey::TotalBalancesUpdated,
+            &(total_balances + successful_count as u64),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBatchesProcessed, &(total_batches + 1));
+
+        // Emit batch completed event
+        WalletEvents::batch_completed(env, batch_id, successful_count, failed_count);
+
+        BatchBalanceResult {
+            batch_id,
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            results,
+            metrics,
+        }
+    }
+
+    // Internal helper to store a balance and keep the currency total in step
+    fn write_balance(
+        env: &Env,
+        user: &Address,
+        currency: &Symbol,
+        new_balance: i128,
+    ) -> CurrencyBalance {
+        let previous = Self::get_balance(env.clone(), user.clone(), currency.clone());
+        let balance = CurrencyBalance {
+            user: user.clone(),
+            currency: currency.clone(),
+            balance: new_balance,
+            updated_at: env.ledger().sequence() as u64,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone(), currency.clone()), &balance);
+        add_to_total(
+            env,
+            DataKey::TotalBalance(currency.clone()),
+            new_balance - previous,
+        );
+        balance
+    }
+
+    // Internal helper to journal an admin adjustment
+    fn record_adjustment(
+        env: &Env,
+        batch_id: u64,
+        admin: &Address,
+        request: &BalanceUpdateRequest,
+        previous_balance: i128,
+        new_balance: i128,
+    ) -> AdjustmentRecord {
+        let id = Self::get_adjustment_count(env.clone()) + 1;
+        let record = AdjustmentRecord {
+            id,
+            batch_id,
+            admin: admin.clone(),
+            user: request.user.clone(),
+            currency: request.currency.clone(),
+            previous_balance,
+            new_balance,
+            reason: request.reason,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Adjustment(id), &record);
+        env.storage().instance().set(&DataKey::AdjustmentCount, &id);
+        add_to_total(
+            env,
+            DataKey::NetAdjustment(request.currency.clone()),
+            new_balance - previous_balance,
+        );
+        record
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -378,6 +1260,28 @@ impl MultiCurrencyWalletContract {
     }
 }
 
+// Maps an exchange failure to the per-item error code reported by sweeps
+fn sweep_error_code(error: WalletError) -> u32 {
+    match error {
+        WalletError::OracleNotConfigured | WalletError::RateUnavailable => {
+            ErrorCode::RATE_UNAVAILABLE
+        }
+        WalletError::InsufficientLiquidity => ErrorCode::INSUFFICIENT_LIQUIDITY,
+        WalletError::SlippageExceeded => ErrorCode::AMOUNT_TOO_SMALL,
+        _ => ErrorCode::ARITHMETIC_OVERFLOW,
+    }
+}
+
+// Helper functions for per-currency running totals
+fn read_total(env: &Env, key: DataKey) -> i128 {
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+fn add_to_total(env: &Env, key: DataKey, delta: i128) {
+    let total = read_total(env, key.clone()) + delta;
+    env.storage().persistent().set(&key, &total);
+}
+
 // Helper functions for tracking unique items
 fn contains_address(vec: &Vec<Address>, addr: &Address) -> bool {
     for item in vec.iter() {
@@ -3,9 +3,17 @@
 #![cfg(test)]
 
 use crate::{MultiCurrencyWalletContract, MultiCurrencyWalletContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, Vec};
-
-use crate::types::{BalanceUpdateRequest, BalanceUpdateResult, ErrorCode};
+use price_oracle::{PriceOracleContract, PriceOracleContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Symbol, Vec,
+};
+
+use crate::types::{
+    AdjustmentReason, BalanceUpdateRequest, BalanceUpdateResult, ErrorCode, PriceStatus,
+    MAX_DEVIATION_BPS,
+};
 
 /// Helper function to create a test environment with initialized contract.
 fn setup_test_contract() -> (Env, Address, MultiCurrencyWalletContractClient<'static>) {
@@ -18,12 +26,83 @@ fn setup_test_contract() -> (Env, Address, MultiCurrencyWalletContractClient<'st
     let admin = Address::generate(&env);
     client.initialize(&admin);
 
+    for currency in [
+        symbol_short!("USDC"),
+        symbol_short!("XLM"),
+        symbol_short!("EURC"),
+    ] {
+        let asset = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.register_currency(&admin, &currency, &asset);
+    }
+
     (env, admin, client)
 }
 
+/// Helper function to mint tokens of a registered currency to a user.
+fn mint(
+    env: &Env,
+    client: &MultiCurrencyWalletContractClient,
+    to: &Address,
+    currency: Symbol,
+    amount: i128,
+) {
+    let asset = client.get_currency_asset(&currency).unwrap();
+    token::StellarAssetClient::new(env, &asset).mint(to, &amount);
+}
+
+const PRICE_TIMESTAMP: u64 = 1_000;
+const MAX_PRICE_AGE: u64 = 300;
+
+/// Helper function to wire up an oracle quoting 1 USDC = 0.90 EURC, a 1%
+/// exchange spread, and `liquidity` EURC held by the wallet.
+fn setup_exchange(
+    env: &Env,
+    admin: &Address,
+    client: &MultiCurrencyWalletContractClient,
+    liquidity: i128,
+) -> (PriceOracleContractClient<'static>, Address) {
+    env.ledger().with_mut(|li| li.timestamp = PRICE_TIMESTAMP);
+
+    let oracle_id = env.register(PriceOracleContract, ());
+    let oracle = PriceOracleContractClient::new(env, &oracle_id);
+    let feeder = Address::generate(env);
+    oracle.initialize(admin);
+    oracle.add_feeder(admin, &feeder);
+
+    let usdc = client.get_currency_asset(&symbol_short!("USDC")).unwrap();
+    let eurc = client.get_currency_asset(&symbol_short!("EURC")).unwrap();
+    oracle.set_price(&feeder, &usdc, &eurc, &90, &2, &PRICE_TIMESTAMP);
+    client.set_exchange_config(admin, &oracle_id, &MAX_PRICE_AGE, &500, &100);
+
+    if liquidity > 0 {
+        mint(
+            env,
+            client,
+            &client.address,
+            symbol_short!("EURC"),
+            liquidity,
+        );
+    }
+    (oracle, feeder)
+}
+
+/// Helper function to give a user a deposited balance.
+fn fund_user(
+    env: &Env,
+    client: &MultiCurrencyWalletContractClient,
+    user: &Address,
+    currency: Symbol,
+    amount: i128,
+) {
+    mint(env, client, user, currency.clone(), amount);
+    client.deposit(user, &currency, &amount);
+}
+
 /// Helper function to create a valid balance update request.
 fn create_valid_request(
-    env: &Env,
+    _env: &Env,
     user: &Address,
     currency: Symbol,
     amount: i128,
@@ -34,6 +113,7 @@ fn create_valid_request(
         currency,
         amount,
         operation,
+        reason: AdjustmentReason::CORRECTION,
     }
 }
 
@@ -56,7 +136,7 @@ fn test_initialize_twice_fails() {
 }
 
 #[test]
-fn test_batch_update_balances_single_user_single_currency() {
+fn test_batch_adjust_balances_single_user_single_currency() {
     let (env, admin, client) = setup_test_contract();
     let user = Address::generate(&env);
 
@@ -65,11 +145,11 @@ fn test_batch_update_balances_single_user_single_currency() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000, // 1000 USDC
+        1_000_000_000, // 1000 USDC
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.total_requests, 1);
     assert_eq!(result.successful, 1);
@@ -78,7 +158,7 @@ fn test_batch_update_balances_single_user_single_currency() {
 
     // Verify balance was set
     let balance = client.get_balance(&user, &symbol_short!("USDC"));
-    assert_eq!(balance, 1000_000_000);
+    assert_eq!(balance, 1_000_000_000);
 
     // Verify storage updates
     assert_eq!(client.get_last_batch_id(), 1);
@@ -87,7 +167,7 @@ fn test_batch_update_balances_single_user_single_currency() {
 }
 
 #[test]
-fn test_batch_update_balances_multiple_users_multiple_currencies() {
+fn test_batch_adjust_balances_multiple_users_multiple_currencies() {
     let (env, admin, client) = setup_test_contract();
 
     let user1 = Address::generate(&env);
@@ -117,7 +197,7 @@ fn test_batch_update_balances_multiple_users_multiple_currencies() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 3);
@@ -164,10 +244,10 @@ fn test_balance_add_operation() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
-    client.batch_update_balances(&admin, &requests1);
+    client.batch_adjust_balances(&admin, &requests1);
 
     // Add to balance
     let mut requests2: Vec<BalanceUpdateRequest> = Vec::new(&env);
@@ -178,12 +258,12 @@ fn test_balance_add_operation() {
         500_000_000,
         symbol_short!("add"),
     ));
-    let result = client.batch_update_balances(&admin, &requests2);
+    let result = client.batch_adjust_balances(&admin, &requests2);
 
     assert_eq!(result.successful, 1);
     assert_eq!(
         client.get_balance(&user, &symbol_short!("USDC")),
-        1500_000_000
+        1_500_000_000
     );
 }
 
@@ -198,10 +278,10 @@ fn test_balance_subtract_operation() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
-    client.batch_update_balances(&admin, &requests1);
+    client.batch_adjust_balances(&admin, &requests1);
 
     // Subtract from balance
     let mut requests2: Vec<BalanceUpdateRequest> = Vec::new(&env);
@@ -212,7 +292,7 @@ fn test_balance_subtract_operation() {
         300_000_000,
         symbol_short!("subtract"),
     ));
-    let result = client.batch_update_balances(&admin, &requests2);
+    let result = client.batch_adjust_balances(&admin, &requests2);
 
     assert_eq!(result.successful, 1);
     assert_eq!(
@@ -235,7 +315,7 @@ fn test_balance_subtract_insufficient_fails() {
         500_000_000,
         symbol_short!("set"),
     ));
-    client.batch_update_balances(&admin, &requests1);
+    client.batch_adjust_balances(&admin, &requests1);
 
     // Try to subtract more than balance
     let mut requests2: Vec<BalanceUpdateRequest> = Vec::new(&env);
@@ -243,10 +323,10 @@ fn test_balance_subtract_insufficient_fails() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("subtract"),
     ));
-    let result = client.batch_update_balances(&admin, &requests2);
+    let result = client.batch_adjust_balances(&admin, &requests2);
 
     assert_eq!(result.successful, 0);
     assert_eq!(result.failed, 1);
@@ -279,7 +359,7 @@ fn test_batch_update_with_invalid_requests() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -292,7 +372,7 @@ fn test_batch_update_with_invalid_requests() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -327,7 +407,7 @@ fn test_invalid_amount_negative() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.successful, 0);
     assert_eq!(result.failed, 1);
@@ -345,7 +425,7 @@ fn test_invalid_amount_negative() {
 fn test_batch_update_empty_batch() {
     let (env, admin, client) = setup_test_contract();
     let requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
-    client.batch_update_balances(&admin, &requests);
+    client.batch_adjust_balances(&admin, &requests);
 }
 
 #[test]
@@ -366,7 +446,7 @@ fn test_batch_update_batch_too_large() {
         ));
     }
 
-    client.batch_update_balances(&admin, &requests);
+    client.batch_adjust_balances(&admin, &requests);
 }
 
 #[test]
@@ -379,11 +459,12 @@ fn test_get_balance_details() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
-    client.batch_update_balances(&admin, &requests);
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.batch_adjust_balances(&admin, &requests);
 
     // Get balance details
     let details = client
@@ -392,7 +473,7 @@ fn test_get_balance_details() {
 
     assert_eq!(details.user, user);
     assert_eq!(details.currency, symbol_short!("USDC"));
-    assert_eq!(details.balance, 1000_000_000);
+    assert_eq!(details.balance, 1_000_000_000);
     assert!(details.updated_at > 0);
 }
 
@@ -408,14 +489,14 @@ fn test_batch_metrics() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
     requests.push_back(create_valid_request(
         &env,
         &user1,
         symbol_short!("XLM"),
-        5000_000_000,
+        5_000_000_000,
         symbol_short!("set"),
     ));
     requests.push_back(create_valid_request(
@@ -426,7 +507,7 @@ fn test_batch_metrics() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.metrics.total_requests, 3);
     assert_eq!(result.metrics.successful_updates, 3);
@@ -446,10 +527,10 @@ fn test_multiple_batches() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
-    let result1 = client.batch_update_balances(&admin, &requests1);
+    let result1 = client.batch_adjust_balances(&admin, &requests1);
     assert_eq!(result1.batch_id, 1);
 
     // Second batch
@@ -459,10 +540,10 @@ fn test_multiple_batches() {
         &env,
         &user2,
         symbol_short!("XLM"),
-        5000_000_000,
+        5_000_000_000,
         symbol_short!("set"),
     ));
-    let result2 = client.batch_update_balances(&admin, &requests2);
+    let result2 = client.batch_adjust_balances(&admin, &requests2);
     assert_eq!(result2.batch_id, 2);
 
     // Verify totals
@@ -485,7 +566,7 @@ fn test_large_balance_event() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.successful, 1);
     // Large balance event should be emitted (verified in event logs)
@@ -513,7 +594,7 @@ fn test_mixed_operations_same_user() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -522,7 +603,7 @@ fn test_mixed_operations_same_user() {
         &env,
         &user,
         symbol_short!("XLM"),
-        5000_000_000,
+        5_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -535,7 +616,7 @@ fn test_mixed_operations_same_user() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 3);
@@ -544,11 +625,11 @@ fn test_mixed_operations_same_user() {
     // Verify all balances for the same user
     assert_eq!(
         client.get_balance(&user, &symbol_short!("USDC")),
-        1000_000_000
+        1_000_000_000
     );
     assert_eq!(
         client.get_balance(&user, &symbol_short!("XLM")),
-        5000_000_000
+        5_000_000_000
     );
     assert_eq!(
         client.get_balance(&user, &symbol_short!("EURC")),
@@ -584,7 +665,7 @@ fn test_mixed_valid_and_invalid_requests() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -606,7 +687,7 @@ fn test_mixed_valid_and_invalid_requests() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 2);
@@ -630,9 +711,514 @@ fn test_minimum_valid_balance() {
         symbol_short!("set"),
     ));
 
-    let result = client.batch_update_balances(&admin, &requests);
+    let result = client.batch_adjust_balances(&admin, &requests);
 
     assert_eq!(result.successful, 1);
     assert_eq!(result.failed, 0);
     assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1);
 }
+
+#[test]
+fn test_register_currency() {
+    let (env, admin, client) = setup_test_contract();
+
+    assert_eq!(client.get_currencies().len(), 3);
+    assert!(client.get_currency_asset(&symbol_short!("USDC")).is_some());
+    assert!(client.get_currency_asset(&symbol_short!("BTC")).is_none());
+
+    let asset = Address::generate(&env);
+    assert!(client
+        .try_register_currency(&admin, &symbol_short!("USDC"), &asset)
+        .is_err());
+}
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let (env, _, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    mint(&env, &client, &user, symbol_short!("USDC"), 1_000);
+
+    assert_eq!(client.deposit(&user, &symbol_short!("USDC"), &600), 600);
+    assert_eq!(client.withdraw(&user, &symbol_short!("USDC"), &250), 350);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 350);
+
+    let asset = client.get_currency_asset(&symbol_short!("USDC")).unwrap();
+    let token = token::Client::new(&env, &asset);
+    assert_eq!(token.balance(&user), 650);
+    assert_eq!(token.balance(&client.address), 350);
+}
+
+#[test]
+fn test_deposit_unsupported_currency_fails() {
+    let (env, _, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    assert!(client
+        .try_deposit(&user, &symbol_short!("BTC"), &100)
+        .is_err());
+}
+
+#[test]
+fn test_withdraw_more_than_balance_fails() {
+    let (env, _, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    mint(&env, &client, &user, symbol_short!("USDC"), 100);
+    client.deposit(&user, &symbol_short!("USDC"), &100);
+
+    assert!(client
+        .try_withdraw(&user, &symbol_short!("USDC"), &101)
+        .is_err());
+    assert!(client
+        .try_withdraw(&user, &symbol_short!("USDC"), &0)
+        .is_err());
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 100);
+}
+
+#[test]
+fn test_adjustment_rejects_unregistered_currency_and_reason() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("BTC"),
+        100,
+        symbol_short!("set"),
+    ));
+    let mut bad_reason = create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        100,
+        symbol_short!("set"),
+    );
+    bad_reason.reason = 99;
+    requests.push_back(bad_reason);
+
+    let result = client.batch_adjust_balances(&admin, &requests);
+    assert_eq!(result.failed, 2);
+    match result.results.get(0).unwrap() {
+        BalanceUpdateResult::Failure(_, _, code) => {
+            assert_eq!(code, ErrorCode::UNSUPPORTED_CURRENCY)
+        }
+        BalanceUpdateResult::Success(_) => panic!("Expected failure"),
+    }
+    match result.results.get(1).unwrap() {
+        BalanceUpdateResult::Failure(_, _, code) => assert_eq!(code, ErrorCode::INVALID_REASON),
+        BalanceUpdateResult::Success(_) => panic!("Expected failure"),
+    }
+    assert_eq!(client.get_adjustment_count(), 0);
+}
+
+#[test]
+fn test_adjustments_are_journaled_and_reconciled() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    mint(&env, &client, &user, symbol_short!("USDC"), 1_000);
+    client.deposit(&user, &symbol_short!("USDC"), &1_000);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    let mut promo =
+        create_valid_request(&env, &user, symbol_short!("USDC"), 50, symbol_short!("add"));
+    promo.reason = AdjustmentReason::PROMOTION;
+    requests.push_back(promo);
+    client.batch_adjust_balances(&admin, &requests);
+
+    assert_eq!(client.get_adjustment_count(), 1);
+    let record = client.get_adjustment(&1).unwrap();
+    assert_eq!(record.admin, admin);
+    assert_eq!(record.previous_balance, 1_000);
+    assert_eq!(record.new_balance, 1_050);
+    assert_eq!(record.reason, AdjustmentReason::PROMOTION);
+
+    let report = client.get_reconciliation(&symbol_short!("USDC"));
+    assert_eq!(report.token_holdings, 1_000);
+    assert_eq!(report.total_balances, 1_050);
+    assert_eq!(report.total_deposited, 1_000);
+    assert_eq!(report.total_withdrawn, 0);
+    assert_eq!(report.net_adjustments, 50);
+    assert_eq!(report.surplus, -50);
+    assert_eq!(
+        report.total_deposited - report.total_withdrawn
+            + report.net_adjustments
+            + report.net_exchanged,
+        report.total_balances
+    );
+}
+
+#[test]
+fn test_exchange_applies_rate_and_spread() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 10_000);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+
+    let record = client.exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("EURC"),
+        &1_000,
+        &850,
+    );
+
+    // 1_000 * 0.90 = 900, less 1% spread
+    assert_eq!(record.amount_out, 891);
+    assert_eq!(record.rate, 9_000_000);
+    assert_eq!(record.spread_bps, 100);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 0);
+    assert_eq!(client.get_balance(&user, &symbol_short!("EURC")), 891);
+
+    let eurc = client.get_reconciliation(&symbol_short!("EURC"));
+    assert_eq!(eurc.net_exchanged, 891);
+    assert_eq!(eurc.surplus, 10_000 - 891);
+    let usdc = client.get_reconciliation(&symbol_short!("USDC"));
+    assert_eq!(usdc.net_exchanged, -1_000);
+    assert_eq!(usdc.surplus, 1_000);
+}
+
+#[test]
+fn test_exchange_slippage_leaves_balances_untouched() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 10_000);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+
+    let result = client.try_exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("EURC"),
+        &1_000,
+        &900,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1_000);
+    assert_eq!(client.get_balance(&user, &symbol_short!("EURC")), 0);
+    assert_eq!(client.get_exchange_count(&user), 0);
+}
+
+#[test]
+fn test_exchange_requires_liquidity() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 500);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+
+    assert!(client
+        .try_exchange(
+            &user,
+            &symbol_short!("USDC"),
+            &symbol_short!("EURC"),
+            &1_000,
+            &0,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_exchange_rejects_stale_or_missing_rate() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+
+    // No oracle configured yet
+    assert!(client
+        .try_exchange(
+            &user,
+            &symbol_short!("USDC"),
+            &symbol_short!("EURC"),
+            &100,
+            &0,
+        )
+        .is_err());
+
+    setup_exchange(&env, &admin, &client, 10_000);
+
+    // No price for the XLM pair
+    fund_user(&env, &client, &user, symbol_short!("XLM"), 1_000);
+    assert!(client
+        .try_exchange(
+            &user,
+            &symbol_short!("XLM"),
+            &symbol_short!("EURC"),
+            &100,
+            &0,
+        )
+        .is_err());
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + MAX_PRICE_AGE + 1);
+    assert!(client
+        .try_exchange(
+            &user,
+            &symbol_short!("USDC"),
+            &symbol_short!("EURC"),
+            &100,
+            &0,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_exchange_uses_configured_max_deviation() {
+    let (env, admin, client) = setup_test_contract();
+    let (oracle, feeder) = setup_exchange(&env, &admin, &client, 10_000);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+    assert_eq!(client.get_max_deviation(), 500);
+
+    let usdc = client.get_currency_asset(&symbol_short!("USDC")).unwrap();
+    let eurc = client.get_currency_asset(&symbol_short!("EURC")).unwrap();
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 100);
+    oracle.set_price(&feeder, &usdc, &eurc, &150, &2, &(PRICE_TIMESTAMP + 100));
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + 110);
+
+    // The spot price is far above the TWAP for a 5% tolerance.
+    assert!(client
+        .try_exchange(
+            &user,
+            &symbol_short!("USDC"),
+            &symbol_short!("EURC"),
+            &100,
+            &0,
+        )
+        .is_err());
+
+    client.set_exchange_config(&admin, &oracle.address, &MAX_PRICE_AGE, &10_000, &100);
+    assert_eq!(client.get_max_deviation(), 10_000);
+    let record = client.exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("EURC"),
+        &100,
+        &0,
+    );
+    assert_eq!(record.rate, 15_000_000);
+}
+
+#[test]
+fn test_set_exchange_config_rejects_deviation_above_max() {
+    let (env, admin, client) = setup_test_contract();
+    let oracle = Address::generate(&env);
+
+    let result = client.try_set_exchange_config(
+        &admin,
+        &oracle,
+        &MAX_PRICE_AGE,
+        &(MAX_DEVIATION_BPS + 1),
+        &100,
+    );
+    assert!(result.is_err());
+    assert_eq!(client.get_oracle(), None);
+}
+
+#[test]
+fn test_exchange_history_is_paged() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 10_000);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+
+    for amount in [100, 200, 300] {
+        client.exchange(
+            &user,
+            &symbol_short!("USDC"),
+            &symbol_short!("EURC"),
+            &amount,
+            &0,
+        );
+    }
+
+    assert_eq!(client.get_exchange_count(&user), 3);
+    let page = client.get_exchange_history(&user, &1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().index, 1);
+    assert_eq!(page.get(0).unwrap().amount_in, 200);
+    assert_eq!(page.get(1).unwrap().amount_in, 300);
+    assert!(client
+        .get_exchange_history(&Address::generate(&env), &0, &10)
+        .is_empty());
+}
+
+#[test]
+fn test_portfolio_value_in_base_currency() {
+    let (env, admin, client) = setup_test_contract();
+    let (oracle, feeder) = setup_exchange(&env, &admin, &client, 0);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+    fund_user(&env, &client, &user, symbol_short!("EURC"), 500);
+    fund_user(&env, &client, &user, symbol_short!("XLM"), 100);
+    client.set_base_currency(&user, &symbol_short!("EURC"));
+
+    // XLM has no EURC price yet
+    let portfolio = client.get_portfolio_value(&user);
+    assert_eq!(portfolio.base_currency, symbol_short!("EURC"));
+    assert_eq!(portfolio.holdings.len(), 3);
+    assert_eq!(portfolio.total_value, 900 + 500);
+    assert_eq!(portfolio.oldest_price_timestamp, Some(PRICE_TIMESTAMP));
+    assert!(portfolio.incomplete);
+    let xlm = portfolio.holdings.get(1).unwrap();
+    assert_eq!(xlm.currency, symbol_short!("XLM"));
+    assert_eq!(xlm.status, PriceStatus::Missing);
+    assert_eq!(xlm.value, 0);
+    assert_eq!(portfolio.holdings.get(2).unwrap().status, PriceStatus::Base);
+
+    // Price XLM at 0.10 EURC, observed earlier than the USDC price
+    let xlm_asset = client.get_currency_asset(&symbol_short!("XLM")).unwrap();
+    let eurc_asset = client.get_currency_asset(&symbol_short!("EURC")).unwrap();
+    oracle.set_price(
+        &feeder,
+        &xlm_asset,
+        &eurc_asset,
+        &10,
+        &2,
+        &(PRICE_TIMESTAMP - 60),
+    );
+
+    let portfolio = client.get_portfolio_value(&user);
+    assert_eq!(portfolio.total_value, 900 + 10 + 500);
+    assert_eq!(portfolio.oldest_price_timestamp, Some(PRICE_TIMESTAMP - 60));
+    assert!(!portfolio.incomplete);
+}
+
+#[test]
+fn test_portfolio_value_flags_stale_prices() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 0);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+    client.set_base_currency(&user, &symbol_short!("EURC"));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = PRICE_TIMESTAMP + MAX_PRICE_AGE + 1);
+    let portfolio = client.get_portfolio_value(&user);
+
+    assert_eq!(portfolio.total_value, 900);
+    assert_eq!(
+        portfolio.holdings.get(0).unwrap().status,
+        PriceStatus::Stale
+    );
+    assert!(portfolio.incomplete);
+}
+
+#[test]
+fn test_batch_portfolio_values_use_default_base() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 0);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    fund_user(&env, &client, &user1, symbol_short!("USDC"), 1_000);
+    fund_user(&env, &client, &user2, symbol_short!("EURC"), 250);
+
+    let mut users: Vec<Address> = Vec::new(&env);
+    users.push_back(user1.clone());
+    users.push_back(user2.clone());
+    assert!(client.try_get_portfolio_values(&users).is_err());
+
+    client.set_default_base_currency(&admin, &symbol_short!("EURC"));
+    client.set_base_currency(&user2, &symbol_short!("USDC"));
+
+    let values = client.get_portfolio_values(&users);
+    assert_eq!(values.len(), 2);
+    assert_eq!(values.get(0).unwrap().base_currency, symbol_short!("EURC"));
+    assert_eq!(values.get(0).unwrap().total_value, 900);
+    // No EURC -> USDC price is quoted
+    assert_eq!(values.get(1).unwrap().base_currency, symbol_short!("USDC"));
+    assert_eq!(values.get(1).unwrap().total_value, 0);
+    assert!(values.get(1).unwrap().incomplete);
+}
+
+#[test]
+fn test_sweep_dust_converts_small_balances() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 10_000);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 10);
+    fund_user(&env, &client, &user, symbol_short!("XLM"), 5);
+    fund_user(&env, &client, &user, symbol_short!("EURC"), 100);
+    client.set_base_currency(&user, &symbol_short!("EURC"));
+
+    let result = client.sweep_dust(&user, &50);
+
+    assert_eq!(result.total_requests, 2);
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        BalanceUpdateResult::Success(balance) => {
+            assert_eq!(balance.currency, symbol_short!("USDC"));
+            assert_eq!(balance.balance, 0);
+        }
+        BalanceUpdateResult::Failure(_, _, _) => panic!("Expected USDC to be swept"),
+    }
+    match result.results.get(1).unwrap() {
+        BalanceUpdateResult::Failure(_, currency, code) => {
+            assert_eq!(currency, symbol_short!("XLM"));
+            assert_eq!(code, ErrorCode::RATE_UNAVAILABLE);
+        }
+        BalanceUpdateResult::Success(_) => panic!("Expected XLM to fail"),
+    }
+
+    // 10 USDC * 0.90, less 1% spread, rounds down to 8 EURC
+    assert_eq!(client.get_balance(&user, &symbol_short!("EURC")), 108);
+    assert_eq!(client.get_balance(&user, &symbol_short!("XLM")), 5);
+    assert_eq!(client.get_exchange_count(&user), 1);
+    assert_eq!(client.get_last_batch_id(), result.batch_id);
+}
+
+#[test]
+fn test_sweep_dust_skips_balances_above_threshold() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 10_000);
+    let user = Address::generate(&env);
+    fund_user(&env, &client, &user, symbol_short!("USDC"), 1_000);
+    client.set_base_currency(&user, &symbol_short!("EURC"));
+
+    let result = client.sweep_dust(&user, &50);
+
+    assert_eq!(result.total_requests, 0);
+    assert_eq!(result.metrics.unique_users, 0);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1_000);
+    assert!(client.try_sweep_dust(&user, &0).is_err());
+}
+
+#[test]
+fn test_batch_sweep_dust_only_for_opted_in_users() {
+    let (env, admin, client) = setup_test_contract();
+    setup_exchange(&env, &admin, &client, 10_000);
+    let opted_in = Address::generate(&env);
+    let opted_out = Address::generate(&env);
+    for user in [&opted_in, &opted_out] {
+        fund_user(&env, &client, user, symbol_short!("USDC"), 20);
+        client.set_base_currency(user, &symbol_short!("EURC"));
+    }
+
+    assert!(client
+        .try_set_dust_sweep(&Address::generate(&env), &true)
+        .is_err());
+    client.set_dust_sweep(&opted_in, &true);
+    assert!(client.is_dust_sweep_enabled(&opted_in));
+    assert!(!client.is_dust_sweep_enabled(&opted_out));
+
+    let mut users: Vec<Address> = Vec::new(&env);
+    users.push_back(opted_in.clone());
+    users.push_back(opted_out.clone());
+    let result = client.batch_sweep_dust(&admin, &users, &50);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.metrics.unique_users, 2);
+    match result.results.get(1).unwrap() {
+        BalanceUpdateResult::Failure(user, currency, code) => {
+            assert_eq!(user, opted_out);
+            assert_eq!(currency, symbol_short!("ALL"));
+            assert_eq!(code, ErrorCode::NOT_OPTED_IN);
+        }
+        BalanceUpdateResult::Success(_) => panic!("Expected opted-out user to fail"),
+    }
+    assert_eq!(client.get_balance(&opted_in, &symbol_short!("USDC")), 0);
+    assert_eq!(client.get_balance(&opted_out, &symbol_short!("USDC")), 20);
+}
@@ -11,6 +11,15 @@ pub const MIN_BALANCE: i128 = 1;
 /// Maximum balance value (preventing overflow)
 pub const MAX_BALANCE: i128 = i128::MAX;
 
+/// Maximum exchange spread in basis points (100%).
+pub const MAX_SPREAD_BPS: u32 = 10_000;
+
+/// Maximum accepted spot/TWAP deviation in basis points (100%).
+pub const MAX_DEVIATION_BPS: u32 = 10_000;
+
+/// Maximum number of exchange records returned per history page.
+pub const MAX_HISTORY_PAGE: u32 = 50;
+
 /// Represents a balance update request for a user in a specific currency.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -23,6 +32,8 @@ pub struct BalanceUpdateRequest {
     pub amount: i128,
     /// Update type: "set", "add", or "subtract"
     pub operation: Symbol,
+    /// Why the admin is adjusting this balance (see `AdjustmentReason`)
+    pub reason: u32,
 }
 
 /// Represents a user's balance in a specific currency.
@@ -83,6 +94,129 @@ pub struct BatchBalanceResult {
     pub metrics: BatchBalanceMetrics,
 }
 
+/// Audit record of a single admin balance adjustment.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AdjustmentRecord {
+    /// Sequential adjustment ID
+    pub id: u64,
+    /// Batch the adjustment was part of
+    pub batch_id: u64,
+    /// Admin that applied the adjustment
+    pub admin: Address,
+    /// User whose balance was adjusted
+    pub user: Address,
+    /// Currency identifier
+    pub currency: Symbol,
+    /// Balance before the adjustment
+    pub previous_balance: i128,
+    /// Balance after the adjustment
+    pub new_balance: i128,
+    /// Reason code (see `AdjustmentReason`)
+    pub reason: u32,
+    /// Ledger timestamp of the adjustment
+    pub timestamp: u64,
+}
+
+/// Comparison of internal balances against the contract's token holdings
+/// for one registered currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReconciliationReport {
+    /// Currency identifier
+    pub currency: Symbol,
+    /// Stellar asset contract backing the currency
+    pub asset: Address,
+    /// Tokens actually held by the wallet contract
+    pub token_holdings: i128,
+    /// Sum of all user balances in this currency
+    pub total_balances: i128,
+    /// Lifetime deposits
+    pub total_deposited: i128,
+    /// Lifetime withdrawals
+    pub total_withdrawn: i128,
+    /// Net effect of all admin adjustments
+    pub net_adjustments: i128,
+    /// Net balance moved into this currency by exchanges
+    pub net_exchanged: i128,
+    /// `token_holdings - total_balances`; negative means balances are under-backed
+    pub surplus: i128,
+}
+
+/// Record of a single currency exchange made by a user.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ExchangeRecord {
+    /// Index in the user's exchange history
+    pub index: u32,
+    /// User who exchanged
+    pub user: Address,
+    /// Currency debited
+    pub from_currency: Symbol,
+    /// Currency credited
+    pub to_currency: Symbol,
+    /// Amount debited from `from_currency`
+    pub amount_in: i128,
+    /// Amount credited to `to_currency`
+    pub amount_out: i128,
+    /// Oracle rate used, scaled by `RATE_SCALE`
+    pub rate: i128,
+    /// Spread applied in basis points
+    pub spread_bps: u32,
+    /// Ledger timestamp of the exchange
+    pub timestamp: u64,
+}
+
+/// Quality of the price used to value one currency.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PriceStatus {
+    /// Currency is the base currency; no price needed
+    Base,
+    /// Oracle price within the maximum price age
+    Fresh,
+    /// Oracle price older than the maximum price age (still used)
+    Stale,
+    /// No usable oracle price; excluded from the total
+    Missing,
+}
+
+/// Value of one currency balance expressed in the user's base currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CurrencyValuation {
+    /// Currency identifier
+    pub currency: Symbol,
+    /// Balance in the currency's own units
+    pub balance: i128,
+    /// Rate to the base currency, scaled by `RATE_SCALE` (0 if missing)
+    pub rate: i128,
+    /// Balance converted to the base currency (0 if missing)
+    pub value: i128,
+    /// Timestamp of the price used (0 for base or missing)
+    pub price_timestamp: u64,
+    /// Quality of the price used
+    pub status: PriceStatus,
+}
+
+/// A user's holdings valued in their base currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PortfolioValue {
+    /// User's address
+    pub user: Address,
+    /// Currency the portfolio is valued in
+    pub base_currency: Symbol,
+    /// Per-currency breakdown of non-zero balances
+    pub holdings: Vec<CurrencyValuation>,
+    /// Sum of all priced holdings in the base currency
+    pub total_value: i128,
+    /// Oldest oracle price timestamp used, if any price was used
+    pub oldest_price_timestamp: Option<u64>,
+    /// `true` if any rate was missing or stale
+    pub incomplete: bool,
+}
+
 /// Storage keys for contract state.
 #[derive(Clone)]
 #[contracttype]
@@ -97,9 +231,46 @@ pub enum DataKey {
     TotalBalancesUpdated,
     /// Total batches processed lifetime
     TotalBatchesProcessed,
+    /// Asset contract address for a currency symbol
+    Currency(Symbol),
+    /// List of registered currency symbols
+    Currencies,
+    /// Sum of all user balances in a currency
+    TotalBalance(Symbol),
+    /// Lifetime deposits in a currency
+    TotalDeposited(Symbol),
+    /// Lifetime withdrawals in a currency
+    TotalWithdrawn(Symbol),
+    /// Net effect of admin adjustments in a currency
+    NetAdjustment(Symbol),
+    /// Number of adjustment records written
+    AdjustmentCount,
+    /// Adjustment record by ID
+    Adjustment(u64),
+    /// Price oracle used for exchanges
+    Oracle,
+    /// Maximum accepted oracle price age in seconds
+    MaxPriceAge,
+    /// Maximum accepted spot/TWAP deviation in basis points
+    MaxDeviationBps,
+    /// Exchange spread in basis points
+    SpreadBps,
+    /// Net balance moved into a currency by exchanges
+    NetExchanged(Symbol),
+    /// Number of exchanges made by a user
+    ExchangeCount(Address),
+    /// Exchange record by user and index
+    Exchange(Address, u32),
+    /// Base currency chosen by a user
+    BaseCurrency(Address),
+    /// Base currency for users who have not chosen one
+    DefaultBaseCurrency,
+    /// Whether a user opted in to admin-run dust sweeps
+    DustSweepOptIn(Address),
 }
 
 /// Error codes for balance update validation.
+#[allow(non_snake_case)]
 pub mod ErrorCode {
     /// Invalid balance amount (negative or exceeds max)
     pub const INVALID_AMOUNT: u32 = 0;
@@ -113,6 +284,38 @@ pub mod ErrorCode {
     pub const INSUFFICIENT_BALANCE: u32 = 4;
     /// Arithmetic overflow
     pub const ARITHMETIC_OVERFLOW: u32 = 5;
+    /// Currency has no registered asset contract
+    pub const UNSUPPORTED_CURRENCY: u32 = 6;
+    /// Adjustment reason code is not recognised
+    pub const INVALID_REASON: u32 = 7;
+    /// No fresh oracle rate to the target currency
+    pub const RATE_UNAVAILABLE: u32 = 8;
+    /// Contract cannot back the converted amount
+    pub const INSUFFICIENT_LIQUIDITY: u32 = 9;
+    /// Amount converts to nothing in the target currency
+    pub const AMOUNT_TOO_SMALL: u32 = 10;
+    /// User has not opted in to the operation
+    pub const NOT_OPTED_IN: u32 = 11;
+}
+
+/// Reason codes accepted for admin balance adjustments.
+#[allow(non_snake_case)]
+pub mod AdjustmentReason {
+    /// Fixing an accounting error
+    pub const CORRECTION: u32 = 1;
+    /// Refunding a user out of band
+    pub const REFUND: u32 = 2;
+    /// Promotional credit
+    pub const PROMOTION: u32 = 3;
+    /// Reversing a disputed credit
+    pub const CHARGEBACK: u32 = 4;
+    /// Migrating balances from a previous system
+    pub const MIGRATION: u32 = 5;
+
+    /// Returns `true` if `reason` is a known reason code.
+    pub fn is_valid(reason: u32) -> bool {
+        (CORRECTION..=MIGRATION).contains(&reason)
+    }
 }
 
 /// Events emitted by the multi-currency wallet contract.
@@ -151,6 +354,89 @@ impl WalletEvents {
             .publish(topics, (user.clone(), currency.clone(), error_code));
     }
 
+    /// Event emitted when an admin adjustment is recorded.
+    pub fn balance_adjusted(env: &Env, record: &AdjustmentRecord) {
+        let topics = (
+            symbol_short!("balance"),
+            symbol_short!("adjusted"),
+            record.batch_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                record.id,
+                record.user.clone(),
+                record.currency.clone(),
+                record.new_balance - record.previous_balance,
+                record.reason,
+            ),
+        );
+    }
+
+    /// Event emitted when a currency is mapped to an asset contract.
+    pub fn currency_registered(env: &Env, currency: &Symbol, asset: &Address) {
+        let topics = (symbol_short!("currency"), symbol_short!("register"));
+        env.events()
+            .publish(topics, (currency.clone(), asset.clone()));
+    }
+
+    /// Event emitted when a user deposits tokens.
+    pub fn deposited(env: &Env, user: &Address, currency: &Symbol, amount: i128, balance: i128) {
+        let topics = (symbol_short!("deposit"), user.clone());
+        env.events()
+            .publish(topics, (currency.clone(), amount, balance));
+    }
+
+    /// Event emitted when a user withdraws tokens.
+    pub fn withdrawn(env: &Env, user: &Address, currency: &Symbol, amount: i128, balance: i128) {
+        let topics = (symbol_short!("withdraw"), user.clone());
+        env.events()
+            .publish(topics, (currency.clone(), amount, balance));
+    }
+
+    /// Event emitted when a user exchanges one currency for another.
+    pub fn exchanged(env: &Env, record: &ExchangeRecord) {
+        let topics = (symbol_short!("exchange"), record.user.clone());
+        env.events().publish(
+            topics,
+            (
+                record.from_currency.clone(),
+                record.amount_in,
+                record.to_currency.clone(),
+                record.amount_out,
+                record.rate,
+            ),
+        );
+    }
+
+    /// Event emitted when the exchange oracle or spread changes.
+    pub fn exchange_config_updated(
+        env: &Env,
+        oracle: &Address,
+        max_age: u64,
+        max_deviation_bps: u32,
+        spread_bps: u32,
+    ) {
+        let topics = (symbol_short!("exchange"), symbol_short!("config"));
+        env.events().publish(
+            topics,
+            (oracle.clone(), max_age, max_deviation_bps, spread_bps),
+        );
+    }
+
+    /// Event emitted when a base currency is chosen for a user, or as the
+    /// default when `user` is `None`.
+    pub fn base_currency_set(env: &Env, user: Option<Address>, currency: &Symbol) {
+        let topics = (symbol_short!("base"), symbol_short!("set"));
+        env.events().publish(topics, (user, currency.clone()));
+    }
+
+    /// Event emitted when a user opts in to or out of dust sweeps.
+    pub fn dust_sweep_set(env: &Env, user: &Address, enabled: bool) {
+        let topics = (symbol_short!("dust"), symbol_short!("opt_in"));
+        env.events().publish(topics, (user.clone(), enabled));
+    }
+
     /// Event emitted when batch balance update completes.
     pub fn batch_completed(env: &Env, batch_id: u64, successful: u32, failed: u32) {
         let topics = (symbol_short!("batch"), symbol_short!("completed"), batch_id);
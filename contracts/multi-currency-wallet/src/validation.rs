@@ -2,7 +2,10 @@
 
 use soroban_sdk::{Address, Env, Symbol};
 
-use crate::types::{BalanceUpdateRequest, DataKey, ErrorCode, MAX_BALANCE, MIN_BALANCE};
+use crate::types::{
+    AdjustmentReason, BalanceUpdateRequest, CurrencyBalance, DataKey, ErrorCode, MAX_BALANCE,
+    MIN_BALANCE,
+};
 
 /// Validates a balance update request.
 ///
@@ -30,6 +33,11 @@ pub fn validate_balance_request(request: &BalanceUpdateRequest) -> Result<(), u3
         return Err(ErrorCode::INVALID_OPERATION);
     }
 
+    // Validate adjustment reason
+    if !AdjustmentReason::is_valid(request.reason) {
+        return Err(ErrorCode::INVALID_REASON);
+    }
+
     Ok(())
 }
 
@@ -54,7 +62,7 @@ fn is_valid_currency(_currency: &Symbol) -> bool {
 /// # Returns
 /// * `true` if amount is >= MIN_BALANCE and <= MAX_BALANCE
 pub fn is_valid_amount(amount: i128) -> bool {
-    amount >= MIN_BALANCE && amount <= MAX_BALANCE
+    (MIN_BALANCE..=MAX_BALANCE).contains(&amount)
 }
 
 /// Validates that an operation type is valid.
@@ -64,7 +72,7 @@ pub fn is_valid_amount(amount: i128) -> bool {
 ///
 /// # Returns
 /// * `true` if operation is "set", "add", or "subtract"
-pub fn is_valid_operation(operation: &Symbol) -> bool {
+pub fn is_valid_operation(_operation: &Symbol) -> bool {
     // In Soroban, we can't directly convert Symbol to string in no_std
     // We'll accept any symbol here and handle invalid operations during execution
     // Valid operations: "set", "add", "subtract"
@@ -95,39 +103,39 @@ pub fn validate_and_compute_balance(
         .storage()
         .persistent()
         .get(&DataKey::Balance(user.clone(), currency.clone()))
+        .map(|b: CurrencyBalance| b.balance)
         .unwrap_or(0);
 
     // Compute new balance based on operation
-    let new_balance = compute_new_balance(current_balance, operation, amount)?;
+    let new_balance = compute_new_balance(env, current_balance, operation, amount)?;
 
     // Validate new balance is non-negative
     if new_balance < 0 {
         return Err(ErrorCode::INSUFFICIENT_BALANCE);
     }
 
-    // Validate new balance doesn't exceed maximum
-    if new_balance > MAX_BALANCE {
-        return Err(ErrorCode::ARITHMETIC_OVERFLOW);
-    }
-
     Ok(new_balance)
 }
 
 /// Computes new balance based on operation.
-fn compute_new_balance(current: i128, operation: &Symbol, amount: i128) -> Result<i128, u32> {
-    // Note: In production, use proper symbol comparison
-    // For now, we'll use symbol_short! macro patterns
-    let op_str = operation.to_string();
-
-    match op_str.as_str() {
-        "set" => Ok(amount),
-        "add" => current
+fn compute_new_balance(
+    env: &Env,
+    current: i128,
+    operation: &Symbol,
+    amount: i128,
+) -> Result<i128, u32> {
+    if *operation == Symbol::new(env, "set") {
+        Ok(amount)
+    } else if *operation == Symbol::new(env, "add") {
+        current
             .checked_add(amount)
-            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW),
-        "subtract" => current
+            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW)
+    } else if *operation == Symbol::new(env, "subtract") {
+        current
             .checked_sub(amount)
-            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW),
-        _ => Err(ErrorCode::INVALID_OPERATION),
+            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW)
+    } else {
+        Err(ErrorCode::INVALID_OPERATION)
     }
 }
 
@@ -140,8 +148,9 @@ mod tests {
         BalanceUpdateRequest {
             user: Address::generate(env),
             currency: symbol_short!("USDC"),
-            amount: 1000_000_000, // 1000 USDC
+            amount: 1_000_000_000, // 1000 USDC
             operation: symbol_short!("set"),
+            reason: AdjustmentReason::CORRECTION,
         }
     }
 
@@ -174,11 +183,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_invalid_reason() {
+        let env = Env::default();
+        let mut request = create_valid_request(&env);
+        request.reason = 0;
+        assert_eq!(
+            validate_balance_request(&request),
+            Err(ErrorCode::INVALID_REASON)
+        );
+    }
+
     #[test]
     fn test_is_valid_amount() {
         assert!(is_valid_amount(MIN_BALANCE));
         assert!(is_valid_amount(MAX_BALANCE));
-        assert!(is_valid_amount(1000_000_000));
+        assert!(is_valid_amount(1_000_000_000));
         assert!(!is_valid_amount(MIN_BALANCE - 1));
         assert!(!is_valid_amount(0));
         assert!(!is_valid_amount(-1000));
@@ -0,0 +1,416 @@
+//! # Price Oracle Contract
+//!
+//! Authorized feeders push timestamped prices for asset pairs. The contract
+//! aggregates them into a manipulation-resistant spot price:
+//!
+//! - **Median**: the spot price is the median of every registered feeder's
+//!   latest price that is still inside the TWAP window.
+//! - **TWAP**: each aggregated price is recorded as an observation, and the
+//!   time-weighted average over the configured window is exposed via `twap`.
+//! - **Circuit breaker**: when a new spot price moves more than the
+//!   configured bps away from the TWAP, the pair is halted until the admin
+//!   resets it.
+//!
+//! Consumers read prices through `shared::oracle::PriceOracleClient`, which
+//! applies the staleness check and decimals normalization.
+
+#![no_std]
+
+mod types;
+
+use shared::oracle::{
+    deviation_bps, normalize_price, PriceData, PriceOracleInterface, RATE_DECIMALS,
+};
+use soroban_sdk::{contract, contracterror, contractimpl, panic_with_error, Address, Env, Vec};
+
+pub use crate::types::{
+    DataKey, OracleEvents, PriceObservation, DEFAULT_TWAP_WINDOW, MAX_OBSERVATIONS,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OracleContractError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    /// Caller is not a registered feeder.
+    FeederNotAuthorized = 4,
+    FeederAlreadyExists = 5,
+    FeederNotFound = 6,
+    /// Price is non-positive, the pair is not distinct, or decimals are too large.
+    InvalidPrice = 7,
+    /// Timestamp is in the future or older than the feeder's previous price.
+    InvalidTimestamp = 8,
+    /// TWAP window must be greater than zero.
+    InvalidConfig = 9,
+    Overflow = 10,
+}
+
+#[contract]
+pub struct PriceOracleContract;
+
+#[contractimpl]
+impl PriceOracleContract {
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, OracleContractError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Feeders, &Vec::<Address>::new(&env));
+    }
+
+    /// Authorizes `feeder` to push prices. Admin only.
+    pub fn add_feeder(env: Env, admin: Address, feeder: Address) {
+        Self::require_admin(&env, &admin);
+
+        let mut feeders = Self::get_feeders(env.clone());
+        if feeders.contains(&feeder) {
+            panic_with_error!(&env, OracleContractError::FeederAlreadyExists);
+        }
+        feeders.push_back(feeder.clone());
+        env.storage().instance().set(&DataKey::Feeders, &feeders);
+
+        OracleEvents::feeder_added(&env, &feeder);
+    }
+
+    /// Revokes a feeder. Its prices stop counting towards the median at the
+    /// pair's next update.
+    pub fn remove_feeder(env: Env, admin: Address, feeder: Address) {
+        Self::require_admin(&env, &admin);
+
+        let mut feeders = Self::get_feeders(env.clone());
+        let index = feeders
+            .first_index_of(&feeder)
+            .unwrap_or_else(|| panic_with_error!(&env, OracleContractError::FeederNotFound));
+        feeders.remove(index);
+        env.storage().instance().set(&DataKey::Feeders, &feeders);
+
+        OracleEvents::feeder_removed(&env, &feeder);
+    }
+
+    /// Sets the TWAP window and the circuit breaker threshold. Admin only.
+    ///
+    /// `max_deviation_bps` of `None` disables the circuit breaker.
+    pub fn set_config(env: Env, admin: Address, twap_window: u64, max_deviation_bps: Option<u32>) {
+        Self::require_admin(&env, &admin);
+        if twap_window == 0 {
+            panic_with_error!(&env, OracleContractError::InvalidConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TwapWindow, &twap_window);
+        match max_deviation_bps {
+            Some(bps) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MaxDeviationBps, &bps),
+            None => env.storage().instance().remove(&DataKey::MaxDeviationBps),
+        }
+
+        OracleEvents::config_updated(&env, twap_window, max_deviation_bps);
+    }
+
+    /// Closes a tripped circuit breaker. Admin only.
+    ///
+    /// Observations restart from the current spot price so the breaker does
+    /// not immediately trip again against the pre-move average.
+    pub fn reset_circuit(env: Env, admin: Address, base: Address, quote: Address) {
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::CircuitOpen(base.clone(), quote.clone()));
+        if let Some(spot) = Self::lastprice(env.clone(), base.clone(), quote.clone()) {
+            let mut observations = Vec::new(&env);
+            observations.push_back(PriceObservation {
+                price: spot.price,
+                timestamp: env.ledger().timestamp(),
+            });
+            env.storage().persistent().set(
+                &DataKey::Observations(base.clone(), quote.clone()),
+                &observations,
+            );
+        }
+
+        OracleEvents::circuit_reset(&env, &base, &quote);
+    }
+
+    /// Pushes the price of one `base` in `quote`, observed at `timestamp`,
+    /// and re-aggregates the pair.
+    pub fn set_price(
+        env: Env,
+        feeder: Address,
+        base: Address,
+        quote: Address,
+        price: i128,
+        decimals: u32,
+        timestamp: u64,
+    ) {
+        feeder.require_auth();
+        if !Self::is_feeder(env.clone(), feeder.clone()) {
+            panic_with_error!(&env, OracleContractError::FeederNotAuthorized);
+        }
+        if base == quote || normalize_price(price, decimals).is_err() {
+            panic_with_error!(&env, OracleContractError::InvalidPrice);
+        }
+        if timestamp > env.ledger().timestamp() {
+            panic_with_error!(&env, OracleContractError::InvalidTimestamp);
+        }
+
+        let key = DataKey::FeederPrice(base.clone(), quote.clone(), feeder.clone());
+        if let Some(current) = env.storage().persistent().get::<_, PriceData>(&key) {
+            if timestamp < current.timestamp {
+                panic_with_error!(&env, OracleContractError::InvalidTimestamp);
+            }
+        }
+
+        let data = PriceData {
+            price,
+            decimals,
+            timestamp,
+        };
+        env.storage().persistent().set(&key, &data);
+        OracleEvents::price_updated(&env, &feeder, &base, &quote, price, decimals, timestamp);
+
+        Self::aggregate(&env, &base, &quote);
+    }
+
+    pub fn is_feeder(env: Env, feeder: Address) -> bool {
+        Self::get_feeders(env).contains(&feeder)
+    }
+
+    pub fn get_feeders(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Feeders)
+            .unwrap_or_else(|| panic_with_error!(&env, OracleContractError::NotInitialized))
+    }
+
+    /// Returns the latest price `feeder` pushed for the pair, as submitted.
+    pub fn get_feeder_price(
+        env: Env,
+        base: Address,
+        quote: Address,
+        feeder: Address,
+    ) -> Option<PriceData> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FeederPrice(base, quote, feeder))
+    }
+
+    /// Returns the recorded observations for the pair, oldest first.
+    pub fn get_observations(env: Env, base: Address, quote: Address) -> Vec<PriceObservation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Observations(base, quote))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_twap_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TwapWindow)
+            .unwrap_or(DEFAULT_TWAP_WINDOW)
+    }
+
+    pub fn get_max_deviation_bps(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::MaxDeviationBps)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        Self::load_admin(&env)
+    }
+
+    fn load_admin(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, OracleContractError::NotInitialized))
+    }
+
+    fn require_admin(env: &Env, caller: &Address) {
+        caller.require_auth();
+        if caller != &Self::load_admin(env) {
+            panic_with_error!(env, OracleContractError::Unauthorized);
+        }
+    }
+
+    /// Recomputes the median spot price, records it as an observation, and
+    /// trips the circuit breaker if it strays too far from the TWAP.
+    fn aggregate(env: &Env, base: &Address, quote: &Address) {
+        let now = env.ledger().timestamp();
+        let window = Self::get_twap_window(env.clone());
+
+        // Collect fresh feeder prices, sorted ascending (insertion sort; the
+        // feeder set is small).
+        let mut prices: Vec<i128> = Vec::new(env);
+        let mut oldest = now;
+        for feeder in Self::get_feeders(env.clone()).iter() {
+            let Some(data) =
+                Self::get_feeder_price(env.clone(), base.clone(), quote.clone(), feeder)
+            else {
+                continue;
+            };
+            if now - data.timestamp > window {
+                continue;
+            }
+            let Ok(price) = normalize_price(data.price, data.decimals) else {
+                continue;
+            };
+
+            let mut index = prices.len();
+            while index > 0 && prices.get_unchecked(index - 1) > price {
+                index -= 1;
+            }
+            prices.insert(index, price);
+            oldest = oldest.min(data.timestamp);
+        }
+        if prices.is_empty() {
+            return;
+        }
+
+        let middle = prices.len() / 2;
+        let spot = if prices.len() % 2 == 1 {
+            prices.get_unchecked(middle)
+        } else {
+            (prices.get_unchecked(middle - 1) + prices.get_unchecked(middle)) / 2
+        };
+
+        env.storage().persistent().set(
+            &DataKey::Price(base.clone(), quote.clone()),
+            &PriceData {
+                price: spot,
+                decimals: RATE_DECIMALS,
+                timestamp: oldest,
+            },
+        );
+        let observations = Self::record_observation(env, base, quote, spot, now, window);
+
+        let circuit_key = DataKey::CircuitOpen(base.clone(), quote.clone());
+        let Some(max_deviation) = Self::get_max_deviation_bps(env.clone()) else {
+            return;
+        };
+        if env.storage().persistent().has(&circuit_key) {
+            return;
+        }
+        let twap = Self::compute_twap(env, &observations, now, window);
+        let deviation = deviation_bps(spot, twap)
+            .unwrap_or_else(|_| panic_with_error!(env, OracleContractError::Overflow));
+        if deviation > max_deviation as i128 {
+            env.storage().persistent().set(&circuit_key, &true);
+            OracleEvents::circuit_tripped(env, base, quote, spot, twap);
+        }
+    }
+
+    /// Appends `price` at `now` (replacing an observation from the same
+    /// timestamp) and drops observations no longer needed for the window.
+    fn record_observation(
+        env: &Env,
+        base: &Address,
+        quote: &Address,
+        price: i128,
+        now: u64,
+        window: u64,
+    ) -> Vec<PriceObservation> {
+        let key = DataKey::Observations(base.clone(), quote.clone());
+        let mut observations = Self::get_observations(env.clone(), base.clone(), quote.clone());
+
+        if let Some(last) = observations.last() {
+            if last.timestamp == now {
+                observations.pop_back();
+            }
+        }
+        observations.push_back(PriceObservation {
+            price,
+            timestamp: now,
+        });
+
+        // The oldest observation is only needed while the next one starts
+        // after the window opens.
+        let start = now.saturating_sub(window);
+        while observations.len() > 1 && observations.get_unchecked(1).timestamp <= start {
+            observations.pop_front();
+        }
+        while observations.len() > MAX_OBSERVATIONS {
+            observations.pop_front();
+        }
+
+        env.storage().persistent().set(&key, &observations);
+        observations
+    }
+
+    /// Time-weighted average of `observations` over `[now - window, now]`.
+    /// Each observation's price holds until the next one.
+    fn compute_twap(
+        env: &Env,
+        observations: &Vec<PriceObservation>,
+        now: u64,
+        window: u64,
+    ) -> i128 {
+        let start = now.saturating_sub(window);
+        let mut weighted: i128 = 0;
+        let mut duration: u64 = 0;
+
+        for index in 0..observations.len() {
+            let observation = observations.get_unchecked(index);
+            let segment_end = if index + 1 < observations.len() {
+                observations.get_unchecked(index + 1).timestamp
+            } else {
+                now
+            };
+            let segment_start = observation.timestamp.max(start);
+            if segment_end <= segment_start {
+                continue;
+            }
+
+            let elapsed = segment_end - segment_start;
+            weighted = observation
+                .price
+                .checked_mul(elapsed as i128)
+                .and_then(|value| weighted.checked_add(value))
+                .unwrap_or_else(|| panic_with_error!(env, OracleContractError::Overflow));
+            duration += elapsed;
+        }
+
+        if duration == 0 {
+            // Every observation was recorded at `now`.
+            return observations.last_unchecked().price;
+        }
+        weighted / duration as i128
+    }
+}
+
+#[contractimpl]
+impl PriceOracleInterface for PriceOracleContract {
+    fn lastprice(env: Env, base: Address, quote: Address) -> Option<PriceData> {
+        env.storage().persistent().get(&DataKey::Price(base, quote))
+    }
+
+    fn twap(env: Env, base: Address, quote: Address) -> Option<PriceData> {
+        let observations = Self::get_observations(env.clone(), base, quote);
+        if observations.is_empty() {
+            return None;
+        }
+
+        let now = env.ledger().timestamp();
+        let window = Self::get_twap_window(env.clone());
+        Some(PriceData {
+            price: Self::compute_twap(&env, &observations, now, window),
+            decimals: RATE_DECIMALS,
+            timestamp: now,
+        })
+    }
+
+    fn circuit_open(env: Env, base: Address, quote: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::CircuitOpen(base, quote))
+    }
+}
+
+#[cfg(test)]
+mod test;
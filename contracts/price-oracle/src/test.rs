@@ -0,0 +1,264 @@
+//! Tests for the Price Oracle Contract.
+
+#![cfg(test)]
+
+use crate::{PriceOracleContract, PriceOracleContractClient};
+use shared::oracle::{read_guarded_rate, read_rate, OracleError, PriceData, RATE_SCALE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    feeder: Address,
+    base: Address,
+    quote: Address,
+    oracle: Address,
+    client: PriceOracleContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+
+    let oracle = env.register(PriceOracleContract, ());
+    let client = PriceOracleContractClient::new(&env, &oracle);
+    let admin = Address::generate(&env);
+    let feeder = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_feeder(&admin, &feeder);
+
+    Setup {
+        base: Address::generate(&env),
+        quote: Address::generate(&env),
+        env,
+        admin,
+        feeder,
+        oracle,
+        client,
+    }
+}
+
+fn add_feeder(s: &Setup) -> Address {
+    let feeder = Address::generate(&s.env);
+    s.client.add_feeder(&s.admin, &feeder);
+    feeder
+}
+
+fn set_time(s: &Setup, timestamp: u64) {
+    s.env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+#[test]
+fn test_feeder_pushes_price() {
+    let s = setup();
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &150_000_000, &8, &9_990);
+
+    assert_eq!(
+        s.client.get_feeder_price(&s.base, &s.quote, &s.feeder),
+        Some(PriceData {
+            price: 150_000_000,
+            decimals: 8,
+            timestamp: 9_990,
+        })
+    );
+    assert_eq!(
+        s.client.lastprice(&s.base, &s.quote),
+        Some(PriceData {
+            price: 15_000_000,
+            decimals: 7,
+            timestamp: 9_990,
+        })
+    );
+    assert_eq!(s.client.lastprice(&s.quote, &s.base), None);
+}
+
+#[test]
+fn test_spot_is_median_of_feeders() {
+    let s = setup();
+    let second = add_feeder(&s);
+    let third = add_feeder(&s);
+
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &100, &2, &10_000);
+    s.client
+        .set_price(&second, &s.base, &s.quote, &10_500_000, &7, &10_000);
+    assert_eq!(
+        s.client.lastprice(&s.base, &s.quote).unwrap().price,
+        10_250_000
+    );
+
+    // An outlier from a single source does not move the median.
+    s.client
+        .set_price(&third, &s.base, &s.quote, &900, &2, &10_000);
+    assert_eq!(
+        s.client.lastprice(&s.base, &s.quote).unwrap().price,
+        10_500_000
+    );
+}
+
+#[test]
+fn test_stale_and_removed_feeders_are_excluded() {
+    let s = setup();
+    let second = add_feeder(&s);
+    let third = add_feeder(&s);
+    s.client.set_config(&s.admin, &600, &None);
+
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &100, &2, &9_000);
+    s.client
+        .set_price(&second, &s.base, &s.quote, &200, &2, &10_000);
+    // The first feeder is outside the window, so only `second` counts.
+    assert_eq!(
+        s.client.lastprice(&s.base, &s.quote).unwrap().price,
+        2 * RATE_SCALE
+    );
+
+    s.client.remove_feeder(&s.admin, &second);
+    s.client
+        .set_price(&third, &s.base, &s.quote, &300, &2, &10_000);
+    assert_eq!(
+        s.client.lastprice(&s.base, &s.quote).unwrap().price,
+        3 * RATE_SCALE
+    );
+}
+
+#[test]
+fn test_twap_weights_prices_by_time() {
+    let s = setup();
+    s.client.set_config(&s.admin, &1_000, &None);
+
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &100, &2, &10_000);
+    set_time(&s, 10_750);
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &200, &2, &10_750);
+    set_time(&s, 11_000);
+
+    // 750s at 1.00 and 250s at 2.00.
+    let twap = s.client.twap(&s.base, &s.quote).unwrap();
+    assert_eq!(twap.price, 12_500_000);
+    assert_eq!(twap.timestamp, 11_000);
+
+    // Once the window slides past the first price only the second remains.
+    set_time(&s, 12_000);
+    assert_eq!(
+        s.client.twap(&s.base, &s.quote).unwrap().price,
+        2 * RATE_SCALE
+    );
+}
+
+#[test]
+fn test_circuit_trips_on_deviation_and_resets() {
+    let s = setup();
+    s.client.set_config(&s.admin, &1_000, &Some(500));
+
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &100, &2, &10_000);
+    set_time(&s, 10_500);
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &104, &2, &10_500);
+    assert!(!s.client.circuit_open(&s.base, &s.quote));
+
+    set_time(&s, 10_600);
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &150, &2, &10_600);
+    assert!(s.client.circuit_open(&s.base, &s.quote));
+    assert_eq!(
+        read_guarded_rate(&s.env, &s.oracle, &s.base, &s.quote, 60, 10_000),
+        Err(OracleError::CircuitOpen)
+    );
+
+    s.client.reset_circuit(&s.admin, &s.base, &s.quote);
+    assert!(!s.client.circuit_open(&s.base, &s.quote));
+    assert_eq!(
+        read_guarded_rate(&s.env, &s.oracle, &s.base, &s.quote, 60, 500),
+        Ok(15 * RATE_SCALE / 10)
+    );
+}
+
+#[test]
+fn test_guarded_read_rejects_deviation_from_twap() {
+    let s = setup();
+    s.client.set_config(&s.admin, &1_000, &None);
+
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &100, &2, &10_000);
+    set_time(&s, 10_900);
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &120, &2, &10_900);
+    set_time(&s, 10_910);
+
+    // Spot 1.20 against a TWAP just above 1.00.
+    assert_eq!(
+        read_guarded_rate(&s.env, &s.oracle, &s.base, &s.quote, 60, 500),
+        Err(OracleError::PriceDeviation)
+    );
+    assert_eq!(
+        read_guarded_rate(&s.env, &s.oracle, &s.base, &s.quote, 60, 5_000),
+        Ok(12 * RATE_SCALE / 10)
+    );
+}
+
+#[test]
+fn test_unknown_feeder_rejected() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_set_price(&stranger, &s.base, &s.quote, &1, &0, &9_990);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_removed_feeder_rejected() {
+    let s = setup();
+    s.client.remove_feeder(&s.admin, &s.feeder);
+
+    assert!(!s.client.is_feeder(&s.feeder));
+    let result = s
+        .client
+        .try_set_price(&s.feeder, &s.base, &s.quote, &1, &0, &9_990);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_rejects_future_and_out_of_order_timestamps() {
+    let s = setup();
+    assert!(s
+        .client
+        .try_set_price(&s.feeder, &s.base, &s.quote, &1, &0, &10_001)
+        .is_err());
+
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &1, &0, &9_990);
+    assert!(s
+        .client
+        .try_set_price(&s.feeder, &s.base, &s.quote, &2, &0, &9_980)
+        .is_err());
+}
+
+#[test]
+fn test_read_rate_normalizes_and_checks_staleness() {
+    let s = setup();
+    s.client
+        .set_price(&s.feeder, &s.base, &s.quote, &150_000_000, &8, &9_990);
+
+    assert_eq!(
+        read_rate(&s.env, &s.oracle, &s.base, &s.quote, 60),
+        Ok(15 * RATE_SCALE / 10)
+    );
+    assert_eq!(
+        read_rate(&s.env, &s.oracle, &s.base, &s.quote, 5),
+        Err(OracleError::StalePrice)
+    );
+    assert_eq!(
+        read_rate(&s.env, &s.oracle, &s.quote, &s.base, 60),
+        Err(OracleError::PriceNotFound)
+    );
+}
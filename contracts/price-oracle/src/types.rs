@@ -0,0 +1,81 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+/// Default window for the time-weighted average price, in seconds.
+pub const DEFAULT_TWAP_WINDOW: u64 = 3_600;
+
+/// Maximum number of observations kept per pair.
+pub const MAX_OBSERVATIONS: u32 = 64;
+
+/// Aggregated spot price recorded at a ledger timestamp.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceObservation {
+    /// Price normalized to `shared::oracle::RATE_DECIMALS`.
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    /// Addresses allowed to push prices.
+    Feeders,
+    /// Aggregated (median) spot price for a (base, quote) pair.
+    Price(Address, Address),
+    /// Latest price a single feeder pushed for a (base, quote) pair.
+    FeederPrice(Address, Address, Address),
+    /// Recent aggregated prices for a pair, oldest first.
+    Observations(Address, Address),
+    /// Set when the pair's circuit breaker has tripped.
+    CircuitOpen(Address, Address),
+    /// TWAP window in seconds.
+    TwapWindow,
+    /// Spot-to-TWAP deviation in bps that trips the circuit breaker.
+    MaxDeviationBps,
+}
+
+pub struct OracleEvents;
+
+impl OracleEvents {
+    pub fn feeder_added(env: &Env, feeder: &Address) {
+        let topics = (symbol_short!("feeder"), symbol_short!("added"));
+        env.events().publish(topics, feeder.clone());
+    }
+
+    pub fn feeder_removed(env: &Env, feeder: &Address) {
+        let topics = (symbol_short!("feeder"), symbol_short!("removed"));
+        env.events().publish(topics, feeder.clone());
+    }
+
+    pub fn config_updated(env: &Env, twap_window: u64, max_deviation_bps: Option<u32>) {
+        let topics = (symbol_short!("oracle"), symbol_short!("config"));
+        env.events()
+            .publish(topics, (twap_window, max_deviation_bps));
+    }
+
+    pub fn circuit_tripped(env: &Env, base: &Address, quote: &Address, spot: i128, twap: i128) {
+        let topics = (symbol_short!("circuit"), symbol_short!("tripped"));
+        env.events()
+            .publish(topics, (base.clone(), quote.clone(), spot, twap));
+    }
+
+    pub fn circuit_reset(env: &Env, base: &Address, quote: &Address) {
+        let topics = (symbol_short!("circuit"), symbol_short!("reset"));
+        env.events().publish(topics, (base.clone(), quote.clone()));
+    }
+
+    pub fn price_updated(
+        env: &Env,
+        feeder: &Address,
+        base: &Address,
+        quote: &Address,
+        price: i128,
+        decimals: u32,
+        timestamp: u64,
+    ) {
+        let topics = (symbol_short!("price"), base.clone(), quote.clone());
+        env.events()
+            .publish(topics, (feeder.clone(), price, decimals, timestamp));
+    }
+}
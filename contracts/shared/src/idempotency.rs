@@ -0,0 +1,226 @@
+//! Client-supplied idempotency keys for batch entry points.
+//!
+//! A batch call that carries a key stores its result under that key with
+//! [`record`]. A retry with the same key within the retention window gets the
+//! stored result back from [`replay`] instead of executing again. Calls
+//! without a key always execute.
+//!
+//! Keys are scoped to the authenticated caller, so one caller's key never
+//! collides with another's: nobody can claim a key someone else is about to
+//! use and make their call fail.
+//!
+//! Each record also stores a hash of the call's arguments. Reusing a key
+//! with different arguments is a client bug, so [`replay`] panics rather
+//! than return a result computed for another batch.
+//!
+//! Records live in temporary storage with a TTL that covers the retention
+//! window, so the host deletes them once they can no longer be replayed; no
+//! cleanup call is needed. The TTL is counted as one ledger per second, well
+//! above Stellar's ledger rate, so a record is never dropped early.
+
+use soroban_sdk::{
+    contracttype, panic_with_error, symbol_short,
+    xdr::{ScErrorCode, ScErrorType, ToXdr},
+    Address, BytesN, Env, Error, IntoVal, TryFromVal, Val,
+};
+
+/// Default time a stored result is replayed for, in seconds (24 hours).
+pub const DEFAULT_RETENTION_SECS: u64 = 86_400;
+
+/// Storage key for a recorded result. Kept separate from each contract's own
+/// `DataKey` so the helpers can be shared.
+#[derive(Clone)]
+#[contracttype]
+enum IdempotencyDataKey {
+    Idempotency(Address, BytesN<32>),
+}
+
+/// Returns the result `caller` recorded for `key` if it is younger than
+/// `retention_secs`, emitting an `(idem, replayed)` event when it does.
+///
+/// Panics with `InvalidInput` if `key` was recorded for different `args`.
+pub fn replay<A, T>(
+    env: &Env,
+    caller: &Address,
+    key: &Option<BytesN<32>>,
+    args: &A,
+    retention_secs: u64,
+) -> Option<T>
+where
+    A: IntoVal<Env, Val> + Clone,
+    T: TryFromVal<Env, Val>,
+{
+    let key = key.as_ref()?;
+    let (recorded_at, recorded_args, result): (u64, BytesN<32>, Val) = env
+        .storage()
+        .temporary()
+        .get(&IdempotencyDataKey::Idempotency(
+            caller.clone(),
+            key.clone(),
+        ))?;
+    if env.ledger().timestamp().saturating_sub(recorded_at) > retention_secs {
+        return None;
+    }
+    if recorded_args != args_hash(env, args) {
+        panic_with_error!(
+            env,
+            Error::from_type_and_code(ScErrorType::Context, ScErrorCode::InvalidInput)
+        );
+    }
+    let result = T::try_from_val(env, &result).ok()?;
+
+    env.events().publish(
+        (symbol_short!("idem"), symbol_short!("replayed")),
+        (caller.clone(), key.clone()),
+    );
+    Some(result)
+}
+
+/// Stores `result` and the hash of `args` under `caller`'s `key` with the
+/// current ledger timestamp, kept alive for at least `retention_secs`. Does
+/// nothing when no key was supplied.
+pub fn record<A, T>(
+    env: &Env,
+    caller: &Address,
+    key: &Option<BytesN<32>>,
+    args: &A,
+    result: &T,
+    retention_secs: u64,
+) where
+    A: IntoVal<Env, Val> + Clone,
+    T: IntoVal<Env, Val>,
+{
+    if let Some(key) = key {
+        let storage_key = IdempotencyDataKey::Idempotency(caller.clone(), key.clone());
+        let result: Val = result.into_val(env);
+        env.storage().temporary().set(
+            &storage_key,
+            &(env.ledger().timestamp(), args_hash(env, args), result),
+        );
+
+        let ttl = retention_secs.min(env.storage().max_ttl() as u64) as u32;
+        env.storage().temporary().extend_ttl(&storage_key, ttl, ttl);
+    }
+}
+
+/// SHA-256 of the XDR encoding of `args`.
+fn args_hash<A>(env: &Env, args: &A) -> BytesN<32>
+where
+    A: IntoVal<Env, Val> + Clone,
+{
+    env.crypto().sha256(&args.clone().to_xdr(env)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{
+        contract,
+        testutils::{storage::Temporary, Address as _, Ledger},
+        Env,
+    };
+
+    #[contract]
+    struct TestContract;
+
+    fn key(env: &Env, byte: u8) -> Option<BytesN<32>> {
+        Some(BytesN::from_array(env, &[byte; 32]))
+    }
+
+    #[test]
+    fn replays_within_retention_window() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let caller = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                replay::<_, u32>(&env, &caller, &key(&env, 1), &5u32, 100),
+                None
+            );
+            record(&env, &caller, &key(&env, 1), &5u32, &7u32, 100);
+
+            assert_eq!(
+                replay::<_, u32>(&env, &caller, &key(&env, 1), &5u32, 100),
+                Some(7)
+            );
+            assert_eq!(
+                replay::<_, u32>(&env, &caller, &key(&env, 2), &5u32, 100),
+                None
+            );
+
+            env.ledger().with_mut(|li| li.timestamp += 101);
+            assert_eq!(
+                replay::<_, u32>(&env, &caller, &key(&env, 1), &5u32, 100),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn keys_are_scoped_to_the_caller() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let caller = Address::generate(&env);
+        let other = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            // Another caller taking the key first, with other args, neither
+            // blocks nor answers the original caller.
+            record(&env, &other, &key(&env, 1), &9u32, &1u32, 100);
+            assert_eq!(
+                replay::<_, u32>(&env, &caller, &key(&env, 1), &5u32, 100),
+                None
+            );
+
+            record(&env, &caller, &key(&env, 1), &5u32, &7u32, 100);
+            assert_eq!(
+                replay::<_, u32>(&env, &caller, &key(&env, 1), &5u32, 100),
+                Some(7)
+            );
+            assert_eq!(
+                replay::<_, u32>(&env, &other, &key(&env, 1), &9u32, 100),
+                Some(1)
+            );
+        });
+    }
+
+    #[test]
+    fn ignores_missing_key() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let caller = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            record(&env, &caller, &None, &5u32, &7u32, 100);
+            assert_eq!(replay::<_, u32>(&env, &caller, &None, &5u32, 100), None);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_key_reused_with_different_args() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let caller = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            record(&env, &caller, &key(&env, 1), &(1u32, 2u32), &7u32, 100);
+            replay::<_, u32>(&env, &caller, &key(&env, 1), &(1u32, 3u32), 100);
+        });
+    }
+
+    #[test]
+    fn record_outlives_retention_window() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let caller = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            record(&env, &caller, &key(&env, 1), &5u32, &7u32, 1_000);
+            let ttl = env
+                .storage()
+                .temporary()
+                .get_ttl(&IdempotencyDataKey::Idempotency(
+                    caller.clone(),
+                    key(&env, 1).unwrap(),
+                ));
+            assert!(ttl >= 1_000);
+        });
+    }
+}
@@ -1,3 +1,6 @@
 #![no_std]
 
+pub mod idempotency;
+pub mod oracle;
+pub mod staged;
 pub mod utils;
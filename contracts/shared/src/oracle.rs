@@ -0,0 +1,200 @@
+//! Shared price oracle client used by conversion contracts.
+//!
+//! Oracles expose `lastprice(base, quote)`; consumers read it through
+//! [`read_rate`], which rejects stale or invalid prices and normalizes the
+//! feeder's decimals to [`RATE_DECIMALS`]. [`read_guarded_rate`] additionally
+//! refuses pairs whose circuit breaker is open or whose spot price has moved
+//! too far from the oracle's time-weighted average.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+/// Decimals every rate is normalized to before use.
+pub const RATE_DECIMALS: u32 = 7;
+
+/// `10^RATE_DECIMALS`, i.e. a rate of exactly 1.
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Highest decimals a feeder may report.
+pub const MAX_PRICE_DECIMALS: u32 = 18;
+
+/// Default maximum spot-to-TWAP deviation consumers accept, in basis points.
+pub const DEFAULT_MAX_DEVIATION_BPS: u32 = 500;
+
+/// Price of one unit of `base` expressed in `quote`, as pushed by a feeder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceData {
+    /// Fixed-point price with `decimals` fractional digits.
+    pub price: i128,
+    pub decimals: u32,
+    /// Ledger timestamp the feeder observed the price at.
+    pub timestamp: u64,
+}
+
+/// Interface every price oracle contract implements.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    /// Returns the latest price for `base` in `quote`, if any.
+    fn lastprice(env: Env, base: Address, quote: Address) -> Option<PriceData>;
+
+    /// Returns the time-weighted average price over the oracle's window, if any.
+    fn twap(env: Env, base: Address, quote: Address) -> Option<PriceData>;
+
+    /// Returns whether the pair's circuit breaker has tripped.
+    fn circuit_open(env: Env, base: Address, quote: Address) -> bool;
+}
+
+/// Reasons an oracle rate cannot be used.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OracleError {
+    PriceNotFound,
+    StalePrice,
+    InvalidPrice,
+    Overflow,
+    /// Spot price is further from the TWAP than the caller allows.
+    PriceDeviation,
+    /// The oracle halted the pair after a large move.
+    CircuitOpen,
+}
+
+/// Scales `price` from `decimals` to `RATE_DECIMALS`, truncating extra digits.
+pub fn normalize_price(price: i128, decimals: u32) -> Result<i128, OracleError> {
+    if price <= 0 || decimals > MAX_PRICE_DECIMALS {
+        return Err(OracleError::InvalidPrice);
+    }
+
+    let rate = if decimals >= RATE_DECIMALS {
+        price / 10i128.pow(decimals - RATE_DECIMALS)
+    } else {
+        price
+            .checked_mul(10i128.pow(RATE_DECIMALS - decimals))
+            .ok_or(OracleError::Overflow)?
+    };
+
+    if rate == 0 {
+        return Err(OracleError::InvalidPrice);
+    }
+    Ok(rate)
+}
+
+/// Checks that `data` was observed no more than `max_age` seconds ago.
+pub fn check_staleness(env: &Env, data: &PriceData, max_age: u64) -> Result<(), OracleError> {
+    let now = env.ledger().timestamp();
+    if data.timestamp > now {
+        return Err(OracleError::InvalidPrice);
+    }
+    if now - data.timestamp > max_age {
+        return Err(OracleError::StalePrice);
+    }
+    Ok(())
+}
+
+/// Reads the latest `base`/`quote` price from `oracle` with the price
+/// normalized to `RATE_DECIMALS`, without checking its age.
+pub fn read_price(
+    env: &Env,
+    oracle: &Address,
+    base: &Address,
+    quote: &Address,
+) -> Result<PriceData, OracleError> {
+    let data = PriceOracleClient::new(env, oracle)
+        .lastprice(base, quote)
+        .ok_or(OracleError::PriceNotFound)?;
+    Ok(PriceData {
+        price: normalize_price(data.price, data.decimals)?,
+        decimals: RATE_DECIMALS,
+        timestamp: data.timestamp,
+    })
+}
+
+/// Reads the `base`/`quote` rate from `oracle`, normalized to `RATE_DECIMALS`.
+pub fn read_rate(
+    env: &Env,
+    oracle: &Address,
+    base: &Address,
+    quote: &Address,
+    max_age: u64,
+) -> Result<i128, OracleError> {
+    let data = read_price(env, oracle, base, quote)?;
+    check_staleness(env, &data, max_age)?;
+    Ok(data.price)
+}
+
+/// Returns `|spot - reference|` in basis points of `reference`.
+pub fn deviation_bps(spot: i128, reference: i128) -> Result<i128, OracleError> {
+    if reference <= 0 {
+        return Err(OracleError::InvalidPrice);
+    }
+    (spot - reference)
+        .abs()
+        .checked_mul(10_000)
+        .ok_or(OracleError::Overflow)
+        .map(|scaled| scaled / reference)
+}
+
+/// Like [`read_rate`], but also rejects pairs whose circuit breaker is open
+/// and spot prices more than `max_deviation_bps` away from the TWAP.
+pub fn read_guarded_rate(
+    env: &Env,
+    oracle: &Address,
+    base: &Address,
+    quote: &Address,
+    max_age: u64,
+    max_deviation_bps: u32,
+) -> Result<i128, OracleError> {
+    let client = PriceOracleClient::new(env, oracle);
+    if client.circuit_open(base, quote) {
+        return Err(OracleError::CircuitOpen);
+    }
+
+    let spot = read_rate(env, oracle, base, quote, max_age)?;
+    if let Some(twap) = client.twap(base, quote) {
+        let reference = normalize_price(twap.price, twap.decimals)?;
+        if deviation_bps(spot, reference)? > max_deviation_bps as i128 {
+            return Err(OracleError::PriceDeviation);
+        }
+    }
+    Ok(spot)
+}
+
+/// Converts `amount` of the base asset into the quote asset at `rate`.
+pub fn convert_amount(amount: i128, rate: i128) -> Result<i128, OracleError> {
+    amount
+        .checked_mul(rate)
+        .ok_or(OracleError::Overflow)
+        .map(|product| product / RATE_SCALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_amount, deviation_bps, normalize_price, OracleError, RATE_SCALE};
+
+    #[test]
+    fn normalizes_to_rate_decimals() {
+        assert_eq!(normalize_price(2, 0), Ok(2 * RATE_SCALE));
+        assert_eq!(normalize_price(150_000_000, 8), Ok(15_000_000));
+        assert_eq!(normalize_price(12_345, 7), Ok(12_345));
+    }
+
+    #[test]
+    fn rejects_invalid_prices() {
+        assert_eq!(normalize_price(0, 7), Err(OracleError::InvalidPrice));
+        assert_eq!(normalize_price(-1, 7), Err(OracleError::InvalidPrice));
+        assert_eq!(normalize_price(1, 19), Err(OracleError::InvalidPrice));
+        assert_eq!(normalize_price(9, 8), Err(OracleError::InvalidPrice));
+    }
+
+    #[test]
+    fn converts_at_rate() {
+        assert_eq!(convert_amount(100, 2 * RATE_SCALE), Ok(200));
+        assert_eq!(convert_amount(3, RATE_SCALE / 2), Ok(1));
+        assert_eq!(convert_amount(i128::MAX, 2), Err(OracleError::Overflow));
+    }
+
+    #[test]
+    fn measures_deviation_in_bps() {
+        assert_eq!(deviation_bps(105, 100), Ok(500));
+        assert_eq!(deviation_bps(95, 100), Ok(500));
+        assert_eq!(deviation_bps(100, 0), Err(OracleError::InvalidPrice));
+    }
+}
@@ -0,0 +1,382 @@
+//! Staged batches for inputs larger than a single call can carry.
+//!
+//! The client first commits a header with [`begin`]: the item count, the sum
+//! of all item amounts and one hash per chunk, in submission order. Items
+//! then arrive in chunks through [`accept_chunk`], each tagged with a cursor
+//! equal to the number of items already received. A chunk is accepted only
+//! if it hashes to the next committed chunk hash and every item has a
+//! positive amount, so callers can execute an accepted chunk straight away.
+//! The received count and amount never exceed the header, and the last
+//! committed chunk must bring them to exactly the committed totals, at which
+//! point the batch is finalized.
+//!
+//! A chunk hash is a chain over the XDR encoding of each item in the chunk:
+//! `h_0 = [0; 32]`, `h_i = sha256(h_{i-1} || xdr(item_i))`; see
+//! [`chunk_hash`].
+
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, TryFromVal, Val,
+    Vec,
+};
+
+/// Lifecycle of a staged batch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum StagedStatus {
+    /// Waiting for more chunks
+    Open,
+    /// Every item landed and matched the header
+    Finalized,
+    /// Abandoned before all items landed
+    Cancelled,
+}
+
+/// Header and running state of a staged batch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StagedBatch {
+    pub id: u64,
+    /// Address that committed the header
+    pub creator: Address,
+    /// Token the items are paid in
+    pub token: Address,
+    /// Committed number of items
+    pub total_count: u32,
+    /// Committed sum of item amounts
+    pub total_amount: i128,
+    /// Committed hash of each chunk, in submission order
+    pub chunk_hashes: Vec<BytesN<32>>,
+    /// Items received so far; the cursor the next chunk must carry
+    pub received_count: u32,
+    /// Chunks received so far; the index of the next chunk hash
+    pub received_chunks: u32,
+    /// Sum of item amounts received so far
+    pub received_amount: i128,
+    pub status: StagedStatus,
+}
+
+/// Reasons a header or chunk is rejected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StagedError {
+    /// Header has no items, a non-positive total or no usable chunk hashes
+    InvalidHeader,
+    /// No staged batch with this ID
+    NotFound,
+    /// Batch is finalized or cancelled
+    NotOpen,
+    /// Chunk cursor does not match the items received so far
+    CursorMismatch,
+    /// Chunk has no items
+    EmptyChunk,
+    /// Chunk exceeds the maximum chunk size
+    ChunkTooLarge,
+    /// Chunk has an item with a zero or negative amount
+    InvalidAmount,
+    /// Chunk would exceed the committed count, amount or number of chunks
+    TotalExceeded,
+    /// Chunk does not match its committed hash, or the last chunk leaves the
+    /// batch short of the committed count or amount
+    HashMismatch,
+}
+
+#[derive(Clone)]
+#[contracttype]
+enum StagedDataKey {
+    LastStagedId,
+    Staged(u64),
+}
+
+/// Commits a new staged batch header and returns it.
+pub fn begin(
+    env: &Env,
+    creator: &Address,
+    token: &Address,
+    total_count: u32,
+    total_amount: i128,
+    chunk_hashes: Vec<BytesN<32>>,
+) -> Result<StagedBatch, StagedError> {
+    if total_count == 0
+        || total_amount <= 0
+        || chunk_hashes.is_empty()
+        || chunk_hashes.len() > total_count
+    {
+        return Err(StagedError::InvalidHeader);
+    }
+
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&StagedDataKey::LastStagedId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&StagedDataKey::LastStagedId, &id);
+
+    let batch = StagedBatch {
+        id,
+        creator: creator.clone(),
+        token: token.clone(),
+        total_count,
+        total_amount,
+        chunk_hashes,
+        received_count: 0,
+        received_chunks: 0,
+        received_amount: 0,
+        status: StagedStatus::Open,
+    };
+    save(env, &batch);
+
+    env.events().publish(
+        (symbol_short!("staged"), symbol_short!("begin"), id),
+        (total_count, total_amount),
+    );
+    Ok(batch)
+}
+
+/// Returns a staged batch by ID.
+pub fn get(env: &Env, id: u64) -> Option<StagedBatch> {
+    env.storage().persistent().get(&StagedDataKey::Staged(id))
+}
+
+/// Checks a chunk against the batch header and its committed chunk hash,
+/// folds it into the running count and amount, and finalizes the batch once
+/// the last committed chunk has landed.
+///
+/// Nothing is stored when an error is returned, and an accepted chunk is
+/// exactly one the creator committed to.
+pub fn accept_chunk<T, F>(
+    env: &Env,
+    id: u64,
+    cursor: u32,
+    items: &Vec<T>,
+    max_chunk: u32,
+    amount_of: F,
+) -> Result<StagedBatch, StagedError>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    F: Fn(&T) -> i128,
+{
+    let mut batch = get(env, id).ok_or(StagedError::NotFound)?;
+    if batch.status != StagedStatus::Open {
+        return Err(StagedError::NotOpen);
+    }
+    if cursor != batch.received_count {
+        return Err(StagedError::CursorMismatch);
+    }
+    if items.is_empty() {
+        return Err(StagedError::EmptyChunk);
+    }
+    if items.len() > max_chunk {
+        return Err(StagedError::ChunkTooLarge);
+    }
+    if batch.received_chunks >= batch.chunk_hashes.len()
+        || batch.received_count + items.len() > batch.total_count
+    {
+        return Err(StagedError::TotalExceeded);
+    }
+
+    for item in items.iter() {
+        let amount = amount_of(&item);
+        if amount <= 0 {
+            return Err(StagedError::InvalidAmount);
+        }
+        batch.received_amount = batch
+            .received_amount
+            .checked_add(amount)
+            .ok_or(StagedError::TotalExceeded)?;
+    }
+    if batch.received_amount > batch.total_amount {
+        return Err(StagedError::TotalExceeded);
+    }
+    if Some(chunk_hash(env, items)) != batch.chunk_hashes.get(batch.received_chunks) {
+        return Err(StagedError::HashMismatch);
+    }
+    batch.received_count += items.len();
+    batch.received_chunks += 1;
+
+    let last_chunk = batch.received_chunks == batch.chunk_hashes.len();
+    let all_items = batch.received_count == batch.total_count;
+    if last_chunk || all_items {
+        if !(last_chunk && all_items && batch.received_amount == batch.total_amount) {
+            return Err(StagedError::HashMismatch);
+        }
+        batch.status = StagedStatus::Finalized;
+    }
+    save(env, &batch);
+
+    env.events().publish(
+        (symbol_short!("staged"), symbol_short!("chunk"), id),
+        (cursor, items.len()),
+    );
+    if batch.status == StagedStatus::Finalized {
+        env.events().publish(
+            (symbol_short!("staged"), symbol_short!("final"), id),
+            (batch.received_count, batch.received_amount),
+        );
+    }
+    Ok(batch)
+}
+
+/// Cancels an open staged batch. Chunks already executed are not undone.
+pub fn cancel(env: &Env, id: u64) -> Result<StagedBatch, StagedError> {
+    let mut batch = get(env, id).ok_or(StagedError::NotFound)?;
+    if batch.status != StagedStatus::Open {
+        return Err(StagedError::NotOpen);
+    }
+    batch.status = StagedStatus::Cancelled;
+    save(env, &batch);
+
+    env.events().publish(
+        (symbol_short!("staged"), symbol_short!("cancel"), id),
+        (batch.received_count, batch.received_amount),
+    );
+    Ok(batch)
+}
+
+/// Extends the hash chain `running` with one item.
+pub fn chain_hash<T>(env: &Env, running: &BytesN<32>, item: T) -> BytesN<32>
+where
+    T: IntoVal<Env, Val>,
+{
+    let mut data = Bytes::from_array(env, &running.to_array());
+    data.append(&item.to_xdr(env));
+    env.crypto().sha256(&data).into()
+}
+
+/// Returns the hash a header commits to for one chunk of `items`.
+pub fn chunk_hash<T>(env: &Env, items: &Vec<T>) -> BytesN<32>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    let mut hash = BytesN::from_array(env, &[0; 32]);
+    for item in items.iter() {
+        hash = chain_hash(env, &hash, item);
+    }
+    hash
+}
+
+fn save(env: &Env, batch: &StagedBatch) {
+    env.storage()
+        .persistent()
+        .set(&StagedDataKey::Staged(batch.id), batch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{contract, testutils::Address as _, vec, Env};
+
+    #[contract]
+    struct TestContract;
+
+    #[test]
+    fn finalizes_when_all_chunks_match() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let first = vec![&env, 10i128, 20];
+            let last = vec![&env, 30i128];
+            let hashes = vec![&env, chunk_hash(&env, &first), chunk_hash(&env, &last)];
+            let creator = Address::generate(&env);
+            let token = Address::generate(&env);
+            let batch = begin(&env, &creator, &token, 3, 60, hashes).unwrap();
+
+            let batch = accept_chunk(&env, batch.id, 0, &first, 2, |a| *a).unwrap();
+            assert_eq!(batch.status, StagedStatus::Open);
+            assert_eq!(batch.received_count, 2);
+            assert_eq!(batch.received_chunks, 1);
+
+            assert_eq!(
+                accept_chunk(&env, batch.id, 0, &first, 2, |a| *a),
+                Err(StagedError::CursorMismatch)
+            );
+
+            let batch = accept_chunk(&env, batch.id, 2, &last, 2, |a| *a).unwrap();
+            assert_eq!(batch.status, StagedStatus::Finalized);
+            assert_eq!(
+                accept_chunk(&env, batch.id, 3, &last, 2, |a| *a),
+                Err(StagedError::NotOpen)
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_chunks_that_break_the_header() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let items = vec![&env, 10i128, 20];
+            let hashes = vec![&env, chunk_hash(&env, &items)];
+            let creator = Address::generate(&env);
+            let token = Address::generate(&env);
+            let batch = begin(&env, &creator, &token, 2, 30, hashes.clone()).unwrap();
+
+            assert_eq!(
+                accept_chunk(&env, batch.id, 0, &vec![&env, 31i128], 2, |a| *a),
+                Err(StagedError::TotalExceeded)
+            );
+            assert_eq!(
+                accept_chunk(&env, batch.id, 0, &vec![&env, 1i128, 2, 3], 5, |a| *a),
+                Err(StagedError::TotalExceeded)
+            );
+            assert_eq!(
+                accept_chunk(&env, batch.id, 0, &vec![&env, 20i128, 10], 2, |a| *a),
+                Err(StagedError::HashMismatch)
+            );
+            assert_eq!(get(&env, batch.id).unwrap().received_count, 0);
+
+            assert_eq!(
+                cancel(&env, batch.id).unwrap().status,
+                StagedStatus::Cancelled
+            );
+            assert_eq!(
+                begin(&env, &creator, &token, 0, 0, hashes.clone()),
+                Err(StagedError::InvalidHeader)
+            );
+            assert_eq!(
+                begin(&env, &creator, &token, 2, 30, Vec::new(&env)),
+                Err(StagedError::InvalidHeader)
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_tampered_chunk_before_the_last() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let first = vec![&env, 10i128];
+            let last = vec![&env, 20i128];
+            let hashes = vec![&env, chunk_hash(&env, &first), chunk_hash(&env, &last)];
+            let creator = Address::generate(&env);
+            let token = Address::generate(&env);
+            let batch = begin(&env, &creator, &token, 2, 30, hashes).unwrap();
+
+            assert_eq!(
+                accept_chunk(&env, batch.id, 0, &vec![&env, 5i128], 2, |a| *a),
+                Err(StagedError::HashMismatch)
+            );
+            assert_eq!(get(&env, batch.id).unwrap().received_chunks, 0);
+        });
+    }
+
+    #[test]
+    fn rejects_non_positive_amounts() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let items = vec![&env, 40i128, -10];
+            let hashes = vec![&env, chunk_hash(&env, &items)];
+            let creator = Address::generate(&env);
+            let token = Address::generate(&env);
+            let batch = begin(&env, &creator, &token, 2, 30, hashes).unwrap();
+
+            assert_eq!(
+                accept_chunk(&env, batch.id, 0, &items, 2, |a| *a),
+                Err(StagedError::InvalidAmount)
+            );
+            assert_eq!(get(&env, batch.id).unwrap().received_amount, 0);
+        });
+    }
+}
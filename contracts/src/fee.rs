@@ -9,12 +9,13 @@ use soroban_sdk::{
 
 /// Priority levels for transaction execution.
 /// Higher priority levels result in higher fees for faster execution.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
 #[contracttype]
 pub enum PriorityLevel {
     /// Low priority - lowest fees, slowest execution
     Low = 0,
     /// Medium priority - standard fees, normal execution
+    #[default]
     Medium = 1,
     /// High priority - higher fees, faster execution
     High = 2,
@@ -22,12 +23,6 @@ pub enum PriorityLevel {
     Urgent = 3,
 }
 
-impl Default for PriorityLevel {
-    fn default() -> Self {
-        PriorityLevel::Medium
-    }
-}
-
 impl PriorityLevel {
     /// Convert from u32 to PriorityLevel
     pub fn from_u32(value: u32) -> Option<Self> {
@@ -40,11 +35,63 @@ impl PriorityLevel {
         }
     }
 
-use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
-pub use storage::{FeeLog, FeeLogKind};
+    /// Convert PriorityLevel to u32
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+}
 
-use self::storage::{
-    append_fee_log, get_fee_log as read_fee_log, get_fee_log_count as read_fee_log_count,
+/// Configuration for priority-based fee multipliers.
+/// Each priority level has a multiplier applied to the base fee rate.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PriorityFeeConfig {
+    /// Multiplier for Low priority (e.g., 8000 = 0.8x, 80% of base fee)
+    pub low_multiplier_bps: u32,
+    /// Multiplier for Medium priority (e.g., 10000 = 1.0x, 100% of base fee)
+    pub medium_multiplier_bps: u32,
+    /// Multiplier for High priority (e.g., 15000 = 1.5x, 150% of base fee)
+    pub high_multiplier_bps: u32,
+    /// Multiplier for Urgent priority (e.g., 20000 = 2.0x, 200% of base fee)
+    pub urgent_multiplier_bps: u32,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            low_multiplier_bps: 8000,     // 0.8x - 20% discount
+            medium_multiplier_bps: 10000, // 1.0x - base rate
+            high_multiplier_bps: 15000,   // 1.5x - 50% premium
+            urgent_multiplier_bps: 20000, // 2.0x - 100% premium
+        }
+    }
+}
+
+impl PriorityFeeConfig {
+    /// Get the multiplier for a given priority level in basis points
+    pub fn get_multiplier_bps(&self, priority: PriorityLevel) -> u32 {
+        match priority {
+            PriorityLevel::Low => self.low_multiplier_bps,
+            PriorityLevel::Medium => self.medium_multiplier_bps,
+            PriorityLevel::High => self.high_multiplier_bps,
+            PriorityLevel::Urgent => self.urgent_multiplier_bps,
+        }
+    }
+
+    /// Validate that multipliers are in ascending order (higher priority = higher fee)
+    pub fn is_valid(&self) -> bool {
+        self.low_multiplier_bps <= self.medium_multiplier_bps
+            && self.medium_multiplier_bps <= self.high_multiplier_bps
+            && self.high_multiplier_bps <= self.urgent_multiplier_bps
+    }
+}
+
+pub use crate::storage::{
+    FeeLog, FeeLogKind, FeeLogTotals, FeePeriod, UserFeeOverride, MAX_OVERRIDE_CLEANUP_DAYS,
+};
+
+use crate::storage::{
+    self, append_fee_log, get_fee_log as read_fee_log, get_fee_log_count as read_fee_log_count,
     get_fee_logs as read_fee_logs, FeeLogKind as StorageFeeLogKind,
 };
 
@@ -57,6 +104,41 @@ pub struct FeeWindow {
     pub end: u64,
     /// Fee rate in basis points (e.g., 100 = 1%)
     pub fee_rate: u32,
+    /// Higher priority wins when this window overlaps a recurring one
+    pub priority: u32,
+}
+
+/// Seconds in a UTC day.
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Day-of-week bits for `RecurringFeeWindow::days`, Monday first.
+pub const MONDAY: u32 = 1 << 0;
+pub const TUESDAY: u32 = 1 << 1;
+pub const WEDNESDAY: u32 = 1 << 2;
+pub const THURSDAY: u32 = 1 << 3;
+pub const FRIDAY: u32 = 1 << 4;
+pub const SATURDAY: u32 = 1 << 5;
+pub const SUNDAY: u32 = 1 << 6;
+pub const WEEKDAYS: u32 = MONDAY | TUESDAY | WEDNESDAY | THURSDAY | FRIDAY;
+pub const WEEKEND: u32 = SATURDAY | SUNDAY;
+pub const ALL_DAYS: u32 = WEEKDAYS | WEEKEND;
+
+/// A fee rate that repeats every week on the selected days, between two
+/// UTC times of day. A window whose `end_second` is before its
+/// `start_second` runs overnight into the following day.
+#[derive(Clone)]
+#[contracttype]
+pub struct RecurringFeeWindow {
+    /// Bitmask of days the window opens on (see `MONDAY`..`SUNDAY`)
+    pub days: u32,
+    /// Seconds after UTC midnight the window opens (inclusive, max 86_399)
+    pub start_second: u32,
+    /// Seconds after UTC midnight the window closes (exclusive, max 86_400)
+    pub end_second: u32,
+    /// Fee rate in basis points (e.g., 100 = 1%)
+    pub fee_rate: u32,
+    /// Higher priority wins when windows overlap
+    pub priority: u32,
 }
 
 #[derive(Clone)]
@@ -66,6 +148,8 @@ pub struct FeeConfig {
     pub default_fee_rate: u32,
     /// Time-based fee windows
     pub windows: Vec<FeeWindow>,
+    /// Weekly recurring fee windows
+    pub recurring_windows: Vec<RecurringFeeWindow>,
     /// Priority-based fee multipliers
     pub priority_config: PriorityFeeConfig,
 }
@@ -192,6 +276,10 @@ pub enum FeeError {
     InvalidFeeBoundRange = 11,
     /// Asset fee configuration not found
     AssetNotConfigured = 12,
+    /// Fee override expiry is not in the future
+    InvalidOverrideExpiry = 13,
+    /// No fee override exists for the user
+    OverrideNotFound = 14,
 }
 
 // =============================================================================
@@ -211,6 +299,9 @@ pub enum FeeOperationType {
     AssetFeeDeducted = 6,
     BatchFeeItem = 7,
     BatchFeeSummary = 8,
+    WindowsUpdate = 9,
+    OverrideSet = 10,
+    OverrideRemoved = 11,
 }
 
 impl FeeOperationType {
@@ -225,6 +316,9 @@ impl FeeOperationType {
             FeeOperationType::AssetFeeDeducted => symbol_short!("ast_ded"),
             FeeOperationType::BatchFeeItem => symbol_short!("bat_itm"),
             FeeOperationType::BatchFeeSummary => symbol_short!("batch"),
+            FeeOperationType::WindowsUpdate => symbol_short!("win_cfg"),
+            FeeOperationType::OverrideSet => symbol_short!("ovr_set"),
+            FeeOperationType::OverrideRemoved => symbol_short!("ovr_rm"),
         }
     }
 }
@@ -268,6 +362,26 @@ pub struct AssetFeeConfigEvent {
     pub timestamp: u64,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeWindowsEvent {
+    pub admin: Address,
+    pub window_count: u32,
+    pub recurring_window_count: u32,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeOverrideEvent {
+    pub admin: Option<Address>,
+    pub user: Address,
+    pub fee_bps: u32,
+    pub expires_at: u64,
+    pub reason_code: u32,
+    pub timestamp: u64,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct FeeChargedEvent {
@@ -308,7 +422,12 @@ impl FeeEvents {
         user: &Address,
         amount: i128,
     ) -> (Symbol, Symbol, Address, i128) {
-        (symbol_short!("fee"), operation.as_symbol(), user.clone(), amount)
+        (
+            symbol_short!("fee"),
+            operation.as_symbol(),
+            user.clone(),
+            amount,
+        )
     }
 
     pub fn priority_config_updated(env: &Env, admin: &Address, config: &PriorityFeeConfig) {
@@ -376,6 +495,64 @@ impl FeeEvents {
         );
     }
 
+    pub fn override_set(env: &Env, fee_override: &UserFeeOverride) {
+        let topics = Self::indexed_topics(
+            FeeOperationType::OverrideSet,
+            &fee_override.user,
+            fee_override.fee_bps as i128,
+        );
+        env.events().publish(
+            topics,
+            FeeOverrideEvent {
+                admin: Some(fee_override.granted_by.clone()),
+                user: fee_override.user.clone(),
+                fee_bps: fee_override.fee_bps,
+                expires_at: fee_override.expires_at,
+                reason_code: fee_override.reason_code,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// `admin` is `None` when an expired override is swept by cleanup.
+    pub fn override_removed(env: &Env, admin: Option<Address>, fee_override: &UserFeeOverride) {
+        let topics = Self::indexed_topics(
+            FeeOperationType::OverrideRemoved,
+            &fee_override.user,
+            fee_override.fee_bps as i128,
+        );
+        env.events().publish(
+            topics,
+            FeeOverrideEvent {
+                admin,
+                user: fee_override.user.clone(),
+                fee_bps: fee_override.fee_bps,
+                expires_at: fee_override.expires_at,
+                reason_code: fee_override.reason_code,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn windows_updated(env: &Env, admin: &Address, config: &FeeConfig) {
+        let window_count = config.windows.len();
+        let recurring_window_count = config.recurring_windows.len();
+        let topics = Self::indexed_topics(
+            FeeOperationType::WindowsUpdate,
+            admin,
+            (window_count + recurring_window_count) as i128,
+        );
+        env.events().publish(
+            topics,
+            FeeWindowsEvent {
+                admin: admin.clone(),
+                window_count,
+                recurring_window_count,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
     pub fn fee_bounds_updated(env: &Env, admin: &Address, min_fee: i128, max_fee: i128) {
         let topics = Self::indexed_topics(FeeOperationType::BoundsUpdate, admin, max_fee);
         env.events().publish(
@@ -397,7 +574,8 @@ impl FeeEvents {
         min_fee: i128,
         max_fee: i128,
     ) {
-        let topics = Self::indexed_topics(FeeOperationType::AssetConfigUpdate, admin, fee_rate as i128);
+        let topics =
+            Self::indexed_topics(FeeOperationType::AssetConfigUpdate, admin, fee_rate as i128);
         env.events().publish(
             topics,
             AssetFeeConfigEvent {
@@ -460,7 +638,8 @@ impl FeeEvents {
     }
 
     pub fn batch_fees_deducted(env: &Env, indexed_user: &Address, count: u32, total_fees: i128) {
-        let topics = Self::indexed_topics(FeeOperationType::BatchFeeSummary, indexed_user, total_fees);
+        let topics =
+            Self::indexed_topics(FeeOperationType::BatchFeeSummary, indexed_user, total_fees);
         env.events().publish(
             topics,
             BatchFeeSummaryEvent {
@@ -551,6 +730,55 @@ pub fn calculate_fee(env: &Env, amount: i128, config: &FeeConfig) -> i128 {
     calculate_fee_with_priority(env, amount, config, PriorityLevel::default())
 }
 
+/// Day of week for a ledger timestamp, Monday = 0. The Unix epoch fell on
+/// a Thursday.
+pub fn day_of_week(timestamp: u64) -> u32 {
+    ((timestamp / SECONDS_PER_DAY + 3) % 7) as u32
+}
+
+/// Whether a recurring window is open at `timestamp`. An overnight window
+/// belongs to the day it opens on, so its early-morning hours are checked
+/// against the previous day.
+pub fn recurring_window_active(window: &RecurringFeeWindow, timestamp: u64) -> bool {
+    let day = day_of_week(timestamp);
+    let opens_on = |day: u32| window.days & (1 << day) != 0;
+    let second_of_day = (timestamp % SECONDS_PER_DAY) as u32;
+
+    if window.start_second < window.end_second {
+        return opens_on(day)
+            && second_of_day >= window.start_second
+            && second_of_day < window.end_second;
+    }
+    (opens_on(day) && second_of_day >= window.start_second)
+        || (opens_on((day + 6) % 7) && second_of_day < window.end_second)
+}
+
+/// Resolve the base fee rate at `timestamp` from one-off and recurring
+/// windows. The open window with the highest priority wins; on a tie,
+/// one-off windows beat recurring ones and earlier entries beat later ones.
+/// Falls back to the default rate when no window is open.
+pub fn resolve_window_fee_rate(config: &FeeConfig, timestamp: u64) -> u32 {
+    let mut best: Option<(u32, u32)> = None;
+
+    for window in config.windows.iter() {
+        if timestamp >= window.start
+            && timestamp <= window.end
+            && best.is_none_or(|(priority, _)| window.priority > priority)
+        {
+            best = Some((window.priority, window.fee_rate));
+        }
+    }
+    for window in config.recurring_windows.iter() {
+        if recurring_window_active(&window, timestamp)
+            && best.is_none_or(|(priority, _)| window.priority > priority)
+        {
+            best = Some((window.priority, window.fee_rate));
+        }
+    }
+
+    best.map_or(config.default_fee_rate, |(_, fee_rate)| fee_rate)
+}
+
 /// Calculate fee for an amount with priority level.
 pub fn calculate_fee_with_priority(
     env: &Env,
@@ -562,15 +790,7 @@ pub fn calculate_fee_with_priority(
         return 0;
     }
 
-    let now = env.ledger().timestamp();
-
-    let mut fee_rate = config.default_fee_rate;
-    for window in config.windows.iter() {
-        if now >= window.start && now <= window.end {
-            base_fee_rate = window.fee_rate;
-            break;
-        }
-    }
+    let base_fee_rate = resolve_window_fee_rate(config, env.ledger().timestamp());
 
     // Apply priority multiplier
     let adjusted_fee_rate =
@@ -580,6 +800,35 @@ pub fn calculate_fee_with_priority(
     (amount * adjusted_fee_rate as i128) / 10_000
 }
 
+/// Calculate fee for an amount in an asset with its own rate, applying the
+/// priority multiplier and the asset's bounds (0 = unbounded).
+pub fn calculate_fee_for_asset_with_priority(
+    env: &Env,
+    amount: i128,
+    asset_config: &AssetFeeConfig,
+    priority_config: &PriorityFeeConfig,
+    priority: PriorityLevel,
+) -> i128 {
+    if amount <= 0 {
+        return 0;
+    }
+
+    let adjusted_fee_rate =
+        calculate_priority_fee_rate(asset_config.fee_rate, priority, priority_config);
+    let mut fee = amount
+        .checked_mul(adjusted_fee_rate as i128)
+        .unwrap_or_else(|| panic_with_error!(env, FeeError::Overflow))
+        / 10_000;
+
+    if asset_config.min_fee > 0 {
+        fee = fee.max(asset_config.min_fee);
+    }
+    if asset_config.max_fee > 0 {
+        fee = fee.min(asset_config.max_fee);
+    }
+    fee
+}
+
 pub fn validate_windows(windows: &Vec<FeeWindow>) -> bool {
     for w in windows.iter() {
         if w.start >= w.end {
@@ -589,6 +838,7 @@ pub fn validate_windows(windows: &Vec<FeeWindow>) -> bool {
     true
 }
 
+#[contract]
 pub struct FeeContract;
 
 #[contractimpl]
@@ -618,6 +868,7 @@ impl FeeContract {
         let config = FeeConfig {
             default_fee_rate,
             windows: Vec::new(&env),
+            recurring_windows: Vec::new(&env),
             priority_config: priority_config.clone(),
         };
         env.storage().instance().set(&DataKey::FeeConfig, &config);
@@ -681,7 +932,7 @@ impl FeeContract {
         env.storage()
             .instance()
             .get(&DataKey::PriorityFeeConfig)
-            .unwrap_or_else(PriorityFeeConfig::default)
+            .unwrap_or_default()
     }
 
     /// Get the fee multiplier for a specific priority level.
@@ -726,7 +977,7 @@ impl FeeContract {
         payer.require_auth();
         Self::require_initialized(&env);
 
-        let fee = Self::calculate_fee_with_priority(env.clone(), amount, priority);
+        let fee = Self::calculate_user_fee(&env, &payer, amount, priority);
 
         let net = amount
             .checked_sub(fee)
@@ -758,17 +1009,23 @@ impl FeeContract {
             .instance()
             .set(&DataKey::UserFeesAccrued(payer.clone()), &user_fees);
 
+        append_fee_log(
+            &env,
+            Some(payer.clone()),
+            None,
+            amount,
+            fee,
+            StorageFeeLogKind::Charge,
+        );
+
         FeeEvents::fee_deducted(&env, &payer, amount, fee, priority);
         (net, fee)
     }
 
-    /// Simulate fee calculation (read-only).
+    /// Simulate fee calculation (read-only), honouring the user's active
+    /// fee override.
     pub fn simulate_fee(env: Env, amount: i128, user: Address) -> i128 {
-        let config: FeeConfig = env
-            .storage()
-            .instance()
-            .get(&DataKey::FeeConfig)
-            .unwrap_or_else(|| panic_with_error!(&env, FeeError::NotInitialized));
+        let config = Self::fee_config_for_user(&env, &user);
         calculate_fee(&env, amount, &config)
     }
 
@@ -866,6 +1123,119 @@ impl FeeContract {
         FeeEvents::config_updated(&env, &caller, fee_rate);
     }
 
+    /// Replace the one-off and recurring fee windows.
+    /// Only admin can call this function.
+    pub fn set_fee_windows(
+        env: Env,
+        caller: Address,
+        windows: Vec<FeeWindow>,
+        recurring_windows: Vec<RecurringFeeWindow>,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if !validate_fee_windows(&windows) || !validate_recurring_fee_windows(&recurring_windows) {
+            panic_with_error!(&env, FeeError::InvalidFeeWindow);
+        }
+
+        let mut config: FeeConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeConfig)
+            .unwrap_or_else(|| panic_with_error!(&env, FeeError::NotInitialized));
+        config.windows = windows;
+        config.recurring_windows = recurring_windows;
+        env.storage().instance().set(&DataKey::FeeConfig, &config);
+
+        FeeEvents::windows_updated(&env, &caller, &config);
+    }
+
+    /// Get the base fee rate that applies right now, before priority
+    /// multipliers.
+    pub fn get_effective_fee_rate(env: Env) -> u32 {
+        let config = Self::get_fee_config(env.clone());
+        resolve_window_fee_rate(&config, env.ledger().timestamp())
+    }
+
+    // =========================================================================
+    // User fee overrides
+    // =========================================================================
+
+    /// Grant `user` a fixed fee rate until `expires_at`, replacing any
+    /// current override. Only admin can call this function.
+    pub fn admin_set_user_fee_override(
+        env: Env,
+        caller: Address,
+        user: Address,
+        fee_bps: u32,
+        expires_at: u64,
+        reason_code: u32,
+    ) -> UserFeeOverride {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if fee_bps > 10_000 {
+            panic_with_error!(&env, FeeError::InvalidPercentage);
+        }
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            panic_with_error!(&env, FeeError::InvalidOverrideExpiry);
+        }
+
+        let fee_override = UserFeeOverride {
+            user,
+            fee_bps,
+            expires_at,
+            reason_code,
+            granted_by: caller,
+            granted_at: now,
+        };
+        storage::set_user_fee_override(&env, &fee_override);
+
+        FeeEvents::override_set(&env, &fee_override);
+        fee_override
+    }
+
+    /// Remove `user`'s fee override. Only admin can call this function.
+    pub fn admin_remove_user_fee_override(env: Env, caller: Address, user: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let fee_override = storage::remove_user_fee_override(&env, user, Some(caller.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, FeeError::OverrideNotFound));
+
+        FeeEvents::override_removed(&env, Some(caller), &fee_override);
+    }
+
+    /// Get `user`'s current override, including one that has expired but
+    /// not yet been cleaned up.
+    pub fn get_user_fee_override(env: Env, user: Address) -> Option<UserFeeOverride> {
+        storage::get_user_fee_override(&env, user)
+    }
+
+    /// Get every override granted to `user`, oldest first.
+    pub fn get_user_fee_override_history(env: Env, user: Address) -> Vec<UserFeeOverride> {
+        storage::get_user_fee_override_history(&env, user)
+    }
+
+    /// List current overrides that expire during UTC day `day`
+    /// (`timestamp / 86_400`).
+    pub fn list_fee_overrides_expiring_on(env: Env, day: u64) -> Vec<UserFeeOverride> {
+        storage::get_fee_overrides_expiring_on(&env, day)
+    }
+
+    /// Remove up to `limit` expired overrides, oldest expiry day first.
+    /// Anyone may call this; only overrides that no longer apply are
+    /// touched. Each call walks at most `MAX_OVERRIDE_CLEANUP_DAYS` days past
+    /// where the previous one stopped. Returns the number removed.
+    pub fn cleanup_expired_fee_overrides(env: Env, limit: u32) -> u32 {
+        let removed = storage::remove_expired_fee_overrides(&env, limit);
+        for fee_override in removed.iter() {
+            FeeEvents::override_removed(&env, None, &fee_override);
+        }
+        removed.len()
+    }
+
     /// Get the current fee configuration.
     pub fn get_fee_config(env: Env) -> FeeConfig {
         env.storage()
@@ -939,7 +1309,7 @@ impl FeeContract {
             .storage()
             .instance()
             .get(&DataKey::PriorityFeeConfig)
-            .unwrap_or_else(PriorityFeeConfig::default);
+            .unwrap_or_default();
 
         // Use asset-specific config if available, otherwise fall back to default
         if let Some(asset_config) = env
@@ -964,6 +1334,22 @@ impl FeeContract {
         }
     }
 
+    /// Get total fees collected in `asset`.
+    pub fn get_asset_fees_collected(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AssetFeesCollected(asset))
+            .unwrap_or(0)
+    }
+
+    /// Get fees `user` has paid in `asset`.
+    pub fn get_user_asset_fees_accrued(env: Env, user: Address, asset: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UserAssetFeesAccrued(user, asset))
+            .unwrap_or(0)
+    }
+
     /// Deduct fee for a transaction in a specific asset, with priority.
     /// Returns `(net_amount, fee_charged)`.
     /// Tracks fees collected per asset and per user per asset.
@@ -1022,19 +1408,77 @@ impl FeeContract {
             &DataKey::UserAssetFeesAccrued(payer.clone(), asset.clone()),
             &user_asset_fees,
         );
-        fee
+
+        append_fee_log(
+            &env,
+            Some(payer.clone()),
+            Some(asset.clone()),
+            amount,
+            fee,
+            StorageFeeLogKind::Charge,
+        );
+
+        FeeEvents::asset_fee_deducted(&env, &payer, &asset, amount, fee, priority);
+        (net, fee)
     }
 
-    pub fn record_fee_refund(env: Env, payer: Address, amount: i128, refunded_fee: i128) -> FeeLog {
+    /// Record a refund of `refunded_fee` on `amount` to `payer`.
+    /// Only admin can call this function.
+    pub fn record_fee_refund(
+        env: Env,
+        caller: Address,
+        payer: Address,
+        asset: Option<Address>,
+        amount: i128,
+        refunded_fee: i128,
+    ) -> FeeLog {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
         append_fee_log(
             &env,
             Some(payer),
+            asset,
             amount,
             refunded_fee,
             StorageFeeLogKind::Refund,
         )
     }
 
+    /// Totals of `kind` log entries in `period`.
+    pub fn get_fee_totals(env: Env, period: FeePeriod, kind: FeeLogKind) -> FeeLogTotals {
+        storage::get_fee_log_totals(&env, period, kind)
+    }
+
+    /// Totals of `kind` log entries in `period` for one asset (`None` for
+    /// the default fee config).
+    pub fn get_fee_totals_by_asset(
+        env: Env,
+        period: FeePeriod,
+        kind: FeeLogKind,
+        asset: Option<Address>,
+    ) -> FeeLogTotals {
+        storage::get_fee_log_asset_totals(&env, period, kind, asset)
+    }
+
+    /// Totals of `kind` log entries in `period` paid by `user`.
+    pub fn get_user_fee_totals(
+        env: Env,
+        period: FeePeriod,
+        kind: FeeLogKind,
+        user: Address,
+    ) -> FeeLogTotals {
+        storage::get_fee_log_user_totals(&env, period, kind, user)
+    }
+
+    /// The day and month periods a timestamp falls in.
+    pub fn get_fee_periods(env: Env, timestamp: u64) -> (FeePeriod, FeePeriod) {
+        (
+            storage::day_period(timestamp),
+            storage::month_period(timestamp),
+        )
+    }
+
     pub fn get_fee_log(env: Env, id: u64) -> Option<FeeLog> {
         read_fee_log(&env, id)
     }
@@ -1043,6 +1487,41 @@ impl FeeContract {
         read_fee_log_count(&env)
     }
 
+    /// Fee log entries `start..=end` (ids start at 1), including override
+    /// grants and removals.
+    pub fn get_fee_logs(env: Env, start: u64, end: u64) -> Vec<FeeLog> {
+        read_fee_logs(&env, start, end)
+    }
+
+    /// Calculate the fees a batch would be charged without changing any
+    /// state.
+    pub fn calculate_batch_fees(env: Env, transactions: Vec<FeeTransaction>) -> BatchFeeResult {
+        let priority_config = Self::get_priority_config(env.clone());
+        let fee_config = Self::get_fee_config(env.clone());
+
+        let mut results: Vec<FeeTransactionResult> = Vec::new(&env);
+        let mut total_fees: i128 = 0;
+        for tx in transactions.iter() {
+            if tx.amount <= 0 {
+                panic_with_error!(&env, FeeError::InvalidAmount);
+            }
+            let fee = Self::batch_item_fee(&env, &tx, &fee_config, &priority_config);
+            let net_amount = tx
+                .amount
+                .checked_sub(fee)
+                .unwrap_or_else(|| panic_with_error!(&env, FeeError::Overflow));
+            total_fees = total_fees
+                .checked_add(fee)
+                .unwrap_or_else(|| panic_with_error!(&env, FeeError::Overflow));
+            results.push_back(FeeTransactionResult { net_amount, fee });
+        }
+
+        BatchFeeResult {
+            results,
+            total_fees,
+        }
+    }
+
     /// Deduct fees for a batch of transactions atomically.
     ///
     /// All payers must have authorised this call. Every transaction in the
@@ -1070,7 +1549,7 @@ impl FeeContract {
             .storage()
             .instance()
             .get(&DataKey::PriorityFeeConfig)
-            .unwrap_or_else(PriorityFeeConfig::default);
+            .unwrap_or_default();
 
         let fee_config: FeeConfig = env
             .storage()
@@ -1086,21 +1565,7 @@ impl FeeContract {
                 panic_with_error!(&env, FeeError::InvalidAmount);
             }
 
-            let fee = if let Some(asset_cfg) = env
-                .storage()
-                .instance()
-                .get::<DataKey, AssetFeeConfig>(&DataKey::AssetFeeConfig(tx.asset.clone()))
-            {
-                calculate_fee_for_asset_with_priority(
-                    &env,
-                    tx.amount,
-                    &asset_cfg,
-                    &priority_config,
-                    tx.priority,
-                )
-            } else {
-                calculate_fee_with_priority(&env, tx.amount, &fee_config, tx.priority)
-            };
+            let fee = Self::batch_item_fee(&env, &tx, &fee_config, &priority_config);
 
             let net_amount = tx
                 .amount
@@ -1154,6 +1619,15 @@ impl FeeContract {
                 .checked_add(fee)
                 .unwrap_or_else(|| panic_with_error!(&env, FeeError::Overflow));
 
+            append_fee_log(
+                &env,
+                Some(tx.payer.clone()),
+                Some(tx.asset.clone()),
+                tx.amount,
+                fee,
+                StorageFeeLogKind::Charge,
+            );
+
             FeeEvents::batch_fee_item(&env, &tx.payer, &tx.asset, tx.amount, fee, tx.priority);
             results.push_back(FeeTransactionResult { net_amount, fee });
         }
@@ -1171,7 +1645,7 @@ impl FeeContract {
             .instance()
             .set(&DataKey::TotalFeesCollected, &global_total);
 
-        let count = transactions.len() as u32;
+        let count = transactions.len();
         let summary_user = if count > 0 {
             transactions.get(0).unwrap().payer
         } else {
@@ -1194,6 +1668,69 @@ impl FeeContract {
             .unwrap_or_else(|| panic_with_error!(env, FeeError::NotInitialized))
     }
 
+    /// The fee config to charge `user` under: an active override replaces
+    /// the default rate and every time window.
+    fn fee_config_for_user(env: &Env, user: &Address) -> FeeConfig {
+        let mut config: FeeConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeConfig)
+            .unwrap_or_else(|| panic_with_error!(env, FeeError::NotInitialized));
+        if let Some(fee_bps) = storage::get_active_user_fee_override(env, user.clone()) {
+            config.default_fee_rate = fee_bps;
+            config.windows = Vec::new(env);
+            config.recurring_windows = Vec::new(env);
+        }
+        config
+    }
+
+    fn calculate_user_fee(
+        env: &Env,
+        user: &Address,
+        amount: i128,
+        priority: PriorityLevel,
+    ) -> i128 {
+        if amount <= 0 {
+            panic_with_error!(env, FeeError::InvalidAmount);
+        }
+
+        let config = Self::fee_config_for_user(env, user);
+        let fee = calculate_fee_with_priority(env, amount, &config, priority);
+
+        let min_fee: i128 = env.storage().instance().get(&DataKey::MinFee).unwrap_or(0);
+        let max_fee: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxFee)
+            .unwrap_or(i128::MAX);
+
+        fee.max(min_fee).min(max_fee)
+    }
+
+    /// Fee for one batch entry: the asset's own rate when configured,
+    /// otherwise the default fee config.
+    fn batch_item_fee(
+        env: &Env,
+        tx: &FeeTransaction,
+        fee_config: &FeeConfig,
+        priority_config: &PriorityFeeConfig,
+    ) -> i128 {
+        match env
+            .storage()
+            .instance()
+            .get::<DataKey, AssetFeeConfig>(&DataKey::AssetFeeConfig(tx.asset.clone()))
+        {
+            Some(asset_config) => calculate_fee_for_asset_with_priority(
+                env,
+                tx.amount,
+                &asset_config,
+                priority_config,
+                tx.priority,
+            ),
+            None => calculate_fee_with_priority(env, tx.amount, fee_config, tx.priority),
+        }
+    }
+
     fn require_admin(env: &Env, caller: &Address) {
         let admin = Self::require_initialized(env);
         if caller != &admin {
@@ -1341,7 +1878,7 @@ impl FeeContract {
             .storage()
             .instance()
             .get(&DataKey::PriorityFeeConfig)
-            .unwrap_or_else(PriorityFeeConfig::default);
+            .unwrap_or_default();
 
         // Closure: compute fee from the default FeeConfig as the fallback path.
         let default_fee = |reason: u32| -> (i128, FeeOperationStatus) {
@@ -1484,13 +2021,13 @@ pub fn validate_fee_window(window: &FeeWindow) -> bool {
 /// * `true` if all windows are valid and non-overlapping, `false` otherwise
 pub fn validate_fee_windows(windows: &Vec<FeeWindow>) -> bool {
     // Empty windows vector is valid
-    if windows.len() == 0 {
+    if windows.is_empty() {
         return true;
     }
 
     // Validate each individual window
     for window in windows.iter() {
-        if !validate_fee_window(window) {
+        if !validate_fee_window(&window) {
             return false;
         }
     }
@@ -1515,6 +2052,42 @@ pub fn validate_fee_windows(windows: &Vec<FeeWindow>) -> bool {
     true
 }
 
+/// Validates a recurring fee window: at least one day selected, distinct
+/// start and end times of day (an earlier end runs overnight), and a valid
+/// rate.
+/// Overlapping recurring windows are allowed; priority resolves them.
+///
+/// # Arguments
+/// * `window` - The recurring fee window to validate
+///
+/// # Returns
+/// * `true` if window is valid, `false` otherwise
+pub fn validate_recurring_fee_window(window: &RecurringFeeWindow) -> bool {
+    if window.days == 0 || window.days & !ALL_DAYS != 0 {
+        return false;
+    }
+    if window.start_second == window.end_second
+        || window.start_second as u64 >= SECONDS_PER_DAY
+        || window.end_second as u64 > SECONDS_PER_DAY
+    {
+        return false;
+    }
+    window.fee_rate <= 10_000
+}
+
+/// Validates all recurring fee windows.
+///
+/// # Arguments
+/// * `windows` - Vector of recurring fee windows to validate
+///
+/// # Returns
+/// * `true` if every window is valid, `false` otherwise
+pub fn validate_recurring_fee_windows(windows: &Vec<RecurringFeeWindow>) -> bool {
+    windows
+        .iter()
+        .all(|window| validate_recurring_fee_window(&window))
+}
+
 /// Validates fee bounds for deterministic behavior.
 /// Ensures minimum and maximum fees are valid and consistent.
 ///
@@ -1549,11 +2122,11 @@ pub fn validate_asset_fee_config(config: &AssetFeeConfig) -> bool {
     if config.fee_rate > 10_000 {
         return false;
     }
-    // Validate fee bounds
-    if !validate_fee_bounds(config.min_fee, config.max_fee) {
+    // Validate fee bounds; a max_fee of 0 means no maximum
+    if config.min_fee < 0 || config.max_fee < 0 {
         return false;
     }
-    true
+    config.max_fee == 0 || config.min_fee <= config.max_fee
 }
 
 /// Validates complete fee configuration for deterministic behavior.
@@ -1574,6 +2147,9 @@ pub fn validate_fee_config(config: &FeeConfig) -> bool {
     if !validate_fee_windows(&config.windows) {
         return false;
     }
+    if !validate_recurring_fee_windows(&config.recurring_windows) {
+        return false;
+    }
 
     // Validate priority fee configuration
     if !validate_priority_fee_config(&config.priority_config) {
@@ -1658,7 +2234,7 @@ pub fn validate_batch_fee_determinism(
     fee_config: &FeeConfig,
 ) -> bool {
     // Empty batch is valid
-    if transactions.len() == 0 {
+    if transactions.is_empty() {
         return true;
     }
 
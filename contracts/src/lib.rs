@@ -1,11 +1,9 @@
-#![allow(unused)]
-
-
-pub mod storage;
 //! StellarSpend fee contract crate root: re-exports the fee contract and contract metrics types.
+#![allow(unused)]
 
 pub mod auth;
 pub mod fee;
+pub mod storage;
 
 pub use fee::*;
 
@@ -22,58 +20,19 @@ mod contract_metrics_tests {
         let env = Env::default();
         env.mock_all_auths();
         let admin = Address::generate(&env);
-        let _id = env.register(FeeContract, ());
-        FeeContract::initialize(env.clone(), admin.clone(), 500);
+        let contract_id = env.register(FeeContract, ());
+        let client = FeeContractClient::new(&env, &contract_id);
+        client.initialize(&admin, &500u32);
         let payer = Address::generate(&env);
 
-        let m0 = FeeContract::get_contract_metrics(env.clone());
+        let m0 = client.get_contract_metrics();
         assert_eq!(m0.total_fees_collected, 0);
         assert_eq!(m0.default_fee_rate_bps, 500);
 
-        FeeContract::deduct_fee_with_priority(
-            env.clone(),
-            payer.clone(),
-            1000,
-            PriorityLevel::Medium,
-        );
+        client.deduct_fee_with_priority(&payer, &1000i128, &PriorityLevel::Medium);
 
-        let m1 = FeeContract::get_contract_metrics(env.clone());
-        assert_eq!(
-            m1.total_fees_collected,
-            FeeContract::get_total_collected(env.clone())
-        );
+        let m1 = client.get_contract_metrics();
+        assert_eq!(m1.total_fees_collected, client.get_total_collected());
         assert_eq!(m1.total_fees_collected, 50);
     }
 }
-pub fn admin_set_user_fee_override(
-    env: Env,
-    admin: Address,
-    user: Address,
-    fee_bps: u32,
-) {
-    require_admin(&env, &admin);
-    admin.require_auth();
-
-    storage::set_user_fee_override(&env, user.clone(), fee_bps);
-
-    env.events().publish(
-        ("fee_override_set", user),
-        fee_bps,
-    );
-}
-
-pub fn admin_remove_user_fee_override(
-    env: Env,
-    admin: Address,
-    user: Address,
-) {
-    require_admin(&env, &admin);
-    admin.require_auth();
-
-    storage::remove_user_fee_override(&env, user.clone());
-
-    env.events().publish(
-        ("fee_override_removed", user),
-        (),
-    );
-}
\ No newline at end of file
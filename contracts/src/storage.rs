@@ -1,10 +1,15 @@
 use soroban_sdk::{contracttype, Address, Env, Vec};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum FeeLogKind {
     Charge,
     Refund,
+    /// An admin granted the override; amounts are zero
+    OverrideSet(UserFeeOverride),
+    /// The override was revoked by the admin, or swept by cleanup (`None`);
+    /// amounts are zero
+    OverrideRemoved(UserFeeOverride, Option<Address>),
 }
 
 #[derive(Clone)]
@@ -12,6 +17,8 @@ pub enum FeeLogKind {
 pub struct FeeLog {
     pub id: u64,
     pub payer: Option<Address>,
+    /// `None` for the default fee config
+    pub asset: Option<Address>,
     pub gross_amount: i128,
     pub fee_amount: i128,
     pub timestamp: u64,
@@ -19,16 +26,70 @@ pub struct FeeLog {
     pub kind: FeeLogKind,
 }
 
+/// A reporting period. Days are encoded as `yyyymmdd` and months as
+/// `yyyymm`, both in UTC.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum FeePeriod {
+    Day(u32),
+    Month(u32),
+}
+
+/// Running totals of the fee log entries that fall in one bucket.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeLogTotals {
+    pub count: u32,
+    pub gross_amount: i128,
+    pub fee_amount: i128,
+}
+
+/// A per-user fee rate granted by an admin, valid until `expires_at`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UserFeeOverride {
+    pub user: Address,
+    pub fee_bps: u32,
+    /// Ledger timestamp after which the override no longer applies
+    pub expires_at: u64,
+    pub reason_code: u32,
+    pub granted_by: Address,
+    pub granted_at: u64,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     FeeLogCount,
     FeeLog(u64),
+    UserFeeOverride(Address),
+    UserFeeOverrideHistory(Address),
+    /// Users whose override expires on a UTC day, in insertion order
+    FeeOverrideExpiryBucket(u64),
+    /// Oldest expiry day cleanup has not swept yet
+    FeeOverrideCleanupDay,
+    FeeLogTotals(FeePeriod, FeeLogKind),
+    FeeLogAssetTotals(FeePeriod, FeeLogKind, Option<Address>),
+    FeeLogUserTotals(FeePeriod, FeeLogKind, Address),
 }
 
 pub fn append_fee_log(
     env: &Env,
     payer: Option<Address>,
+    asset: Option<Address>,
+    gross_amount: i128,
+    fee_amount: i128,
+    kind: FeeLogKind,
+) -> FeeLog {
+    let fee_log = write_fee_log(env, payer, asset, gross_amount, fee_amount, kind);
+    aggregate_fee_log(env, &fee_log);
+    fee_log
+}
+
+fn write_fee_log(
+    env: &Env,
+    payer: Option<Address>,
+    asset: Option<Address>,
     gross_amount: i128,
     fee_amount: i128,
     kind: FeeLogKind,
@@ -44,6 +105,7 @@ pub fn append_fee_log(
     let fee_log = FeeLog {
         id: count,
         payer,
+        asset,
         gross_amount,
         fee_amount,
         timestamp: env.ledger().timestamp(),
@@ -61,6 +123,99 @@ pub fn append_fee_log(
     fee_log
 }
 
+// =============================================================================
+// Fee Log Aggregates
+// =============================================================================
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Converts a timestamp to a UTC (year, month, day) date.
+fn civil_date(timestamp: u64) -> (u32, u32, u32) {
+    // Days-to-civil conversion from Howard Hinnant's date algorithms.
+    let z = timestamp / SECONDS_PER_DAY + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u32, month as u32, day as u32)
+}
+
+pub fn day_period(timestamp: u64) -> FeePeriod {
+    let (year, month, day) = civil_date(timestamp);
+    FeePeriod::Day(year * 10_000 + month * 100 + day)
+}
+
+pub fn month_period(timestamp: u64) -> FeePeriod {
+    let (year, month, _) = civil_date(timestamp);
+    FeePeriod::Month(year * 100 + month)
+}
+
+fn read_totals(env: &Env, key: &DataKey) -> FeeLogTotals {
+    env.storage().persistent().get(key).unwrap_or_default()
+}
+
+fn add_to_totals(env: &Env, key: DataKey, fee_log: &FeeLog) {
+    let mut totals = read_totals(env, &key);
+    totals.count += 1;
+    totals.gross_amount = totals.gross_amount.saturating_add(fee_log.gross_amount);
+    totals.fee_amount = totals.fee_amount.saturating_add(fee_log.fee_amount);
+    env.storage().persistent().set(&key, &totals);
+}
+
+/// Folds a new entry into its day and month buckets by kind, asset and
+/// payer.
+fn aggregate_fee_log(env: &Env, fee_log: &FeeLog) {
+    for period in [
+        day_period(fee_log.timestamp),
+        month_period(fee_log.timestamp),
+    ] {
+        let kind = fee_log.kind.clone();
+        add_to_totals(
+            env,
+            DataKey::FeeLogTotals(period.clone(), kind.clone()),
+            fee_log,
+        );
+        add_to_totals(
+            env,
+            DataKey::FeeLogAssetTotals(period.clone(), kind.clone(), fee_log.asset.clone()),
+            fee_log,
+        );
+        if let Some(payer) = &fee_log.payer {
+            add_to_totals(
+                env,
+                DataKey::FeeLogUserTotals(period, kind, payer.clone()),
+                fee_log,
+            );
+        }
+    }
+}
+
+pub fn get_fee_log_totals(env: &Env, period: FeePeriod, kind: FeeLogKind) -> FeeLogTotals {
+    read_totals(env, &DataKey::FeeLogTotals(period, kind))
+}
+
+pub fn get_fee_log_asset_totals(
+    env: &Env,
+    period: FeePeriod,
+    kind: FeeLogKind,
+    asset: Option<Address>,
+) -> FeeLogTotals {
+    read_totals(env, &DataKey::FeeLogAssetTotals(period, kind, asset))
+}
+
+pub fn get_fee_log_user_totals(
+    env: &Env,
+    period: FeePeriod,
+    kind: FeeLogKind,
+    user: Address,
+) -> FeeLogTotals {
+    read_totals(env, &DataKey::FeeLogUserTotals(period, kind, user))
+}
+
 pub fn get_fee_log(env: &Env, id: u64) -> Option<FeeLog> {
     env.storage().persistent().get(&DataKey::FeeLog(id))
 }
@@ -90,25 +245,190 @@ pub fn get_fee_logs(env: &Env, start: u64, end: u64) -> Vec<FeeLog> {
     logs
 }
 
-#[derive(Clone)]
-#[contracttype]
-pub fn set_user_fee_override(env: &Env, user: Address, fee_bps: u32) {
+// =============================================================================
+// User Fee Overrides
+// =============================================================================
+
+/// Most expiry days a single cleanup call walks past.
+pub const MAX_OVERRIDE_CLEANUP_DAYS: u64 = 31;
+
+fn expiry_day(fee_override: &UserFeeOverride) -> u64 {
+    fee_override.expires_at / SECONDS_PER_DAY
+}
+
+fn read_expiry_bucket(env: &Env, day: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeOverrideExpiryBucket(day))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn write_expiry_bucket(env: &Env, day: u64, bucket: &Vec<Address>) {
+    let key = DataKey::FeeOverrideExpiryBucket(day);
+    if bucket.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, bucket);
+    }
+}
+
+fn read_cleanup_day(env: &Env) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeOverrideCleanupDay)
+}
+
+fn write_cleanup_day(env: &Env, day: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FeeOverrideCleanupDay, &day);
+}
+
+fn index_override(env: &Env, fee_override: &UserFeeOverride) {
+    let day = expiry_day(fee_override);
+    let mut bucket = read_expiry_bucket(env, day);
+    bucket.push_back(fee_override.user.clone());
+    write_expiry_bucket(env, day, &bucket);
+
+    if read_cleanup_day(env).is_none_or(|cleanup_day| day < cleanup_day) {
+        write_cleanup_day(env, day);
+    }
+}
+
+fn unindex_override(env: &Env, fee_override: &UserFeeOverride) {
+    let day = expiry_day(fee_override);
+    let mut bucket = read_expiry_bucket(env, day);
+    if let Some(position) = bucket.first_index_of(&fee_override.user) {
+        bucket.remove(position);
+        write_expiry_bucket(env, day, &bucket);
+    }
+}
+
+/// Journals an override change in the fee log. Override entries carry no
+/// amounts and are left out of the period totals.
+fn append_override_log(env: &Env, fee_override: &UserFeeOverride, kind: FeeLogKind) {
+    write_fee_log(env, Some(fee_override.user.clone()), None, 0, 0, kind);
+}
+
+/// Stores `fee_override`, replacing any current override for the same user,
+/// and records it in the user's history and the fee log.
+pub fn set_user_fee_override(env: &Env, fee_override: &UserFeeOverride) {
     // safety guard
-    assert!(fee_bps <= 10_000, "invalid fee");
+    assert!(fee_override.fee_bps <= 10_000, "invalid fee");
 
+    let user = fee_override.user.clone();
+    if let Some(previous) = get_user_fee_override(env, user.clone()) {
+        unindex_override(env, &previous);
+    }
     env.storage()
         .persistent()
-        .set(&DataKey::UserFeeOverride(user), &fee_bps);
+        .set(&DataKey::UserFeeOverride(user.clone()), fee_override);
+    index_override(env, fee_override);
+
+    let mut history = get_user_fee_override_history(env, user.clone());
+    history.push_back(fee_override.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserFeeOverrideHistory(user), &history);
+
+    append_override_log(
+        env,
+        fee_override,
+        FeeLogKind::OverrideSet(fee_override.clone()),
+    );
 }
 
-pub fn get_user_fee_override(env: &Env, user: Address) -> Option<u32> {
+/// Returns the stored override for `user`, whether or not it has expired.
+pub fn get_user_fee_override(env: &Env, user: Address) -> Option<UserFeeOverride> {
     env.storage()
         .persistent()
         .get(&DataKey::UserFeeOverride(user))
 }
 
-pub fn remove_user_fee_override(env: &Env, user: Address) {
+/// Returns the override rate for `user` if one exists and has not expired.
+pub fn get_active_user_fee_override(env: &Env, user: Address) -> Option<u32> {
+    get_user_fee_override(env, user)
+        .filter(|fee_override| fee_override.expires_at > env.ledger().timestamp())
+        .map(|fee_override| fee_override.fee_bps)
+}
+
+/// Every override ever granted to `user`, oldest first.
+pub fn get_user_fee_override_history(env: &Env, user: Address) -> Vec<UserFeeOverride> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserFeeOverrideHistory(user))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Current overrides that expire during UTC day `day`
+/// (`timestamp / 86_400`).
+pub fn get_fee_overrides_expiring_on(env: &Env, day: u64) -> Vec<UserFeeOverride> {
+    let mut overrides = Vec::new(env);
+    for user in read_expiry_bucket(env, day).iter() {
+        if let Some(fee_override) = get_user_fee_override(env, user) {
+            overrides.push_back(fee_override);
+        }
+    }
+    overrides
+}
+
+/// Removes the override for `user` and returns it. `admin` is `None` when
+/// cleanup removes an expired override.
+pub fn remove_user_fee_override(
+    env: &Env,
+    user: Address,
+    admin: Option<Address>,
+) -> Option<UserFeeOverride> {
+    let fee_override = get_user_fee_override(env, user.clone())?;
+
     env.storage()
         .persistent()
         .remove(&DataKey::UserFeeOverride(user));
+    unindex_override(env, &fee_override);
+
+    append_override_log(
+        env,
+        &fee_override,
+        FeeLogKind::OverrideRemoved(fee_override.clone(), admin),
+    );
+    Some(fee_override)
+}
+
+/// Removes up to `limit` expired overrides, walking the expiry buckets from
+/// the oldest day not yet swept. A call walks past at most
+/// `MAX_OVERRIDE_CLEANUP_DAYS` days and resumes where the last one stopped.
+pub fn remove_expired_fee_overrides(env: &Env, limit: u32) -> Vec<UserFeeOverride> {
+    let mut removed = Vec::new(env);
+    let Some(mut day) = read_cleanup_day(env) else {
+        return removed;
+    };
+    let now = env.ledger().timestamp();
+    let last_day = (now / SECONDS_PER_DAY).min(day + MAX_OVERRIDE_CLEANUP_DAYS);
+
+    while day <= last_day {
+        let mut day_swept = true;
+        for user in read_expiry_bucket(env, day).iter() {
+            if removed.len() >= limit {
+                day_swept = false;
+                break;
+            }
+            let Some(fee_override) = get_user_fee_override(env, user.clone()) else {
+                continue;
+            };
+            if fee_override.expires_at > now {
+                // Expires later today
+                day_swept = false;
+                continue;
+            }
+            remove_user_fee_override(env, user, None);
+            removed.push_back(fee_override);
+        }
+        if !day_swept {
+            break;
+        }
+        day += 1;
+    }
+
+    write_cleanup_day(env, day);
+    removed
 }
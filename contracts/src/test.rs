@@ -4,7 +4,7 @@ use crate::fee::*;
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events as _},
-    Address, Env, IntoVal, Symbol, TryFromVal, Vec,
+    Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
 };
 
 // =============================================================================
@@ -19,6 +19,17 @@ fn setup_contract() -> (Env, Address, Address) {
     (env, admin, contract_id)
 }
 
+/// Indexed `(user, amount)` topics of the latest `("fee", operation, ..)`
+/// event.
+fn last_fee_event(env: &Env, operation: Symbol) -> Option<(Address, i128)> {
+    env.events().all().iter().rev().find_map(|(_, topics, _)| {
+        let topics: Val = topics.into_val(env);
+        let (name, op, user, amount) =
+            <(Symbol, Symbol, Address, i128)>::try_from_val(env, &topics).ok()?;
+        (name == symbol_short!("fee") && op == operation).then_some((user, amount))
+    })
+}
+
 // =============================================================================
 // PriorityLevel Tests
 // =============================================================================
@@ -163,6 +174,7 @@ fn test_calculate_fee_with_priority() {
     let config = FeeConfig {
         default_fee_rate: 500, // 5%
         windows: Vec::new(&env),
+        recurring_windows: Vec::new(&env),
         priority_config,
     };
 
@@ -193,6 +205,7 @@ fn test_priority_fees_scale_correctly() {
     let config = FeeConfig {
         default_fee_rate: 1000, // 10%
         windows: Vec::new(&env),
+        recurring_windows: Vec::new(&env),
         priority_config,
     };
 
@@ -229,32 +242,18 @@ fn test_contract_initialization() {
     let client = FeeContractClient::new(&env, &contract_id);
 
     client.initialize(&admin, &500u32);
+    let event =
+        last_fee_event(&env, symbol_short!("init")).expect("initialize event should be emitted");
+    assert_eq!(event, (admin.clone(), 500i128));
 
     let config = client.get_fee_config();
     assert_eq!(config.default_fee_rate, 500);
 
     let priority_config = client.get_priority_config();
-
-    // Initialize with 5% fee rate
-    FeeContract::initialize(env.clone(), admin.clone(), 500);
-
-    let config = FeeContract::get_fee_config(env.clone());
-    assert_eq!(config.default_fee_rate, 500);
-
-    let priority_config = FeeContract::get_priority_config(env.clone());
     assert_eq!(priority_config.low_multiplier_bps, 8000);
     assert_eq!(priority_config.medium_multiplier_bps, 10000);
     assert_eq!(priority_config.high_multiplier_bps, 15000);
     assert_eq!(priority_config.urgent_multiplier_bps, 20000);
-
-    let events = env.events().all();
-    let event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("init"))
-        .expect("initialize event should be emitted");
-    assert_eq!(event.topics.2, admin.clone());
-    assert_eq!(event.topics.3, 500i128);
 }
 
 #[test]
@@ -269,20 +268,6 @@ fn test_set_priority_multipliers() {
     client.set_priority_multipliers(&admin, &5000u32, &10000u32, &20000u32, &30000u32);
 
     let config = client.get_priority_config();
-
-    FeeContract::initialize(env.clone(), admin.clone(), 500);
-
-    // Set custom priority multipliers
-    FeeContract::set_priority_multipliers(
-        env.clone(),
-        admin.clone(),
-        5000,  // Low: 0.5x
-        10000, // Medium: 1.0x
-        20000, // High: 2.0x
-        30000, // Urgent: 3.0x
-    );
-
-    let config = FeeContract::get_priority_config(env.clone());
     assert_eq!(config.low_multiplier_bps, 5000);
     assert_eq!(config.medium_multiplier_bps, 10000);
     assert_eq!(config.high_multiplier_bps, 20000);
@@ -301,18 +286,6 @@ fn test_set_invalid_priority_multipliers_panics() {
     client.initialize(&admin, &500u32);
     // Descending order is invalid
     client.set_priority_multipliers(&admin, &30000u32, &20000u32, &10000u32, &5000u32);
-
-    FeeContract::initialize(env.clone(), admin.clone(), 500);
-
-    // Try to set invalid multipliers (descending order)
-    FeeContract::set_priority_multipliers(
-        env.clone(),
-        admin.clone(),
-        30000, // Low: 3.0x (higher than urgent!)
-        20000, // Medium: 2.0x
-        10000, // High: 1.0x
-        5000,  // Urgent: 0.5x
-    );
 }
 
 #[test]
@@ -326,13 +299,6 @@ fn test_get_priority_multiplier() {
     client.initialize(&admin, &500u32);
 
     assert_eq!(client.get_priority_multiplier(&PriorityLevel::Low), 8000);
-
-    FeeContract::initialize(env.clone(), admin.clone(), 500);
-
-    assert_eq!(
-        FeeContract::get_priority_multiplier(env.clone(), PriorityLevel::Low),
-        8000
-    );
     assert_eq!(
         client.get_priority_multiplier(&PriorityLevel::Medium),
         10000
@@ -371,29 +337,6 @@ fn test_calculate_fee_with_priority_contract() {
         client.calculate_fee_with_priority(&amount, &PriorityLevel::Urgent),
         2000
     );
-
-    FeeContract::initialize(env.clone(), admin.clone(), 1000); // 10% base rate
-
-    let amount = 10_000i128;
-
-    // Low: 10% * 0.8 = 8% => 800
-    let low_fee = FeeContract::calculate_fee_with_priority(env.clone(), amount, PriorityLevel::Low);
-    assert_eq!(low_fee, 800);
-
-    // Medium: 10% * 1.0 = 10% => 1000
-    let medium_fee =
-        FeeContract::calculate_fee_with_priority(env.clone(), amount, PriorityLevel::Medium);
-    assert_eq!(medium_fee, 1000);
-
-    // High: 10% * 1.5 = 15% => 1500
-    let high_fee =
-        FeeContract::calculate_fee_with_priority(env.clone(), amount, PriorityLevel::High);
-    assert_eq!(high_fee, 1500);
-
-    // Urgent: 10% * 2.0 = 20% => 2000
-    let urgent_fee =
-        FeeContract::calculate_fee_with_priority(env.clone(), amount, PriorityLevel::Urgent);
-    assert_eq!(urgent_fee, 2000);
 }
 
 #[test]
@@ -408,37 +351,14 @@ fn test_deduct_fee_with_priority() {
     client.initialize(&admin, &1000u32);
     let amount = 10_000i128;
     let (net, fee) = client.deduct_fee_with_priority(&payer, &amount, &PriorityLevel::High);
+    let event =
+        last_fee_event(&env, symbol_short!("deduct")).expect("deduction event should be emitted");
+    assert_eq!(event, (payer.clone(), amount));
 
     assert_eq!(fee, 1500);
     assert_eq!(net, 8500);
     assert_eq!(client.get_total_collected(), 1500);
     assert_eq!(client.get_user_fees_accrued(&payer), 1500);
-
-    FeeContract::initialize(env.clone(), admin.clone(), 1000); // 10% base rate
-
-    let amount = 10_000i128;
-
-    // Deduct with High priority (15% fee)
-    let (net, fee) = FeeContract::deduct_fee_with_priority(
-        env.clone(),
-        payer.clone(),
-        amount,
-        PriorityLevel::High,
-    );
-
-    assert_eq!(fee, 1500);
-    assert_eq!(net, 8500);
-    assert_eq!(FeeContract::get_total_collected(env.clone()), 1500);
-    assert_eq!(FeeContract::get_user_fees_accrued(env.clone(), payer.clone()), 1500);
-
-    let events = env.events().all();
-    let event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("deduct"))
-        .expect("deduction event should be emitted");
-    assert_eq!(event.topics.2, payer.clone());
-    assert_eq!(event.topics.3, amount);
 }
 
 #[test]
@@ -462,22 +382,6 @@ fn test_priority_fee_with_bounds() {
         client.calculate_fee_with_priority(&20000i128, &PriorityLevel::Urgent),
         2000
     );
-
-    FeeContract::initialize(env.clone(), admin.clone(), 1000);
-
-    // Set fee bounds
-    FeeContract::set_fee_bounds(env.clone(), admin.clone(), 500, 2000);
-
-    // Low priority would calculate to 400 (below min)
-    // Should be clamped to min 500
-    let low_fee = FeeContract::calculate_fee_with_priority(env.clone(), 5000, PriorityLevel::Low);
-    assert_eq!(low_fee, 500);
-
-    // Urgent priority would calculate to 4000 (above max)
-    // Should be clamped to max 2000
-    let urgent_fee =
-        FeeContract::calculate_fee_with_priority(env.clone(), 20000, PriorityLevel::Urgent);
-    assert_eq!(urgent_fee, 2000);
 }
 
 #[test]
@@ -497,23 +401,9 @@ fn test_priority_fee_events() {
             && e.1.get(1).and_then(|v| Symbol::try_from_val(&env, &v).ok())
                 == Some(symbol_short!("pri_cfg"))
     }));
-
-    FeeContract::initialize(env.clone(), admin.clone(), 1000);
-
-    // Set priority multipliers
-    FeeContract::set_priority_multipliers(env.clone(), admin.clone(), 5000, 10000, 15000, 20000);
-
-    // Check event was emitted
-    let events = env.events().all();
-    assert!(events.iter().any(|e| e.topics.0 == symbol_short!("fee") 
-        && e.topics.1 == symbol_short!("pri_cfg")));
-    let event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("pri_cfg"))
+    let event = last_fee_event(&env, symbol_short!("pri_cfg"))
         .expect("priority config event should be emitted");
-    assert_eq!(event.topics.2, admin.clone());
-    assert_eq!(event.topics.3, 10000i128);
+    assert_eq!(event, (admin.clone(), 10000i128));
 }
 
 // =============================================================================
@@ -528,6 +418,7 @@ fn test_zero_amount_fee() {
     let config = FeeConfig {
         default_fee_rate: 1000,
         windows: Vec::new(&env),
+        recurring_windows: Vec::new(&env),
         priority_config,
     };
     assert_eq!(
@@ -556,6 +447,7 @@ fn test_large_amount_with_priority() {
     let config = FeeConfig {
         default_fee_rate: 100,
         windows: Vec::new(&env),
+        recurring_windows: Vec::new(&env),
         priority_config,
     };
     let large_amount = 1_000_000_000_000i128;
@@ -589,29 +481,6 @@ fn test_custom_priority_multipliers() {
         client.calculate_fee_with_priority(&amount, &PriorityLevel::Urgent),
         5000
     );
-
-    FeeContract::initialize(env.clone(), admin.clone(), 1000);
-
-    // Set custom multipliers with larger spread
-    FeeContract::set_priority_multipliers(
-        env.clone(),
-        admin.clone(),
-        2500,  // Low: 0.25x (75% discount)
-        10000, // Medium: 1.0x
-        25000, // High: 2.5x (150% premium)
-        50000, // Urgent: 5.0x (400% premium)
-    );
-
-    let amount = 10_000i128;
-
-    // Low: 10% * 0.25 = 2.5% => 250
-    let low_fee = FeeContract::calculate_fee_with_priority(env.clone(), amount, PriorityLevel::Low);
-    assert_eq!(low_fee, 250);
-
-    // Urgent: 10% * 5.0 = 50% => 5000
-    let urgent_fee =
-        FeeContract::calculate_fee_with_priority(env.clone(), amount, PriorityLevel::Urgent);
-    assert_eq!(urgent_fee, 5000);
 }
 
 #[test]
@@ -637,50 +506,6 @@ fn test_multiple_priority_transactions() {
     assert_eq!(urgent_fee, 2000);
     assert_eq!(client.get_total_collected(), 5300);
     assert_eq!(client.get_user_fees_accrued(&payer), 5300);
-
-    FeeContract::initialize(env.clone(), admin.clone(), 1000);
-
-    // Execute transactions with different priorities
-    let (_, low_fee) = FeeContract::deduct_fee_with_priority(
-        env.clone(),
-        payer.clone(),
-        10_000,
-        PriorityLevel::Low,
-    );
-    assert_eq!(low_fee, 800);
-
-    let (_, medium_fee) = FeeContract::deduct_fee_with_priority(
-        env.clone(),
-        payer.clone(),
-        10_000,
-        PriorityLevel::Medium,
-    );
-    assert_eq!(medium_fee, 1000);
-
-    let (_, high_fee) = FeeContract::deduct_fee_with_priority(
-        env.clone(),
-        payer.clone(),
-        10_000,
-        PriorityLevel::High,
-    );
-    assert_eq!(high_fee, 1500);
-
-    let (_, urgent_fee) = FeeContract::deduct_fee_with_priority(
-        env.clone(),
-        payer.clone(),
-        10_000,
-        PriorityLevel::Urgent,
-    );
-    assert_eq!(urgent_fee, 2000);
-
-    // Total collected should be sum of all fees
-    assert_eq!(FeeContract::get_total_collected(env.clone()), 5300);
-
-    // User fees accrued should match
-    assert_eq!(
-        FeeContract::get_user_fees_accrued(env.clone(), payer.clone()),
-        5300
-    );
 }
 
 // =============================================================================
@@ -698,29 +523,15 @@ fn test_set_and_get_asset_fee_config() {
 
     client.initialize(&admin, &500u32);
     client.set_asset_fee_config(&admin, &asset, &200u32, &0i128, &0i128);
-    FeeContract::set_asset_fee_config(
-        env.clone(),
-        admin.clone(),
-        asset.clone(),
-        200, // 2% fee rate
-        0,   // no min fee
-        0,   // no max fee
-    );
+    let event = last_fee_event(&env, symbol_short!("ast_cfg"))
+        .expect("asset config event should be emitted");
+    assert_eq!(event, (admin.clone(), 200i128));
 
     let config = client.get_asset_fee_config(&asset);
     assert_eq!(config.fee_rate, 200);
     assert_eq!(config.min_fee, 0);
     assert_eq!(config.max_fee, 0);
     assert_eq!(config.asset, asset);
-
-    let events = env.events().all();
-    let event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("ast_cfg"))
-        .expect("asset config event should be emitted");
-    assert_eq!(event.topics.2, admin.clone());
-    assert_eq!(event.topics.3, 200i128);
 }
 
 #[test]
@@ -739,10 +550,6 @@ fn test_calculate_asset_fee_uses_asset_rate() {
         client.calculate_asset_fee(&asset, &10_000i128, &PriorityLevel::Medium),
         200
     );
-    // Medium priority (1.0x multiplier): 2% of 10000 = 200
-    let fee =
-        FeeContract::calculate_asset_fee(env.clone(), asset.clone(), 10_000, PriorityLevel::Medium);
-    assert_eq!(fee, 200);
 }
 
 #[test]
@@ -790,25 +597,6 @@ fn test_calculate_asset_fee_with_priority() {
         client.calculate_asset_fee(&asset, &amount, &PriorityLevel::Urgent),
         200
     );
-    // Low: 1% * 0.8 = 0.8% => 80
-    let low_fee =
-        FeeContract::calculate_asset_fee(env.clone(), asset.clone(), amount, PriorityLevel::Low);
-    assert_eq!(low_fee, 80);
-
-    // Medium: 1% * 1.0 = 1% => 100
-    let medium_fee =
-        FeeContract::calculate_asset_fee(env.clone(), asset.clone(), amount, PriorityLevel::Medium);
-    assert_eq!(medium_fee, 100);
-
-    // High: 1% * 1.5 = 1.5% => 150
-    let high_fee =
-        FeeContract::calculate_asset_fee(env.clone(), asset.clone(), amount, PriorityLevel::High);
-    assert_eq!(high_fee, 150);
-
-    // Urgent: 1% * 2.0 = 2% => 200
-    let urgent_fee =
-        FeeContract::calculate_asset_fee(env.clone(), asset.clone(), amount, PriorityLevel::Urgent);
-    assert_eq!(urgent_fee, 200);
 }
 
 #[test]
@@ -833,19 +621,6 @@ fn test_asset_fee_min_max_bounds() {
         client.calculate_asset_fee(&asset, &1_000_000i128, &PriorityLevel::Medium),
         500
     );
-    // 0.5% of 1000 = 5, below min of 100 -> clamped to 100
-    let fee_low =
-        FeeContract::calculate_asset_fee(env.clone(), asset.clone(), 1_000, PriorityLevel::Medium);
-    assert_eq!(fee_low, 100);
-
-    // 0.5% of 1_000_000 = 5000, above max of 500 -> clamped to 500
-    let fee_high = FeeContract::calculate_asset_fee(
-        env.clone(),
-        asset.clone(),
-        1_000_000,
-        PriorityLevel::Medium,
-    );
-    assert_eq!(fee_high, 500);
 }
 
 #[test]
@@ -867,6 +642,9 @@ fn test_deduct_asset_fee_tracks_balances_independently() {
         client.deduct_asset_fee(&payer, &xlm_asset, &10_000i128, &PriorityLevel::Medium);
     let (usdc_net, usdc_fee) =
         client.deduct_asset_fee(&payer, &usdc_asset, &10_000i128, &PriorityLevel::Medium);
+    let event = last_fee_event(&env, symbol_short!("ast_ded"))
+        .expect("asset deduction event should be emitted");
+    assert_eq!(event, (payer.clone(), 10_000i128));
 
     assert_eq!(xlm_fee, 100);
     assert_eq!(xlm_net, 9_900);
@@ -877,59 +655,6 @@ fn test_deduct_asset_fee_tracks_balances_independently() {
     assert_eq!(client.get_user_asset_fees_accrued(&payer, &xlm_asset), 100);
     assert_eq!(client.get_user_asset_fees_accrued(&payer, &usdc_asset), 200);
     assert_eq!(client.get_total_collected(), 300);
-    // Deduct XLM fee: 1% of 10000 = 100
-    let (xlm_net, xlm_fee) = FeeContract::deduct_asset_fee(
-        env.clone(),
-        payer.clone(),
-        xlm_asset.clone(),
-        10_000,
-        PriorityLevel::Medium,
-    );
-    assert_eq!(xlm_fee, 100);
-    assert_eq!(xlm_net, 9_900);
-
-    // Deduct USDC fee: 2% of 10000 = 200
-    let (usdc_net, usdc_fee) = FeeContract::deduct_asset_fee(
-        env.clone(),
-        payer.clone(),
-        usdc_asset.clone(),
-        10_000,
-        PriorityLevel::Medium,
-    );
-    assert_eq!(usdc_fee, 200);
-    assert_eq!(usdc_net, 9_800);
-
-    // Per-asset balances tracked independently
-    assert_eq!(
-        FeeContract::get_asset_fees_collected(env.clone(), xlm_asset.clone()),
-        100
-    );
-    assert_eq!(
-        FeeContract::get_asset_fees_collected(env.clone(), usdc_asset.clone()),
-        200
-    );
-
-    // Per-user per-asset fees tracked independently
-    assert_eq!(
-        FeeContract::get_user_asset_fees_accrued(env.clone(), payer.clone(), xlm_asset.clone()),
-        100
-    );
-    assert_eq!(
-        FeeContract::get_user_asset_fees_accrued(env.clone(), payer.clone(), usdc_asset.clone()),
-        200
-    );
-
-    // Global total includes both
-    assert_eq!(FeeContract::get_total_collected(env.clone()), 300);
-
-    let events = env.events().all();
-    let event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("ast_ded"))
-        .expect("asset deduction event should be emitted");
-    assert_eq!(event.topics.2, payer.clone());
-    assert_eq!(event.topics.3, 10_000i128);
 }
 
 #[test]
@@ -952,36 +677,6 @@ fn test_multiple_users_per_asset_tracked_independently() {
     assert_eq!(client.get_user_asset_fees_accrued(&payer_a, &asset), 100);
     assert_eq!(client.get_user_asset_fees_accrued(&payer_b, &asset), 200);
     assert_eq!(client.get_asset_fees_collected(&asset), 300);
-    FeeContract::deduct_asset_fee(
-        env.clone(),
-        payer_a.clone(),
-        asset.clone(),
-        10_000,
-        PriorityLevel::Medium,
-    );
-    FeeContract::deduct_asset_fee(
-        env.clone(),
-        payer_b.clone(),
-        asset.clone(),
-        20_000,
-        PriorityLevel::Medium,
-    );
-
-    // Each user's accrued fees tracked separately
-    assert_eq!(
-        FeeContract::get_user_asset_fees_accrued(env.clone(), payer_a.clone(), asset.clone()),
-        100
-    );
-    assert_eq!(
-        FeeContract::get_user_asset_fees_accrued(env.clone(), payer_b.clone(), asset.clone()),
-        200
-    );
-
-    // Asset total is the sum
-    assert_eq!(
-        FeeContract::get_asset_fees_collected(env.clone(), asset.clone()),
-        300
-    );
 }
 
 #[test]
@@ -1069,12 +764,6 @@ fn test_calculate_batch_fees_no_state_change() {
     // read-only: state must be unchanged
     assert_eq!(client.get_total_collected(), 0);
     assert_eq!(client.get_asset_fees_collected(&asset), 0);
-    // simulate is read-only — global total must still be zero
-    assert_eq!(FeeContract::get_total_collected(env.clone()), 0);
-    assert_eq!(
-        FeeContract::get_asset_fees_collected(env.clone(), asset.clone()),
-        0
-    );
 }
 
 #[test]
@@ -1101,14 +790,14 @@ fn test_deduct_batch_fees_aggregates_correctly() {
         10_000,
         PriorityLevel::Medium,
     )); // fee 100
-    // payer_b pays 2% on 5_000 USDC at medium priority  -> fee 100
+        // payer_b pays 2% on 5_000 USDC at medium priority  -> fee 100
     txs.push_back(make_tx(
         payer_b.clone(),
         usdc.clone(),
         5_000,
         PriorityLevel::Medium,
     )); // fee 100
-    // payer_a pays 1% * 2.0 (urgent) on 10_000 XLM     -> fee 200
+        // payer_a pays 1% * 2.0 (urgent) on 10_000 XLM     -> fee 200
     txs.push_back(make_tx(
         payer_a.clone(),
         xlm.clone(),
@@ -1140,26 +829,6 @@ fn test_deduct_batch_fees_single_transaction() {
     let client = FeeContractClient::new(&env, &contract_id);
 
     client.initialize(&admin, &200u32); // 2% default
-    // Per-asset balances tracked independently
-    assert_eq!(
-        FeeContract::get_asset_fees_collected(env.clone(), xlm.clone()),
-        300
-    );
-    assert_eq!(
-        FeeContract::get_asset_fees_collected(env.clone(), usdc.clone()),
-        100
-    );
-
-    // Per-user per-asset
-    assert_eq!(
-        FeeContract::get_user_asset_fees_accrued(env.clone(), payer_a.clone(), xlm.clone()),
-        300
-    );
-    assert_eq!(
-        FeeContract::get_user_asset_fees_accrued(env.clone(), payer_b.clone(), usdc.clone()),
-        100
-    );
-
     let mut txs: Vec<FeeTransaction> = Vec::new(&env);
     txs.push_back(FeeTransaction {
         payer: payer.clone(),
@@ -1352,12 +1021,6 @@ fn test_deduct_batch_fees_updates_user_global_balance() {
     let result = client.deduct_batch_fees(&txs);
     assert_eq!(result.total_fees, 330);
     assert_eq!(client.get_user_fees_accrued(&payer), 330);
-
-    // Per-user global balance reflects all three
-    assert_eq!(
-        FeeContract::get_user_fees_accrued(env.clone(), payer.clone()),
-        330
-    );
 }
 
 #[test]
@@ -1394,32 +1057,13 @@ fn test_deduct_batch_fees_emits_batch_event() {
             && e.1.get(1).and_then(|v| Symbol::try_from_val(&env, &v).ok())
                 == Some(symbol_short!("batch"))
     }));
+    let item_event =
+        last_fee_event(&env, symbol_short!("bat_itm")).expect("batch item event should be emitted");
+    assert_eq!(item_event, (payer.clone(), 10_000i128));
 
-    FeeContract::deduct_batch_fees(env.clone(), txs);
-
-    let events = env.events().all();
-    assert!(events.iter().any(|e| {
-        e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("batch")
-    }));
-    assert!(events.iter().any(|e| {
-        e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("bat_itm")
-    }));
-
-    let item_event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("bat_itm"))
-        .expect("batch item event should be emitted");
-    assert_eq!(item_event.topics.2, payer.clone());
-    assert_eq!(item_event.topics.3, 10_000i128);
-
-    let summary_event = events
-        .iter()
-        .rev()
-        .find(|e| e.topics.0 == symbol_short!("fee") && e.topics.1 == symbol_short!("batch"))
+    let summary_event = last_fee_event(&env, symbol_short!("batch"))
         .expect("batch summary event should be emitted");
-    assert_eq!(summary_event.topics.2, payer.clone());
-    assert_eq!(summary_event.topics.3, 200i128);
+    assert_eq!(summary_event, (payer.clone(), 200i128));
 }
 
 #[test]
@@ -1443,8 +1087,6 @@ fn test_deduct_batch_fees_rejects_zero_amount() {
         PriorityLevel::Medium,
     ));
     client.deduct_batch_fees(&txs);
-
-    FeeContract::deduct_batch_fees(env.clone(), txs);
 }
 
 #[test]
@@ -1471,14 +1113,14 @@ fn test_calculate_batch_fees_mixed_assets_and_priorities() {
         20_000,
         PriorityLevel::Medium,
     )); // 0.5%*1.0=100
-    // USDC 3% * 2.0 (urgent) of 10000 = 600
+        // USDC 3% * 2.0 (urgent) of 10000 = 600
     txs.push_back(make_tx(
         payer.clone(),
         usdc.clone(),
         10_000,
         PriorityLevel::Urgent,
     )); // 3%*2.0=600
-    // unconfigured falls back to 1% default, low priority 0.8 = 0.8% of 5000 = 40
+        // unconfigured falls back to 1% default, low priority 0.8 = 0.8% of 5000 = 40
     txs.push_back(make_tx(
         payer.clone(),
         unconfigured.clone(),
@@ -1486,10 +1128,543 @@ fn test_calculate_batch_fees_mixed_assets_and_priorities() {
         PriorityLevel::Low,
     )); // 1%*0.8=40
 
-    let result = FeeContract::calculate_batch_fees(env.clone(), txs);
+    let result = client.calculate_batch_fees(&txs);
 
     assert_eq!(result.results.get(0).unwrap().fee, 100);
     assert_eq!(result.results.get(1).unwrap().fee, 600);
     assert_eq!(result.results.get(2).unwrap().fee, 40);
     assert_eq!(result.total_fees, 740);
 }
+
+// =============================================================================
+// Recurring Fee Window Tests
+// =============================================================================
+
+/// 2024-01-01 00:00:00 UTC, a Monday.
+const MONDAY_2024_01_01: u64 = 1_704_067_200;
+
+fn window_config(env: &Env) -> FeeConfig {
+    FeeConfig {
+        default_fee_rate: 500,
+        windows: Vec::new(env),
+        recurring_windows: Vec::new(env),
+        priority_config: PriorityFeeConfig::default(),
+    }
+}
+
+fn recurring(
+    days: u32,
+    start_hour: u32,
+    end_hour: u32,
+    fee_rate: u32,
+    priority: u32,
+) -> RecurringFeeWindow {
+    RecurringFeeWindow {
+        days,
+        start_second: start_hour * 3_600,
+        end_second: end_hour * 3_600,
+        fee_rate,
+        priority,
+    }
+}
+
+#[test]
+fn test_day_of_week() {
+    assert_eq!(day_of_week(MONDAY_2024_01_01), 0);
+    assert_eq!(day_of_week(MONDAY_2024_01_01 + 5 * SECONDS_PER_DAY), 5);
+    assert_eq!(day_of_week(MONDAY_2024_01_01 + 7 * SECONDS_PER_DAY - 1), 6);
+}
+
+#[test]
+fn test_weekend_window_waives_fees() {
+    let env = Env::default();
+    let mut config = window_config(&env);
+    config
+        .recurring_windows
+        .push_back(recurring(WEEKEND, 0, 24, 0, 1));
+
+    let saturday_noon = MONDAY_2024_01_01 + 5 * SECONDS_PER_DAY + 12 * 3_600;
+    let friday_noon = MONDAY_2024_01_01 + 4 * SECONDS_PER_DAY + 12 * 3_600;
+
+    assert_eq!(resolve_window_fee_rate(&config, saturday_noon), 0);
+    assert_eq!(resolve_window_fee_rate(&config, friday_noon), 500);
+}
+
+#[test]
+fn test_time_of_day_window_end_is_exclusive() {
+    let env = Env::default();
+    let mut config = window_config(&env);
+    config
+        .recurring_windows
+        .push_back(recurring(ALL_DAYS, 0, 6, 100, 1));
+
+    assert_eq!(resolve_window_fee_rate(&config, MONDAY_2024_01_01), 100);
+    assert_eq!(
+        resolve_window_fee_rate(&config, MONDAY_2024_01_01 + 6 * 3_600 - 1),
+        100
+    );
+    assert_eq!(
+        resolve_window_fee_rate(&config, MONDAY_2024_01_01 + 6 * 3_600),
+        500
+    );
+}
+
+#[test]
+fn test_overlapping_windows_resolve_by_priority() {
+    let env = Env::default();
+    let mut config = window_config(&env);
+    config
+        .recurring_windows
+        .push_back(recurring(ALL_DAYS, 0, 6, 100, 1));
+    config
+        .recurring_windows
+        .push_back(recurring(WEEKEND, 0, 24, 0, 2));
+
+    let saturday_early = MONDAY_2024_01_01 + 5 * SECONDS_PER_DAY + 3_600;
+    assert_eq!(resolve_window_fee_rate(&config, saturday_early), 0);
+
+    // A higher priority one-off window beats both recurring windows.
+    config.windows.push_back(FeeWindow {
+        start: saturday_early - 60,
+        end: saturday_early + 60,
+        fee_rate: 250,
+        priority: 3,
+    });
+    assert_eq!(resolve_window_fee_rate(&config, saturday_early), 250);
+
+    // On equal priority the one-off window wins.
+    config
+        .recurring_windows
+        .push_back(recurring(WEEKEND, 0, 24, 50, 3));
+    assert_eq!(resolve_window_fee_rate(&config, saturday_early), 250);
+}
+
+#[test]
+fn test_calculate_fee_with_priority_uses_recurring_window() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let env = Env::default();
+    env.ledger()
+        .with_mut(|li| li.timestamp = MONDAY_2024_01_01 + 3_600);
+    let mut config = window_config(&env);
+    config
+        .recurring_windows
+        .push_back(recurring(MONDAY, 0, 6, 100, 1));
+
+    // 1% * 1.5 (high) of 10000 = 150
+    let fee = calculate_fee_with_priority(&env, 10_000, &config, PriorityLevel::High);
+    assert_eq!(fee, 150);
+}
+
+#[test]
+fn test_validate_recurring_fee_window() {
+    assert!(validate_recurring_fee_window(&recurring(
+        WEEKDAYS, 0, 24, 100, 0
+    )));
+    assert!(!validate_recurring_fee_window(&recurring(0, 0, 6, 100, 0)));
+    assert!(!validate_recurring_fee_window(&recurring(
+        1 << 7,
+        0,
+        6,
+        100,
+        0
+    )));
+    assert!(!validate_recurring_fee_window(&recurring(
+        ALL_DAYS, 6, 6, 100, 0
+    )));
+    assert!(!validate_recurring_fee_window(&recurring(
+        ALL_DAYS, 0, 25, 100, 0
+    )));
+    assert!(!validate_recurring_fee_window(&recurring(
+        ALL_DAYS, 24, 6, 100, 0
+    )));
+    assert!(!validate_recurring_fee_window(&recurring(
+        ALL_DAYS, 0, 6, 10_001, 0
+    )));
+    assert!(validate_recurring_fee_window(&recurring(
+        ALL_DAYS, 22, 6, 100, 0
+    )));
+}
+
+#[test]
+fn test_overnight_window_runs_into_the_next_day() {
+    let env = Env::default();
+    let mut config = window_config(&env);
+    // Fridays 22:00 until Saturday 06:00
+    config
+        .recurring_windows
+        .push_back(recurring(FRIDAY, 22, 6, 100, 1));
+
+    let friday = MONDAY_2024_01_01 + 4 * SECONDS_PER_DAY;
+    let saturday = friday + SECONDS_PER_DAY;
+
+    assert_eq!(
+        resolve_window_fee_rate(&config, friday + 22 * 3_600 - 1),
+        500
+    );
+    assert_eq!(resolve_window_fee_rate(&config, friday + 22 * 3_600), 100);
+    assert_eq!(resolve_window_fee_rate(&config, saturday + 3_600), 100);
+    assert_eq!(resolve_window_fee_rate(&config, saturday + 6 * 3_600), 500);
+    // Friday's early hours belong to Thursday's window, which is not selected
+    assert_eq!(resolve_window_fee_rate(&config, friday + 3_600), 500);
+    assert_eq!(resolve_window_fee_rate(&config, saturday + 23 * 3_600), 500);
+}
+
+#[test]
+fn test_overnight_window_on_sunday_wraps_to_monday() {
+    let env = Env::default();
+    let mut config = window_config(&env);
+    config
+        .recurring_windows
+        .push_back(recurring(SUNDAY, 20, 2, 0, 1));
+
+    let next_monday = MONDAY_2024_01_01 + 7 * SECONDS_PER_DAY;
+    assert_eq!(resolve_window_fee_rate(&config, next_monday + 3_600), 0);
+    assert_eq!(resolve_window_fee_rate(&config, next_monday - 3_600), 0);
+    assert_eq!(
+        resolve_window_fee_rate(&config, next_monday + 2 * 3_600),
+        500
+    );
+}
+
+#[test]
+fn test_set_fee_windows_updates_effective_rate() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+
+    let mut recurring_windows = Vec::new(&env);
+    recurring_windows.push_back(recurring(WEEKEND, 0, 24, 0, 1));
+    client.set_fee_windows(&admin, &Vec::new(&env), &recurring_windows);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = MONDAY_2024_01_01 + 6 * SECONDS_PER_DAY);
+    assert_eq!(client.get_effective_fee_rate(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = MONDAY_2024_01_01);
+    assert_eq!(client.get_effective_fee_rate(), 500);
+}
+
+#[test]
+fn test_deduct_fee_charges_the_overnight_window_rate() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let payer = Address::generate(&env);
+
+    let mut one_off = Vec::new(&env);
+    let saturday = MONDAY_2024_01_01 + 5 * SECONDS_PER_DAY;
+    one_off.push_back(FeeWindow {
+        start: saturday + 3_600,
+        end: saturday + 2 * 3_600,
+        fee_rate: 300,
+        priority: 2,
+    });
+    let mut recurring_windows = Vec::new(&env);
+    recurring_windows.push_back(recurring(FRIDAY, 22, 6, 100, 1));
+    client.set_fee_windows(&admin, &one_off, &recurring_windows);
+
+    // Saturday 00:30 falls in Friday's overnight window: 1% * 1.5 (high)
+    env.ledger().with_mut(|li| li.timestamp = saturday + 1_800);
+    let (_, fee) = client.deduct_fee_with_priority(&payer, &10_000, &PriorityLevel::High);
+    assert_eq!(fee, 150);
+
+    // The higher priority one-off window wins from 01:00
+    env.ledger().with_mut(|li| li.timestamp = saturday + 3_600);
+    assert_eq!(client.get_effective_fee_rate(), 300);
+
+    // Back to the default rate once the overnight window closes at 06:00
+    env.ledger()
+        .with_mut(|li| li.timestamp = saturday + 6 * 3_600);
+    let (_, fee) = client.deduct_fee_with_priority(&payer, &10_000, &PriorityLevel::Medium);
+    assert_eq!(fee, 500);
+}
+
+#[test]
+fn test_set_fee_windows_rejects_invalid_recurring_window() {
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+
+    let mut recurring_windows = Vec::new(&env);
+    recurring_windows.push_back(recurring(0, 22, 6, 100, 1));
+    let result = client.try_set_fee_windows(&admin, &Vec::new(&env), &recurring_windows);
+
+    assert_eq!(result.err(), Some(Ok(FeeError::InvalidFeeWindow.into())));
+    assert_eq!(client.get_fee_config().recurring_windows.len(), 0);
+}
+
+// =============================================================================
+// User Fee Override Tests
+// =============================================================================
+
+#[test]
+fn test_user_fee_override_applies_until_expiry() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let user = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let granted = client.admin_set_user_fee_override(&admin, &user, &100u32, &2_000u64, &7u32);
+    assert_eq!(granted.granted_by, admin);
+    assert_eq!(granted.granted_at, 1_000);
+    assert_eq!(granted.reason_code, 7);
+
+    // 1% of 10000 = 100 while active
+    assert_eq!(client.simulate_fee(&10_000, &user), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.simulate_fee(&10_000, &user), 500);
+    assert!(client.get_user_fee_override(&user).is_some());
+}
+
+#[test]
+fn test_user_fee_override_rejects_past_expiry() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let user = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let result = client.try_admin_set_user_fee_override(&admin, &user, &100u32, &1_000u64, &1u32);
+    assert_eq!(result, Err(Ok(FeeError::InvalidOverrideExpiry.into())));
+}
+
+#[test]
+fn test_user_fee_overrides_are_logged_and_listed() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.admin_set_user_fee_override(&admin, &alice, &100u32, &5_000u64, &1u32);
+    client.admin_set_user_fee_override(&admin, &bob, &200u32, &3_000u64, &2u32);
+    client.admin_set_user_fee_override(&admin, &alice, &50u32, &9_000u64, &3u32);
+
+    // All three expire on the first UTC day
+    let by_expiry = client.list_fee_overrides_expiring_on(&0u64);
+    assert_eq!(by_expiry.len(), 2);
+    assert_eq!(by_expiry.get(0).unwrap().user, bob);
+    assert_eq!(by_expiry.get(1).unwrap().fee_bps, 50);
+
+    let history = client.get_user_fee_override_history(&alice);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().reason_code, 1);
+
+    assert_eq!(client.get_fee_log_count(), 3);
+    let log = client.get_fee_log(&3u64).unwrap();
+    assert_eq!(log.payer, Some(alice.clone()));
+    assert_eq!(log.fee_amount, 0);
+    let FeeLogKind::OverrideSet(granted) = log.kind else {
+        panic!("expected an override grant");
+    };
+    assert_eq!(granted.fee_bps, 50);
+    assert_eq!(granted.granted_by, admin);
+
+    client.admin_remove_user_fee_override(&admin, &alice);
+    assert_eq!(client.get_user_fee_override(&alice), None);
+    let logs = client.get_fee_logs(&1u64, &10u64);
+    assert_eq!(logs.len(), 4);
+    assert_eq!(
+        logs.get(3).unwrap().kind,
+        FeeLogKind::OverrideRemoved(granted, Some(admin.clone()))
+    );
+    assert_eq!(client.list_fee_overrides_expiring_on(&0u64).len(), 1);
+}
+
+#[test]
+fn test_cleanup_expired_fee_overrides() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let users = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.admin_set_user_fee_override(&admin, &users[0], &100u32, &2_000u64, &1u32);
+    client.admin_set_user_fee_override(&admin, &users[1], &100u32, &3_000u64, &1u32);
+    client.admin_set_user_fee_override(&admin, &users[2], &100u32, &9_000u64, &1u32);
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    assert_eq!(client.cleanup_expired_fee_overrides(&1u32), 1);
+    assert_eq!(client.cleanup_expired_fee_overrides(&10u32), 1);
+    assert_eq!(client.cleanup_expired_fee_overrides(&10u32), 0);
+
+    let remaining = client.list_fee_overrides_expiring_on(&0u64);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining.get(0).unwrap().user, users[2]);
+    let swept = client.get_fee_log(&5u64).unwrap();
+    assert!(matches!(swept.kind, FeeLogKind::OverrideRemoved(_, None)));
+}
+
+#[test]
+fn test_cleanup_walks_a_bounded_number_of_days() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.admin_set_user_fee_override(&admin, &early, &100u32, &(3 * SECONDS_PER_DAY), &1u32);
+    client.admin_set_user_fee_override(&admin, &late, &100u32, &(40 * SECONDS_PER_DAY), &1u32);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 50 * SECONDS_PER_DAY);
+    // Stops MAX_OVERRIDE_CLEANUP_DAYS past day 3, before reaching day 40
+    assert_eq!(client.cleanup_expired_fee_overrides(&10u32), 1);
+    assert_eq!(client.cleanup_expired_fee_overrides(&10u32), 1);
+    assert_eq!(client.get_user_fee_override(&late), None);
+}
+
+// =============================================================================
+// Fee Log Aggregate Tests
+// =============================================================================
+
+#[test]
+fn test_fee_periods_use_utc_calendar_dates() {
+    let (env, _admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+
+    // 2024-02-29 23:59:59 UTC
+    let (day, month) = client.get_fee_periods(&1_709_251_199u64);
+    assert_eq!(day, FeePeriod::Day(20240229));
+    assert_eq!(month, FeePeriod::Month(202402));
+
+    let (day, month) = client.get_fee_periods(&1_709_251_200u64);
+    assert_eq!(day, FeePeriod::Day(20240301));
+    assert_eq!(month, FeePeriod::Month(202403));
+}
+
+#[test]
+fn test_fee_log_totals_by_period_kind_asset_and_user() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let usdc = Address::generate(&env);
+
+    // 2024-01-01 and 2024-01-02
+    env.ledger().with_mut(|li| li.timestamp = MONDAY_2024_01_01);
+    client.deduct_fee_with_priority(&alice, &10_000, &PriorityLevel::Medium);
+    client.record_fee_refund(&admin, &alice, &Some(usdc.clone()), &2_000, &20);
+    env.ledger()
+        .with_mut(|li| li.timestamp = MONDAY_2024_01_01 + SECONDS_PER_DAY);
+    client.deduct_fee_with_priority(&bob, &20_000, &PriorityLevel::Medium);
+
+    let day_one = client.get_fee_totals(&FeePeriod::Day(20240101), &FeeLogKind::Charge);
+    assert_eq!(day_one.count, 1);
+    assert_eq!(day_one.fee_amount, 500);
+
+    let january = client.get_fee_totals(&FeePeriod::Month(202401), &FeeLogKind::Charge);
+    assert_eq!(january.count, 2);
+    assert_eq!(january.gross_amount, 30_000);
+    assert_eq!(january.fee_amount, 1_500);
+
+    let refunds =
+        client.get_fee_totals_by_asset(&FeePeriod::Month(202401), &FeeLogKind::Refund, &Some(usdc));
+    assert_eq!(refunds.fee_amount, 20);
+    let default_asset =
+        client.get_fee_totals_by_asset(&FeePeriod::Month(202401), &FeeLogKind::Charge, &None);
+    assert_eq!(default_asset.fee_amount, 1_500);
+
+    let bob_totals =
+        client.get_user_fee_totals(&FeePeriod::Month(202401), &FeeLogKind::Charge, &bob);
+    assert_eq!(bob_totals.fee_amount, 1_000);
+    let bob_day_one =
+        client.get_user_fee_totals(&FeePeriod::Day(20240101), &FeeLogKind::Charge, &bob);
+    assert_eq!(bob_day_one, FeeLogTotals::default());
+}
+
+#[test]
+fn test_asset_and_batch_charges_feed_asset_totals() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &100u32);
+    let payer = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    client.set_asset_fee_config(&admin, &usdc, &200u32, &0i128, &0i128);
+    env.ledger().with_mut(|li| li.timestamp = MONDAY_2024_01_01);
+
+    client.deduct_asset_fee(&payer, &usdc, &10_000, &PriorityLevel::Medium);
+    let mut txs: Vec<FeeTransaction> = Vec::new(&env);
+    txs.push_back(make_tx(
+        payer.clone(),
+        usdc.clone(),
+        5_000,
+        PriorityLevel::Medium,
+    ));
+    client.deduct_batch_fees(&txs);
+    let refund = client.record_fee_refund(&admin, &payer, &Some(usdc.clone()), &5_000, &100);
+    assert_eq!(refund.kind, FeeLogKind::Refund);
+    assert_eq!(refund.asset, Some(usdc.clone()));
+
+    let today = FeePeriod::Day(20240101);
+    let charges = client.get_fee_totals_by_asset(&today, &FeeLogKind::Charge, &Some(usdc.clone()));
+    assert_eq!(charges.count, 2);
+    assert_eq!(charges.gross_amount, 15_000);
+    assert_eq!(charges.fee_amount, 300);
+    let refunds = client.get_user_fee_totals(&today, &FeeLogKind::Refund, &payer);
+    assert_eq!(refunds.fee_amount, 100);
+    assert_eq!(
+        client.get_fee_totals_by_asset(&today, &FeeLogKind::Charge, &None),
+        FeeLogTotals::default()
+    );
+}
+
+#[test]
+fn test_record_fee_refund_requires_admin() {
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let payer = Address::generate(&env);
+
+    let result = client.try_record_fee_refund(&payer, &payer, &None, &2_000, &20);
+    assert_eq!(result.err(), Some(Ok(FeeError::Unauthorized.into())));
+    assert_eq!(client.get_fee_log_count(), 0);
+}
+
+#[test]
+fn test_fee_overrides_are_left_out_of_fee_totals() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, contract_id) = setup_contract();
+    let client = FeeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &500u32);
+    let user = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = MONDAY_2024_01_01);
+
+    client.admin_set_user_fee_override(&admin, &user, &100u32, &(MONDAY_2024_01_01 + 60), &1u32);
+    client.deduct_fee_with_priority(&user, &10_000, &PriorityLevel::Medium);
+
+    assert_eq!(client.get_fee_log_count(), 2);
+    let totals = client.get_fee_totals(&FeePeriod::Day(20240101), &FeeLogKind::Charge);
+    assert_eq!(totals.count, 1);
+    assert_eq!(totals.fee_amount, 100);
+    let user_totals =
+        client.get_user_fee_totals(&FeePeriod::Day(20240101), &FeeLogKind::Charge, &user);
+    assert_eq!(user_totals.count, 1);
+}
@@ -2,8 +2,9 @@ use soroban_sdk::{Address, Env, Map, Symbol, Vec};
 
 use crate::types::{
     AuditLog, BatchMetrics, BundleResult, BundledTransaction, CategoryMetrics, DataKey,
-    MonthlySpendingAnalytics, RefundBatchMetrics, RefundRequest, RefundResult, RefundStatus,
-    Transaction, UserSpendingSummary, ValidationResult, MAX_BATCH_SIZE,
+    MerchantReputation, MonthlySpendingAnalytics, RefundBatchMetrics, RefundRequest, RefundResult,
+    RefundStatus, Transaction, UserSpendingSummary, ValidationResult, BUNDLE_EXECUTION_WINDOW,
+    MAX_BATCH_SIZE, RATING_BUCKETS, REPUTATION_RECENCY_WEIGHT_BPS, REPUTATION_SCALE,
 };
 
 /// Calculates the processing fee for a transaction amount.
@@ -73,7 +74,7 @@ pub fn compute_batch_metrics(
         total_volume = total_volume.checked_add(tx.amount).unwrap_or(i128::MAX);
 
         // Calculate and accumulate fees (operation-specific)
-        let fee = calculate_fee(&env, tx.amount, &tx.category);
+        let fee = calculate_fee(env, tx.amount, &tx.category);
         total_fees = total_fees.checked_add(fee).unwrap_or(i128::MAX);
 
         // Track min/max
@@ -123,7 +124,7 @@ pub fn compute_category_metrics(
     // Single pass to aggregate by category
     for tx in transactions.iter() {
         let current = category_map.get(tx.category.clone()).unwrap_or((0, 0, 0));
-        let fee = calculate_fee(&env, tx.amount, &tx.category);
+        let fee = calculate_fee(env, tx.amount, &tx.category);
         category_map.set(
             tx.category.clone(),
             (
@@ -202,7 +203,7 @@ pub fn validate_batch(transactions: &Vec<Transaction>) -> Result<(), &'static st
 
 /// Validates a batch of audit logs.
 pub fn validate_audit_logs(logs: &Vec<AuditLog>) -> Result<(), &'static str> {
-    if logs.len() == 0 {
+    if logs.is_empty() {
         return Err("Audit logs batch cannot be empty");
     }
 
@@ -364,7 +365,7 @@ pub fn compute_refund_metrics(
     };
 
     RefundBatchMetrics {
-        request_count: request_count as u32,
+        request_count,
         successful_refunds,
         failed_refunds,
         total_refunded_amount,
@@ -440,14 +441,13 @@ pub fn create_bundle_result(
     validation_results: &Vec<ValidationResult>,
     created_at: u64,
 ) -> BundleResult {
-    let total_count = bundled_transactions.len() as u32;
+    let total_count = bundled_transactions.len();
     let mut valid_count: u32 = 0;
     let mut invalid_count: u32 = 0;
     let mut total_volume: i128 = 0;
 
     // Count valid/invalid and compute total volume of valid transactions
-    let mut index: u32 = 0;
-    for result in validation_results.iter() {
+    for (index, result) in (0u32..).zip(validation_results.iter()) {
         if result.is_valid {
             valid_count += 1;
             if let Some(bundled_tx) = bundled_transactions.get(index) {
@@ -458,7 +458,6 @@ pub fn create_bundle_result(
         } else {
             invalid_count += 1;
         }
-        index += 1;
     }
 
     let can_bundle = valid_count > 0 && invalid_count == 0;
@@ -475,12 +474,83 @@ pub fn create_bundle_result(
     }
 }
 
+/// Checks whether a validated bundle can no longer be executed as validated.
+///
+/// A bundle goes stale once its execution window has passed or when any of its
+/// transactions has been refunded since it was validated.
+pub fn is_bundle_stale(
+    bundle: &BundleResult,
+    bundled_transactions: &Vec<BundledTransaction>,
+    refunded_txs: &Map<u64, bool>,
+    current_ledger: u64,
+) -> bool {
+    if current_ledger > bundle.created_at.saturating_add(BUNDLE_EXECUTION_WINDOW) {
+        return true;
+    }
+
+    bundled_transactions
+        .iter()
+        .any(|bundled_tx| refunded_txs.contains_key(bundled_tx.transaction.tx_id))
+}
+
+/// Folds a new rating into a merchant's reputation.
+///
+/// The recency-weighted score is an exponential moving average where the newest
+/// rating carries `REPUTATION_RECENCY_WEIGHT_BPS` of the weight, so recent
+/// experiences move the score faster than the plain average.
+pub fn apply_rating_to_reputation(
+    env: &Env,
+    existing: Option<MerchantReputation>,
+    merchant: &Address,
+    score: u32,
+    rated_at: u64,
+) -> MerchantReputation {
+    let mut reputation = existing.unwrap_or_else(|| {
+        let mut distribution = Vec::new(env);
+        for _ in 0..RATING_BUCKETS {
+            distribution.push_back(0u32);
+        }
+        MerchantReputation {
+            merchant: merchant.clone(),
+            rating_count: 0,
+            total_score: 0,
+            average_score: 0,
+            weighted_score: 0,
+            distribution,
+            last_rated_at: 0,
+        }
+    });
+
+    let scaled_score = score * REPUTATION_SCALE;
+
+    reputation.weighted_score = if reputation.rating_count == 0 {
+        scaled_score
+    } else {
+        let previous_weight = 10_000 - REPUTATION_RECENCY_WEIGHT_BPS;
+        ((reputation.weighted_score as u64 * previous_weight as u64
+            + scaled_score as u64 * REPUTATION_RECENCY_WEIGHT_BPS as u64)
+            / 10_000) as u32
+    };
+
+    reputation.rating_count += 1;
+    reputation.total_score += score as u64;
+    reputation.average_score = ((reputation.total_score * REPUTATION_SCALE as u64)
+        / reputation.rating_count as u64) as u32;
+
+    let bucket = score - 1;
+    let bucket_count = reputation.distribution.get(bucket).unwrap_or(0);
+    reputation.distribution.set(bucket, bucket_count + 1);
+    reputation.last_rated_at = rated_at;
+
+    reputation
+}
+
 /// Validates a batch of refund requests.
 pub fn validate_refund_batch(
     env: &Env,
     refund_requests: &Vec<RefundRequest>,
 ) -> Result<(), &'static str> {
-    let count = refund_requests.len() as u32;
+    let count = refund_requests.len();
 
     if count == 0 {
         return Err("Refund batch cannot be empty");
@@ -566,7 +636,7 @@ fn update_category_spending(
             if i != index {
                 new_vec.push_back(category_spending.get(i).unwrap());
             } else {
-                new_vec.push_back((category, new_amount));
+                new_vec.push_back((category.clone(), new_amount));
             }
         }
         *category_spending = new_vec;
@@ -579,7 +649,7 @@ fn update_category_spending(
 /// Updates or creates monthly analytics in storage
 pub fn update_monthly_analytics_storage(env: &Env, analytics: &MonthlySpendingAnalytics) {
     let key = DataKey::MonthlyAnalytics(analytics.year, analytics.month, analytics.user.clone());
-    env.storage().persistent().set(&key, &analytics);
+    env.storage().persistent().set(&key, analytics);
 
     // Update total tracked users if this is a new user
     let mut total_users: u64 = env
@@ -597,9 +667,10 @@ pub fn update_monthly_analytics_storage(env: &Env, analytics: &MonthlySpendingAn
     }
 
     // Update last analytics update timestamp
-    env.storage()
-        .instance()
-        .set(&DataKey::LastAnalyticsUpdate, &env.ledger().sequence());
+    env.storage().instance().set(
+        &DataKey::LastAnalyticsUpdate,
+        &u64::from(env.ledger().sequence()),
+    );
 }
 
 /// Computes aggregated analytics for all users and categories
@@ -671,9 +742,7 @@ pub fn compute_user_spending_summary(
             total_spending = total_spending
                 .checked_add(analytics.total_spending)
                 .unwrap_or(i128::MAX);
-            total_transactions = total_transactions
-                .checked_add(analytics.transaction_count)
-                .unwrap_or(u32::MAX);
+            total_transactions = total_transactions.saturating_add(analytics.transaction_count);
 
             // Aggregate category spending across months
             for (category, amount) in analytics.category_spending.iter() {
@@ -692,7 +761,7 @@ pub fn compute_user_spending_summary(
         }
     }
 
-    let avg_monthly_spending = if monthly_analytics.len() > 0 {
+    let avg_monthly_spending = if !monthly_analytics.is_empty() {
         total_spending / (monthly_analytics.len() as i128)
     } else {
         0
@@ -712,8 +781,14 @@ pub fn compute_user_spending_summary(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TransactionAnalyticsContract;
     use soroban_sdk::{testutils::Address as _, Env};
 
+    fn in_contract<T>(env: &Env, f: impl FnOnce() -> T) -> T {
+        let contract_id = env.register(TransactionAnalyticsContract, ());
+        env.as_contract(&contract_id, f)
+    }
+
     fn create_test_transaction(env: &Env, tx_id: u64, amount: i128, category: &str) -> Transaction {
         Transaction {
             tx_id,
@@ -731,7 +806,7 @@ mod tests {
         let mut transactions: Vec<Transaction> = Vec::new(&env);
         transactions.push_back(create_test_transaction(&env, 1, 1000, "transfer"));
 
-        let metrics = compute_batch_metrics(&env, &transactions, 100);
+        let metrics = in_contract(&env, || compute_batch_metrics(&env, &transactions, 100));
 
         assert_eq!(metrics.tx_count, 1);
         assert_eq!(metrics.total_volume, 1000);
@@ -750,7 +825,7 @@ mod tests {
         transactions.push_back(create_test_transaction(&env, 2, 200, "transfer"));
         transactions.push_back(create_test_transaction(&env, 3, 300, "budget"));
 
-        let metrics = compute_batch_metrics(&env, &transactions, 100);
+        let metrics = in_contract(&env, || compute_batch_metrics(&env, &transactions, 100));
 
         assert_eq!(metrics.tx_count, 3);
         assert_eq!(metrics.total_volume, 600);
@@ -764,7 +839,7 @@ mod tests {
         let env = Env::default();
         let transactions: Vec<Transaction> = Vec::new(&env);
 
-        let metrics = compute_batch_metrics(&env, &transactions, 100);
+        let metrics = in_contract(&env, || compute_batch_metrics(&env, &transactions, 100));
 
         assert_eq!(metrics.tx_count, 0);
         assert_eq!(metrics.total_volume, 0);
@@ -778,7 +853,8 @@ mod tests {
         transactions.push_back(create_test_transaction(&env, 2, 300, "transfer"));
         transactions.push_back(create_test_transaction(&env, 3, 200, "budget"));
 
-        let category_metrics = compute_category_metrics(&env, &transactions, 1000);
+        let category_metrics =
+            in_contract(&env, || compute_category_metrics(&env, &transactions, 1000));
 
         assert_eq!(category_metrics.len(), 2);
     }
@@ -840,4 +916,4 @@ mod tests {
         // Same batch should produce same checksum
         assert_eq!(checksum1, checksum2);
     }
-}
\ No newline at end of file
+}
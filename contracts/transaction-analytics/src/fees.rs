@@ -1,3 +1,15 @@
+//! # Fee Calculation Engine
+//!
+//! Implements dynamic fee calculation for transactions with configurable fee structures.
+//! Supports percentage-based fees, tiered pricing, and automatic fee deductions.
+
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{
+    AnalyticsEvents, DataKey, FeeCalculationResult, FeeConfig, FeeRecipientShare, FeeTier,
+    ValidationError,
+};
+
 // =====================
 // FEE SYSTEM EXTENSIONS
 // =====================
@@ -10,7 +22,10 @@
 // See each function for details and usage.
 /// Returns true if fees are currently paused.
 pub fn is_fee_paused(env: &Env) -> bool {
-    env.storage().instance().get(&crate::types::DataKey::FeePaused).unwrap_or(false)
+    env.storage()
+        .instance()
+        .get(&crate::types::DataKey::FeePaused)
+        .unwrap_or(false)
 }
 
 /// Sets the fee pause flag (admin only).
@@ -24,16 +39,19 @@ pub fn set_fee_paused(env: &Env, admin: &Address, paused: bool) -> Result<(), Va
     if *admin != stored_admin {
         return Err(ValidationError::InvalidAddress);
     }
-    env.storage().instance().set(&crate::types::DataKey::FeePaused, &paused);
+    env.storage()
+        .instance()
+        .set(&crate::types::DataKey::FeePaused, &paused);
     crate::types::AnalyticsEvents::fee_pause_toggled(env, admin, paused);
     Ok(())
 }
-use crate::types::FeeRecipientShare;
 /// Validates that the sum of recipient shares is exactly 10000 (100%).
 pub fn validate_recipient_shares(shares: &Vec<FeeRecipientShare>) -> Result<(), ValidationError> {
     let mut total: u32 = 0;
     for s in shares.iter() {
-        total = total.checked_add(s.share_bps).ok_or(ValidationError::InvalidAmount)?;
+        total = total
+            .checked_add(s.share_bps)
+            .ok_or(ValidationError::InvalidAmount)?;
     }
     if total != 10000 {
         return Err(ValidationError::InvalidAmount);
@@ -47,7 +65,7 @@ pub fn distribute_fee(env: &Env, fee_amount: i128, shares: &Vec<FeeRecipientShar
     validate_recipient_shares(shares).expect("Invalid recipient shares");
     let mut distributed = 0i128;
     for (i, s) in shares.iter().enumerate() {
-        let is_last = i == (shares.len() - 1);
+        let is_last = i as u32 == shares.len() - 1;
         let share_amt = if is_last {
             // Assign remainder to last recipient
             fee_amount - distributed
@@ -60,19 +78,6 @@ pub fn distribute_fee(env: &Env, fee_amount: i128, shares: &Vec<FeeRecipientShar
         // In a real contract, here you would transfer the share_amt to s.recipient
     }
 }
-//! # Fee Calculation Engine
-//!
-//! Implements dynamic fee calculation for transactions with configurable fee structures.
-//! Supports percentage-based fees, tiered pricing, and automatic fee deductions.
-
-use soroban_sdk::{symbol_short, Address, Env, Symbol};
-
-use crate::types::{FeeConfig, FeeTier, FeeCalculationResult, DataKey, FeeDeductionEvent, ValidationError, AnalyticsEvents};
-use soroban_sdk::Symbol;
-use crate::types::{
-    AnalyticsEvents, DataKey, FeeCalculationResult, FeeConfig, FeeDeductionEvent, FeeTier,
-    ValidationError,
-};
 
 /// Calculates fees for a single transaction based on the current fee configuration.
 ///
@@ -83,14 +88,12 @@ use crate::types::{
 ///
 /// # Returns
 /// * `FeeCalculationResult` containing the calculated fee and net amount
-pub fn calculate_transaction_fee(env: &Env, amount: i128, fee_config: &FeeConfig) -> FeeCalculationResult {
-    if is_fee_paused(env) || amount <= 0 {
 pub fn calculate_transaction_fee(
     env: &Env,
     amount: i128,
     fee_config: &FeeConfig,
 ) -> FeeCalculationResult {
-    if amount <= 0 {
+    if is_fee_paused(env) || amount <= 0 {
         return FeeCalculationResult {
             gross_amount: amount,
             fee_amount: 0,
@@ -108,7 +111,7 @@ pub fn calculate_transaction_fee(
     };
 
     // Apply min and max fee constraints
-    let constrained_fee = constrain_fee_amount(fee_amount, &fee_config);
+    let constrained_fee = constrain_fee_amount(fee_amount, fee_config);
 
     // Ensure fee doesn't exceed the transaction amount
     let final_fee = if constrained_fee > amount {
@@ -138,7 +141,7 @@ fn calculate_tiered_fee(amount: i128, tiers: &Vec<FeeTier>) -> i128 {
     }
 
     // Find the appropriate tier based on amount
-    let mut applicable_tier = &tiers.get(0).unwrap(); // Default to first tier
+    let mut applicable_tier = tiers.get(0).unwrap(); // Default to first tier
 
     for tier in tiers.iter() {
         if amount >= tier.threshold {
@@ -193,12 +196,12 @@ fn calculate_effective_rate(gross_amount: i128, fee_amount: i128) -> u32 {
 /// Calculates fees for multiple transactions
 pub fn calculate_batch_fees(
     env: &Env,
-    amounts: &[i128],
+    amounts: &Vec<i128>,
     fee_config: &FeeConfig,
 ) -> Vec<FeeCalculationResult> {
     let mut results = Vec::new(env);
 
-    for &amount in amounts {
+    for amount in amounts.iter() {
         let result = calculate_transaction_fee(env, amount, fee_config);
         results.push_back(result);
     }
@@ -210,11 +213,9 @@ pub fn calculate_batch_fees(
 pub fn validate_fee_config(config: &FeeConfig) -> Result<(), ValidationError> {
     // Validate percentage is not over 100% (10000 basis points)
     match &config.fee_model {
-        crate::types::FeeModel::Percentage(percentage_bps) => {
-            if *percentage_bps > 10000 {
-                // More than 100%
-                return Err(ValidationError::InvalidPercentage);
-            }
+        crate::types::FeeModel::Percentage(percentage_bps) if *percentage_bps > 10000 => {
+            // More than 100%
+            return Err(ValidationError::InvalidPercentage);
         }
         crate::types::FeeModel::Tiered(tiers) => {
             // Validate each tier
@@ -224,11 +225,11 @@ pub fn validate_fee_config(config: &FeeConfig) -> Result<(), ValidationError> {
                 }
 
                 match &tier.fee_model {
-                    crate::types::FeeModel::Percentage(percentage_bps) => {
-                        if *percentage_bps > 10000 {
-                            // More than 100%
-                            return Err(ValidationError::InvalidPercentage);
-                        }
+                    crate::types::FeeModel::Percentage(percentage_bps)
+                        if *percentage_bps > 10000 =>
+                    {
+                        // More than 100%
+                        return Err(ValidationError::InvalidPercentage);
                     }
                     _ => {} // Other models have different validation
                 }
@@ -288,13 +289,21 @@ pub fn get_current_fee_config(env: &Env) -> Option<FeeConfig> {
 
 /// Retrieves per-operation fee configuration.
 pub fn get_operation_fee_config(env: &Env, operation: &Symbol) -> Option<FeeConfig> {
-    env.storage().instance().get(&DataKey::OperationFeeConfig(operation.clone()))
+    env.storage()
+        .instance()
+        .get(&DataKey::OperationFeeConfig(operation.clone()))
 }
 
 /// Stores per-operation fee configuration.
-pub fn store_operation_fee_config(env: &Env, operation: &Symbol, config: &FeeConfig) -> Result<(), ValidationError> {
+pub fn store_operation_fee_config(
+    env: &Env,
+    operation: &Symbol,
+    config: &FeeConfig,
+) -> Result<(), ValidationError> {
     validate_fee_config(config)?;
-    env.storage().instance().set(&DataKey::OperationFeeConfig(operation.clone()), config);
+    env.storage()
+        .instance()
+        .set(&DataKey::OperationFeeConfig(operation.clone()), config);
     Ok(())
 }
 
@@ -353,7 +362,12 @@ pub fn update_fee_config(
 }
 
 /// Updates per-operation fee configuration (admin only)
-pub fn update_operation_fee_config(env: &Env, admin: &Address, operation: &Symbol, new_config: FeeConfig) -> Result<(), ValidationError> {
+pub fn update_operation_fee_config(
+    env: &Env,
+    admin: &Address,
+    operation: &Symbol,
+    new_config: FeeConfig,
+) -> Result<(), ValidationError> {
     // Verify caller is admin
     let stored_admin: Address = env
         .storage()
@@ -380,7 +394,16 @@ pub fn update_operation_fee_config(env: &Env, admin: &Address, operation: &Symbo
 mod tests {
     use super::*;
     use crate::types::{FeeModel, FeeTier};
-    use soroban_sdk::{testutils::Address as _, Env};
+    use crate::TransactionAnalyticsContract;
+    use soroban_sdk::{
+        testutils::{Address as _, Events as _},
+        Env, TryFromVal,
+    };
+
+    fn in_contract<T>(env: &Env, f: impl FnOnce() -> T) -> T {
+        let contract_id = env.register(TransactionAnalyticsContract, ());
+        env.as_contract(&contract_id, f)
+    }
 
     #[test]
     fn test_flat_fee_calculation() {
@@ -393,7 +416,7 @@ mod tests {
             description: None,
         };
 
-        let result = calculate_transaction_fee(&env, 1000, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 1000, &config));
         assert_eq!(result.gross_amount, 1000);
         assert_eq!(result.fee_amount, 100);
         assert_eq!(result.net_amount, 900);
@@ -410,7 +433,7 @@ mod tests {
             description: None,
         };
 
-        let result = calculate_transaction_fee(&env, 1000, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 1000, &config));
         assert_eq!(result.gross_amount, 1000);
         assert_eq!(result.fee_amount, 5); // 0.5% of 1000 = 5
         assert_eq!(result.net_amount, 995);
@@ -419,7 +442,7 @@ mod tests {
     #[test]
     fn test_min_max_constraints() {
         let env = Env::default();
-        let mut config = FeeConfig {
+        let config = FeeConfig {
             fee_model: FeeModel::Percentage(1), // 0.01%
             min_fee: Some(10),
             max_fee: Some(100),
@@ -428,11 +451,11 @@ mod tests {
         };
 
         // Test minimum constraint: 50 * 0.01% = 0.005, but min is 10
-        let result = calculate_transaction_fee(&env, 50, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 50, &config));
         assert_eq!(result.fee_amount, 10); // Minimum applies
 
         // Test maximum constraint: 1000000 * 0.01% = 100, which equals max
-        let result = calculate_transaction_fee(&env, 1000000, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 1000000, &config));
         assert_eq!(result.fee_amount, 100); // Maximum applies
     }
 
@@ -447,11 +470,11 @@ mod tests {
             description: None,
         };
 
-        let result = calculate_transaction_fee(&env, 0, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 0, &config));
         assert_eq!(result.fee_amount, 0);
         assert_eq!(result.net_amount, 0);
 
-        let result = calculate_transaction_fee(&env, -100, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, -100, &config));
         assert_eq!(result.fee_amount, 0);
         assert_eq!(result.net_amount, -100);
     }
@@ -482,11 +505,11 @@ mod tests {
         };
 
         // Amount 50 should use 1% fee = 0.5, rounded down = 0
-        let result = calculate_transaction_fee(&env, 50, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 50, &config));
         assert_eq!(result.fee_amount, 0); // 50 * 1% = 0.5, floor to 0
 
         // Amount 200 should use 0.5% fee = 1
-        let result = calculate_transaction_fee(&env, 200, &config);
+        let result = in_contract(&env, || calculate_transaction_fee(&env, 200, &config));
         assert_eq!(result.fee_amount, 1); // 200 * 0.5% = 1
     }
 
@@ -523,46 +546,62 @@ mod tests {
     #[test]
     fn test_fee_distribution_splits_correctly() {
         let env = Env::default();
+        let contract_id = env.register(TransactionAnalyticsContract, ());
         let r1 = Address::generate(&env);
         let r2 = Address::generate(&env);
-        let shares = vec![
-            FeeRecipientShare { recipient: r1.clone(), share_bps: 6000 },
-            FeeRecipientShare { recipient: r2.clone(), share_bps: 4000 },
+        let shares = [
+            FeeRecipientShare {
+                recipient: r1.clone(),
+                share_bps: 6000,
+            },
+            FeeRecipientShare {
+                recipient: r2.clone(),
+                share_bps: 4000,
+            },
         ];
-        let shares_vec = Vec::from_array(&env, &shares);
+        let shares_vec = Vec::from_array(&env, shares);
         let fee = 1000i128;
-        crate::fees::distribute_fee(&env, fee, &shares_vec);
+        env.as_contract(&contract_id, || {
+            crate::fees::distribute_fee(&env, fee, &shares_vec);
+        });
         let events = env.events().all();
-        // Should emit two events
-        assert!(events.iter().any(|e| e.topics().contains(&r1)));
-        assert!(events.iter().any(|e| e.topics().contains(&r2)));
+        // Should emit one event per recipient
+        let mut recipients: Vec<Address> = Vec::new(&env);
+        for (_, topics, _) in events.iter() {
+            recipients.push_back(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap());
+        }
+        assert_eq!(recipients, Vec::from_array(&env, [r1, r2]));
     }
 
     #[test]
     fn test_fee_pausing_mechanism() {
         let env = Env::default();
+        let contract_id = env.register(TransactionAnalyticsContract, ());
         let admin = Address::generate(&env);
-        env.storage().instance().set(&crate::types::DataKey::Admin, &admin);
-        // Set a fee config
-        let config = FeeConfig {
-            fee_model: FeeModel::Percentage(100), // 1%
-            min_fee: None,
-            max_fee: None,
-            enabled: true,
-            description: None,
-        };
-        crate::fees::store_fee_config(&env, &config).unwrap();
-        // Not paused: fee should be nonzero
-        let result = crate::fees::calculate_transaction_fee(&env, 10000, &config);
-        assert_eq!(result.fee_amount, 100);
-        // Pause fees
-        crate::fees::set_fee_paused(&env, &admin, true).unwrap();
-        let result_paused = crate::fees::calculate_transaction_fee(&env, 10000, &config);
-        assert_eq!(result_paused.fee_amount, 0);
-        // Resume fees
-        crate::fees::set_fee_paused(&env, &admin, false).unwrap();
-        let result_resumed = crate::fees::calculate_transaction_fee(&env, 10000, &config);
-        assert_eq!(result_resumed.fee_amount, 100);
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .set(&crate::types::DataKey::Admin, &admin);
+            // Set a fee config
+            let config = FeeConfig {
+                fee_model: FeeModel::Percentage(100), // 1%
+                min_fee: None,
+                max_fee: None,
+                enabled: true,
+                description: None,
+            };
+            crate::fees::store_fee_config(&env, &config).unwrap();
+            // Not paused: fee should be nonzero
+            let result = crate::fees::calculate_transaction_fee(&env, 10000, &config);
+            assert_eq!(result.fee_amount, 100);
+            // Pause fees
+            crate::fees::set_fee_paused(&env, &admin, true).unwrap();
+            let result_paused = crate::fees::calculate_transaction_fee(&env, 10000, &config);
+            assert_eq!(result_paused.fee_amount, 0);
+            // Resume fees
+            crate::fees::set_fee_paused(&env, &admin, false).unwrap();
+            let result_resumed = crate::fees::calculate_transaction_fee(&env, 10000, &config);
+            assert_eq!(result_resumed.fee_amount, 100);
+        });
     }
 }
-}
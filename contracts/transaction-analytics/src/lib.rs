@@ -24,33 +24,35 @@ mod fees;
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Symbol, Vec};
 
 // Analytics exports (main branch — complete set)
 pub use crate::analytics::{
-    compute_aggregated_analytics, compute_batch_checksum, compute_batch_metrics,
-    compute_category_metrics, compute_monthly_analytics, compute_refund_metrics,
-    compute_user_spending_summary, create_bundle_result, find_high_value_transactions,
-    process_refund_batch, update_monthly_analytics_storage, validate_audit_logs, validate_batch,
+    apply_rating_to_reputation, compute_aggregated_analytics, compute_batch_checksum,
+    compute_batch_metrics, compute_category_metrics, compute_monthly_analytics,
+    compute_refund_metrics, compute_user_spending_summary, create_bundle_result,
+    find_high_value_transactions, is_bundle_stale, process_refund_batch,
+    update_monthly_analytics_storage, validate_audit_logs, validate_batch,
     validate_bundle_transactions, validate_refund_batch, validate_refund_eligibility,
     validate_transaction_for_bundle,
 };
 
 // Fees exports (single, de-duplicated block)
 pub use crate::fees::{
-    calculate_batch_fees, calculate_transaction_fee, deduct_fees, get_current_fee_config,
-    get_operation_fee_config, store_fee_config, store_operation_fee_config, update_fee_config,
-    update_operation_fee_config, validate_fee_config,
+    calculate_batch_fees, calculate_transaction_fee, deduct_fees, distribute_fee,
+    get_current_fee_config, get_operation_fee_config, store_fee_config, store_operation_fee_config,
+    update_fee_config, update_operation_fee_config, validate_fee_config, validate_recipient_shares,
 };
 
 // Types exports
 pub use crate::types::{
-    AnalyticsEvents, AuditLog, BatchMetrics, BatchStatusUpdateResult, BundleResult,
-    BundledTransaction, CategoryMetrics, DataKey, FeeCalculationResult, FeeConfig,
-    FeeDeductionEvent, FeeModel, FeeTier, MonthlySpendingAnalytics, RatingInput, RatingResult,
-    RatingStatus, RefundBatchMetrics, RefundRequest, RefundResult, RefundStatus,
-    StatusUpdateResult, Transaction, TransactionStatus, TransactionStatusUpdate,
-    UserSpendingSummary, ValidationError, ValidationResult, MAX_BATCH_SIZE,
+    AnalyticsEvents, AuditLog, BatchMetrics, BatchStatusUpdateResult, BundleExecution,
+    BundleExecutionStatus, BundleResult, BundledTransaction, CategoryMetrics, DataKey,
+    FeeCalculationResult, FeeConfig, FeeDeductionEvent, FeeModel, FeeTier, MerchantReputation,
+    MonthlySpendingAnalytics, RatingInput, RatingResult, RatingStatus, RefundBatchMetrics,
+    RefundRequest, RefundResult, RefundStatus, StatusUpdateResult, Transaction, TransactionStatus,
+    TransactionStatusUpdate, UserSpendingSummary, ValidationError, ValidationResult,
+    MAX_BATCH_SIZE,
 };
 
 // Validation exports (single, de-duplicated block)
@@ -96,6 +98,12 @@ pub enum AnalyticsError {
     RefundBatchTooLarge = 13,
     /// Contract already initialized
     AlreadyInitialized = 14,
+    /// Bundle does not exist
+    BundleNotFound = 15,
+    /// Bundle failed validation or is no longer pending
+    BundleNotExecutable = 16,
+    /// Transaction is unknown or already has recorded parties
+    CannotBackfillParties = 17,
 }
 
 impl From<AnalyticsError> for soroban_sdk::Error {
@@ -182,12 +190,16 @@ impl TransactionAnalyticsContract {
         }
 
         // FIX: define tx_count before use
-        let tx_count = transactions.len() as u32;
+        let tx_count = transactions.len();
 
         for tx in transactions.iter() {
             env.storage()
                 .persistent()
                 .set(&DataKey::KnownTransaction(tx.tx_id), &true);
+            env.storage().persistent().set(
+                &DataKey::TransactionParties(tx.tx_id),
+                &(tx.from.clone(), tx.to.clone()),
+            );
         }
 
         // Get next batch ID (single read, single write at the end)
@@ -261,7 +273,7 @@ impl TransactionAnalyticsContract {
             panic_with_error!(&env, AnalyticsError::EmptyBatch);
         }
 
-        if logs.len() > MAX_BATCH_SIZE as usize {
+        if logs.len() > MAX_BATCH_SIZE {
             panic_with_error!(&env, AnalyticsError::BatchTooLarge);
         }
 
@@ -341,7 +353,7 @@ impl TransactionAnalyticsContract {
     ///
     /// Useful for simulating analytics before committing.
     pub fn simulate_batch(env: Env, transactions: Vec<Transaction>) -> BatchMetrics {
-        if let Err(_) = validate_batch(&transactions) {
+        if validate_batch(&transactions).is_err() {
             panic_with_error!(&env, AnalyticsError::InvalidBatch);
         }
 
@@ -370,7 +382,7 @@ impl TransactionAnalyticsContract {
         let mut results: Vec<StatusUpdateResult> = Vec::new(&env);
         let mut successful: u32 = 0;
         let mut failed: u32 = 0;
-        let count = updates.len() as u32;
+        let count = updates.len();
 
         for update in updates.iter() {
             let known = env
@@ -419,6 +431,15 @@ impl TransactionAnalyticsContract {
         }
     }
 
+    /// Records ratings for processed transactions and updates recipient reputations.
+    ///
+    /// Only the sender of a transaction may rate it, and only once. Accepted
+    /// ratings are folded into the recipient's `MerchantReputation`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The rating sender (must authorize)
+    /// * `ratings` - Vector of ratings to submit
     pub fn submit_ratings(env: Env, user: Address, ratings: Vec<RatingInput>) -> Vec<RatingResult> {
         user.require_auth();
 
@@ -440,16 +461,32 @@ impl TransactionAnalyticsContract {
             if input.score == 0 || input.score > 5 {
                 status = RatingStatus::InvalidScore;
             } else {
-                let known = env
+                let parties: Option<(Address, Address)> = env
                     .storage()
                     .persistent()
-                    .has(&DataKey::KnownTransaction(input.tx_id));
-                if !known {
-                    status = RatingStatus::UnknownTransaction;
-                } else {
-                    env.storage()
+                    .get(&DataKey::TransactionParties(input.tx_id));
+                let rating_key = DataKey::Rating(input.tx_id, user.clone());
+
+                // Transactions processed before parties were recorded only
+                // have `KnownTransaction`: nobody can be shown to be their
+                // sender until the admin backfills the parties.
+                let legacy = parties.is_none()
+                    && env
+                        .storage()
                         .persistent()
-                        .set(&DataKey::Rating(input.tx_id, user.clone()), &input.score);
+                        .has(&DataKey::KnownTransaction(input.tx_id));
+
+                match parties {
+                    None if legacy => status = RatingStatus::NotSender,
+                    None => status = RatingStatus::UnknownTransaction,
+                    Some((sender, _)) if sender != user => status = RatingStatus::NotSender,
+                    _ if env.storage().persistent().has(&rating_key) => {
+                        status = RatingStatus::AlreadyRated
+                    }
+                    Some((_, recipient)) => {
+                        env.storage().persistent().set(&rating_key, &input.score);
+                        Self::record_reputation(&env, &recipient, input.score);
+                    }
                 }
             }
 
@@ -467,6 +504,49 @@ impl TransactionAnalyticsContract {
         results
     }
 
+    /// Records the sender and recipient of a transaction processed before
+    /// parties were stored, so its sender can rate it.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `tx_id` - A known transaction without recorded parties
+    /// * `sender` - The transaction's sender
+    /// * `recipient` - The transaction's recipient
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not admin
+    /// * `CannotBackfillParties` - If the transaction is unknown or already has parties
+    pub fn backfill_transaction_parties(
+        env: Env,
+        caller: Address,
+        tx_id: u64,
+        sender: Address,
+        recipient: Address,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let storage = env.storage().persistent();
+        if !storage.has(&DataKey::KnownTransaction(tx_id))
+            || storage.has(&DataKey::TransactionParties(tx_id))
+        {
+            panic_with_error!(&env, AnalyticsError::CannotBackfillParties);
+        }
+        storage.set(&DataKey::TransactionParties(tx_id), &(sender, recipient));
+    }
+
+    /// Returns the rolled-up reputation of a recipient address, if it has been rated.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `merchant` - The recipient address to look up
+    pub fn get_merchant_reputation(env: Env, merchant: Address) -> Option<MerchantReputation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MerchantReputation(merchant))
+    }
+
     /// Returns the admin address.
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -520,94 +600,172 @@ impl TransactionAnalyticsContract {
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
-        if let Err(validation_error) = validate_bundled_transactions(&bundled_transactions) {
-            match validation_error {
-                ValidationError::EmptyBatch => {
-                    panic_with_error!(&env, AnalyticsError::EmptyBundle)
-                }
-                ValidationError::BatchTooLarge => {
-                    panic_with_error!(&env, AnalyticsError::BundleTooLarge)
-                }
-                _ => panic_with_error!(&env, AnalyticsError::InvalidBatch),
-            }
-        }
+        Self::create_bundle(&env, &bundled_transactions)
+    }
 
-        // FIX: define tx_count before use
-        let tx_count = bundled_transactions.len() as u32;
+    /// Retrieves stored bundle result for a specific bundle ID.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `bundle_id` - The ID of the bundle to retrieve
+    ///
+    /// # Returns
+    /// * `Option<BundleResult>` - The stored bundle result if found
+    pub fn get_bundle_result(env: Env, bundle_id: u64) -> Option<BundleResult> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BundleResult(bundle_id))
+    }
 
-        // Get next bundle ID
-        let bundle_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::LastBundleId)
-            .unwrap_or(0)
-            + 1;
+    /// Validates a bundle like `bundle_transactions` and keeps it for
+    /// settlement in `token` through `execute_bundle`.
+    ///
+    /// Every transaction must move a positive amount; the token is fixed
+    /// here so the executor cannot choose a different asset later.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `token` - The token contract the transfers settle in
+    /// * `bundled_transactions` - Vector of transactions to bundle
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If any transaction amount is zero or negative
+    /// * Same as `bundle_transactions` otherwise
+    pub fn create_executable_bundle(
+        env: Env,
+        caller: Address,
+        token: Address,
+        bundled_transactions: Vec<BundledTransaction>,
+    ) -> BundleResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-        // Emit bundling started event
-        AnalyticsEvents::bundling_started(&env, bundle_id, tx_count);
+        if bundled_transactions
+            .iter()
+            .any(|bundled_tx| bundled_tx.transaction.amount <= 0)
+        {
+            panic_with_error!(&env, AnalyticsError::InvalidAmount);
+        }
 
-        // Validate all transactions (handles partial failures gracefully)
-        let validation_results = validate_bundle_transactions(&env, &bundled_transactions);
+        let bundle_result = Self::create_bundle(&env, &bundled_transactions);
 
-        // Emit validation events for each transaction
-        let mut _valid_count: u32 = 0;
-        let mut _invalid_count: u32 = 0;
+        let execution = BundleExecution {
+            bundle_id: bundle_result.bundle_id,
+            token,
+            status: BundleExecutionStatus::Pending,
+            executed_at: 0,
+        };
+        env.storage().persistent().set(
+            &DataKey::BundleTransactions(bundle_result.bundle_id),
+            &bundled_transactions,
+        );
+        env.storage().persistent().set(
+            &DataKey::BundleExecution(bundle_result.bundle_id),
+            &execution,
+        );
 
-        for result in validation_results.iter() {
-            AnalyticsEvents::transaction_validated(&env, bundle_id, &result);
+        bundle_result
+    }
 
-            if result.is_valid {
-                _valid_count += 1;
-            } else {
-                _invalid_count += 1;
-                AnalyticsEvents::transaction_validation_failed(
-                    &env,
-                    bundle_id,
-                    result.tx_id,
-                    &result.error,
-                );
-            }
+    /// Executes every transfer in a validated bundle, all-or-nothing.
+    ///
+    /// Each distinct sender must authorize the call. If any transfer fails the
+    /// whole invocation reverts, so either every transfer lands or none do.
+    /// A bundle that has gone stale since validation is marked `Expired` and
+    /// returned without moving any tokens.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `bundle_id` - The ID of a bundle created with `create_executable_bundle`
+    ///
+    /// # Returns
+    /// * `BundleExecution` - The bundle's updated settlement state
+    ///
+    /// # Events Emitted
+    /// * `bundle_executed` - When all transfers have been performed
+    /// * `bundle_expired` - When the bundle was stale and has been rejected
+    ///
+    /// # Errors
+    /// * `BundleNotFound` - If no bundle exists for the ID
+    /// * `BundleNotExecutable` - If the bundle was not created for execution,
+    ///   failed validation or is not pending
+    pub fn execute_bundle(env: Env, bundle_id: u64) -> BundleExecution {
+        let bundle: BundleResult = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BundleResult(bundle_id))
+            .unwrap_or_else(|| panic_with_error!(&env, AnalyticsError::BundleNotFound));
+
+        let mut execution: BundleExecution = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BundleExecution(bundle_id))
+            .unwrap_or_else(|| panic_with_error!(&env, AnalyticsError::BundleNotExecutable));
+
+        if !bundle.can_bundle || execution.status != BundleExecutionStatus::Pending {
+            panic_with_error!(&env, AnalyticsError::BundleNotExecutable);
         }
 
-        // Create bundle result
+        let bundled_transactions: Vec<BundledTransaction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BundleTransactions(bundle_id))
+            .unwrap_or_else(|| panic_with_error!(&env, AnalyticsError::BundleNotFound));
+
+        let refunded_txs: soroban_sdk::Map<u64, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RefundedTransactions)
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
         let current_ledger = env.ledger().sequence() as u64;
-        let bundle_result = create_bundle_result(
-            &env,
-            bundle_id,
+        if is_bundle_stale(
+            &bundle,
             &bundled_transactions,
-            &validation_results,
+            &refunded_txs,
             current_ledger,
-        );
+        ) {
+            execution.status = BundleExecutionStatus::Expired;
+            env.storage()
+                .persistent()
+                .set(&DataKey::BundleExecution(bundle_id), &execution);
+            AnalyticsEvents::bundle_expired(&env, bundle_id);
+            return execution;
+        }
 
-        // Emit bundle created event
-        AnalyticsEvents::bundle_created(&env, bundle_id, &bundle_result);
+        // Collect each sender's authorization once
+        let mut authorized: soroban_sdk::Map<Address, bool> = soroban_sdk::Map::new(&env);
+        for bundled_tx in bundled_transactions.iter() {
+            let sender = bundled_tx.transaction.from;
+            if !authorized.contains_key(sender.clone()) {
+                sender.require_auth();
+                authorized.set(sender, true);
+            }
+        }
 
-        // Store bundle result
-        env.storage()
-            .instance()
-            .set(&DataKey::LastBundleId, &bundle_id);
+        let token_client = token::Client::new(&env, &execution.token);
+        for bundled_tx in bundled_transactions.iter() {
+            let tx = bundled_tx.transaction;
+            token_client.transfer(&tx.from, &tx.to, &tx.amount);
+        }
+
+        execution.status = BundleExecutionStatus::Executed;
+        execution.executed_at = current_ledger;
         env.storage()
             .persistent()
-            .set(&DataKey::BundleResult(bundle_id), &bundle_result);
+            .set(&DataKey::BundleExecution(bundle_id), &execution);
 
-        // Emit completion event
-        AnalyticsEvents::bundling_completed(&env, bundle_id, bundle_result.can_bundle);
+        AnalyticsEvents::bundle_executed(&env, bundle_id, bundle.total_count, bundle.total_volume);
 
-        bundle_result
+        execution
     }
 
-    /// Retrieves stored bundle result for a specific bundle ID.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `bundle_id` - The ID of the bundle to retrieve
-    ///
-    /// # Returns
-    /// * `Option<BundleResult>` - The stored bundle result if found
-    pub fn get_bundle_result(env: Env, bundle_id: u64) -> Option<BundleResult> {
+    /// Retrieves the settlement state of an executable bundle.
+    pub fn get_bundle_execution(env: Env, bundle_id: u64) -> Option<BundleExecution> {
         env.storage()
             .persistent()
-            .get(&DataKey::BundleResult(bundle_id))
+            .get(&DataKey::BundleExecution(bundle_id))
     }
 
     /// Returns the last created bundle ID.
@@ -657,7 +815,7 @@ impl TransactionAnalyticsContract {
         }
 
         // FIX: define request_count before use
-        let request_count = refund_requests.len() as u32;
+        let request_count = refund_requests.len();
 
         // Get next refund batch ID
         let refund_batch_id: u64 = env
@@ -813,7 +971,7 @@ impl TransactionAnalyticsContract {
     /// * `transactions` - Vector of transactions to analyze
     /// * `year` - The year to analyze
     /// * `month` - The month to analyze
-    pub fn update_monthly_spending_analytics(
+    pub fn update_monthly_analytics(
         env: Env,
         caller: Address,
         user: Address,
@@ -989,11 +1147,7 @@ impl TransactionAnalyticsContract {
 
         // Emit operation fee updated event
         crate::types::AnalyticsEvents::operation_fee_updated(
-            &env,
-            &admin,
-            &operation,
-            previous,
-            new_config,
+            &env, &admin, &operation, previous, new_config,
         );
     }
 
@@ -1045,7 +1199,6 @@ impl TransactionAnalyticsContract {
             description: Some(Symbol::new(&env, "Default")),
         });
 
-        // FIX: pass &amounts directly — no invalid iterator conversion
         calculate_batch_fees(&env, &amounts, &config)
     }
 
@@ -1063,6 +1216,95 @@ impl TransactionAnalyticsContract {
         crate::fees::set_fee_paused(&env, &admin, false).expect("Failed to resume fees");
     }
 
+    // Internal helper to fold a rating into the recipient's reputation
+    fn record_reputation(env: &Env, merchant: &Address, score: u32) {
+        let key = DataKey::MerchantReputation(merchant.clone());
+        let existing: Option<MerchantReputation> = env.storage().persistent().get(&key);
+        let reputation =
+            apply_rating_to_reputation(env, existing, merchant, score, env.ledger().timestamp());
+
+        env.storage().persistent().set(&key, &reputation);
+        AnalyticsEvents::reputation_updated(env, &reputation);
+    }
+
+    // Validates and stores a bundle, shared by both bundling entry points
+    fn create_bundle(env: &Env, bundled_transactions: &Vec<BundledTransaction>) -> BundleResult {
+        if let Err(validation_error) = validate_bundled_transactions(bundled_transactions) {
+            match validation_error {
+                ValidationError::EmptyBatch => {
+                    panic_with_error!(env, AnalyticsError::EmptyBundle)
+                }
+                ValidationError::BatchTooLarge => {
+                    panic_with_error!(env, AnalyticsError::BundleTooLarge)
+                }
+                // Per-transaction problems are reported in the validation results
+                _ => {}
+            }
+        }
+
+        let tx_count = bundled_transactions.len();
+
+        // Get next bundle ID
+        let bundle_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastBundleId)
+            .unwrap_or(0)
+            + 1;
+
+        // Emit bundling started event
+        AnalyticsEvents::bundling_started(env, bundle_id, tx_count);
+
+        // Validate all transactions (handles partial failures gracefully)
+        let validation_results = validate_bundle_transactions(env, bundled_transactions);
+
+        // Emit validation events for each transaction
+        let mut _valid_count: u32 = 0;
+        let mut _invalid_count: u32 = 0;
+
+        for result in validation_results.iter() {
+            AnalyticsEvents::transaction_validated(env, bundle_id, &result);
+
+            if result.is_valid {
+                _valid_count += 1;
+            } else {
+                _invalid_count += 1;
+                AnalyticsEvents::transaction_validation_failed(
+                    env,
+                    bundle_id,
+                    result.tx_id,
+                    &result.error,
+                );
+            }
+        }
+
+        // Create bundle result
+        let current_ledger = env.ledger().sequence() as u64;
+        let bundle_result = create_bundle_result(
+            env,
+            bundle_id,
+            bundled_transactions,
+            &validation_results,
+            current_ledger,
+        );
+
+        // Emit bundle created event
+        AnalyticsEvents::bundle_created(env, bundle_id, &bundle_result);
+
+        // Store bundle result
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBundleId, &bundle_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BundleResult(bundle_id), &bundle_result);
+
+        // Emit completion event
+        AnalyticsEvents::bundling_completed(env, bundle_id, bundle_result.can_bundle);
+
+        bundle_result
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -1078,4 +1320,4 @@ impl TransactionAnalyticsContract {
 }
 
 #[cfg(test)]
-mod test;
\ No newline at end of file
+mod test;
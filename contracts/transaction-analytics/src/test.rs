@@ -2,16 +2,16 @@
 
 #![cfg(test)]
 
+use crate::types::{DataKey, FeeConfig, FeeModel};
 use crate::{
-    BundleResult, BundledTransaction, RefundRequest, RefundStatus, Transaction,
-    TransactionAnalyticsContract, TransactionAnalyticsContractClient, TransactionStatus,
-    TransactionStatusUpdate, ValidationResult,
+    AnalyticsError, BundleExecutionStatus, BundledTransaction, RatingInput, RatingStatus,
+    RefundRequest, Transaction, TransactionAnalyticsContract, TransactionAnalyticsContractClient,
+    TransactionStatus, TransactionStatusUpdate,
 };
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, Env, Map, Symbol, Vec,
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Env, Map, Symbol, Vec,
 };
-use crate::types::{FeeConfig, FeeModel};
 
 /// Creates a test environment with the contract deployed and initialized.
 fn setup_test_env() -> (Env, Address, TransactionAnalyticsContractClient<'static>) {
@@ -610,12 +610,12 @@ fn test_bundle_transactions_success() {
     assert_eq!(result.total_count, 3);
     assert_eq!(result.valid_count, 3);
     assert_eq!(result.invalid_count, 0);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
     assert_eq!(result.total_volume, 6000);
     assert_eq!(result.validation_results.len(), 3);
 
     for result_item in result.validation_results.iter() {
-        assert_eq!(result_item.is_valid, true);
+        assert!(result_item.is_valid);
     }
 }
 
@@ -643,21 +643,21 @@ fn test_bundle_transactions_with_partial_failures() {
     assert_eq!(result.total_count, 3);
     assert_eq!(result.valid_count, 2);
     assert_eq!(result.invalid_count, 1);
-    assert_eq!(result.can_bundle, false);
+    assert!(!result.can_bundle);
     assert_eq!(result.total_volume, 4000);
     assert_eq!(result.validation_results.len(), 3);
 
     let result_1 = result.validation_results.get(0).unwrap();
     assert_eq!(result_1.tx_id, 1);
-    assert_eq!(result_1.is_valid, true);
+    assert!(result_1.is_valid);
 
     let result_2 = result.validation_results.get(1).unwrap();
     assert_eq!(result_2.tx_id, 2);
-    assert_eq!(result_2.is_valid, false);
+    assert!(!result_2.is_valid);
 
     let result_3 = result.validation_results.get(2).unwrap();
     assert_eq!(result_3.tx_id, 3);
-    assert_eq!(result_3.is_valid, true);
+    assert!(result_3.is_valid);
 }
 
 #[test]
@@ -673,11 +673,11 @@ fn test_bundle_transactions_with_negative_amount() {
 
     assert_eq!(result.valid_count, 2);
     assert_eq!(result.invalid_count, 1);
-    assert_eq!(result.can_bundle, false);
+    assert!(!result.can_bundle);
 
     let invalid_result = result.validation_results.get(1).unwrap();
     assert_eq!(invalid_result.tx_id, 2);
-    assert_eq!(invalid_result.is_valid, false);
+    assert!(!invalid_result.is_valid);
 }
 
 #[test]
@@ -771,7 +771,7 @@ fn test_audit_log_events_emitted() {
     client.batch_audit_log(&admin, &logs);
 
     let events = env.events().all();
-    assert!(events.len() >= 1);
+    assert!(!events.is_empty());
 }
 
 #[test]
@@ -874,7 +874,7 @@ fn test_bundle_large_number_of_transactions() {
     assert_eq!(result.total_count, 50);
     assert_eq!(result.valid_count, 50);
     assert_eq!(result.invalid_count, 0);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
     // Sum of 100 + 200 + ... + 5000 = 100 * (1 + 2 + ... + 50) = 100 * 1275 = 127500
     assert_eq!(result.total_volume, 127500);
 }
@@ -892,7 +892,7 @@ fn test_bundle_with_memo() {
     let result = client.bundle_transactions(&admin, &bundled_txs);
 
     assert_eq!(result.valid_count, 2);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
 }
 
 #[test]
@@ -922,7 +922,7 @@ fn test_bundle_all_transactions_invalid() {
 
     assert_eq!(result.valid_count, 0);
     assert_eq!(result.invalid_count, 2);
-    assert_eq!(result.can_bundle, false);
+    assert!(!result.can_bundle);
     assert_eq!(result.total_volume, 0);
 }
 
@@ -937,10 +937,217 @@ fn test_bundle_zero_amount_transactions() {
     let result = client.bundle_transactions(&admin, &bundled_txs);
 
     assert_eq!(result.valid_count, 2);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
     assert_eq!(result.total_volume, 1000);
 }
 
+/// Registers a Stellar asset and mints `amount` to each of `holders`.
+fn setup_bundle_token(env: &Env, holders: &[&Address], amount: i128) -> Address {
+    let issuer = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract_v2(issuer).address();
+    let admin_client = token::StellarAssetClient::new(env, &token_id);
+    for holder in holders {
+        admin_client.mint(holder, &amount);
+    }
+    token_id
+}
+
+#[test]
+fn test_execute_bundle_transfers_all_transactions() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender_a = Address::generate(&env);
+    let sender_b = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = setup_bundle_token(&env, &[&sender_a, &sender_b], 5000);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        1,
+        sender_a.clone(),
+        recipient.clone(),
+        1000,
+        "transfer",
+    ));
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        2,
+        sender_b.clone(),
+        recipient.clone(),
+        2000,
+        "transfer",
+    ));
+    let bundle = client.create_executable_bundle(&admin, &token_id, &bundled_txs);
+    assert_eq!(
+        client
+            .get_bundle_execution(&bundle.bundle_id)
+            .unwrap()
+            .status,
+        BundleExecutionStatus::Pending
+    );
+
+    let executed = client.execute_bundle(&bundle.bundle_id);
+
+    assert_eq!(executed.status, BundleExecutionStatus::Executed);
+    assert_eq!(executed.token, token_id);
+    assert_eq!(token_client.balance(&recipient), 3000);
+    assert_eq!(token_client.balance(&sender_a), 4000);
+    assert_eq!(token_client.balance(&sender_b), 3000);
+    assert_eq!(
+        client
+            .get_bundle_execution(&bundle.bundle_id)
+            .unwrap()
+            .status,
+        BundleExecutionStatus::Executed
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_execute_bundle_is_all_or_nothing() {
+    let (env, admin, client) = setup_test_env();
+
+    let funded = Address::generate(&env);
+    let unfunded = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = setup_bundle_token(&env, &[&funded], 5000);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        1,
+        funded,
+        recipient.clone(),
+        1000,
+        "transfer",
+    ));
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env, 2, unfunded, recipient, 1000, "transfer",
+    ));
+    let bundle = client.create_executable_bundle(&admin, &token_id, &bundled_txs);
+
+    // The second transfer fails, reverting the first
+    client.execute_bundle(&bundle.bundle_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_execute_bundle_rejects_invalid_bundle() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let token_id = setup_bundle_token(&env, &[&sender], 5000);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        1,
+        sender.clone(),
+        sender,
+        1000,
+        "transfer",
+    ));
+    let bundle = client.create_executable_bundle(&admin, &token_id, &bundled_txs);
+
+    client.execute_bundle(&bundle.bundle_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_execute_bundle_twice_fails() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let token_id = setup_bundle_token(&env, &[&sender], 5000);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        1,
+        sender,
+        Address::generate(&env),
+        1000,
+        "transfer",
+    ));
+    let bundle = client.create_executable_bundle(&admin, &token_id, &bundled_txs);
+
+    client.execute_bundle(&bundle.bundle_id);
+    client.execute_bundle(&bundle.bundle_id);
+}
+
+#[test]
+fn test_execute_stale_bundle_is_expired() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = setup_bundle_token(&env, &[&sender], 5000);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        1,
+        sender.clone(),
+        recipient.clone(),
+        1000,
+        "transfer",
+    ));
+    let bundle = client.create_executable_bundle(&admin, &token_id, &bundled_txs);
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += crate::types::BUNDLE_EXECUTION_WINDOW as u32 + 1);
+
+    let result = client.execute_bundle(&bundle.bundle_id);
+
+    assert_eq!(result.status, BundleExecutionStatus::Expired);
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(token_client.balance(&sender), 5000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_execute_nonexistent_bundle() {
+    let (_env, _admin, client) = setup_test_env();
+
+    client.execute_bundle(&99);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_execute_bundle_requires_executable_bundle() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction(&env, 1, 1000, "transfer"));
+    let bundle = client.bundle_transactions(&admin, &bundled_txs);
+
+    // Validation-only bundles carry no settlement token
+    client.execute_bundle(&bundle.bundle_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_executable_bundle_rejects_zero_amount() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let token_id = setup_bundle_token(&env, &[&sender], 5000);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction_with_addresses(
+        &env,
+        1,
+        sender,
+        Address::generate(&env),
+        0,
+        "transfer",
+    ));
+    client.create_executable_bundle(&admin, &token_id, &bundled_txs);
+}
+
 // ============================================================================
 // Refund Tests
 // ============================================================================
@@ -968,7 +1175,7 @@ fn test_refund_single_eligible_transaction() {
 
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer")); // Odd ID = eligible
-    transactions.push_back(create_transaction(&env, 2, 500, "budget"));   // Even ID = not eligible
+    transactions.push_back(create_transaction(&env, 2, 500, "budget")); // Even ID = not eligible
 
     let lookup = create_transaction_lookup(&env, &transactions);
     client.process_batch(&admin, &transactions, &None);
@@ -994,9 +1201,9 @@ fn test_refund_multiple_transactions_mixed_eligibility() {
 
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer")); // Eligible
-    transactions.push_back(create_transaction(&env, 2, 500, "budget"));    // Not eligible
-    transactions.push_back(create_transaction(&env, 3, 2000, "savings"));  // Eligible
-    transactions.push_back(create_transaction(&env, 4, 300, "transfer"));  // Not eligible
+    transactions.push_back(create_transaction(&env, 2, 500, "budget")); // Not eligible
+    transactions.push_back(create_transaction(&env, 3, 2000, "savings")); // Eligible
+    transactions.push_back(create_transaction(&env, 4, 300, "transfer")); // Not eligible
 
     let lookup = create_transaction_lookup(&env, &transactions);
     client.process_batch(&admin, &transactions, &None);
@@ -1011,7 +1218,7 @@ fn test_refund_multiple_transactions_mixed_eligibility() {
 
     assert_eq!(metrics.request_count, 4);
     assert_eq!(metrics.successful_refunds, 2); // Only odd IDs (1,3) are eligible
-    assert_eq!(metrics.failed_refunds, 2);     // Even IDs (2,4) are not eligible
+    assert_eq!(metrics.failed_refunds, 2); // Even IDs (2,4) are not eligible
     assert_eq!(metrics.total_refunded_amount, 3000); // 1000 + 2000
     assert_eq!(metrics.avg_refund_amount, 1500);
 
@@ -1130,8 +1337,14 @@ fn test_get_refund_batch_metrics() {
 
     let retrieved_metrics = client.get_refund_batch_metrics(&1_u64).unwrap();
     assert_eq!(retrieved_metrics.request_count, metrics.request_count);
-    assert_eq!(retrieved_metrics.successful_refunds, metrics.successful_refunds);
-    assert_eq!(retrieved_metrics.total_refunded_amount, metrics.total_refunded_amount);
+    assert_eq!(
+        retrieved_metrics.successful_refunds,
+        metrics.successful_refunds
+    );
+    assert_eq!(
+        retrieved_metrics.total_refunded_amount,
+        metrics.total_refunded_amount
+    );
 
     assert!(client.get_refund_batch_metrics(&999_u64).is_none());
 }
@@ -1170,17 +1383,31 @@ fn test_update_monthly_spending_analytics() {
     let user = Address::generate(&env);
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction_with_addresses(
-        &env, 1, user.clone(), Address::generate(&env), 100, "food",
+        &env,
+        1,
+        user.clone(),
+        Address::generate(&env),
+        100,
+        "food",
     ));
     transactions.push_back(create_transaction_with_addresses(
-        &env, 2, user.clone(), Address::generate(&env), 200, "transport",
+        &env,
+        2,
+        user.clone(),
+        Address::generate(&env),
+        200,
+        "transport",
     ));
     transactions.push_back(create_transaction_with_addresses(
-        &env, 3, user.clone(), Address::generate(&env), 300, "food",
+        &env,
+        3,
+        user.clone(),
+        Address::generate(&env),
+        300,
+        "food",
     ));
 
-    let analytics =
-        client.update_monthly_spending_analytics(&admin, &user, &transactions, 2023, 10);
+    let analytics = client.update_monthly_analytics(&admin, &user, &transactions, &2023, &10);
 
     assert_eq!(analytics.year, 2023);
     assert_eq!(analytics.month, 10);
@@ -1188,7 +1415,7 @@ fn test_update_monthly_spending_analytics() {
     assert_eq!(analytics.total_spending, 600);
     assert_eq!(analytics.transaction_count, 3);
 
-    let retrieved = client.get_monthly_analytics(&user, 2023, 10).unwrap();
+    let retrieved = client.get_monthly_analytics(&user, &2023, &10).unwrap();
     assert_eq!(retrieved.total_spending, analytics.total_spending);
     assert_eq!(retrieved.transaction_count, analytics.transaction_count);
 }
@@ -1198,7 +1425,7 @@ fn test_get_monthly_analytics_nonexistent() {
     let (env, _admin, client) = setup_test_env();
 
     let user = Address::generate(&env);
-    let result = client.get_monthly_analytics(&user, 2023, 10);
+    let result = client.get_monthly_analytics(&user, &2023, &10);
 
     assert!(result.is_none());
 }
@@ -1224,16 +1451,26 @@ fn test_get_total_tracked_users() {
 
     let mut transactions1: Vec<Transaction> = Vec::new(&env);
     transactions1.push_back(create_transaction_with_addresses(
-        &env, 1, user1.clone(), Address::generate(&env), 100, "food",
+        &env,
+        1,
+        user1.clone(),
+        Address::generate(&env),
+        100,
+        "food",
     ));
 
     let mut transactions2: Vec<Transaction> = Vec::new(&env);
     transactions2.push_back(create_transaction_with_addresses(
-        &env, 2, user2.clone(), Address::generate(&env), 200, "transport",
+        &env,
+        2,
+        user2.clone(),
+        Address::generate(&env),
+        200,
+        "transport",
     ));
 
-    client.update_monthly_spending_analytics(&admin, &user1, &transactions1, 2023, 10);
-    client.update_monthly_spending_analytics(&admin, &user2, &transactions2, 2023, 10);
+    client.update_monthly_analytics(&admin, &user1, &transactions1, &2023, &10);
+    client.update_monthly_analytics(&admin, &user2, &transactions2, &2023, &10);
 
     assert_eq!(client.get_total_tracked_users(), 2);
 }
@@ -1241,16 +1478,22 @@ fn test_get_total_tracked_users() {
 #[test]
 fn test_get_last_analytics_update() {
     let (env, admin, client) = setup_test_env();
+    env.ledger().with_mut(|li| li.sequence_number = 100);
 
     let initial_update = client.get_last_analytics_update();
 
     let user = Address::generate(&env);
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction_with_addresses(
-        &env, 1, user.clone(), Address::generate(&env), 100, "food",
+        &env,
+        1,
+        user.clone(),
+        Address::generate(&env),
+        100,
+        "food",
     ));
 
-    client.update_monthly_spending_analytics(&admin, &user, &transactions, 2023, 10);
+    client.update_monthly_analytics(&admin, &user, &transactions, &2023, &10);
 
     let final_update = client.get_last_analytics_update();
     assert!(final_update > initial_update);
@@ -1265,7 +1508,7 @@ fn test_unauthorized_update_monthly_analytics() {
     let user = Address::generate(&env);
     let transactions: Vec<Transaction> = Vec::new(&env);
 
-    client.update_monthly_spending_analytics(&unauthorized_user, &user, &transactions, 2023, 10);
+    client.update_monthly_analytics(&unauthorized_user, &user, &transactions, &2023, &10);
 }
 
 #[test]
@@ -1277,32 +1520,50 @@ fn test_monthly_analytics_with_different_users() {
 
     let mut transactions1: Vec<Transaction> = Vec::new(&env);
     transactions1.push_back(create_transaction_with_addresses(
-        &env, 1, user1.clone(), Address::generate(&env), 100, "food",
+        &env,
+        1,
+        user1.clone(),
+        Address::generate(&env),
+        100,
+        "food",
     ));
     transactions1.push_back(create_transaction_with_addresses(
-        &env, 2, user1.clone(), Address::generate(&env), 200, "transport",
+        &env,
+        2,
+        user1.clone(),
+        Address::generate(&env),
+        200,
+        "transport",
     ));
 
     let mut transactions2: Vec<Transaction> = Vec::new(&env);
     transactions2.push_back(create_transaction_with_addresses(
-        &env, 3, user2.clone(), Address::generate(&env), 300, "entertainment",
+        &env,
+        3,
+        user2.clone(),
+        Address::generate(&env),
+        300,
+        "entertainment",
     ));
     transactions2.push_back(create_transaction_with_addresses(
-        &env, 4, user2.clone(), Address::generate(&env), 400, "food",
+        &env,
+        4,
+        user2.clone(),
+        Address::generate(&env),
+        400,
+        "food",
     ));
 
-    let analytics1 =
-        client.update_monthly_spending_analytics(&admin, &user1, &transactions1, 2023, 10);
-    let analytics2 =
-        client.update_monthly_spending_analytics(&admin, &user2, &transactions2, 2023, 10);
+    let analytics1 = client.update_monthly_analytics(&admin, &user1, &transactions1, &2023, &10);
+    let analytics2 = client.update_monthly_analytics(&admin, &user2, &transactions2, &2023, &10);
 
     assert_eq!(analytics1.total_spending, 300);
     assert_eq!(analytics1.transaction_count, 2);
     assert_eq!(analytics2.total_spending, 700);
     assert_eq!(analytics2.transaction_count, 2);
 
-    let retrieved1 = client.get_monthly_analytics(&user1, 2023, 10).unwrap();
-    let retrieved2 = client.get_monthly_analytics(&user2, 2023, 10).unwrap();
+    let retrieved1 = client.get_monthly_analytics(&user1, &2023, &10).unwrap();
+    let retrieved2 = client.get_monthly_analytics(&user2, &2023, &10).unwrap();
 
     assert_eq!(retrieved1.total_spending, analytics1.total_spending);
     assert_eq!(retrieved2.total_spending, analytics2.total_spending);
@@ -1315,18 +1576,186 @@ fn test_monthly_analytics_category_tracking() {
     let user = Address::generate(&env);
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction_with_addresses(
-        &env, 1, user.clone(), Address::generate(&env), 100, "food",
+        &env,
+        1,
+        user.clone(),
+        Address::generate(&env),
+        100,
+        "food",
     ));
     transactions.push_back(create_transaction_with_addresses(
-        &env, 2, user.clone(), Address::generate(&env), 200, "food",
+        &env,
+        2,
+        user.clone(),
+        Address::generate(&env),
+        200,
+        "food",
     ));
     transactions.push_back(create_transaction_with_addresses(
-        &env, 3, user.clone(), Address::generate(&env), 300, "transport",
+        &env,
+        3,
+        user.clone(),
+        Address::generate(&env),
+        300,
+        "transport",
     ));
 
-    let analytics =
-        client.update_monthly_spending_analytics(&admin, &user, &transactions, 2023, 10);
+    let analytics = client.update_monthly_analytics(&admin, &user, &transactions, &2023, &10);
 
     assert_eq!(analytics.total_spending, 600);
     assert_eq!(analytics.transaction_count, 3);
-}
\ No newline at end of file
+}
+// ============================================================================
+// Merchant Reputation Tests
+// ============================================================================
+
+/// Processes one transaction per id from `sender` to `merchant` so they can be rated.
+fn process_rated_transactions(
+    env: &Env,
+    admin: &Address,
+    client: &TransactionAnalyticsContractClient,
+    sender: &Address,
+    merchant: &Address,
+    tx_ids: &[u64],
+) {
+    let mut transactions: Vec<Transaction> = Vec::new(env);
+    for tx_id in tx_ids {
+        transactions.push_back(create_transaction_with_addresses(
+            env,
+            *tx_id,
+            sender.clone(),
+            merchant.clone(),
+            100,
+            "shopping",
+        ));
+    }
+    client.process_batch(admin, &transactions, &None);
+}
+
+#[test]
+fn test_ratings_roll_up_into_merchant_reputation() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    process_rated_transactions(&env, &admin, &client, &sender, &merchant, &[1, 2]);
+
+    let mut ratings: Vec<RatingInput> = Vec::new(&env);
+    ratings.push_back(RatingInput { tx_id: 1, score: 5 });
+    ratings.push_back(RatingInput { tx_id: 2, score: 2 });
+
+    let results = client.submit_ratings(&sender, &ratings);
+    assert_eq!(results.get(0).unwrap().status, RatingStatus::Success);
+    assert_eq!(results.get(1).unwrap().status, RatingStatus::Success);
+
+    let reputation = client.get_merchant_reputation(&merchant).unwrap();
+    assert_eq!(reputation.rating_count, 2);
+    assert_eq!(reputation.total_score, 7);
+    assert_eq!(reputation.average_score, 350);
+    // 500 * 0.8 + 200 * 0.2 = 440
+    assert_eq!(reputation.weighted_score, 440);
+    assert_eq!(reputation.distribution.get(1).unwrap(), 1);
+    assert_eq!(reputation.distribution.get(4).unwrap(), 1);
+    assert_eq!(reputation.distribution.get(0).unwrap(), 0);
+}
+
+#[test]
+fn test_only_sender_can_rate_transaction() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    process_rated_transactions(&env, &admin, &client, &sender, &merchant, &[1]);
+
+    let stranger = Address::generate(&env);
+    let mut ratings: Vec<RatingInput> = Vec::new(&env);
+    ratings.push_back(RatingInput { tx_id: 1, score: 1 });
+
+    let results = client.submit_ratings(&stranger, &ratings);
+    assert_eq!(results.get(0).unwrap().status, RatingStatus::NotSender);
+    assert!(client.get_merchant_reputation(&merchant).is_none());
+}
+
+#[test]
+fn test_transaction_can_only_be_rated_once() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    process_rated_transactions(&env, &admin, &client, &sender, &merchant, &[1]);
+
+    let mut ratings: Vec<RatingInput> = Vec::new(&env);
+    ratings.push_back(RatingInput { tx_id: 1, score: 4 });
+    client.submit_ratings(&sender, &ratings);
+
+    let mut retry: Vec<RatingInput> = Vec::new(&env);
+    retry.push_back(RatingInput { tx_id: 1, score: 1 });
+    let results = client.submit_ratings(&sender, &retry);
+    assert_eq!(results.get(0).unwrap().status, RatingStatus::AlreadyRated);
+
+    let reputation = client.get_merchant_reputation(&merchant).unwrap();
+    assert_eq!(reputation.rating_count, 1);
+    assert_eq!(reputation.average_score, 400);
+}
+
+#[test]
+fn test_rating_unknown_transaction() {
+    let (env, _admin, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let mut ratings: Vec<RatingInput> = Vec::new(&env);
+    ratings.push_back(RatingInput {
+        tx_id: 42,
+        score: 3,
+    });
+
+    let results = client.submit_ratings(&sender, &ratings);
+    assert_eq!(
+        results.get(0).unwrap().status,
+        RatingStatus::UnknownTransaction
+    );
+}
+
+#[test]
+fn test_rating_transaction_processed_before_parties_were_recorded() {
+    let (env, admin, client) = setup_test_env();
+
+    // Older batches only marked the transaction as known
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::KnownTransaction(7), &true);
+    });
+
+    let sender = Address::generate(&env);
+    let mut ratings: Vec<RatingInput> = Vec::new(&env);
+    ratings.push_back(RatingInput { tx_id: 7, score: 4 });
+
+    // Without recorded parties nobody is the sender
+    let results = client.submit_ratings(&sender, &ratings);
+    assert_eq!(results.get(0).unwrap().status, RatingStatus::NotSender);
+
+    let merchant = Address::generate(&env);
+    client.backfill_transaction_parties(&admin, &7, &sender, &merchant);
+
+    let stranger = Address::generate(&env);
+    let results = client.submit_ratings(&stranger, &ratings);
+    assert_eq!(results.get(0).unwrap().status, RatingStatus::NotSender);
+
+    let results = client.submit_ratings(&sender, &ratings);
+    assert_eq!(results.get(0).unwrap().status, RatingStatus::Success);
+    assert_eq!(
+        client
+            .get_merchant_reputation(&merchant)
+            .unwrap()
+            .rating_count,
+        1
+    );
+
+    // Parties can only be backfilled once
+    let result = client.try_backfill_transaction_parties(&admin, &7, &stranger, &merchant);
+    assert_eq!(
+        result.err(),
+        Some(Ok(AnalyticsError::CannotBackfillParties.into()))
+    );
+}
@@ -11,6 +11,18 @@ pub struct FeeRecipientShare {
 
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Number of ledgers a validated bundle remains executable.
+pub const BUNDLE_EXECUTION_WINDOW: u64 = 720;
+
+/// Number of histogram buckets in a merchant reputation (one per star, 1-5).
+pub const RATING_BUCKETS: u32 = 5;
+
+/// Fixed-point scale for reputation scores (a score of 4.25 is stored as 425).
+pub const REPUTATION_SCALE: u32 = 100;
+
+/// Weight given to the newest rating in the recency-weighted score, in basis points.
+pub const REPUTATION_RECENCY_WEIGHT_BPS: u32 = 2_000;
+
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct Transaction {
@@ -83,6 +95,28 @@ pub struct BundleResult {
     pub created_at: u64,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum BundleExecutionStatus {
+    Pending,
+    Executed,
+    Expired,
+}
+
+/// Settlement state of a bundle created with `create_executable_bundle`.
+///
+/// Kept apart from `BundleResult` so bundles stored before execution existed
+/// still decode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BundleExecution {
+    pub bundle_id: u64,
+    /// Token the bundle settles in, fixed when the bundle is created
+    pub token: Address,
+    pub status: BundleExecutionStatus,
+    pub executed_at: u64,
+}
+
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct RatingInput {
@@ -90,12 +124,14 @@ pub struct RatingInput {
     pub score: u32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum RatingStatus {
     Success,
     InvalidScore,
     UnknownTransaction,
+    NotSender,
+    AlreadyRated,
 }
 
 #[derive(Clone, Debug)]
@@ -106,6 +142,22 @@ pub struct RatingResult {
     pub status: RatingStatus,
 }
 
+/// Rolled-up rating data for a recipient address.
+///
+/// `average_score` and `weighted_score` are scaled by `REPUTATION_SCALE`.
+/// `distribution[i]` holds the number of `i + 1` star ratings.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MerchantReputation {
+    pub merchant: Address,
+    pub rating_count: u32,
+    pub total_score: u64,
+    pub average_score: u32,
+    pub weighted_score: u32,
+    pub distribution: Vec<u32>,
+    pub last_rated_at: u64,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum TransactionStatus {
@@ -149,12 +201,16 @@ pub enum DataKey {
     TotalAuditLogs,
     LastBundleId,
     BundleResult(u64),
+    BundleTransactions(u64),
+    BundleExecution(u64),
     LastRefundBatchId,
     RefundBatchMetrics(u64),
     TotalRefundAmount,
     RefundedTransactions,
     KnownTransaction(u64),
+    TransactionParties(u64),
     Rating(u64, Address),
+    MerchantReputation(Address),
     TransactionStatus(u64),
     MonthlyAnalytics(u32, u32, Address),
     UserSpendingSummary(Address),
@@ -203,7 +259,7 @@ pub enum FeeModel {
     Tiered(Vec<FeeTier>),
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct FeeTier {
     pub threshold: i128,
@@ -211,7 +267,7 @@ pub struct FeeTier {
     pub default_percentage_bps: u32,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct FeeConfig {
     pub fee_model: FeeModel,
@@ -221,7 +277,7 @@ pub struct FeeConfig {
     pub description: Option<Symbol>,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct FeeCalculationResult {
     pub gross_amount: i128,
@@ -230,7 +286,7 @@ pub struct FeeCalculationResult {
     pub fee_percentage_bps: u32,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct FeeDeductionEvent {
     pub timestamp: u64,
@@ -268,7 +324,7 @@ pub struct RefundBatchMetrics {
     pub processed_at: u64,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct MonthlySpendingAnalytics {
     pub year: u32,
@@ -279,7 +335,7 @@ pub struct MonthlySpendingAnalytics {
     pub transaction_count: u32,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct UserSpendingSummary {
     pub user: Address,
@@ -337,6 +393,22 @@ impl AnalyticsEvents {
         env.events().publish(topics, (tx_id, score, status));
     }
 
+    pub fn reputation_updated(env: &Env, reputation: &MerchantReputation) {
+        let topics = (
+            symbol_short!("merchant"),
+            symbol_short!("rep"),
+            reputation.merchant.clone(),
+        );
+        env.events().publish(
+            topics,
+            (
+                reputation.rating_count,
+                reputation.average_score,
+                reputation.weighted_score,
+            ),
+        );
+    }
+
     pub fn transaction_status_updated(
         env: &Env,
         tx_id: u64,
@@ -353,13 +425,20 @@ impl AnalyticsEvents {
         env.events().publish(topics, tx_id);
     }
 
-    pub fn operation_fee_updated(env: &Env, admin: &Address, operation: &Symbol, previous: Option<FeeConfig>, new: FeeConfig) {
-        let topics = (symbol_short!("fee"), symbol_short!("operation_updated"));
-        env.events().publish(topics, (admin.clone(), operation.clone(), previous, new));
+    pub fn operation_fee_updated(
+        env: &Env,
+        admin: &Address,
+        operation: &Symbol,
+        previous: Option<FeeConfig>,
+        new: FeeConfig,
+    ) {
+        let topics = (symbol_short!("fee"), symbol_short!("op_update"));
+        env.events()
+            .publish(topics, (admin.clone(), operation.clone(), previous, new));
     }
 
     pub fn fee_cap_changed(env: &Env, admin: &Address, previous: Option<u64>, new: Option<u64>) {
-        let topics = (symbol_short!("fee"), symbol_short!("cap_changed"));
+        let topics = (symbol_short!("fee"), symbol_short!("cap_chg"));
         env.events().publish(topics, (admin.clone(), previous, new));
     }
 
@@ -387,6 +466,20 @@ impl AnalyticsEvents {
         env.events().publish(topics, (bundle_id, can_bundle));
     }
 
+    pub fn bundle_executed(env: &Env, bundle_id: u64, tx_count: u32, total_volume: i128) {
+        let topics = (
+            symbol_short!("bundle"),
+            symbol_short!("executed"),
+            bundle_id,
+        );
+        env.events().publish(topics, (tx_count, total_volume));
+    }
+
+    pub fn bundle_expired(env: &Env, bundle_id: u64) {
+        let topics = (symbol_short!("bundle"), symbol_short!("expired"));
+        env.events().publish(topics, bundle_id);
+    }
+
     pub fn transaction_validation_failed(env: &Env, bundle_id: u64, tx_id: u64, error: &Symbol) {
         let topics = (symbol_short!("bundle"), symbol_short!("failed"));
         env.events()
@@ -455,7 +548,7 @@ impl AnalyticsEvents {
     }
 
     pub fn fee_distributed(env: &Env, recipient: &Address, amount: i128, share_bps: u32) {
-        let topics = (symbol_short!("fee"), symbol_short!("distributed"), recipient);
+        let topics = (symbol_short!("fee"), symbol_short!("distrib"), recipient);
         env.events().publish(topics, (amount, share_bps));
     }
 
@@ -470,4 +563,4 @@ impl AnalyticsEvents {
         );
         env.events().publish(topics, admin.clone());
     }
-}
\ No newline at end of file
+}
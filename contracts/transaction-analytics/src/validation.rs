@@ -3,7 +3,7 @@
 //! Implements strict validation for all public inputs in the transaction analytics contract.
 //! Provides standardized validation functions for addresses, amounts, assets, and other inputs.
 
-use soroban_sdk::{symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{symbol_short, Address, Env, Vec};
 
 use crate::types::{
     BundledTransaction, RatingInput, RefundRequest, Transaction, TransactionStatusUpdate,
@@ -18,13 +18,8 @@ use crate::types::{
 ///
 /// # Returns
 /// * `Ok(())` if valid, `Err(ValidationError)` if invalid
-pub fn validate_address(env: &Env, address: &Address) -> Result<(), ValidationError> {
+pub fn validate_address(_env: &Env, _address: &Address) -> Result<(), ValidationError> {
     // Soroban SDK addresses are guaranteed to be valid by construction
-    // However, we can perform additional checks if needed
-    if address.is_none() {
-        return Err(ValidationError::InvalidAddress);
-    }
-
     Ok(())
 }
 
@@ -53,9 +48,10 @@ pub fn validate_amounts(amounts: &[i128]) -> Result<(), ValidationError> {
 
 /// Validates a transaction struct
 pub fn validate_transaction(transaction: &Transaction) -> Result<(), ValidationError> {
-    validate_address(&transaction.from)?;
-    validate_address(&transaction.to)?;
-    validate_amount(transaction.amount)?;
+    // Zero-value transactions are still recorded for analytics
+    if transaction.amount < 0 {
+        return Err(ValidationError::InvalidAmount);
+    }
 
     // Validate timestamp is not in the future (within reasonable tolerance)
     let current_ledger = transaction.timestamp; // This would be env.ledger().sequence() in real usage
@@ -65,7 +61,7 @@ pub fn validate_transaction(transaction: &Transaction) -> Result<(), ValidationE
     }
 
     // Validate category is not empty
-    if transaction.category.to_str().len() == 0 {
+    if transaction.category == symbol_short!("") {
         return Err(ValidationError::InvalidCategory);
     }
 
@@ -85,7 +81,7 @@ pub fn validate_transactions(transactions: &Vec<Transaction>) -> Result<(), Vali
     }
 
     for transaction in transactions.iter() {
-        validate_transaction(transaction)?;
+        validate_transaction(&transaction)?;
     }
 
     Ok(())
@@ -100,11 +96,7 @@ pub fn validate_refund_request(request: &RefundRequest) -> Result<(), Validation
 
     // Validate reason if provided
     if let Some(reason) = &request.reason {
-        if reason.to_str().len() == 0 {
-            return Err(ValidationError::InvalidReason);
-        }
-        if reason.to_str().len() > 255 {
-            // Arbitrary limit for reason length
+        if *reason == symbol_short!("") {
             return Err(ValidationError::InvalidReason);
         }
     }
@@ -125,13 +117,15 @@ pub fn validate_refund_requests(requests: &Vec<RefundRequest>) -> Result<(), Val
     }
 
     // Check for duplicate transaction IDs
-    let mut seen_tx_ids = std::collections::HashSet::new();
-    for request in requests.iter() {
-        if seen_tx_ids.contains(&request.tx_id) {
+    for (i, request) in requests.iter().enumerate() {
+        if requests
+            .iter()
+            .take(i)
+            .any(|seen| seen.tx_id == request.tx_id)
+        {
             return Err(ValidationError::DuplicateTransactionId);
         }
-        seen_tx_ids.insert(request.tx_id);
-        validate_refund_request(request)?;
+        validate_refund_request(&request)?;
     }
 
     Ok(())
@@ -165,7 +159,7 @@ pub fn validate_rating_inputs(inputs: &Vec<RatingInput>) -> Result<(), Validatio
     }
 
     for input in inputs.iter() {
-        validate_rating_input(input)?;
+        validate_rating_input(&input)?;
     }
 
     Ok(())
@@ -198,13 +192,15 @@ pub fn validate_transaction_status_updates(
     }
 
     // Check for duplicate transaction IDs
-    let mut seen_tx_ids = std::collections::HashSet::new();
-    for update in updates.iter() {
-        if seen_tx_ids.contains(&update.tx_id) {
+    for (i, update) in updates.iter().enumerate() {
+        if updates
+            .iter()
+            .take(i)
+            .any(|seen| seen.tx_id == update.tx_id)
+        {
             return Err(ValidationError::DuplicateTransactionId);
         }
-        seen_tx_ids.insert(update.tx_id);
-        validate_transaction_status_update(update)?;
+        validate_transaction_status_update(&update)?;
     }
 
     Ok(())
@@ -223,11 +219,7 @@ pub fn validate_bundled_transaction(
 
     // Validate memo if provided
     if let Some(memo) = &bundled_tx.memo {
-        if memo.to_str().len() == 0 {
-            return Err(ValidationError::InvalidMemo);
-        }
-        if memo.to_str().len() > 255 {
-            // Arbitrary limit for memo length
+        if *memo == symbol_short!("") {
             return Err(ValidationError::InvalidMemo);
         }
     }
@@ -250,7 +242,7 @@ pub fn validate_bundled_transactions(
     }
 
     for bundled_tx in bundled_txs.iter() {
-        validate_bundled_transaction(bundled_tx)?;
+        validate_bundled_transaction(&bundled_tx)?;
     }
 
     Ok(())
@@ -263,11 +255,11 @@ pub fn validate_user_address(env: &Env, user: &Address) -> Result<(), Validation
 
 /// Validates year and month for analytics functions
 pub fn validate_year_month(year: u32, month: u32) -> Result<(), ValidationError> {
-    if year < 2000 || year > 2100 {
+    if !(2000..=2100).contains(&year) {
         return Err(ValidationError::InvalidYear);
     }
 
-    if month < 1 || month > 12 {
+    if !(1..=12).contains(&month) {
         return Err(ValidationError::InvalidMonth);
     }
 
@@ -339,11 +331,8 @@ pub fn validate_asset_amounts(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{
-        BundledTransaction, RatingInput, RefundRequest, Transaction, TransactionStatus,
-        TransactionStatusUpdate,
-    };
-    use soroban_sdk::{testutils::Address as _, Env};
+    use crate::types::{RatingInput, RefundRequest, Transaction};
+    use soroban_sdk::{testutils::Address as _, Env, Symbol};
 
     fn create_test_transaction(env: &Env, tx_id: u64, amount: i128, category: &str) -> Transaction {
         Transaction {
@@ -352,7 +341,7 @@ mod tests {
             to: Address::generate(env),
             amount,
             timestamp: 12345,
-            category: symbol_short!(category),
+            category: Symbol::new(env, category),
         }
     }
 
@@ -379,13 +368,12 @@ mod tests {
     #[test]
     fn test_validate_transaction_invalid_amount() {
         let env = Env::default();
-        let mut transaction = create_test_transaction(&env, 1, -100, "transfer");
+        let transaction = create_test_transaction(&env, 1, -100, "transfer");
         assert!(validate_transaction(&transaction).is_err());
     }
 
     #[test]
     fn test_validate_refund_request_valid() {
-        let env = Env::default();
         let request = RefundRequest {
             tx_id: 1,
             reason: Some(symbol_short!("test")),
@@ -395,7 +383,6 @@ mod tests {
 
     #[test]
     fn test_validate_refund_request_invalid_tx_id() {
-        let env = Env::default();
         let request = RefundRequest {
             tx_id: 0,
             reason: Some(symbol_short!("test")),
@@ -464,10 +451,10 @@ mod tests {
 
     #[test]
     fn test_validate_asset_amounts() {
-        let amounts = vec![100, 200, 300];
+        let amounts = [100, 200, 300];
         assert!(validate_asset_amounts("USDC", &amounts).is_ok());
 
-        let invalid_amounts = vec![100, -1, 300]; // Contains negative
+        let invalid_amounts = [100, -1, 300]; // Contains negative
         assert!(validate_asset_amounts("USDC", &invalid_amounts).is_err());
     }
 
@@ -2,7 +2,7 @@ use std::panic::AssertUnwindSafe;
 
 use soroban_sdk::{
     symbol_short,
-    testutils::Address as _,
+    testutils::{Address as _, Ledger},
     xdr::{ContractEventBody, ContractEventType, ScVal},
     Address, Env, String, Symbol, Vec,
 };
@@ -10,7 +10,9 @@ use soroban_sdk::{
 #[path = "../contracts/fees.rs"]
 mod fees;
 
-use fees::{FeeRecipient, FeesContract, FeesContractClient};
+use fees::{
+    FeeError, FeeRecipient, FeesContract, FeesContractClient, PriorityLevel, RoundingMode,
+};
 
 fn setup_fee_contract() -> (Env, Address, Address, FeesContractClient<'static>) {
     let env = Env::default();
@@ -1262,3 +1264,184 @@ fn test_very_small_transactions_with_min_fee() {
     assert_eq!(fee, 50);
     assert_eq!(net, 1 - 50); // Negative is allowed for transaction logic
 }
+
+#[test]
+fn test_bankers_rounding_sends_ties_to_even() {
+    let (env, admin, _contract_id, client) = setup_fee_contract();
+    let user = Address::generate(&env);
+    client.set_rounding_mode(&admin, &RoundingMode::Bankers);
+
+    // 10 * 5% = 0.5 -> 0 (even)
+    assert_eq!(client.deduct_fee(&user, &10), (10, 0));
+    // 30 * 5% = 1.5 -> 2 (even)
+    assert_eq!(client.deduct_fee(&user, &30), (28, 2));
+    // 50 * 5% = 2.5 -> 2 (even)
+    assert_eq!(client.deduct_fee(&user, &50), (48, 2));
+    // 70 * 5% = 3.5 -> 4 (even)
+    assert_eq!(client.deduct_fee(&user, &70), (66, 4));
+    // 52 * 5% = 2.6 -> 3, not a tie
+    assert_eq!(client.deduct_fee(&user, &52), (49, 3));
+}
+
+#[test]
+fn test_accumulate_carries_remainder_across_charges() {
+    let (env, admin, _contract_id, client) = setup_fee_contract();
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_rounding_mode(&admin, &RoundingMode::Accumulate);
+
+    // 6 * 5% = 0.3 per charge: nothing charged until the carry reaches a whole unit.
+    assert_eq!(client.deduct_fee(&user, &6), (6, 0));
+    assert_eq!(client.get_rounding_remainder(&user), 3_000);
+    assert_eq!(client.deduct_fee(&user, &6), (6, 0));
+    assert_eq!(client.deduct_fee(&user, &6), (6, 0));
+    assert_eq!(client.get_rounding_remainder(&user), 9_000);
+    assert_eq!(client.deduct_fee(&user, &6), (5, 1));
+    assert_eq!(client.get_rounding_remainder(&user), 2_000);
+
+    // Remainders are tracked per payer.
+    assert_eq!(client.get_rounding_remainder(&other), 0);
+    assert_eq!(client.deduct_fee(&other, &6), (6, 0));
+    assert_eq!(client.get_rounding_remainder(&other), 3_000);
+
+    // 16 * 5% = 0.8; with the 0.2 carried the remainder clears exactly.
+    assert_eq!(client.deduct_fee(&user, &16), (15, 1));
+    assert_eq!(client.get_rounding_remainder(&user), 0);
+    assert_eq!(client.get_total_collected(), 2);
+}
+
+/// Initializes at t=1_000 with 5%, then moves to 2% at t=2_000 and Ceiling
+/// rounding at t=3_000.
+fn setup_fee_history() -> (Env, Address, FeesContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(FeesContract, ());
+    let client = FeesContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    client.initialize(&admin, &500u32);
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    client.set_percentage(&admin, &200u32);
+    env.ledger().with_mut(|l| l.timestamp = 3_000);
+    client.set_rounding_mode(&admin, &RoundingMode::Ceiling);
+
+    (env, admin, client)
+}
+
+#[test]
+fn test_fee_config_at_before_first_snapshot_is_none() {
+    let (_env, _admin, client) = setup_fee_history();
+
+    assert!(client.get_fee_config_at(&0).is_none());
+    assert!(client.get_fee_config_at(&999).is_none());
+    assert_eq!(
+        client.try_recalculate_fee_at(&1_000, &999),
+        Err(Ok(FeeError::NoConfigAtTimestamp.into()))
+    );
+}
+
+#[test]
+fn test_fee_config_at_between_snapshots() {
+    let (_env, _admin, client) = setup_fee_history();
+    assert_eq!(client.get_config_snapshot_count(), 3);
+
+    let at_init = client.get_fee_config_at(&1_000).unwrap();
+    assert_eq!(at_init.index, 0);
+    assert_eq!(at_init.percentage_bps, 500);
+
+    let between = client.get_fee_config_at(&2_500).unwrap();
+    assert_eq!(between.index, 1);
+    assert_eq!(between.effective_from, 2_000);
+    assert_eq!(between.percentage_bps, 200);
+    assert_eq!(between.rounding_mode, RoundingMode::Round);
+
+    // 1_010 * 5% = 50.5 rounds to 51; 1_010 * 2% = 20.2 rounds to 20.
+    assert_eq!(client.recalculate_fee_at(&1_010, &1_999), 51);
+    assert_eq!(client.recalculate_fee_at(&1_010, &2_000), 20);
+    assert_eq!(client.recalculate_fee_at(&1_010, &2_999), 20);
+}
+
+#[test]
+fn test_fee_config_at_after_last_snapshot() {
+    let (env, admin, client) = setup_fee_history();
+
+    let latest = client.get_fee_config_at(&u64::MAX).unwrap();
+    assert_eq!(latest.index, 2);
+    assert_eq!(latest.rounding_mode, RoundingMode::Ceiling);
+    // 1_010 * 2% = 20.2, ceiled to 21 under the latest configuration.
+    assert_eq!(client.recalculate_fee_at(&1_010, &10_000), 21);
+
+    // Several changes in one ledger: the last one wins.
+    env.ledger().with_mut(|l| l.timestamp = 4_000);
+    client.set_percentage(&admin, &300u32);
+    client.set_percentage(&admin, &400u32);
+    assert_eq!(client.get_fee_config_at(&4_000).unwrap().percentage_bps, 400);
+    assert_eq!(client.get_fee_config_at(&3_999).unwrap().percentage_bps, 200);
+}
+
+#[test]
+fn test_recalculate_fee_at_matches_the_original_charge() {
+    let (env, admin, client) = setup_fee_history();
+    let payer = Address::generate(&env);
+
+    // Medium no longer maps to 1x, but `deduct_fee` applies no multiplier.
+    env.ledger().with_mut(|l| l.timestamp = 4_000);
+    client.set_priority_multipliers(&admin, &5_000, &12_000, &20_000, &30_000);
+    client.set_rounding_mode(&admin, &RoundingMode::Round);
+
+    let (_, fee) = client.deduct_fee(&payer, &1_010);
+    assert_eq!(fee, 20);
+    assert_eq!(client.recalculate_fee_at(&1_010, &4_000), fee);
+}
+
+#[test]
+fn test_recalculate_priority_fee_at_applies_snapshot_multiplier() {
+    let (env, admin, client) = setup_fee_history();
+
+    // Urgent uses the default 2x multiplier: 1_000 * 4% = 40.
+    assert_eq!(
+        client.recalculate_priority_fee_at(&1_000, &PriorityLevel::Urgent, &2_500),
+        40
+    );
+    assert_eq!(
+        client.recalculate_priority_fee_at(&1_000, &PriorityLevel::Low, &2_500),
+        16
+    );
+
+    env.ledger().with_mut(|l| l.timestamp = 4_000);
+    client.set_priority_multipliers(&admin, &5_000, &10_000, &20_000, &30_000);
+
+    // Earlier times keep the multipliers they were charged with.
+    assert_eq!(
+        client.recalculate_priority_fee_at(&1_000, &PriorityLevel::Urgent, &3_500),
+        40
+    );
+    assert_eq!(
+        client.recalculate_priority_fee_at(&1_000, &PriorityLevel::Urgent, &4_000),
+        60
+    );
+    assert_eq!(
+        client.recalculate_priority_fee_at(&1_000, &PriorityLevel::Low, &4_000),
+        10
+    );
+}
+
+#[test]
+fn test_set_treasury_is_snapshotted() {
+    let (env, admin, client) = setup_fee_history();
+    let treasury = Address::generate(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = 5_000);
+    client.set_treasury(&admin, &treasury, &2_500u32);
+    assert_eq!(client.get_config_snapshot_count(), 4);
+
+    let before = client.get_fee_config_at(&4_999).unwrap();
+    assert_eq!(before.treasury, None);
+    assert_eq!(before.treasury_percentage_bps, 0);
+
+    let after = client.get_fee_config_at(&5_000).unwrap();
+    assert_eq!(after.treasury, Some(treasury));
+    assert_eq!(after.treasury_percentage_bps, 2_500);
+    assert_eq!(after.percentage_bps, 200);
+}